@@ -1,4 +1,6 @@
 use crate::Currency;
+use anyhow::Result;
+use iban_validate::IbanLike;
 
 use super::{AccountId, Id};
 
@@ -12,23 +14,161 @@ impl std::fmt::Display for Bic {
 }
 
 impl Bic {
-    pub fn new(bic: String) -> Self {
+    /// Parses and validates a BIC according to ISO 9362.
+    /// Spaces are removed and letters are converted to uppercase.
+    pub fn new(bic: String) -> Result<Self> {
+        let bic = bic.to_uppercase().replace(' ', "");
+        if bic.len() != 8 && bic.len() != 11 {
+            anyhow::bail!(
+                "A BIC must have 8 or 11 characters but {bic} has {}",
+                bic.len()
+            );
+        }
+        if !bic.chars().all(|c| c.is_ascii_alphanumeric()) {
+            anyhow::bail!("A BIC can only contain letters and digits");
+        }
+        if !bic[4..6].chars().all(|c| c.is_ascii_alphabetic()) {
+            anyhow::bail!("The country code of a BIC (5th and 6th character) must be letters");
+        }
+        Ok(Self(bic))
+    }
+
+    /// Creates a BIC without validating it.
+    /// Used for BICs that were stored before they got validated.
+    #[cfg(feature = "sqlite")]
+    pub(crate) fn new_unchecked(bic: String) -> Self {
         Self(bic.to_uppercase().replace(' ', ""))
     }
+
+    /// The ISO 3166-1 country code of the BIC.
+    /// Returns `None` if the BIC was not validated and is too short.
+    pub fn country_code(&self) -> Option<&str> {
+        self.0.get(4..6)
+    }
 }
 
-impl From<String> for Bic {
-    fn from(value: String) -> Self {
+impl std::str::FromStr for Bic {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s.to_string())
+    }
+}
+
+impl TryFrom<String> for Bic {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
         Self::new(value)
     }
 }
 
+/// Countries whose banks can use the IBANs of the other countries of the same group.
+/// For example banks on Jersey use British IBANs and banks on Guadeloupe French IBANs.
+const IBAN_TERRITORIES: [&[&str]; 3] = [
+    &["GB", "JE", "GG", "IM"],
+    &[
+        "FR", "GF", "GP", "MQ", "RE", "YT", "PM", "BL", "MF", "NC", "PF", "WF",
+    ],
+    &["FI", "AX"],
+];
+
+fn countries_match(iban_country: &str, bic_country: &str) -> bool {
+    iban_country == bic_country
+        || IBAN_TERRITORIES.iter().any(|territories| {
+            territories.contains(&iban_country) && territories.contains(&bic_country)
+        })
+}
+
+/// Checks that the country code of the BIC matches the country code of the IBAN.
+/// Territories that use the IBANs of another country, like Jersey with British IBANs, match that country.
+/// Account ids that are no IBAN are not checked.
+pub fn check_iban_bic_country(iban: Option<&AccountId>, bic: Option<&Bic>) -> Result<()> {
+    if let (Some(AccountId::Iban(iban)), Some(bic)) = (iban, bic)
+        && let Some(bic_country) = bic.country_code()
+        && !countries_match(iban.country_code(), bic_country)
+    {
+        anyhow::bail!(
+            "The country code of the BIC {bic} ({bic_country}) does not match the country code of the IBAN {iban} ({})",
+            iban.country_code()
+        );
+    }
+    Ok(())
+}
+
 impl<'a> From<&'a Bic> for &'a String {
     fn from(val: &'a Bic) -> Self {
         &val.0
     }
 }
 
+/// Classification of an account like in double-entry bookkeeping.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
+pub enum AccountKind {
+    Asset,
+    Liability,
+    Income,
+    Expense,
+    Equity,
+}
+
+impl AccountKind {
+    pub const ALL: [AccountKind; 5] = [
+        AccountKind::Asset,
+        AccountKind::Liability,
+        AccountKind::Income,
+        AccountKind::Expense,
+        AccountKind::Equity,
+    ];
+
+    /// The kinds a [`BookCheckingAccount`] can have. Own assets are [`AssetAccount`]s.
+    pub const BOOK_CHECKING: [AccountKind; 4] = [
+        AccountKind::Liability,
+        AccountKind::Income,
+        AccountKind::Expense,
+        AccountKind::Equity,
+    ];
+}
+
+impl std::fmt::Display for AccountKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccountKind::Asset => write!(f, "Asset"),
+            AccountKind::Liability => write!(f, "Liability"),
+            AccountKind::Income => write!(f, "Income"),
+            AccountKind::Expense => write!(f, "Expense"),
+            AccountKind::Equity => write!(f, "Equity"),
+        }
+    }
+}
+
+/// What an [`AssetAccount`] holds.
+#[derive(
+    Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash, Default,
+)]
+pub enum AssetType {
+    #[default]
+    Cash,
+    /// Cash and positions in securities (see [`crate::securities`]).
+    Securities,
+    /// A non-cash asset like a house or car valued manually (see [`crate::valuation`]).
+    Valuation,
+}
+
+impl AssetType {
+    pub const ALL: [AssetType; 3] = [AssetType::Cash, AssetType::Securities, AssetType::Valuation];
+}
+
+impl std::fmt::Display for AssetType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssetType::Cash => write!(f, "Cash"),
+            AssetType::Securities => write!(f, "Securities"),
+            AssetType::Valuation => write!(f, "Valuation"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub struct AssetAccount {
     pub id: Id,
@@ -36,7 +176,18 @@ pub struct AssetAccount {
     pub note: Option<String>,
     pub iban: Option<AccountId>,
     pub bic: Option<Bic>,
+    /// Further identifiers the account is known by, for example other IBANs of the same counterparty.
+    #[serde(default)]
+    pub additional_ids: Vec<AccountId>,
+    /// Other names the account is known by.
+    #[serde(default)]
+    pub aliases: Vec<String>,
     pub offset: super::Currency,
+    #[serde(default)]
+    pub asset_type: AssetType,
+    /// Depreciation of the latest valuation of a [`AssetType::Valuation`] account.
+    #[serde(default)]
+    pub depreciation: Option<crate::valuation::Depreciation>,
 }
 
 impl AssetAccount {
@@ -54,7 +205,11 @@ impl AssetAccount {
             note,
             iban,
             bic,
+            additional_ids: Vec::new(),
+            aliases: Vec::new(),
             offset,
+            asset_type: AssetType::Cash,
+            depreciation: None,
         }
     }
 }
@@ -72,6 +227,18 @@ pub struct BookCheckingAccount {
     pub note: Option<String>,
     pub iban: Option<AccountId>,
     pub bic: Option<Bic>,
+    /// `None` if the account is not classified yet.
+    #[serde(default)]
+    pub kind: Option<AccountKind>,
+    /// Only for accounts of the kind [`AccountKind::Liability`].
+    #[serde(default)]
+    pub loan: Option<Box<crate::loan::Loan>>,
+    /// Further identifiers the account is known by, for example other IBANs of the same counterparty.
+    #[serde(default)]
+    pub additional_ids: Vec<AccountId>,
+    /// Other names the account is known by.
+    #[serde(default)]
+    pub aliases: Vec<String>,
 }
 
 impl std::fmt::Display for BookCheckingAccount {
@@ -100,6 +267,10 @@ impl BookCheckingAccount {
             note,
             iban,
             bic,
+            kind: None,
+            loan: None,
+            additional_ids: Vec::new(),
+            aliases: Vec::new(),
         }
     }
 }
@@ -145,6 +316,72 @@ impl Account {
             Account::BookCheckingAccount(acc) => acc.bic.as_ref(),
         }
     }
+
+    /// Asset accounts are always [`AccountKind::Asset`].
+    pub fn kind(&self) -> Option<AccountKind> {
+        match self {
+            Account::AssetAccount(_) => Some(AccountKind::Asset),
+            Account::BookCheckingAccount(acc) => acc.kind,
+        }
+    }
+
+    pub fn additional_ids(&self) -> &[AccountId] {
+        match self {
+            Account::AssetAccount(acc) => &acc.additional_ids,
+            Account::BookCheckingAccount(acc) => &acc.additional_ids,
+        }
+    }
+
+    pub fn aliases(&self) -> &[String] {
+        match self {
+            Account::AssetAccount(acc) => &acc.aliases,
+            Account::BookCheckingAccount(acc) => &acc.aliases,
+        }
+    }
+
+    /// The iban followed by the additional ids.
+    pub fn account_ids(&self) -> impl Iterator<Item = &AccountId> {
+        self.iban().into_iter().chain(self.additional_ids())
+    }
+
+    /// Checks if one of the account ids matches `account_id` (see [`AccountId::matches`]).
+    pub fn has_account_id(&self, account_id: &AccountId) -> bool {
+        self.account_ids().any(|x| x.matches(account_id))
+    }
+
+    /// Checks if the name or one of the aliases matches `name`, ignoring case and surrounding whitespace.
+    pub fn has_name(&self, name: &str) -> bool {
+        let name = name.trim().to_lowercase();
+        std::iter::once(self.name())
+            .chain(self.aliases().iter().map(String::as_str))
+            .any(|x| x.trim().to_lowercase() == name)
+    }
+
+    /// Adds `account_id` to the additional ids if the account is not already known by it.
+    /// Returns if the account was changed.
+    pub fn add_account_id(&mut self, account_id: AccountId) -> bool {
+        if self.has_account_id(&account_id) {
+            return false;
+        }
+        match self {
+            Account::AssetAccount(acc) => acc.additional_ids.push(account_id),
+            Account::BookCheckingAccount(acc) => acc.additional_ids.push(account_id),
+        }
+        true
+    }
+
+    /// Adds `alias` to the aliases if the account is not already known by that name.
+    /// Returns if the account was changed.
+    pub fn add_alias(&mut self, alias: String) -> bool {
+        if alias.trim().is_empty() || self.has_name(&alias) {
+            return false;
+        }
+        match self {
+            Account::AssetAccount(acc) => acc.aliases.push(alias),
+            Account::BookCheckingAccount(acc) => acc.aliases.push(alias),
+        }
+        true
+    }
 }
 
 impl std::fmt::Display for Account {
@@ -179,3 +416,47 @@ impl PartialEq<Id> for Account {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bic_validation() {
+        assert_eq!(
+            Bic::new("cobade ffxxx".to_string()).unwrap().to_string(),
+            "COBADEFFXXX"
+        );
+        assert_eq!(
+            Bic::new("DEUTDEFF".to_string()).unwrap().country_code(),
+            Some("DE")
+        );
+        assert!(Bic::new("DEUTDEF".to_string()).is_err());
+        assert!(Bic::new("DEUTDEFF50".to_string()).is_err());
+        assert!(Bic::new("DEUT1EFF".to_string()).is_err());
+        assert!(Bic::new("DEUTDEFF-00".to_string()).is_err());
+        assert!("".parse::<Bic>().is_err());
+    }
+
+    #[test]
+    fn iban_bic_country() {
+        let iban: AccountId = "DE89370400440532013000".parse().unwrap();
+        let german_bic: Bic = "COBADEFFXXX".parse().unwrap();
+        let french_bic: Bic = "BNPAFRPP".parse().unwrap();
+        assert!(check_iban_bic_country(Some(&iban), Some(&german_bic)).is_ok());
+        assert!(check_iban_bic_country(Some(&iban), Some(&french_bic)).is_err());
+        assert!(check_iban_bic_country(None, Some(&french_bic)).is_ok());
+        assert!(check_iban_bic_country(Some(&iban), None).is_ok());
+        assert!(
+            check_iban_bic_country(Some(&"PAYPAL-1".parse().unwrap()), Some(&french_bic)).is_ok()
+        );
+
+        let british_iban: AccountId = "GB29NWBK60161331926819".parse().unwrap();
+        let jersey_bic: Bic = "RBOSJESH".parse().unwrap();
+        assert!(check_iban_bic_country(Some(&british_iban), Some(&jersey_bic)).is_ok());
+        let french_iban: AccountId = "FR1420041010050500013M02606".parse().unwrap();
+        let guadeloupe_bic: Bic = "BNPAGPGP".parse().unwrap();
+        assert!(check_iban_bic_country(Some(&french_iban), Some(&guadeloupe_bic)).is_ok());
+        assert!(check_iban_bic_country(Some(&french_iban), Some(&jersey_bic)).is_err());
+    }
+}
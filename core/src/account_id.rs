@@ -1,26 +1,58 @@
 use iban_validate::IbanLike;
 use std::str::FromStr;
 
+/// Characters statements use to mask the digits of a card number.
+const CARD_MASK_CHARACTERS: [char; 4] = ['*', 'X', '#', '•'];
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub enum AccountId {
     Iban(iban_validate::Iban),
+    /// Card number of a credit or debit card where the middle digits are masked.
+    Card(MaskedCardNumber),
+    /// Wallet (like PayPal) that is identified by an email address.
+    Wallet(WalletId),
+    /// Account number together with the bank code of a national banking system.
+    National(NationalAccountNumber),
     /// For cases where no valid iban but something else is available
     Other(String),
 }
 
 impl AccountId {
+    /// The normalized representation that is also used for storage.
+    /// Parsing it again results in the same [`AccountId`].
     pub fn electronic_str(&self) -> &str {
         match self {
             AccountId::Iban(iban) => iban.electronic_str(),
+            AccountId::Card(card) => &card.0,
+            AccountId::Wallet(wallet) => &wallet.0,
+            AccountId::National(national) => &national.0,
             AccountId::Other(other) => other,
         }
     }
+
+    /// Checks if both ids identify the same account.
+    /// In contrast to `==` a masked card number without the first digits matches
+    /// the same card number with the first digits.
+    pub fn matches(&self, other: &AccountId) -> bool {
+        match (self, other) {
+            (AccountId::Card(a), AccountId::Card(b)) => {
+                a.last_digits() == b.last_digits()
+                    && (a.first_digits().is_empty()
+                        || b.first_digits().is_empty()
+                        || a.first_digits() == b.first_digits())
+            }
+            _ => self == other,
+        }
+    }
 }
 
 impl std::fmt::Display for AccountId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             AccountId::Iban(iban) => write!(f, "{iban}"),
+            AccountId::Card(card) => write!(f, "{card}"),
+            AccountId::Wallet(wallet) => write!(f, "{wallet}"),
+            AccountId::National(national) => write!(f, "{national}"),
             AccountId::Other(other) => write!(f, "{other}"),
         }
     }
@@ -32,6 +64,12 @@ impl FromStr for AccountId {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Ok(iban) = s.trim().to_uppercase().replace(" ", "").parse() {
             Ok(AccountId::Iban(iban))
+        } else if let Ok(card) = s.parse() {
+            Ok(AccountId::Card(card))
+        } else if let Ok(wallet) = s.parse() {
+            Ok(AccountId::Wallet(wallet))
+        } else if let Ok(national) = s.parse() {
+            Ok(AccountId::National(national))
         } else {
             Ok(AccountId::Other(s.to_string()))
         }
@@ -43,3 +81,205 @@ impl From<String> for AccountId {
         s.parse().unwrap()
     }
 }
+
+/// Card number like `4111 11** **** 1234` or `XXXX XXXX XXXX 1234`.
+/// Stored as the visible first digits, `****` and the last four digits.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct MaskedCardNumber(String);
+
+impl MaskedCardNumber {
+    /// The visible digits before the mask. Can be empty.
+    pub fn first_digits(&self) -> &str {
+        self.0.split('*').next().unwrap_or_default()
+    }
+
+    pub fn last_digits(&self) -> &str {
+        self.0.rsplit('*').next().unwrap_or_default()
+    }
+}
+
+impl std::fmt::Display for MaskedCardNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for MaskedCardNumber {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = s
+            .trim()
+            .to_uppercase()
+            .replace([' ', '-'], "")
+            .replace(CARD_MASK_CHARACTERS, "*");
+        if !number.chars().all(|c| c.is_ascii_digit() || c == '*') {
+            anyhow::bail!("A masked card number can only contain digits and mask characters");
+        }
+        let first_digits = number.split('*').next().unwrap_or_default();
+        let last_digits = number.rsplit('*').next().unwrap_or_default();
+        // the mask has to be in one piece
+        if first_digits.len() + last_digits.len() + number.matches('*').count() != number.len() {
+            anyhow::bail!("A masked card number must have exactly one masked part");
+        }
+        // the normalized form without first digits is 8 characters long
+        if last_digits.len() != 4 || first_digits.len() > 8 || !(8..=19).contains(&number.len()) {
+            anyhow::bail!("{s} is not a masked card number");
+        }
+        Ok(Self(format!("{first_digits}****{last_digits}")))
+    }
+}
+
+/// Wallet id in the form of an email address. Stored in lowercase.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct WalletId(String);
+
+impl WalletId {
+    pub fn email(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for WalletId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for WalletId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let email = s.trim().to_lowercase();
+        let valid = match email.split_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && !domain.contains('@')
+                    && domain
+                        .split_once('.')
+                        .is_some_and(|(name, tld)| !name.is_empty() && !tld.is_empty())
+                    && !email.contains(char::is_whitespace)
+            }
+            None => false,
+        };
+        if !valid {
+            anyhow::bail!("{s} is not an email address");
+        }
+        Ok(Self(email))
+    }
+}
+
+/// Account number with bank code like `37040044/532013000` or a sort code like `40-00-04/12345678`.
+/// Stored as `<bank code>/<account number>` without leading zeros of the account number.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct NationalAccountNumber(String);
+
+impl NationalAccountNumber {
+    pub fn bank_code(&self) -> &str {
+        self.0.split('/').next().unwrap_or_default()
+    }
+
+    pub fn account_number(&self) -> &str {
+        self.0.rsplit('/').next().unwrap_or_default()
+    }
+}
+
+impl std::fmt::Display for NationalAccountNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for NationalAccountNumber {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (bank_code, account_number) = s
+            .split_once('/')
+            .ok_or_else(|| anyhow::anyhow!("{s} has no bank code separated by /"))?;
+        let bank_code = bank_code.replace([' ', '-'], "").to_uppercase();
+        let account_number = account_number.replace([' ', '-'], "").to_uppercase();
+        let account_number = account_number.trim_start_matches('0');
+        if bank_code.is_empty()
+            || account_number.is_empty()
+            || !bank_code.chars().all(|c| c.is_ascii_digit())
+            || !account_number.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            anyhow::bail!("{s} is not an account number with bank code");
+        }
+        Ok(Self(format!("{bank_code}/{account_number}")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_variants() {
+        assert!(matches!(
+            "DE89 3704 0044 0532 0130 00".parse().unwrap(),
+            AccountId::Iban(_)
+        ));
+        assert_eq!(
+            "4111 11** **** 1234".parse::<AccountId>().unwrap(),
+            AccountId::Card(MaskedCardNumber("411111****1234".to_string()))
+        );
+        assert_eq!(
+            "xxxx-xxxx-xxxx-1234".parse::<AccountId>().unwrap(),
+            AccountId::Card(MaskedCardNumber("****1234".to_string()))
+        );
+        assert_eq!(
+            " John.Doe@Example.com".parse::<AccountId>().unwrap(),
+            AccountId::Wallet(WalletId("john.doe@example.com".to_string()))
+        );
+        assert_eq!(
+            "370 400 44 / 0532013000".parse::<AccountId>().unwrap(),
+            AccountId::National(NationalAccountNumber("37040044/532013000".to_string()))
+        );
+        assert_eq!(
+            "40-00-04/12345678".parse::<AccountId>().unwrap(),
+            AccountId::National(NationalAccountNumber("400004/12345678".to_string()))
+        );
+        for other in [
+            "PAYPAL-1",
+            "1234",
+            "12**34**5678",
+            "Ref123/456",
+            "@example.com",
+        ] {
+            assert_eq!(
+                other.parse::<AccountId>().unwrap(),
+                AccountId::Other(other.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn electronic_str_roundtrip() {
+        for id in [
+            "DE89370400440532013000",
+            "4111 11** **** 1234",
+            "**** 1234",
+            "John.Doe@Example.com",
+            "37040044/0532013000",
+            "something else",
+        ] {
+            let id: AccountId = id.parse().unwrap();
+            assert_eq!(id.electronic_str().parse::<AccountId>().unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn card_matches() {
+        let full: AccountId = "411111******1234".parse().unwrap();
+        let short: AccountId = "************1234".parse().unwrap();
+        let other_prefix: AccountId = "522222******1234".parse().unwrap();
+        let other_card: AccountId = "411111******4321".parse().unwrap();
+        assert!(full.matches(&short));
+        assert!(short.matches(&full));
+        assert!(!full.matches(&other_prefix));
+        assert!(!full.matches(&other_card));
+        assert_ne!(full, short);
+    }
+}
@@ -0,0 +1,97 @@
+//! Sums of transactions grouped by category, account, budget and/or period.
+//!
+//! Aggregations are calculated by the [`crate::FinanceManager`] so that only the sums and not the
+//! transactions have to be transferred. [`aggregate`] is the in-memory reference implementation.
+
+use super::{Currency, DateTime, Id, Sign, Transaction};
+use std::collections::BTreeMap;
+
+/// What the transactions are grouped by.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
+pub enum GroupBy {
+    /// Signed by the sign of the category.
+    /// A transaction counts for each of its categories, transactions without a category are left out.
+    Category,
+    /// Positive for the destination and negative for the source of a transaction.
+    Account,
+    /// Signed by the sign of the budget, transactions without a budget are left out.
+    Budget,
+}
+
+/// The length of the periods the transactions are additionally grouped by.
+/// The offset decides on which day a transaction is.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
+pub enum Period {
+    Day(time::UtcOffset),
+    Month(time::UtcOffset),
+}
+
+impl Period {
+    /// The first day of the period that contains `date`.
+    pub fn start_of(&self, date: DateTime) -> time::Date {
+        match self {
+            Period::Day(offset) => date.to_offset(*offset).date(),
+            Period::Month(offset) => date.to_offset(*offset).date().replace_day(1).unwrap(),
+        }
+    }
+}
+
+/// The sum of a group.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct Aggregate {
+    /// The category, account or budget. `None` if the transactions are not grouped.
+    pub group: Option<Id>,
+    /// The first day of the period. `None` if the transactions are not grouped by period.
+    pub period: Option<time::Date>,
+    /// Without grouping the amounts are summed up unsigned.
+    pub sum: Currency,
+    /// The number of transactions in the group.
+    pub count: usize,
+}
+
+/// Sums up the transactions per group and period.
+/// The result is sorted by group and period, no grouping is sorted first.
+pub fn aggregate(
+    transactions: &[Transaction],
+    group_by: Option<GroupBy>,
+    period: Option<Period>,
+) -> Vec<Aggregate> {
+    let mut groups: BTreeMap<(Option<Id>, Option<time::Date>), (Currency, usize)> = BTreeMap::new();
+    for transaction in transactions {
+        let period = period.map(|period| period.start_of(transaction.date));
+        let signed_groups = match group_by {
+            None => vec![(None, Sign::Positive)],
+            Some(GroupBy::Category) => transaction
+                .categories
+                .iter()
+                .map(|(id, sign)| (Some(*id), *sign))
+                .collect(),
+            Some(GroupBy::Account) => vec![
+                (Some(transaction.destination), Sign::Positive),
+                (Some(transaction.source), Sign::Negative),
+            ],
+            Some(GroupBy::Budget) => transaction
+                .budget
+                .iter()
+                .map(|(id, sign)| (Some(*id), *sign))
+                .collect(),
+        };
+        for (group, sign) in signed_groups {
+            let (sum, count) = groups.entry((group, period)).or_default();
+            match sign {
+                Sign::Positive => *sum += transaction.amount(),
+                Sign::Negative => *sum -= transaction.amount(),
+            }
+            *count += 1;
+        }
+    }
+    groups
+        .into_iter()
+        .map(|((group, period), (sum, count))| Aggregate {
+            group,
+            period,
+            sum,
+            count,
+        })
+        .collect()
+}
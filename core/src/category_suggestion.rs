@@ -0,0 +1,235 @@
+//! Category suggestions learned from the already categorized transactions.
+//!
+//! The model is a multinomial naive bayes classifier over the title tokens, the accounts
+//! (source and destination) and a rough amount bucket of a transaction.
+
+use super::{Currency, Id, Sign, Transaction};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CategorySuggestion {
+    pub category: Id,
+    /// The sign that was used most often for this category.
+    pub sign: Sign,
+    /// Value between 0 and 1. The confidences of all suggestions for one query sum up to 1.
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct CategoryStatistics {
+    transactions: usize,
+    positive: usize,
+    negative: usize,
+    feature_counts: HashMap<String, usize>,
+    total_features: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CategoryModel {
+    categories: HashMap<Id, CategoryStatistics>,
+    vocabulary: HashSet<String>,
+    trained_transactions: usize,
+}
+
+impl CategoryModel {
+    pub fn new(transactions: &[Transaction]) -> Self {
+        let mut model = Self::default();
+        for transaction in transactions {
+            model.train(transaction);
+        }
+        model
+    }
+
+    /// Adds the transaction to the model. Transactions without categories are ignored.
+    pub fn train(&mut self, transaction: &Transaction) {
+        if transaction.categories.is_empty() {
+            return;
+        }
+        self.trained_transactions += 1;
+
+        let features = features(
+            &transaction.title,
+            Some(transaction.source),
+            Some(transaction.destination),
+            transaction.amount(),
+        );
+        for feature in &features {
+            self.vocabulary.insert(feature.clone());
+        }
+
+        for (category, sign) in &transaction.categories {
+            let statistics = self.categories.entry(*category).or_default();
+            statistics.transactions += 1;
+            match sign {
+                Sign::Positive => statistics.positive += 1,
+                Sign::Negative => statistics.negative += 1,
+            }
+            for feature in &features {
+                *statistics
+                    .feature_counts
+                    .entry(feature.clone())
+                    .or_default() += 1;
+                statistics.total_features += 1;
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trained_transactions == 0
+    }
+
+    /// Returns the suggestions sorted by confidence (highest first).
+    pub fn suggest(
+        &self,
+        title: &str,
+        source: Option<Id>,
+        destination: Option<Id>,
+        amount: &Currency,
+    ) -> Vec<CategorySuggestion> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+
+        let features = features(title, source, destination, amount);
+        let vocabulary_size = self.vocabulary.len() as f64;
+
+        let mut scores = Vec::with_capacity(self.categories.len());
+        for (category, statistics) in &self.categories {
+            let mut score =
+                (statistics.transactions as f64 / self.trained_transactions as f64).ln();
+            for feature in &features {
+                let count = statistics.feature_counts.get(feature).copied().unwrap_or(0);
+                // laplace smoothing
+                score += ((count as f64 + 1.0)
+                    / (statistics.total_features as f64 + vocabulary_size))
+                    .ln();
+            }
+            let sign = if statistics.negative > statistics.positive {
+                Sign::Negative
+            } else {
+                Sign::Positive
+            };
+            scores.push((*category, sign, score));
+        }
+
+        // softmax to turn the log scores into confidences
+        let max_score = scores.iter().map(|x| x.2).fold(f64::NEG_INFINITY, f64::max);
+        let total: f64 = scores.iter().map(|x| (x.2 - max_score).exp()).sum();
+
+        let mut suggestions = scores
+            .into_iter()
+            .map(|(category, sign, score)| CategorySuggestion {
+                category,
+                sign,
+                confidence: (score - max_score).exp() / total,
+            })
+            .collect::<Vec<_>>();
+        suggestions.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        suggestions
+    }
+}
+
+fn features(
+    title: &str,
+    source: Option<Id>,
+    destination: Option<Id>,
+    amount: &Currency,
+) -> Vec<String> {
+    let mut features = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| token.chars().count() >= 2 && token.chars().any(char::is_alphabetic))
+        .map(|token| format!("title:{}", token.to_lowercase()))
+        .collect::<Vec<_>>();
+    if let Some(source) = source {
+        features.push(format!("account:{source}"));
+    }
+    if let Some(destination) = destination {
+        features.push(format!("account:{destination}"));
+    }
+    // amounts in the same power of two end up in the same bucket
+    let amount = amount.get_eur_num().abs();
+    if amount >= 1.0 {
+        features.push(format!("amount:{}", amount.log2().floor() as i64));
+    } else {
+        features.push("amount:<1".to_string());
+    }
+    features
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn transaction(
+        id: Id,
+        title: &str,
+        destination: Id,
+        amount: f64,
+        categories: HashMap<Id, Sign>,
+    ) -> Transaction {
+        Transaction::new(
+            id,
+            Currency::from(amount),
+            title.to_string(),
+            None,
+            1,
+            destination,
+            None,
+            time::OffsetDateTime::now_utc(),
+            HashMap::new(),
+            categories,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn empty_model_has_no_suggestions() {
+        let model = CategoryModel::new(&[transaction(1, "Rent", 2, 800.0, HashMap::new())]);
+        assert!(model.is_empty());
+        assert!(
+            model
+                .suggest("Rent", Some(1), Some(2), &Currency::default())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn suggest_by_title_and_account() {
+        let model = CategoryModel::new(&[
+            transaction(
+                1,
+                "Supermarket Groceries",
+                2,
+                34.5,
+                HashMap::from([(10, Sign::Negative)]),
+            ),
+            transaction(
+                2,
+                "SUPERMARKET Berlin",
+                2,
+                12.0,
+                HashMap::from([(10, Sign::Negative)]),
+            ),
+            transaction(
+                3,
+                "Rent April",
+                3,
+                800.0,
+                HashMap::from([(20, Sign::Positive)]),
+            ),
+        ]);
+
+        let suggestions = model.suggest("supermarket", Some(1), Some(2), &Currency::from(20.0));
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].category, 10);
+        assert_eq!(suggestions[0].sign, Sign::Negative);
+        assert!(suggestions[0].confidence > 0.5);
+
+        let total: f64 = suggestions.iter().map(|x| x.confidence).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        let suggestions = model.suggest("Rent May", Some(1), Some(3), &Currency::from(800.0));
+        assert_eq!(suggestions[0].category, 20);
+        assert_eq!(suggestions[0].sign, Sign::Positive);
+    }
+}
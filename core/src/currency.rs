@@ -34,6 +34,13 @@ impl Currency {
         }
     }
 
+    /// Multiplies the amount with `factor` and rounds it to cents.
+    pub fn scale(&self, factor: &BigDecimal) -> Self {
+        match self {
+            Currency::Eur(x) => Currency::Eur((x * factor).round(2)),
+        }
+    }
+
     pub fn negative(&self) -> Self {
         match self {
             Currency::Eur(x) => Currency::Eur(x.neg()),
@@ -188,3 +195,9 @@ impl Default for Currency {
         Currency::Eur(BigDecimal::from_f64(0.0).unwrap())
     }
 }
+
+/// An exact EUR amount like `eur("12.34")` for tests.
+#[cfg(any(test, feature = "test"))]
+pub fn eur(value: &str) -> Currency {
+    Currency::from_currency_id(1, value.parse().unwrap()).unwrap()
+}
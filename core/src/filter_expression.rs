@@ -0,0 +1,432 @@
+//! Composable filters of transactions.
+//!
+//! A [`FilterExpression`] combines [`Condition`]s with AND, OR and NOT and can express everything
+//! a [`TransactionFilter`] can (see the [`From`] implementation) and more, e.g.
+//! "category Food AND account Daily, OR budget Groceries".
+
+use super::{Bill, Id, Timespan, Transaction};
+use crate::transaction_filter::{
+    AmountRange, Filter, MetadataFilter, TextSearch, TransactionFilter,
+};
+
+/// A single property of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash)]
+pub enum Condition {
+    /// The source or destination is the account. `None` matches every transaction.
+    Account(Option<Id>),
+    /// The transaction has the category. `None` matches transactions with any category.
+    Category(Option<Id>),
+    /// The transaction is in the bill. `None` matches transactions in any bill.
+    Bill(Option<Id>),
+    /// The transaction has the budget. `None` matches transactions with any budget.
+    Budget(Option<Id>),
+    Text(TextSearch),
+    Amount(AmountRange),
+    Metadata(MetadataFilter),
+    /// The date is in the inclusive timespan.
+    Timespan(Timespan),
+}
+
+impl Condition {
+    pub fn matches(&self, transaction: &Transaction, bills: &[Bill]) -> bool {
+        match self {
+            Condition::Account(Some(id)) => transaction.connection_with_account(*id),
+            Condition::Account(None) => true,
+            Condition::Category(Some(id)) => transaction.categories.contains_key(id),
+            Condition::Category(None) => !transaction.categories.is_empty(),
+            Condition::Bill(id) => bills.iter().any(|bill| {
+                id.is_none_or(|id| bill.id == id) && bill.transactions.contains_key(&transaction.id)
+            }),
+            Condition::Budget(Some(id)) => transaction.budget.is_some_and(|x| x.0 == *id),
+            Condition::Budget(None) => transaction.budget.is_some(),
+            Condition::Text(search) => search.matches(transaction),
+            Condition::Amount(range) => range.contains(transaction.amount()),
+            Condition::Metadata(predicate) => predicate.matches(transaction),
+            Condition::Timespan((start, end)) => {
+                start.is_none_or(|start| start <= transaction.date)
+                    && end.is_none_or(|end| end >= transaction.date)
+            }
+        }
+    }
+}
+
+/// A tree of conditions.
+/// An empty `And` matches every transaction, an empty `Or` none.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash)]
+pub enum FilterExpression {
+    Condition(Condition),
+    And(Vec<FilterExpression>),
+    Or(Vec<FilterExpression>),
+    Not(Box<FilterExpression>),
+}
+
+impl FilterExpression {
+    pub fn matches(&self, transaction: &Transaction, bills: &[Bill]) -> bool {
+        match self {
+            FilterExpression::Condition(condition) => condition.matches(transaction, bills),
+            FilterExpression::And(expressions) => expressions
+                .iter()
+                .all(|expression| expression.matches(transaction, bills)),
+            FilterExpression::Or(expressions) => expressions
+                .iter()
+                .any(|expression| expression.matches(transaction, bills)),
+            FilterExpression::Not(expression) => !expression.matches(transaction, bills),
+        }
+    }
+
+    pub fn filter_transactions(
+        &self,
+        mut transactions: Vec<Transaction>,
+        bills: &[Bill],
+    ) -> Vec<Transaction> {
+        transactions.retain(|transaction| self.matches(transaction, bills));
+        transactions
+    }
+
+    /// A timespan that contains every matching transaction.
+    /// It is only narrower than `(None, None)` if the expression restricts the date.
+    pub fn timespan(&self) -> Timespan {
+        match self {
+            FilterExpression::Condition(Condition::Timespan(timespan)) => *timespan,
+            FilterExpression::Condition(_) | FilterExpression::Not(_) => (None, None),
+            FilterExpression::And(expressions) => {
+                expressions.iter().map(FilterExpression::timespan).fold(
+                    (None, None),
+                    |(start, end), (other_start, other_end)| {
+                        // `None` is no end and not the earliest end
+                        let end = match (end, other_end) {
+                            (Some(end), Some(other)) => Some(end.min(other)),
+                            (end, other) => end.or(other),
+                        };
+                        (start.max(other_start), end)
+                    },
+                )
+            }
+            FilterExpression::Or(expressions) => {
+                let mut timespans = expressions.iter().map(FilterExpression::timespan);
+                let Some(first) = timespans.next() else {
+                    return (None, None);
+                };
+                timespans.fold(first, |(start, end), (other_start, other_end)| {
+                    (
+                        start
+                            .zip(other_start)
+                            .map(|(start, other)| start.min(other)),
+                        end.zip(other_end).map(|(end, other)| end.max(other)),
+                    )
+                })
+            }
+        }
+    }
+}
+
+impl From<Condition> for FilterExpression {
+    fn from(value: Condition) -> Self {
+        FilterExpression::Condition(value)
+    }
+}
+
+impl std::ops::Not for FilterExpression {
+    type Output = FilterExpression;
+
+    fn not(self) -> Self::Output {
+        match self {
+            FilterExpression::Not(expression) => *expression,
+            expression => FilterExpression::Not(Box::new(expression)),
+        }
+    }
+}
+
+impl std::ops::BitAnd for FilterExpression {
+    type Output = FilterExpression;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        match self {
+            FilterExpression::And(mut expressions) => {
+                expressions.push(rhs);
+                FilterExpression::And(expressions)
+            }
+            expression => FilterExpression::And(vec![expression, rhs]),
+        }
+    }
+}
+
+impl std::ops::BitOr for FilterExpression {
+    type Output = FilterExpression;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        match self {
+            FilterExpression::Or(mut expressions) => {
+                expressions.push(rhs);
+                FilterExpression::Or(expressions)
+            }
+            expression => FilterExpression::Or(vec![expression, rhs]),
+        }
+    }
+}
+
+/// Has the same semantics as [`TransactionFilter::filter_transactions`]:
+/// a transaction matches if it matches any include filter and no exclude filter in their timespans.
+impl From<TransactionFilter> for FilterExpression {
+    fn from(value: TransactionFilter) -> Self {
+        let default_timespan = value.default_timespan;
+        let mut includes = Vec::new();
+        let mut excludes = Vec::new();
+        // filters without a condition match everything
+        let mut push = |filter: Filter<()>, condition: Option<Condition>| {
+            let condition = condition.map_or(FilterExpression::And(Vec::new()), From::from);
+            let expression = FilterExpression::And(vec![
+                if filter.negated {
+                    !condition
+                } else {
+                    condition
+                },
+                Condition::Timespan(filter.timespan.unwrap_or(default_timespan)).into(),
+            ]);
+            if filter.include {
+                includes.push(expression);
+            } else {
+                excludes.push(expression);
+            }
+        };
+        for filter in value.accounts {
+            push(without_id(&filter), Some(Condition::Account(filter.id)));
+        }
+        for filter in value.categories {
+            push(without_id(&filter), Some(Condition::Category(filter.id)));
+        }
+        for filter in value.bills {
+            push(
+                without_id(&filter),
+                Some(Condition::Bill(filter.id.map(|bill| bill.id))),
+            );
+        }
+        for filter in value.budgets {
+            push(without_id(&filter), Some(Condition::Budget(filter.id)));
+        }
+        for filter in value.texts {
+            push(without_id(&filter), filter.id.map(Condition::Text));
+        }
+        for filter in value.amounts {
+            push(without_id(&filter), filter.id.map(Condition::Amount));
+        }
+        for filter in value.metadata {
+            push(without_id(&filter), filter.id.map(Condition::Metadata));
+        }
+        FilterExpression::Or(includes) & !FilterExpression::Or(excludes)
+    }
+}
+
+fn without_id<I: Clone + std::fmt::Debug>(filter: &Filter<I>) -> Filter<()> {
+    Filter {
+        negated: filter.negated,
+        id: None,
+        include: filter.include,
+        timespan: filter.timespan,
+        relative_timespan: None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Currency, Sign};
+    use std::collections::HashMap;
+    use time::macros::datetime;
+
+    fn transaction(
+        id: Id,
+        source: Id,
+        destination: Id,
+        budget: Option<Id>,
+        categories: &[Id],
+        date: crate::DateTime,
+    ) -> Transaction {
+        Transaction::new(
+            id,
+            Currency::from(id as f64),
+            format!("Transaction {id}"),
+            None,
+            source,
+            destination,
+            budget.map(|budget| (budget, Sign::Positive)),
+            date,
+            HashMap::new(),
+            categories
+                .iter()
+                .map(|category| (*category, Sign::Negative))
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    fn transactions() -> Vec<Transaction> {
+        vec![
+            transaction(1, 1, 2, None, &[1], datetime!(2024-01-01 12:00 UTC)),
+            transaction(2, 1, 3, Some(1), &[1, 2], datetime!(2024-02-01 12:00 UTC)),
+            transaction(3, 2, 3, Some(2), &[], datetime!(2024-03-01 12:00 UTC)),
+            transaction(4, 3, 1, None, &[2], datetime!(2024-04-01 12:00 UTC)),
+        ]
+    }
+
+    fn bills() -> Vec<Bill> {
+        vec![Bill::new(
+            1,
+            "Bill".to_string(),
+            None,
+            Currency::default(),
+            HashMap::from([(2, Sign::Positive), (3, Sign::Negative)]),
+            None,
+            false,
+        )]
+    }
+
+    fn ids(transactions: Vec<Transaction>) -> Vec<Id> {
+        transactions
+            .iter()
+            .map(|transaction| transaction.id)
+            .collect()
+    }
+
+    fn filter<I: Clone + std::fmt::Debug>(
+        id: Option<I>,
+        negated: bool,
+        include: bool,
+        timespan: Option<Timespan>,
+    ) -> Filter<I> {
+        Filter {
+            negated,
+            id,
+            include,
+            timespan,
+            relative_timespan: None,
+        }
+    }
+
+    #[test]
+    fn legacy_semantics() {
+        let march = Some((Some(datetime!(2024-03-01 0:00 UTC)), None));
+        let filters = [
+            TransactionFilter::default(),
+            TransactionFilter::default().push_account(filter(Some(1), false, true, None)),
+            TransactionFilter::default().push_account(filter(None, false, true, march)),
+            TransactionFilter::default()
+                .push_account(filter(Some(3), false, true, None))
+                .push_category(filter(Some(2), false, false, march)),
+            TransactionFilter::default()
+                .push_account(filter(None, false, true, None))
+                .push_category(filter(None, true, false, None)),
+            TransactionFilter::default().push_bill(filter(
+                Some(bills()[0].clone()),
+                true,
+                true,
+                None,
+            )),
+            TransactionFilter::default().push_bill(filter(None, false, true, None)),
+            TransactionFilter::default()
+                .push_budget(filter(None, false, true, None))
+                .push_budget(filter(Some(2), false, false, None)),
+            TransactionFilter {
+                default_timespan: (
+                    Some(datetime!(2024-02-01 12:00 UTC)),
+                    Some(datetime!(2024-03-01 12:00 UTC)),
+                ),
+                ..Default::default()
+            }
+            .push_account(filter(Some(2), true, true, None))
+            .push_text(filter(
+                Some(TextSearch::Substring("transaction 4".to_string())),
+                false,
+                true,
+                None,
+            )),
+            TransactionFilter::default()
+                .push_amount(filter(
+                    Some(AmountRange {
+                        min: Some(Currency::from(2.0)),
+                        max: None,
+                    }),
+                    false,
+                    true,
+                    None,
+                ))
+                .push_metadata(filter(None, false, false, march)),
+        ];
+        for transaction_filter in filters {
+            assert_eq!(
+                ids(FilterExpression::from(transaction_filter.clone())
+                    .filter_transactions(transactions(), &bills())),
+                ids(transaction_filter.filter_transactions(transactions(), &bills())),
+                "{transaction_filter:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn and_or_not() {
+        // category 1 AND account 3, OR budget 2
+        let expression = (FilterExpression::from(Condition::Category(Some(1)))
+            & Condition::Account(Some(3)).into())
+            | Condition::Budget(Some(2)).into();
+        assert_eq!(
+            ids(expression.filter_transactions(transactions(), &bills())),
+            vec![2, 3]
+        );
+        assert_eq!(
+            ids((!expression.clone()).filter_transactions(transactions(), &bills())),
+            vec![1, 4]
+        );
+        assert_eq!(!!expression.clone(), expression);
+        assert_eq!(
+            ids(FilterExpression::And(Vec::new()).filter_transactions(transactions(), &bills())),
+            vec![1, 2, 3, 4]
+        );
+        assert!(
+            FilterExpression::Or(Vec::new())
+                .filter_transactions(transactions(), &bills())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn timespan() {
+        let january = Condition::Timespan((
+            Some(datetime!(2024-01-01 0:00 UTC)),
+            Some(datetime!(2024-01-31 0:00 UTC)),
+        ));
+        let march = Condition::Timespan((Some(datetime!(2024-03-01 0:00 UTC)), None));
+        let account = FilterExpression::from(Condition::Account(Some(1)));
+        assert_eq!(
+            (account.clone() & january.clone().into()).timespan(),
+            (
+                Some(datetime!(2024-01-01 0:00 UTC)),
+                Some(datetime!(2024-01-31 0:00 UTC))
+            )
+        );
+        assert_eq!(
+            (FilterExpression::from(january.clone()) | march.clone().into()).timespan(),
+            (Some(datetime!(2024-01-01 0:00 UTC)), None)
+        );
+        assert_eq!(
+            (FilterExpression::from(january.clone()) & march.into()).timespan(),
+            (
+                Some(datetime!(2024-03-01 0:00 UTC)),
+                Some(datetime!(2024-01-31 0:00 UTC))
+            )
+        );
+        assert_eq!((account.clone() | january.into()).timespan(), (None, None));
+        assert_eq!((!account).timespan(), (None, None));
+    }
+
+    #[test]
+    fn serde() {
+        let expression = FilterExpression::from(Condition::Category(Some(1)))
+            & !FilterExpression::from(Condition::Text(TextSearch::Regex("^a".to_string())))
+            | Condition::Timespan((Some(datetime!(2024-01-01 0:00 UTC)), None)).into();
+        assert_eq!(
+            serde_json::from_str::<FilterExpression>(&serde_json::to_string(&expression).unwrap())
+                .unwrap(),
+            expression
+        );
+    }
+}
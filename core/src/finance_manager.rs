@@ -25,6 +25,52 @@ pub trait FinanceManager: Send + Clone + Sized + std::fmt::Debug {
     /// This should only delete the account and nothing else (like asserted transactions).
     fn delete_account(&mut self, id: Id) -> impl Future<Output = Result<()>> + MaybeSend;
 
+    /// Updates `keep`, moves all transactions, trades and valuations of `remove` to `keep` and deletes `remove`.
+    /// Implementations should do this atomically if possible.
+    fn merge_accounts(
+        &mut self,
+        keep: account::Account,
+        remove: Id,
+    ) -> impl Future<Output = Result<account::Account>> + MaybeSend {
+        async move {
+            let keep: account::Account = match keep {
+                account::Account::AssetAccount(acc) => self.update_asset_account(acc).await?.into(),
+                account::Account::BookCheckingAccount(acc) => {
+                    self.update_book_checking_account(acc).await?.into()
+                }
+            };
+            for mut transaction in self
+                .get_transactions_of_account(remove, (None, None))
+                .await?
+            {
+                if transaction.source == remove {
+                    transaction.source = *keep.id();
+                }
+                if transaction.destination == remove {
+                    transaction.destination = *keep.id();
+                }
+                self.update_transaction(transaction).await?;
+            }
+            for trade in self.get_trades(remove).await? {
+                self.create_trade(
+                    *keep.id(),
+                    trade.isin,
+                    trade.kind,
+                    trade.quantity,
+                    trade.amount,
+                    trade.date,
+                )
+                .await?;
+            }
+            for valuation in self.get_valuations(remove).await? {
+                self.create_valuation(*keep.id(), valuation.value, valuation.date)
+                    .await?;
+            }
+            self.delete_account(remove).await?;
+            Ok(keep)
+        }
+    }
+
     fn create_book_checking_account(
         &mut self,
         name: String,
@@ -38,29 +84,33 @@ pub trait FinanceManager: Send + Clone + Sized + std::fmt::Debug {
         account: account::BookCheckingAccount,
     ) -> impl Future<Output = Result<account::BookCheckingAccount>> + MaybeSend;
 
-    /// Only get the sum of the transactions for the account at the given date.
+    /// Only get the sum of the transactions for the account at the given date,
+    /// where `date_kind` decides which date of the transactions is compared.
     /// Do not include any AssetAccount.offset or similar!
-    /// This should almost never be overwritten!
+    /// This should almost never be overwritten, except to select the transactions in a query!
     fn get_account_sum(
         &self,
         account: &account::Account,
         date: DateTime,
+        date_kind: DateKind,
     ) -> impl Future<Output = Result<Currency>> + MaybeSend {
-        let transactions_future =
-            self.get_transactions_of_account(*account.id(), (None, Some(date)));
+        // transactions booked after the date can have a value date before it,
+        // so managers that are not in memory should select them in a query instead
+        let transactions_future = self.get_transactions_of_account(
+            *account.id(),
+            match date_kind {
+                DateKind::Booking => (None, Some(date)),
+                DateKind::Value => (None, None),
+            },
+        );
 
         async move {
-            let transactions = transactions_future.await?;
-
-            let mut sum = Currency::default();
-            for transaction in transactions {
-                if transaction.source == *account.id() {
-                    sum -= transaction.amount();
-                } else if transaction.destination == *account.id() {
-                    sum += transaction.amount();
-                }
-            }
-            Ok(sum)
+            Ok(account_sum(
+                *account.id(),
+                transactions_future.await?,
+                date,
+                date_kind,
+            ))
         }
     }
 
@@ -85,6 +135,51 @@ pub trait FinanceManager: Send + Clone + Sized + std::fmt::Debug {
 
     fn delete_bill(&mut self, id: Id) -> impl Future<Output = Result<()>> + MaybeSend;
 
+    fn create_trade(
+        &mut self,
+        account: Id,
+        isin: securities::Isin,
+        kind: securities::TradeKind,
+        quantity: bigdecimal::BigDecimal,
+        amount: Currency,
+        date: DateTime,
+    ) -> impl Future<Output = Result<securities::Trade>> + MaybeSend;
+
+    fn delete_trade(&mut self, id: Id) -> impl Future<Output = Result<()>> + MaybeSend;
+
+    /// Gets the trades of the securities account sorted by date.
+    fn get_trades(
+        &self,
+        account: Id,
+    ) -> impl Future<Output = Result<Vec<securities::Trade>>> + MaybeSend;
+
+    /// Adds the prices to the price history. Replaces prices of the same security at the same date.
+    fn add_prices(
+        &mut self,
+        prices: Vec<securities::Price>,
+    ) -> impl Future<Output = Result<()>> + MaybeSend;
+
+    /// Gets the price history of the security sorted by date.
+    fn get_prices(
+        &self,
+        isin: securities::Isin,
+    ) -> impl Future<Output = Result<Vec<securities::Price>>> + MaybeSend;
+
+    fn create_valuation(
+        &mut self,
+        account: Id,
+        value: Currency,
+        date: DateTime,
+    ) -> impl Future<Output = Result<valuation::Valuation>> + MaybeSend;
+
+    fn delete_valuation(&mut self, id: Id) -> impl Future<Output = Result<()>> + MaybeSend;
+
+    /// Gets the valuations of the account sorted by date.
+    fn get_valuations(
+        &self,
+        account: Id,
+    ) -> impl Future<Output = Result<Vec<valuation::Valuation>>> + MaybeSend;
+
     fn get_filtered_transactions(
         &self,
         filter: transaction_filter::TransactionFilter,
@@ -99,6 +194,54 @@ pub trait FinanceManager: Send + Clone + Sized + std::fmt::Debug {
         }
     }
 
+    fn get_transactions_matching(
+        &self,
+        expression: filter_expression::FilterExpression,
+    ) -> impl Future<Output = Result<Vec<Transaction>>> + MaybeSend {
+        let transactions_future = self.get_transactions_in_timespan(expression.timespan());
+        let bills_future = self.get_bills(None);
+        async move {
+            let transactions = transactions_future.await?;
+            let bills = bills_future.await?;
+            Ok(expression.filter_transactions(transactions, &bills))
+        }
+    }
+
+    /// Streams the transactions matching `expression` in no particular order,
+    /// without loading all of them at once.
+    fn stream_transactions(
+        &self,
+        expression: filter_expression::FilterExpression,
+    ) -> TransactionStream;
+
+    /// Gets one page of the transactions matching `expression` and their total count
+    /// (see [`pagination::paginate`]).
+    fn get_transactions_page(
+        &self,
+        expression: filter_expression::FilterExpression,
+        page: pagination::PageRequest,
+    ) -> impl Future<Output = Result<pagination::Page<Transaction>>> + MaybeSend {
+        let transactions_future = self.get_transactions_matching(expression);
+        async move { Ok(pagination::paginate(transactions_future.await?, &page)) }
+    }
+
+    /// Sums up the transactions matching `filter` (see [`aggregation::aggregate`]).
+    fn aggregate_transactions(
+        &self,
+        filter: transaction_filter::TransactionFilter,
+        group_by: Option<aggregation::GroupBy>,
+        period: Option<aggregation::Period>,
+    ) -> impl Future<Output = Result<Vec<aggregation::Aggregate>>> + MaybeSend {
+        let transactions_future = self.get_filtered_transactions(filter);
+        async move {
+            Ok(aggregation::aggregate(
+                &transactions_future.await?,
+                group_by,
+                period,
+            ))
+        }
+    }
+
     fn get_accounts(&self) -> impl Future<Output = Result<Vec<account::Account>>> + MaybeSend;
 
     fn get_account(
@@ -106,6 +249,37 @@ pub trait FinanceManager: Send + Clone + Sized + std::fmt::Debug {
         id: Id,
     ) -> impl Future<Output = Result<Option<account::Account>>> + MaybeSend;
 
+    /// Returns all accounts that have an iban or additional id that matches `account_id`
+    /// (see [`AccountId::matches`]).
+    fn get_accounts_by_account_id(
+        &self,
+        account_id: AccountId,
+    ) -> impl Future<Output = Result<Vec<account::Account>>> + MaybeSend {
+        let accounts_future = self.get_accounts();
+        async move {
+            Ok(accounts_future
+                .await?
+                .into_iter()
+                .filter(|x| x.has_account_id(&account_id))
+                .collect())
+        }
+    }
+
+    /// Returns all accounts that have `name` as name or as one of their aliases (case insensitive).
+    fn get_accounts_by_name(
+        &self,
+        name: String,
+    ) -> impl Future<Output = Result<Vec<account::Account>>> + MaybeSend {
+        let accounts_future = self.get_accounts();
+        async move {
+            Ok(accounts_future
+                .await?
+                .into_iter()
+                .filter(|x| x.has_name(&name))
+                .collect())
+        }
+    }
+
     fn get_transaction(
         &self,
         id: Id,
@@ -148,6 +322,13 @@ pub trait FinanceManager: Send + Clone + Sized + std::fmt::Debug {
         categories: HashMap<Id, Sign>,
     ) -> impl Future<Output = Result<Transaction>> + MaybeSend;
 
+    /// Creates the transaction with a new id, ignoring the id of `transaction`.
+    /// Unlike [`Self::create_transaction`] fields like the status are taken over in one step.
+    fn create_transaction_from(
+        &mut self,
+        transaction: Transaction,
+    ) -> impl Future<Output = Result<Transaction>> + MaybeSend;
+
     #[allow(clippy::too_many_arguments)]
     fn update_transaction(
         &mut self,
@@ -175,6 +356,26 @@ pub trait FinanceManager: Send + Clone + Sized + std::fmt::Debug {
     /// All connections to other objects should be removed before calling this function.
     fn delete_transaction(&mut self, id: Id) -> impl Future<Output = Result<()>> + MaybeSend;
 
+    /// Updates `keep`, moves the bill entries of `remove` to `keep` and deletes `remove`.
+    /// Implementations should do this atomically if possible.
+    fn merge_transactions(
+        &mut self,
+        keep: Transaction,
+        remove: Id,
+    ) -> impl Future<Output = Result<Transaction>> + MaybeSend {
+        async move {
+            for mut bill in self.get_bills(None).await? {
+                if let Some(sign) = bill.transactions.remove(&remove) {
+                    bill.transactions.entry(keep.id).or_insert(sign);
+                    self.update_bill(bill).await?;
+                }
+            }
+            let keep = self.update_transaction(keep).await?;
+            self.delete_transaction(remove).await?;
+            Ok(keep)
+        }
+    }
+
     fn get_transactions_in_timespan(
         &self,
         timespan: Timespan,
@@ -231,20 +432,79 @@ pub trait FinanceManager: Send + Clone + Sized + std::fmt::Debug {
         id: Id,
         timespan: Timespan,
     ) -> impl Future<Output = Result<Vec<(DateTime, Currency)>>> + MaybeSend {
-        let transactions_future = self.get_transactions_of_category(id, timespan);
+        let filter = transaction_filter::TransactionFilter {
+            default_timespan: timespan,
+            ..Default::default()
+        }
+        .push_category(transaction_filter::Filter {
+            negated: false,
+            id: Some(id),
+            include: true,
+            timespan: None,
+            relative_timespan: None,
+        });
+        let aggregates_future = self.aggregate_transactions(
+            filter,
+            Some(aggregation::GroupBy::Category),
+            Some(aggregation::Period::Day(time::UtcOffset::UTC)),
+        );
         async move {
-            Ok(sum_up_transactions_by_day(
-                transactions_future.await?,
-                |transaction| {
-                    *transaction
-                        .categories
-                        .clone()
-                        .iter()
-                        .find(|(x, _)| **x == id)
-                        .unwrap()
-                        .1
-                },
-            ))
+            let mut sum = Currency::default();
+            Ok(aggregates_future
+                .await?
+                .into_iter()
+                .filter(|aggregate| aggregate.group == Some(id))
+                .map(|aggregate| {
+                    sum += aggregate.sum;
+                    (
+                        aggregate.period.unwrap().midnight().assume_utc(),
+                        sum.clone(),
+                    )
+                })
+                .collect())
+        }
+    }
+
+    fn get_saved_filters(
+        &self,
+    ) -> impl Future<Output = Result<Vec<transaction_filter::SavedFilter>>> + MaybeSend;
+
+    fn get_saved_filter(
+        &self,
+        id: Id,
+    ) -> impl Future<Output = Result<Option<transaction_filter::SavedFilter>>> + MaybeSend;
+
+    fn create_saved_filter(
+        &mut self,
+        name: String,
+        filter: transaction_filter::TransactionFilter,
+    ) -> impl Future<Output = Result<transaction_filter::SavedFilter>> + MaybeSend;
+
+    fn update_saved_filter(
+        &mut self,
+        saved_filter: transaction_filter::SavedFilter,
+    ) -> impl Future<Output = Result<transaction_filter::SavedFilter>> + MaybeSend;
+
+    fn delete_saved_filter(&mut self, id: Id) -> impl Future<Output = Result<()>> + MaybeSend;
+}
+
+/// The sum of the transactions of the account up to `date`, see [`FinanceManager::get_account_sum`].
+pub(crate) fn account_sum(
+    account: Id,
+    transactions: Vec<Transaction>,
+    date: DateTime,
+    date_kind: DateKind,
+) -> Currency {
+    let mut sum = Currency::default();
+    for transaction in transactions {
+        if transaction.date_of(date_kind) > date {
+            continue;
+        }
+        if transaction.source == account {
+            sum -= transaction.amount();
+        } else if transaction.destination == account {
+            sum += transaction.amount();
         }
     }
+    sum
 }
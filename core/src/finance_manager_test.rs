@@ -649,55 +649,1570 @@ pub async fn update_transaction_test<T: FinanceManager>(mut fm: T) {
         )
         .await
         .unwrap();
+    assert_eq!(transaction.status, TransactionStatus::Booked);
     transaction.title = String::from("Changed Name");
+    transaction.status = TransactionStatus::Pending;
     fm.update_transaction(transaction.clone()).await.unwrap();
     let fetched_transaction = fm.get_transaction(transaction.id).await.unwrap().unwrap();
     assert_eq!(fetched_transaction, transaction);
+    assert_eq!(fetched_transaction.title, "Changed Name");
+    assert_eq!(fetched_transaction.status, TransactionStatus::Pending);
+}
+
+pub async fn create_transaction_from_test<T: FinanceManager>(mut fm: T) {
+    let acc1 = fm
+        .create_asset_account(
+            "asset_acc".to_string(),
+            None,
+            None,
+            None,
+            Currency::default(),
+        )
+        .await
+        .unwrap();
+    let acc2 = fm
+        .create_book_checking_account("book_checking_acc".to_string(), None, None, None)
+        .await
+        .unwrap();
+    let category = fm.create_category("category".to_string()).await.unwrap();
+
+    let mut transaction = Transaction::new(
+        0,
+        Currency::from(10.0),
+        "card payment".to_string(),
+        None,
+        acc1.id,
+        acc2.id,
+        None,
+        datetime!(2024-01-31 12:00 UTC),
+        HashMap::from([("key".to_string(), "value".to_string())]),
+        HashMap::from([(category.id, Sign::Negative)]),
+    )
+    .unwrap();
+    transaction.status = TransactionStatus::Pending;
+    transaction.value_date = Some(datetime!(2024-02-01 12:00 UTC));
+
+    let first = fm
+        .create_transaction_from(transaction.clone())
+        .await
+        .unwrap();
+    let second = fm.create_transaction_from(transaction).await.unwrap();
+    assert_ne!(first.id, second.id);
+
+    let fetched = fm.get_transaction(first.id).await.unwrap().unwrap();
+    assert_eq!(fetched.title, "card payment");
+    assert_eq!(fetched.status, TransactionStatus::Pending);
+    assert_eq!(fetched.value_date, Some(datetime!(2024-02-01 12:00 UTC)));
+    assert_eq!(fetched.metadata, first.metadata);
+    assert_eq!(
+        fetched.categories,
+        HashMap::from([(category.id, Sign::Negative)])
+    );
 }
 
 pub async fn create_bill_test<T: FinanceManager>(mut fm: T) {
     let acc1 = fm
-        .create_asset_account(
-            "asset_acc".to_string(),
-            None,
-            None,
-            None,
-            Currency::default(),
-        )
+        .create_asset_account(
+            "asset_acc".to_string(),
+            None,
+            None,
+            None,
+            Currency::default(),
+        )
+        .await
+        .unwrap();
+    let acc2 = fm
+        .create_book_checking_account("book_checking_acc".to_string(), None, None, None)
+        .await
+        .unwrap();
+    let transaction = fm
+        .create_transaction(
+            Currency::default(),
+            "t1".to_string(),
+            None,
+            acc1.id,
+            acc2.id,
+            None,
+            time::OffsetDateTime::now_utc(),
+            HashMap::default(),
+            HashMap::default(),
+        )
+        .await
+        .unwrap();
+    let due_date = time::OffsetDateTime::now_utc().replace_time(time::Time::MIDNIGHT);
+    let bill = fm
+        .create_bill(
+            "Name".to_string(),
+            Some("Description".to_string()),
+            Currency::Eur(bigdecimal::BigDecimal::from_f32(5.0).unwrap()),
+            HashMap::from([(transaction.id, Sign::Positive)]),
+            Some(due_date),
+            true,
+        )
+        .await
+        .unwrap();
+    let fetched_bill = fm.get_bill(&bill.id).await.unwrap().unwrap();
+    assert_eq!(bill, fetched_bill)
+}
+
+pub async fn merge_accounts_test<T: FinanceManager>(mut fm: T) {
+    let asset = fm
+        .create_asset_account(
+            "asset_acc".to_string(),
+            None,
+            None,
+            None,
+            Currency::default(),
+        )
+        .await
+        .unwrap();
+    let mut keep = fm
+        .create_book_checking_account("Supermarket".to_string(), None, None, None)
+        .await
+        .unwrap();
+    let remove = fm
+        .create_book_checking_account(
+            "SUPERMARKET GMBH".to_string(),
+            None,
+            Some("DE89370400440532013000".parse().unwrap()),
+            None,
+        )
+        .await
+        .unwrap();
+    let t1 = fm
+        .create_transaction(
+            Currency::default(),
+            "t1".to_string(),
+            None,
+            asset.id,
+            remove.id,
+            None,
+            time::OffsetDateTime::now_utc(),
+            HashMap::default(),
+            HashMap::default(),
+        )
+        .await
+        .unwrap();
+    let t2 = fm
+        .create_transaction(
+            Currency::default(),
+            "t2".to_string(),
+            None,
+            remove.id,
+            asset.id,
+            None,
+            time::OffsetDateTime::now_utc(),
+            HashMap::default(),
+            HashMap::default(),
+        )
+        .await
+        .unwrap();
+
+    keep.iban = remove.iban.clone();
+    let merged = fm
+        .merge_accounts(keep.clone().into(), remove.id)
+        .await
+        .unwrap();
+    assert_eq!(merged, account::Account::BookCheckingAccount(keep.clone()));
+    assert_eq!(fm.get_account(keep.id).await.unwrap().unwrap(), merged);
+    assert!(fm.get_account(remove.id).await.unwrap().is_none());
+
+    let t1 = fm.get_transaction(t1.id).await.unwrap().unwrap();
+    assert_eq!(t1.source, asset.id);
+    assert_eq!(t1.destination, keep.id);
+    let t2 = fm.get_transaction(t2.id).await.unwrap().unwrap();
+    assert_eq!(t2.source, keep.id);
+    assert_eq!(t2.destination, asset.id);
+}
+
+pub async fn merge_transactions_test<T: FinanceManager>(mut fm: T) {
+    let asset = fm
+        .create_asset_account(
+            "asset_acc".to_string(),
+            None,
+            None,
+            None,
+            Currency::default(),
+        )
+        .await
+        .unwrap();
+    let book = fm
+        .create_book_checking_account("book_acc".to_string(), None, None, None)
+        .await
+        .unwrap();
+    let mut keep = fm
+        .create_transaction(
+            Currency::default(),
+            "keep".to_string(),
+            None,
+            asset.id,
+            book.id,
+            None,
+            time::OffsetDateTime::now_utc(),
+            HashMap::default(),
+            HashMap::default(),
+        )
+        .await
+        .unwrap();
+    let remove = fm
+        .create_transaction(
+            Currency::default(),
+            "remove".to_string(),
+            None,
+            book.id,
+            asset.id,
+            None,
+            time::OffsetDateTime::now_utc(),
+            HashMap::default(),
+            HashMap::default(),
+        )
+        .await
+        .unwrap();
+    let both_bill = fm
+        .create_bill(
+            "both".to_string(),
+            None,
+            Currency::default(),
+            HashMap::from([(keep.id, Sign::Positive), (remove.id, Sign::Negative)]),
+            None,
+            false,
+        )
+        .await
+        .unwrap();
+    let remove_bill = fm
+        .create_bill(
+            "remove".to_string(),
+            None,
+            Currency::default(),
+            HashMap::from([(remove.id, Sign::Negative)]),
+            None,
+            false,
+        )
+        .await
+        .unwrap();
+
+    keep.description = Some("merged".to_string());
+    let merged = fm
+        .merge_transactions(keep.clone(), remove.id)
+        .await
+        .unwrap();
+    assert_eq!(merged.description, keep.description);
+    assert_eq!(
+        fm.get_transaction(keep.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .description,
+        keep.description
+    );
+    assert!(
+        !fm.get_transactions_of_account(asset.id, (None, None))
+            .await
+            .unwrap()
+            .iter()
+            .any(|t| t.id == remove.id)
+    );
+
+    let both_bill = fm.get_bill(&both_bill.id).await.unwrap().unwrap();
+    assert_eq!(
+        both_bill.transactions,
+        HashMap::from([(keep.id, Sign::Positive)])
+    );
+    let remove_bill = fm.get_bill(&remove_bill.id).await.unwrap().unwrap();
+    assert_eq!(
+        remove_bill.transactions,
+        HashMap::from([(keep.id, Sign::Negative)])
+    );
+}
+
+pub async fn account_ids_and_aliases_test<T: FinanceManager>(mut fm: T) {
+    let iban: AccountId = "DE89370400440532013000".parse().unwrap();
+    let other_iban: AccountId = "DE02120300000000202051".parse().unwrap();
+    let mut account = fm
+        .create_book_checking_account("PayPal".to_string(), None, Some(iban.clone()), None)
+        .await
+        .unwrap();
+    let other = fm
+        .create_asset_account(
+            "Other".to_string(),
+            None,
+            Some(other_iban.clone()),
+            None,
+            Currency::default(),
+        )
+        .await
+        .unwrap();
+    assert!(account.additional_ids.is_empty());
+    assert!(account.aliases.is_empty());
+
+    account.additional_ids = vec![
+        other_iban.clone(),
+        "PAYPAL-1".parse().unwrap(),
+        "4111 11** **** 1234".parse().unwrap(),
+    ];
+    account.aliases = vec!["PayPal Europe S.a.r.l.".to_string()];
+    fm.update_book_checking_account(account.clone())
+        .await
+        .unwrap();
+    if let account::Account::BookCheckingAccount(fetched_account) =
+        fm.get_account(account.id).await.unwrap().unwrap()
+    {
+        assert_eq!(fetched_account, account);
+    } else {
+        panic!()
+    }
+
+    let found = fm.get_accounts_by_account_id(iban).await.unwrap();
+    assert_eq!(found, vec![account::Account::from(account.clone())]);
+    let mut found = fm.get_accounts_by_account_id(other_iban).await.unwrap();
+    found.sort_by_key(|x| *x.id());
+    let mut expected = vec![
+        account::Account::from(account.clone()),
+        other.clone().into(),
+    ];
+    expected.sort_by_key(|x| *x.id());
+    assert_eq!(found, expected);
+    assert!(
+        fm.get_accounts_by_account_id("DE75512108001245126199".parse().unwrap())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let found = fm
+        .get_accounts_by_account_id("XXXX XXXX XXXX 1234".parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(found, vec![account::Account::from(account.clone())]);
+
+    let found = fm
+        .get_accounts_by_name("paypal europe s.a.r.l.".to_string())
+        .await
+        .unwrap();
+    assert_eq!(found, vec![account::Account::from(account.clone())]);
+    let found = fm.get_accounts_by_name("other".to_string()).await.unwrap();
+    assert_eq!(found, vec![account::Account::from(other)]);
+
+    fm.delete_account(account.id).await.unwrap();
+    assert!(
+        fm.get_accounts_by_account_id("PAYPAL-1".parse().unwrap())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+pub async fn book_checking_account_kind_test<T: FinanceManager>(mut fm: T) {
+    let mut account = fm
+        .create_book_checking_account("Employer".to_string(), None, None, None)
+        .await
+        .unwrap();
+    assert_eq!(account.kind, None);
+
+    account.kind = Some(account::AccountKind::Income);
+    fm.update_book_checking_account(account.clone())
+        .await
+        .unwrap();
+    let fetched_account = fm.get_account(account.id).await.unwrap().unwrap();
+    assert_eq!(fetched_account.kind(), Some(account::AccountKind::Income));
+    if let account::Account::BookCheckingAccount(fetched_account) = fetched_account {
+        assert_eq!(fetched_account, account);
+    } else {
+        panic!()
+    }
+
+    let asset = fm
+        .create_asset_account("Bank".to_string(), None, None, None, Currency::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        fm.get_account(asset.id).await.unwrap().unwrap().kind(),
+        Some(account::AccountKind::Asset)
+    );
+}
+
+pub async fn loan_test<T: FinanceManager>(mut fm: T) {
+    let interest = fm
+        .create_book_checking_account("Interest".to_string(), None, None, None)
+        .await
+        .unwrap();
+    let mut account = fm
+        .create_book_checking_account("Mortgage".to_string(), None, None, None)
+        .await
+        .unwrap();
+    account.kind = Some(account::AccountKind::Liability);
+    account.loan = Some(Box::new(
+        loan::Loan::new(
+            Currency::from_currency_id(1, "250000".parse().unwrap()).unwrap(),
+            "3.45".parse().unwrap(),
+            360,
+            1,
+            datetime!(2023-05-10 12:00 UTC),
+            Some(interest.id),
+        )
+        .unwrap(),
+    ));
+    fm.update_book_checking_account(account.clone())
+        .await
+        .unwrap();
+    if let account::Account::BookCheckingAccount(fetched_account) =
+        fm.get_account(account.id).await.unwrap().unwrap()
+    {
+        assert_eq!(fetched_account, account);
+    } else {
+        panic!()
+    }
+
+    account.loan = None;
+    fm.update_book_checking_account(account.clone())
+        .await
+        .unwrap();
+    if let account::Account::BookCheckingAccount(fetched_account) =
+        fm.get_account(account.id).await.unwrap().unwrap()
+    {
+        assert_eq!(fetched_account.loan, None);
+    } else {
+        panic!()
+    }
+}
+
+pub async fn securities_test<T: FinanceManager>(mut fm: T) {
+    let mut account = fm
+        .create_asset_account("Depot".to_string(), None, None, None, Currency::default())
+        .await
+        .unwrap();
+    assert_eq!(account.asset_type, account::AssetType::Cash);
+    account.asset_type = account::AssetType::Securities;
+    fm.update_asset_account(account.clone()).await.unwrap();
+    if let account::Account::AssetAccount(fetched_account) =
+        fm.get_account(account.id).await.unwrap().unwrap()
+    {
+        assert_eq!(fetched_account, account);
+    } else {
+        panic!()
+    }
+
+    let isin: securities::Isin = "US0378331005".parse().unwrap();
+    let buy = fm
+        .create_trade(
+            account.id,
+            isin.clone(),
+            securities::TradeKind::Buy,
+            "2.5".parse().unwrap(),
+            eur("450.10"),
+            datetime!(2024-02-01 12:00 UTC),
+        )
+        .await
+        .unwrap();
+    let dividend = fm
+        .create_trade(
+            account.id,
+            isin.clone(),
+            securities::TradeKind::Dividend,
+            0.into(),
+            eur("1.25"),
+            datetime!(2024-01-01 12:00 UTC),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        fm.get_trades(account.id).await.unwrap(),
+        vec![dividend.clone(), buy.clone()]
+    );
+    fm.delete_trade(dividend.id).await.unwrap();
+    assert_eq!(fm.get_trades(account.id).await.unwrap(), vec![buy]);
+
+    let price = |date, value| securities::Price {
+        isin: isin.clone(),
+        date,
+        price: eur(value),
+    };
+    fm.add_prices(vec![
+        price(datetime!(2024-01-02 00:00 UTC), "181"),
+        price(datetime!(2024-01-01 00:00 UTC), "180"),
+    ])
+    .await
+    .unwrap();
+    fm.add_prices(vec![price(datetime!(2024-01-02 00:00 UTC), "182.5")])
+        .await
+        .unwrap();
+    assert_eq!(
+        fm.get_prices(isin.clone()).await.unwrap(),
+        vec![
+            price(datetime!(2024-01-01 00:00 UTC), "180"),
+            price(datetime!(2024-01-02 00:00 UTC), "182.5")
+        ]
+    );
+    assert!(
+        fm.get_prices("DE0005140008".parse().unwrap())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    fm.delete_account(account.id).await.unwrap();
+    assert!(fm.get_trades(account.id).await.unwrap().is_empty());
+}
+
+pub async fn valuation_test<T: FinanceManager>(mut fm: T) {
+    let mut account = fm
+        .create_asset_account("House".to_string(), None, None, None, Currency::default())
+        .await
+        .unwrap();
+    assert_eq!(account.depreciation, None);
+    account.asset_type = account::AssetType::Valuation;
+    account.depreciation = Some(valuation::Depreciation::Percentage("2.5".parse().unwrap()));
+    fm.update_asset_account(account.clone()).await.unwrap();
+    if let account::Account::AssetAccount(fetched_account) =
+        fm.get_account(account.id).await.unwrap().unwrap()
+    {
+        assert_eq!(fetched_account, account);
+    } else {
+        panic!()
+    }
+    account.depreciation = Some(valuation::Depreciation::Linear(eur("1200.50")));
+    fm.update_asset_account(account.clone()).await.unwrap();
+    if let account::Account::AssetAccount(fetched_account) =
+        fm.get_account(account.id).await.unwrap().unwrap()
+    {
+        assert_eq!(fetched_account, account);
+    } else {
+        panic!()
+    }
+
+    let later = fm
+        .create_valuation(account.id, eur("310000"), datetime!(2024-01-01 12:00 UTC))
+        .await
+        .unwrap();
+    let earlier = fm
+        .create_valuation(account.id, eur("300000"), datetime!(2023-01-01 12:00 UTC))
+        .await
+        .unwrap();
+    assert_eq!(
+        fm.get_valuations(account.id).await.unwrap(),
+        vec![earlier.clone(), later.clone()]
+    );
+    fm.delete_valuation(earlier.id).await.unwrap();
+    assert_eq!(fm.get_valuations(account.id).await.unwrap(), vec![later]);
+
+    fm.delete_account(account.id).await.unwrap();
+    assert!(fm.get_valuations(account.id).await.unwrap().is_empty());
+}
+
+pub async fn aggregation_test<T: FinanceManager>(mut fm: T) {
+    let acc1 = fm
+        .create_asset_account("Test1".to_string(), None, None, None, Currency::default())
+        .await
+        .unwrap();
+    let acc2 = fm
+        .create_asset_account("Test2".to_string(), None, None, None, Currency::default())
+        .await
+        .unwrap();
+    let food = fm.create_category("Food".to_string()).await.unwrap();
+    let refund = fm.create_category("Refund".to_string()).await.unwrap();
+    let budget = fm
+        .create_budget(
+            "Budget".to_string(),
+            None,
+            eur("100"),
+            budget::Recurring::DayInMonth(1),
+        )
+        .await
+        .unwrap();
+
+    let transactions = [
+        (
+            "12.50",
+            acc1.id,
+            acc2.id,
+            Some((budget.id, Sign::Positive)),
+            datetime!(2024-01-31 23:30 UTC),
+            vec![(food.id, Sign::Negative)],
+        ),
+        (
+            "20.25",
+            acc1.id,
+            acc2.id,
+            None,
+            datetime!(2024-02-01 08:00 UTC),
+            vec![(food.id, Sign::Negative), (refund.id, Sign::Positive)],
+        ),
+        (
+            "5",
+            acc2.id,
+            acc1.id,
+            Some((budget.id, Sign::Negative)),
+            datetime!(2024-02-01 09:00 UTC),
+            vec![],
+        ),
+        (
+            "100.10",
+            acc2.id,
+            acc1.id,
+            None,
+            datetime!(2024-03-15 12:00 UTC),
+            vec![(refund.id, Sign::Positive)],
+        ),
+    ];
+    let mut ids = Vec::new();
+    for (amount, source, destination, budget, date, categories) in transactions {
+        ids.push(
+            fm.create_transaction(
+                eur(amount),
+                "Transaction".to_string(),
+                None,
+                source,
+                destination,
+                budget,
+                date,
+                HashMap::default(),
+                categories.into_iter().collect(),
+            )
+            .await
+            .unwrap()
+            .id,
+        );
+    }
+    let bill = fm
+        .create_bill(
+            "Bill".to_string(),
+            None,
+            eur("17.50"),
+            HashMap::from([(ids[0], Sign::Positive), (ids[2], Sign::Negative)]),
+            None,
+            false,
+        )
+        .await
+        .unwrap();
+
+    let filter = |account: bool| {
+        transaction_filter::TransactionFilter::default().push_account(transaction_filter::Filter {
+            negated: false,
+            id: if account { Some(acc1.id) } else { None },
+            include: true,
+            timespan: None,
+            relative_timespan: None,
+        })
+    };
+    fn exclude<I: Clone + std::fmt::Debug>(
+        negated: bool,
+        id: Option<I>,
+    ) -> transaction_filter::Filter<I> {
+        transaction_filter::Filter {
+            negated,
+            id,
+            include: false,
+            timespan: Some((Some(datetime!(2024-02-01 00:00 UTC)), None)),
+            relative_timespan: None,
+        }
+    }
+    let filters = [
+        transaction_filter::TransactionFilter::default(),
+        filter(true),
+        filter(false).push_category(exclude(false, Some(food.id))),
+        filter(false).push_category(exclude(true, None)),
+        filter(false).push_bill(exclude(false, Some(bill.clone()))),
+        filter(false).push_bill(exclude(false, None)),
+        filter(true).push_budget(exclude(true, Some(budget.id))),
+        transaction_filter::TransactionFilter {
+            default_timespan: (
+                Some(datetime!(2024-02-01 08:00 UTC)),
+                Some(datetime!(2024-02-01 09:00 UTC)),
+            ),
+            ..filter(false)
+        },
+    ];
+    let group_bys = [
+        None,
+        Some(aggregation::GroupBy::Category),
+        Some(aggregation::GroupBy::Account),
+        Some(aggregation::GroupBy::Budget),
+    ];
+    let periods = [
+        None,
+        Some(aggregation::Period::Day(time::UtcOffset::UTC)),
+        Some(aggregation::Period::Day(offset!(+1))),
+        Some(aggregation::Period::Month(time::UtcOffset::UTC)),
+        Some(aggregation::Period::Month(offset!(+1))),
+    ];
+    let bills = fm.get_bills(None).await.unwrap();
+    for filter in filters {
+        let transactions = filter.filter_transactions(
+            fm.get_transactions_in_timespan(filter.total_timespan())
+                .await
+                .unwrap(),
+            &bills,
+        );
+        assert_eq!(
+            fm.get_filtered_transactions(filter.clone()).await.unwrap(),
+            transactions,
+            "{filter:?}"
+        );
+        for group_by in group_bys {
+            for period in periods {
+                assert_eq!(
+                    fm.aggregate_transactions(filter.clone(), group_by, period)
+                        .await
+                        .unwrap(),
+                    aggregation::aggregate(&transactions, group_by, period),
+                    "{filter:?} {group_by:?} {period:?}"
+                );
+            }
+        }
+    }
+
+    let mut expected = vec![
+        aggregation::Aggregate {
+            group: Some(food.id),
+            period: Some(date!(2024 - 02 - 01)),
+            sum: eur("-32.75"),
+            count: 2,
+        },
+        aggregation::Aggregate {
+            group: Some(refund.id),
+            period: Some(date!(2024 - 02 - 01)),
+            sum: eur("20.25"),
+            count: 1,
+        },
+        aggregation::Aggregate {
+            group: Some(refund.id),
+            period: Some(date!(2024 - 03 - 01)),
+            sum: eur("100.10"),
+            count: 1,
+        },
+    ];
+    expected.sort_by_key(|aggregate| (aggregate.group, aggregate.period));
+    assert_eq!(
+        fm.aggregate_transactions(
+            filter(false),
+            Some(aggregation::GroupBy::Category),
+            Some(aggregation::Period::Month(offset!(+1)))
+        )
+        .await
+        .unwrap(),
+        expected
+    );
+}
+
+pub async fn text_search_test<T: FinanceManager>(mut fm: T) {
+    let acc1 = fm
+        .create_asset_account("Test1".to_string(), None, None, None, Currency::default())
+        .await
+        .unwrap();
+    let acc2 = fm
+        .create_asset_account("Test2".to_string(), None, None, None, Currency::default())
+        .await
+        .unwrap();
+    let mut ids = Vec::new();
+    for (title, description, metadata) in [
+        ("AMAZON Marketplace", None, vec![]),
+        ("Rent", Some("Flat amazon.de"), vec![]),
+        ("Invoice", None, vec![("invoice", "INV-2024-0815")]),
+        (
+            "Groceries",
+            Some("Übersee Markt"),
+            vec![("it's", "\"quoted\"")],
+        ),
+    ] {
+        ids.push(
+            fm.create_transaction(
+                Currency::default(),
+                title.to_string(),
+                description.map(str::to_string),
+                acc1.id,
+                acc2.id,
+                None,
+                DateTime::now_utc(),
+                metadata
+                    .into_iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+                HashMap::default(),
+            )
+            .await
+            .unwrap()
+            .id,
+        );
+    }
+
+    async fn search<T: FinanceManager>(
+        fm: &T,
+        search: transaction_filter::TextSearch,
+        negated: bool,
+    ) -> Vec<Id> {
+        let mut ids = fm
+            .get_filtered_transactions(transaction_filter::TransactionFilter::default().push_text(
+                transaction_filter::Filter {
+                    negated,
+                    id: Some(search),
+                    include: true,
+                    timespan: None,
+                    relative_timespan: None,
+                },
+            ))
+            .await
+            .unwrap()
+            .iter()
+            .map(|transaction| transaction.id)
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids
+    }
+    let substring = |text: &str| transaction_filter::TextSearch::Substring(text.to_string());
+    let regex = |text: &str| transaction_filter::TextSearch::Regex(text.to_string());
+    let sorted = |mut ids: Vec<Id>| {
+        ids.sort();
+        ids
+    };
+
+    assert_eq!(
+        search(&fm, substring("amazon"), false).await,
+        sorted(vec![ids[0], ids[1]])
+    );
+    assert_eq!(
+        search(&fm, substring("amazon"), true).await,
+        sorted(vec![ids[2], ids[3]])
+    );
+    assert_eq!(search(&fm, substring("0815"), false).await, vec![ids[2]]);
+    assert_eq!(search(&fm, substring("übersee"), false).await, vec![ids[3]]);
+    assert_eq!(
+        search(&fm, substring("\"quoted"), false).await,
+        vec![ids[3]]
+    );
+    assert_eq!(search(&fm, substring("."), false).await, vec![ids[1]]);
+    assert_eq!(search(&fm, substring("nv"), false).await, vec![ids[2]]);
+    // metadata keys are not searched
+    assert!(search(&fm, substring("it's"), false).await.is_empty());
+    assert_eq!(search(&fm, regex("^amazon"), false).await, vec![ids[0]]);
+    assert_eq!(
+        search(&fm, regex(r"INV-\d{4}-\d+$"), false).await,
+        vec![ids[2]]
+    );
+    assert!(search(&fm, regex("(unclosed"), false).await.is_empty());
+
+    // the search follows changes of the transactions
+    let mut rent = fm.get_transaction(ids[1]).await.unwrap().unwrap();
+    rent.description = None;
+    rent.metadata = HashMap::from([("shop".to_string(), "Amazon".to_string())]);
+    fm.update_transaction(rent).await.unwrap();
+    assert_eq!(
+        search(&fm, substring("amazon"), false).await,
+        sorted(vec![ids[0], ids[1]])
+    );
+    assert!(search(&fm, substring("flat"), false).await.is_empty());
+    fm.delete_transaction(ids[0]).await.unwrap();
+    assert_eq!(search(&fm, substring("amazon"), false).await, vec![ids[1]]);
+}
+
+pub async fn amount_and_metadata_filter_test<T: FinanceManager>(mut fm: T) {
+    let acc1 = fm
+        .create_asset_account("Test1".to_string(), None, None, None, Currency::default())
         .await
         .unwrap();
     let acc2 = fm
-        .create_book_checking_account("book_checking_acc".to_string(), None, None, None)
+        .create_asset_account("Test2".to_string(), None, None, None, Currency::default())
         .await
         .unwrap();
-    let transaction = fm
-        .create_transaction(
-            Currency::default(),
-            "t1".to_string(),
-            None,
-            acc1.id,
-            acc2.id,
+    let mut ids = Vec::new();
+    for (amount, metadata) in [
+        ("9.99", vec![("importer-import-format", "CSV_CAMT_V2")]),
+        ("10", vec![("importer-import-format", "CAMT")]),
+        ("20.50", vec![("note", "CSV_CAMT_V2")]),
+        ("0.10", vec![]),
+    ] {
+        ids.push(
+            fm.create_transaction(
+                eur(amount),
+                "Transaction".to_string(),
+                None,
+                acc1.id,
+                acc2.id,
+                None,
+                DateTime::now_utc(),
+                metadata
+                    .into_iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+                HashMap::default(),
+            )
+            .await
+            .unwrap()
+            .id,
+        );
+    }
+
+    async fn filtered_ids<T: FinanceManager>(
+        fm: &T,
+        filter: transaction_filter::TransactionFilter,
+    ) -> Vec<Id> {
+        let mut ids = fm
+            .get_filtered_transactions(filter)
+            .await
+            .unwrap()
+            .iter()
+            .map(|transaction| transaction.id)
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids
+    }
+    fn filter<I: Clone + std::fmt::Debug>(
+        id: I,
+        negated: bool,
+        include: bool,
+    ) -> transaction_filter::Filter<I> {
+        transaction_filter::Filter {
+            negated,
+            id: Some(id),
+            include,
+            timespan: None,
+            relative_timespan: None,
+        }
+    }
+    let range = |min: Option<&str>, max: Option<&str>| transaction_filter::AmountRange {
+        min: min.map(eur),
+        max: max.map(eur),
+    };
+    let metadata = |key: &str, value: Option<&str>| transaction_filter::MetadataFilter {
+        key: key.to_string(),
+        value: value.map(str::to_string),
+    };
+    let sorted = |mut ids: Vec<Id>| {
+        ids.sort();
+        ids
+    };
+
+    assert_eq!(
+        filtered_ids(
+            &fm,
+            transaction_filter::TransactionFilter::default().push_amount(filter(
+                range(Some("10"), Some("20.50")),
+                false,
+                true
+            ))
+        )
+        .await,
+        sorted(vec![ids[1], ids[2]])
+    );
+    assert_eq!(
+        filtered_ids(
+            &fm,
+            transaction_filter::TransactionFilter::default().push_amount(filter(
+                range(Some("10"), None),
+                true,
+                true
+            ))
+        )
+        .await,
+        sorted(vec![ids[0], ids[3]])
+    );
+    assert_eq!(
+        filtered_ids(
+            &fm,
+            transaction_filter::TransactionFilter::default().push_amount(filter(
+                range(None, Some("0.10")),
+                false,
+                true
+            ))
+        )
+        .await,
+        vec![ids[3]]
+    );
+    assert_eq!(
+        filtered_ids(
+            &fm,
+            transaction_filter::TransactionFilter::default().push_metadata(filter(
+                metadata("importer-import-format", Some("CSV_CAMT_V2")),
+                false,
+                true
+            ))
+        )
+        .await,
+        vec![ids[0]]
+    );
+    assert_eq!(
+        filtered_ids(
+            &fm,
+            transaction_filter::TransactionFilter::default()
+                .push_metadata(filter(
+                    metadata("importer-import-format", None),
+                    false,
+                    true
+                ))
+                .push_amount(filter(range(None, Some("9.99")), false, false))
+        )
+        .await,
+        vec![ids[1]]
+    );
+    assert_eq!(
+        filtered_ids(
+            &fm,
+            transaction_filter::TransactionFilter::default().push_metadata(filter(
+                metadata("note", None),
+                true,
+                true
+            ))
+        )
+        .await,
+        sorted(vec![ids[0], ids[1], ids[3]])
+    );
+}
+
+pub async fn filter_expression_test<T: FinanceManager>(mut fm: T) {
+    use filter_expression::{Condition, FilterExpression};
+
+    let daily = fm
+        .create_asset_account("Daily".to_string(), None, None, None, Currency::default())
+        .await
+        .unwrap();
+    let shop = fm
+        .create_book_checking_account("Shop".to_string(), None, None, None)
+        .await
+        .unwrap();
+    let food = fm.create_category("Food".to_string()).await.unwrap();
+    let groceries = fm
+        .create_budget(
+            "Groceries".to_string(),
             None,
-            time::OffsetDateTime::now_utc(),
-            HashMap::default(),
-            HashMap::default(),
+            eur("100"),
+            budget::Recurring::DayInMonth(1),
         )
         .await
         .unwrap();
-    let due_date = time::OffsetDateTime::now_utc().replace_time(time::Time::MIDNIGHT);
+
+    let mut ids = Vec::new();
+    for (amount, source, destination, budget, categories, date) in [
+        (
+            "10",
+            daily.id,
+            shop.id,
+            None,
+            vec![food.id],
+            datetime!(2024-01-01 12:00 UTC),
+        ),
+        (
+            "20",
+            shop.id,
+            daily.id,
+            None,
+            vec![food.id],
+            datetime!(2024-02-01 12:00 UTC),
+        ),
+        (
+            "30",
+            shop.id,
+            shop.id,
+            Some(groceries.id),
+            vec![],
+            datetime!(2024-03-01 12:00 UTC),
+        ),
+        (
+            "40",
+            shop.id,
+            shop.id,
+            None,
+            vec![food.id],
+            datetime!(2024-04-01 12:00 UTC),
+        ),
+    ] {
+        ids.push(
+            fm.create_transaction(
+                eur(amount),
+                format!("Transaction {amount}"),
+                None,
+                source,
+                destination,
+                budget.map(|budget| (budget, Sign::Negative)),
+                date,
+                HashMap::from([("amount".to_string(), amount.to_string())]),
+                categories
+                    .into_iter()
+                    .map(|category| (category, Sign::Negative))
+                    .collect(),
+            )
+            .await
+            .unwrap()
+            .id,
+        );
+    }
     let bill = fm
         .create_bill(
-            "Name".to_string(),
-            Some("Description".to_string()),
-            Currency::Eur(bigdecimal::BigDecimal::from_f32(5.0).unwrap()),
-            HashMap::from([(transaction.id, Sign::Positive)]),
-            Some(due_date),
-            true,
+            "Bill".to_string(),
+            None,
+            eur("10"),
+            HashMap::from([(ids[0], Sign::Positive)]),
+            None,
+            false,
         )
         .await
         .unwrap();
-    let fetched_bill = fm.get_bill(&bill.id).await.unwrap().unwrap();
-    assert_eq!(bill, fetched_bill)
+
+    // category Food AND account Daily, OR budget Groceries
+    let example = (FilterExpression::from(Condition::Category(Some(food.id)))
+        & Condition::Account(Some(daily.id)).into())
+        | Condition::Budget(Some(groceries.id)).into();
+    let mut matching = fm
+        .get_transactions_matching(example.clone())
+        .await
+        .unwrap()
+        .iter()
+        .map(|transaction| transaction.id)
+        .collect::<Vec<_>>();
+    matching.sort();
+    let mut expected = vec![ids[0], ids[1], ids[2]];
+    expected.sort();
+    assert_eq!(matching, expected);
+
+    let expressions = [
+        example.clone(),
+        !example,
+        FilterExpression::And(Vec::new()),
+        FilterExpression::Or(Vec::new()),
+        FilterExpression::from(Condition::Bill(Some(bill.id)))
+            | Condition::Bill(None).into()
+            | Condition::Budget(None).into(),
+        !FilterExpression::from(Condition::Category(None))
+            & Condition::Timespan((Some(datetime!(2024-02-01 12:00 UTC)), None)).into(),
+        FilterExpression::from(Condition::Amount(transaction_filter::AmountRange {
+            min: Some(eur("20")),
+            max: Some(eur("30")),
+        })) | Condition::Metadata(transaction_filter::MetadataFilter {
+            key: "amount".to_string(),
+            value: Some("40".to_string()),
+        })
+        .into(),
+        FilterExpression::from(Condition::Text(transaction_filter::TextSearch::Substring(
+            "action 1".to_string(),
+        ))) | Condition::Text(transaction_filter::TextSearch::Regex("4.$".to_string())).into(),
+        FilterExpression::from(
+            transaction_filter::TransactionFilter::default().push_account(
+                transaction_filter::Filter {
+                    negated: false,
+                    id: Some(daily.id),
+                    include: true,
+                    timespan: Some((None, Some(datetime!(2024-01-31 12:00 UTC)))),
+                    relative_timespan: None,
+                },
+            ),
+        ),
+    ];
+    let transactions = fm.get_transactions_in_timespan((None, None)).await.unwrap();
+    let bills = fm.get_bills(None).await.unwrap();
+    for expression in expressions {
+        let mut matching = fm
+            .get_transactions_matching(expression.clone())
+            .await
+            .unwrap();
+        matching.sort_by_key(|transaction| transaction.id);
+        let mut expected = expression.filter_transactions(transactions.clone(), &bills);
+        expected.sort_by_key(|transaction| transaction.id);
+        assert_eq!(matching, expected, "{expression:?}");
+    }
+}
+
+pub async fn filter_query_test<T: FinanceManager>(mut fm: T) {
+    use transaction_filter::query;
+
+    let savings = fm
+        .create_asset_account("Savings".to_string(), None, None, None, Currency::default())
+        .await
+        .unwrap();
+    let shop = fm
+        .create_book_checking_account("Amazon".to_string(), None, None, None)
+        .await
+        .unwrap();
+    let food = fm.create_category("Food".to_string()).await.unwrap();
+
+    let mut ids = Vec::new();
+    for (amount, title, categories, date) in [
+        (
+            "60",
+            "Amazon order",
+            vec![food.id],
+            datetime!(2024-01-15 12:00 UTC),
+        ),
+        (
+            "40",
+            "Amazon order",
+            vec![food.id],
+            datetime!(2024-02-15 12:00 UTC),
+        ),
+        (
+            "70",
+            "Market",
+            vec![food.id],
+            datetime!(2024-03-15 12:00 UTC),
+        ),
+        (
+            "80",
+            "Amazon order",
+            vec![],
+            datetime!(2024-03-15 12:00 UTC),
+        ),
+        (
+            "90",
+            "Amazon order",
+            vec![food.id],
+            datetime!(2024-04-15 12:00 UTC),
+        ),
+    ] {
+        ids.push(
+            fm.create_transaction(
+                eur(amount),
+                title.to_string(),
+                None,
+                shop.id,
+                savings.id,
+                None,
+                date,
+                HashMap::new(),
+                categories
+                    .into_iter()
+                    .map(|category| (category, Sign::Negative))
+                    .collect(),
+            )
+            .await
+            .unwrap()
+            .id,
+        );
+    }
+
+    let names = query::Names::load(&fm).await.unwrap();
+    let expression = query::parse(
+        "category:food account:savings -account:#0 amount>50 date:2024-01..2024-03 \"amazon\"",
+        &names,
+        time::UtcOffset::UTC,
+    )
+    .unwrap();
+    let matching = fm
+        .get_transactions_matching(expression.clone())
+        .await
+        .unwrap()
+        .iter()
+        .map(|transaction| transaction.id)
+        .collect::<Vec<_>>();
+    assert_eq!(matching, vec![ids[0]]);
+    assert_eq!(
+        query::parse(
+            &query::to_query(&expression, &names, time::UtcOffset::UTC),
+            &names,
+            time::UtcOffset::UTC
+        ),
+        Ok(expression)
+    );
+    assert_eq!(
+        query::parse("account:Checking", &names, time::UtcOffset::UTC)
+            .unwrap_err()
+            .position,
+        8
+    );
+}
+
+pub async fn saved_filter_test<T: FinanceManager>(mut fm: T) {
+    use transaction_filter::{Filter, SavedFilter, TextSearch, TransactionFilter};
+
+    let account = fm
+        .create_asset_account("Daily".to_string(), None, None, None, Currency::default())
+        .await
+        .unwrap();
+    let filter = TransactionFilter {
+        default_timespan: (Some(datetime!(2024-01-01 0:00 UTC)), None),
+        ..Default::default()
+    }
+    .push_account(Filter {
+        negated: true,
+        id: Some(account.id),
+        include: true,
+        timespan: None,
+        relative_timespan: None,
+    })
+    .push_text(Filter {
+        negated: false,
+        id: Some(TextSearch::Regex("^amazon".to_string())),
+        include: false,
+        timespan: Some((None, Some(datetime!(2024-06-30 23:59:59 UTC)))),
+        relative_timespan: None,
+    });
+
+    let saved = fm
+        .create_saved_filter("Online".to_string(), filter.clone())
+        .await
+        .unwrap();
+    assert_eq!(saved.name, "Online");
+    assert_eq!(saved.filter, filter);
+    let other = fm
+        .create_saved_filter("Everything".to_string(), TransactionFilter::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        fm.get_saved_filter(saved.id).await.unwrap(),
+        Some(saved.clone())
+    );
+
+    let updated = SavedFilter::new(saved.id, "Online shopping".to_string(), filter);
+    fm.update_saved_filter(updated.clone()).await.unwrap();
+    let mut saved_filters = fm.get_saved_filters().await.unwrap();
+    saved_filters.sort_by_key(|x| x.id);
+    let mut expected = vec![updated, other.clone()];
+    expected.sort_by_key(|x| x.id);
+    assert_eq!(saved_filters, expected);
+
+    fm.delete_saved_filter(saved.id).await.unwrap();
+    assert_eq!(fm.get_saved_filter(saved.id).await.unwrap(), None);
+    assert_eq!(fm.get_saved_filters().await.unwrap(), vec![other]);
+}
+
+pub async fn transactions_page_test<T: FinanceManager>(mut fm: T) {
+    use filter_expression::{Condition, FilterExpression};
+    use pagination::{PageRequest, SortKey};
+
+    let daily = fm
+        .create_asset_account("Daily".to_string(), None, None, None, Currency::default())
+        .await
+        .unwrap();
+    let shop = fm
+        .create_book_checking_account("Shop".to_string(), None, None, None)
+        .await
+        .unwrap();
+
+    let mut ids = HashMap::new();
+    for (title, amount, source, destination, date) in [
+        (
+            "Coffee",
+            "3",
+            daily.id,
+            shop.id,
+            datetime!(2024-03-01 12:00 UTC),
+        ),
+        (
+            "Rent",
+            "800",
+            daily.id,
+            shop.id,
+            datetime!(2024-01-01 12:00 UTC),
+        ),
+        (
+            "Books",
+            "25",
+            daily.id,
+            shop.id,
+            datetime!(2024-02-01 12:00 UTC),
+        ),
+        (
+            "Apples",
+            "3",
+            shop.id,
+            daily.id,
+            datetime!(2024-02-01 12:00 UTC),
+        ),
+        (
+            "Other",
+            "1",
+            shop.id,
+            shop.id,
+            datetime!(2024-02-15 12:00 UTC),
+        ),
+    ] {
+        let transaction = fm
+            .create_transaction(
+                eur(amount),
+                title.to_string(),
+                None,
+                source,
+                destination,
+                None,
+                date,
+                HashMap::new(),
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        ids.insert(title, transaction.id);
+    }
+    // ties are broken by the id
+    let tied = |a: &str, b: &str| {
+        let mut tied = [ids[a], ids[b]];
+        tied.sort();
+        tied
+    };
+    let of_daily = FilterExpression::from(Condition::Account(Some(daily.id)));
+    let page_ids = |page: pagination::Page<Transaction>| {
+        (
+            page.items.iter().map(|x| x.id).collect::<Vec<_>>(),
+            page.total,
+        )
+    };
+
+    let books_apples = tied("Books", "Apples");
+    let coffee_apples = tied("Coffee", "Apples");
+    for (request, expected) in [
+        (
+            PageRequest::new(0, 10),
+            vec![ids["Rent"], books_apples[0], books_apples[1], ids["Coffee"]],
+        ),
+        (
+            PageRequest::new(0, 10).sorted_by(SortKey::Amount, false),
+            vec![
+                coffee_apples[0],
+                coffee_apples[1],
+                ids["Books"],
+                ids["Rent"],
+            ],
+        ),
+        (
+            PageRequest::new(0, 10).sorted_by(SortKey::Title, true),
+            vec![ids["Rent"], ids["Coffee"], ids["Books"], ids["Apples"]],
+        ),
+        (
+            PageRequest::new(1, 2).sorted_by(SortKey::Date, true),
+            vec![books_apples[1], books_apples[0]],
+        ),
+        (PageRequest::new(10, 2), vec![]),
+    ] {
+        assert_eq!(
+            page_ids(
+                fm.get_transactions_page(of_daily.clone(), request)
+                    .await
+                    .unwrap()
+            ),
+            (expected, 4),
+            "{request:?}"
+        );
+    }
+
+    let since_february =
+        of_daily & Condition::Timespan((Some(datetime!(2024-02-01 0:00 UTC)), None)).into();
+    assert_eq!(
+        page_ids(
+            fm.get_transactions_page(
+                since_february,
+                PageRequest::new(0, 1).sorted_by(SortKey::Title, false)
+            )
+            .await
+            .unwrap()
+        ),
+        (vec![ids["Apples"]], 3)
+    );
+}
+
+pub async fn stream_transactions_test<T: FinanceManager>(mut fm: T) {
+    use filter_expression::{Condition, FilterExpression};
+    use futures::TryStreamExt;
+
+    let daily = fm
+        .create_asset_account("Daily".to_string(), None, None, None, Currency::default())
+        .await
+        .unwrap();
+    let shop = fm
+        .create_book_checking_account("Shop".to_string(), None, None, None)
+        .await
+        .unwrap();
+
+    // more than fit into one chunk of the sqlite finance manager
+    let mut expected = Vec::new();
+    for index in 0..250 {
+        let source = if index % 5 == 0 { shop.id } else { daily.id };
+        let transaction = fm
+            .create_transaction(
+                Currency::from(f64::from(index)),
+                format!("Transaction {index}"),
+                None,
+                source,
+                shop.id,
+                None,
+                datetime!(2024-01-01 12:00 UTC),
+                HashMap::new(),
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        if source == daily.id {
+            expected.push(transaction);
+        }
+    }
+    expected.sort_by_key(|transaction| transaction.id);
+
+    let mut streamed: Vec<Transaction> = fm
+        .stream_transactions(FilterExpression::from(Condition::Account(Some(daily.id))))
+        .try_collect()
+        .await
+        .unwrap();
+    streamed.sort_by_key(|transaction| transaction.id);
+    assert_eq!(streamed, expected);
+
+    let none: Vec<Transaction> = fm
+        .stream_transactions(FilterExpression::Or(Vec::new()))
+        .try_collect()
+        .await
+        .unwrap();
+    assert!(none.is_empty());
+}
+
+pub async fn account_sum_by_date_kind_test<T: FinanceManager>(mut fm: T) {
+    let daily = fm
+        .create_asset_account("Daily".to_string(), None, None, None, Currency::default())
+        .await
+        .unwrap();
+    let employer = fm
+        .create_book_checking_account("Employer".to_string(), None, None, None)
+        .await
+        .unwrap();
+
+    // (amount, booking date, value date)
+    for (amount, date, value_date) in [
+        (
+            10.0,
+            datetime!(2024-01-31 12:00 UTC),
+            datetime!(2024-02-01 12:00 UTC),
+        ),
+        (
+            5.0,
+            datetime!(2024-02-02 12:00 UTC),
+            datetime!(2024-01-30 12:00 UTC),
+        ),
+    ] {
+        let mut transaction = fm
+            .create_transaction(
+                Currency::from(amount),
+                "Salary".to_string(),
+                None,
+                employer.id,
+                daily.id,
+                None,
+                date,
+                HashMap::new(),
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(transaction.value_date, None);
+        transaction.value_date = Some(value_date);
+        fm.update_transaction(transaction.clone()).await.unwrap();
+        let fetched = fm.get_transaction(transaction.id).await.unwrap().unwrap();
+        assert_eq!(fetched.value_date, Some(value_date));
+    }
+
+    let account = account::Account::from(daily);
+    let end_of_january = datetime!(2024-01-31 23:59 UTC);
+    assert_eq!(
+        fm.get_account_sum(&account, end_of_january, DateKind::Booking)
+            .await
+            .unwrap(),
+        Currency::from(10.0)
+    );
+    assert_eq!(
+        fm.get_account_sum(&account, end_of_january, DateKind::Value)
+            .await
+            .unwrap(),
+        Currency::from(5.0)
+    );
+    for date_kind in [DateKind::Booking, DateKind::Value] {
+        assert_eq!(
+            fm.get_account_sum(&account, datetime!(2024-02-03 00:00 UTC), date_kind)
+                .await
+                .unwrap(),
+            Currency::from(15.0)
+        );
+    }
 }
 
 pub async fn minimal_last_modified_test<T: FinanceManager>(mut fm: T) {
@@ -854,6 +2369,91 @@ macro_rules! unit_tests {
         async fn create_bill() {
             ($runner)(create_bill_test).await;
         }
+
+        #[async_std::test]
+        async fn merge_accounts() {
+            ($runner)(merge_accounts_test).await;
+        }
+
+        #[async_std::test]
+        async fn merge_transactions() {
+            ($runner)(merge_transactions_test).await;
+        }
+
+        #[async_std::test]
+        async fn account_ids_and_aliases() {
+            ($runner)(account_ids_and_aliases_test).await;
+        }
+
+        #[async_std::test]
+        async fn book_checking_account_kind() {
+            ($runner)(book_checking_account_kind_test).await;
+        }
+
+        #[async_std::test]
+        async fn loan() {
+            ($runner)(loan_test).await;
+        }
+
+        #[async_std::test]
+        async fn securities() {
+            ($runner)(securities_test).await;
+        }
+
+        #[async_std::test]
+        async fn valuation() {
+            ($runner)(valuation_test).await;
+        }
+
+        #[async_std::test]
+        async fn aggregation() {
+            ($runner)(aggregation_test).await;
+        }
+
+        #[async_std::test]
+        async fn text_search() {
+            ($runner)(text_search_test).await;
+        }
+
+        #[async_std::test]
+        async fn amount_and_metadata_filter() {
+            ($runner)(amount_and_metadata_filter_test).await;
+        }
+
+        #[async_std::test]
+        async fn filter_expression() {
+            ($runner)(filter_expression_test).await;
+        }
+
+        #[async_std::test]
+        async fn filter_query() {
+            ($runner)(filter_query_test).await;
+        }
+
+        #[async_std::test]
+        async fn saved_filter() {
+            ($runner)(saved_filter_test).await;
+        }
+
+        #[async_std::test]
+        async fn transactions_page() {
+            ($runner)(transactions_page_test).await;
+        }
+
+        #[async_std::test]
+        async fn stream_transactions() {
+            ($runner)(stream_transactions_test).await;
+        }
+
+        #[async_std::test]
+        async fn create_transaction_from() {
+            ($runner)(create_transaction_from_test).await;
+        }
+
+        #[async_std::test]
+        async fn account_sum_by_date_kind() {
+            ($runner)(account_sum_by_date_kind_test).await;
+        }
     };
 }
 
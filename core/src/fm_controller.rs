@@ -143,11 +143,67 @@ where
         self.finance_manager
             .lock()
             .await
-            .get_filtered_transactions(filter)
+            .get_filtered_transactions(resolve_relative_timespans(filter))
             .await
             .context("Error while getting transactions with applied filter")
     }
 
+    pub async fn get_transactions_matching(
+        &self,
+        expression: filter_expression::FilterExpression,
+    ) -> Result<Vec<Transaction>> {
+        self.finance_manager
+            .lock()
+            .await
+            .get_transactions_matching(expression)
+            .await
+            .context("Error while getting transactions matching filter expression")
+    }
+
+    pub async fn stream_transactions(
+        &self,
+        expression: filter_expression::FilterExpression,
+    ) -> TransactionStream {
+        use futures::TryStreamExt;
+
+        Box::pin(
+            self.finance_manager
+                .lock()
+                .await
+                .stream_transactions(expression)
+                .map_err(|error| error.context("Error while streaming transactions")),
+        )
+    }
+
+    pub async fn get_transactions_page(
+        &self,
+        expression: filter_expression::FilterExpression,
+        page: pagination::PageRequest,
+    ) -> Result<pagination::Page<Transaction>> {
+        self.finance_manager
+            .lock()
+            .await
+            .get_transactions_page(expression, page)
+            .await
+            .context("Error while getting page of transactions")
+    }
+
+    /// Sums up the transactions matching `filter` in the finance manager
+    /// (see [`aggregation::aggregate`]).
+    pub async fn aggregate_transactions(
+        &self,
+        filter: transaction_filter::TransactionFilter,
+        group_by: Option<aggregation::GroupBy>,
+        period: Option<aggregation::Period>,
+    ) -> Result<Vec<aggregation::Aggregate>> {
+        self.finance_manager
+            .lock()
+            .await
+            .aggregate_transactions(resolve_relative_timespans(filter), group_by, period)
+            .await
+            .context("Error while aggregating transactions")
+    }
+
     pub async fn create_asset_account(
         &self,
         name: String,
@@ -156,6 +212,8 @@ where
         bic: Option<Bic>,
         offset: Currency,
     ) -> Result<account::AssetAccount> {
+        account::check_iban_bic_country(iban.as_ref(), bic.as_ref())
+            .context("Error while creating asset account")?;
         self.finance_manager
             .lock()
             .await
@@ -169,6 +227,28 @@ where
         account: account::AssetAccount,
     ) -> Result<account::AssetAccount> {
         let acc_id = account.id;
+        self.check_changed_iban_bic(acc_id, account.iban.as_ref(), account.bic.as_ref())
+            .await
+            .context(format!("Error while updating asset account {acc_id}"))?;
+        if account.asset_type != account::AssetType::Securities
+            && !self.get_trades(acc_id).await?.is_empty()
+        {
+            anyhow::bail!(
+                "Error while updating asset account {acc_id}: an account with trades has to stay a securities account"
+            );
+        }
+        if account.asset_type != account::AssetType::Valuation
+            && !self.get_valuations(acc_id).await?.is_empty()
+        {
+            anyhow::bail!(
+                "Error while updating asset account {acc_id}: an account with valuations has to stay a valuation account"
+            );
+        }
+        if let Some(depreciation) = &account.depreciation {
+            depreciation
+                .validate()
+                .context(format!("Error while updating asset account {acc_id}"))?;
+        }
         self.finance_manager
             .lock()
             .await
@@ -214,6 +294,137 @@ where
         Ok(())
     }
 
+    /// Checks the country codes of IBAN and BIC if they differ from the stored account with the id `id`.
+    /// Unchanged pairs are not checked again, so existing accounts can still be updated.
+    async fn check_changed_iban_bic(
+        &self,
+        id: Id,
+        iban: Option<&AccountId>,
+        bic: Option<&Bic>,
+    ) -> Result<()> {
+        if let Some(stored) = self.get_account(id).await?
+            && stored.iban() == iban
+            && stored.bic() == bic
+        {
+            return Ok(());
+        }
+        account::check_iban_bic_country(iban, bic)
+    }
+
+    /// Merges the account `remove` into the account `keep`.
+    /// All transactions, trades and valuations of `remove` are moved to `keep` and `remove` is deleted.
+    /// IBAN and BIC are taken together from `keep`, or from `remove` if only `remove` has an IBAN or `keep` has neither.
+    /// Notes and asset account offsets are combined.
+    /// The other ids and names of `remove` are added to the additional ids and aliases of `keep`.
+    pub async fn merge_accounts(&self, keep: Id, remove: Id) -> Result<account::Account> {
+        async {
+            if keep == remove {
+                anyhow::bail!("An account cannot be merged with itself");
+            }
+            let keep_account = self
+                .get_account(keep)
+                .await?
+                .context(format!("Could not find account {keep}"))?;
+            let remove_account = self
+                .get_account(remove)
+                .await?
+                .context(format!("Could not find account {remove}"))?;
+
+            if self
+                .get_transactions_of_account(remove, (None, None))
+                .await?
+                .iter()
+                .any(|t| t.connection_with_account(keep))
+            {
+                anyhow::bail!("Accounts with transactions between each other cannot be merged");
+            }
+
+            let note = match (keep_account.note(), remove_account.note()) {
+                (Some(keep_note), Some(remove_note)) if keep_note != remove_note => {
+                    Some(format!("{keep_note}\n{remove_note}"))
+                }
+                (keep_note, remove_note) => keep_note.or(remove_note).cloned(),
+            };
+            let iban_bic_account = if keep_account.iban().is_none()
+                && (remove_account.iban().is_some() || keep_account.bic().is_none())
+            {
+                &remove_account
+            } else {
+                &keep_account
+            };
+            let iban = iban_bic_account.iban().cloned();
+            let bic = iban_bic_account.bic().cloned();
+            let remove_ids = remove_account.account_ids().cloned().collect::<Vec<_>>();
+            let remove_names = std::iter::once(remove_account.name().to_owned())
+                .chain(remove_account.aliases().iter().cloned())
+                .collect::<Vec<_>>();
+
+            let mut merged: account::Account = match (keep_account, remove_account) {
+                (account::Account::BookCheckingAccount(_), account::Account::AssetAccount(_)) => {
+                    anyhow::bail!("An asset account cannot be merged into a book checking account");
+                }
+                (account::Account::AssetAccount(mut acc), remove_account) => {
+                    if let account::Account::AssetAccount(remove_acc) = remove_account {
+                        acc.offset += remove_acc.offset;
+                        match (acc.asset_type, remove_acc.asset_type) {
+                            (_, account::AssetType::Cash) => {}
+                            (account::AssetType::Cash, asset_type) => acc.asset_type = asset_type,
+                            (keep_type, remove_type) if keep_type != remove_type => {
+                                anyhow::bail!(
+                                    "A {remove_type} account cannot be merged into a {keep_type} account"
+                                );
+                            }
+                            _ => {}
+                        }
+                        if acc.depreciation.is_none() {
+                            acc.depreciation = remove_acc.depreciation;
+                        }
+                    }
+                    acc.note = note;
+                    acc.iban = iban;
+                    acc.bic = bic;
+                    acc.into()
+                }
+                (account::Account::BookCheckingAccount(mut acc), remove_account) => {
+                    if acc.kind.is_none() {
+                        acc.kind = remove_account.kind();
+                    }
+                    if acc.loan.is_none()
+                        && let account::Account::BookCheckingAccount(remove_acc) = remove_account
+                    {
+                        acc.loan = remove_acc.loan;
+                    }
+                    acc.note = note;
+                    acc.iban = iban;
+                    acc.bic = bic;
+                    acc.into()
+                }
+            };
+            for account_id in remove_ids {
+                merged.add_account_id(account_id);
+            }
+            for name in remove_names {
+                merged.add_alias(name);
+            }
+            self.check_changed_iban_bic(keep, merged.iban(), merged.bic())
+                .await?;
+
+            let merged = self
+                .finance_manager
+                .lock()
+                .await
+                .merge_accounts(merged, remove)
+                .await?;
+            self.update_saved_filters(|filter| filter.replace_account(remove, keep))
+                .await?;
+            Ok(merged)
+        }
+        .await
+        .context(format!(
+            "Error while merging account {remove} into account {keep}"
+        ))
+    }
+
     pub async fn get_accounts(&self) -> Result<Vec<account::Account>> {
         self.finance_manager
             .lock()
@@ -232,23 +443,547 @@ where
             .context(format!("Error while deleting account with id {id}"))
     }
 
+    pub async fn get_accounts_by_account_id(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<account::Account>> {
+        self.finance_manager
+            .lock()
+            .await
+            .get_accounts_by_account_id(account_id.clone())
+            .await
+            .context(format!("Error while getting accounts with id {account_id}"))
+    }
+
+    pub async fn get_accounts_by_name(&self, name: String) -> Result<Vec<account::Account>> {
+        self.finance_manager
+            .lock()
+            .await
+            .get_accounts_by_name(name.clone())
+            .await
+            .context(format!("Error while getting accounts with name {name}"))
+    }
+
     pub async fn get_account_sum<'a>(
         &'a self,
         account: &'a account::Account,
         date: DateTime,
+        date_kind: DateKind,
     ) -> Result<Currency> {
         let sum = self
             .finance_manager
             .lock()
             .await
-            .get_account_sum(account, date)
+            .get_account_sum(account, date, date_kind)
             .await
             .context("Error while getting account sum")?;
-        if let account::Account::AssetAccount(asset_account) = account {
-            Ok(sum + &asset_account.offset)
-        } else {
-            Ok(sum)
+        let (trades, prices, valuations) = self.value_data_of_account(account).await?;
+        Ok(account_value(
+            account,
+            sum,
+            date,
+            &trades,
+            &prices,
+            &valuations,
+        ))
+    }
+
+    /// The trades, prices and valuations needed by [`account_value`].
+    async fn value_data_of_account(
+        &self,
+        account: &account::Account,
+    ) -> Result<(
+        Vec<securities::Trade>,
+        HashMap<securities::Isin, Vec<securities::Price>>,
+        Vec<valuation::Valuation>,
+    )> {
+        match account {
+            account::Account::AssetAccount(acc)
+                if acc.asset_type == account::AssetType::Securities =>
+            {
+                let trades = self.get_trades(acc.id).await?;
+                let prices = self.prices_of_trades(&trades).await?;
+                Ok((trades, prices, Vec::new()))
+            }
+            account::Account::AssetAccount(acc)
+                if acc.asset_type == account::AssetType::Valuation =>
+            {
+                Ok((
+                    Vec::new(),
+                    HashMap::new(),
+                    self.get_valuations(acc.id).await?,
+                ))
+            }
+            _ => Ok((Vec::new(), HashMap::new(), Vec::new())),
+        }
+    }
+
+    /// The net worth at every `interval` in `timespan` and at its end.
+    /// The net worth is the value of all asset accounts minus the debts of all liability accounts
+    /// (see [`Self::get_account_sum`]). Other book checking accounts are not included.
+    ///
+    /// Without a start the series starts at the first transaction, without an end it ends now.
+    pub async fn net_worth(
+        &self,
+        timespan: Timespan,
+        interval: Interval,
+    ) -> Result<Vec<(DateTime, Currency)>> {
+        async {
+            let end = timespan.1.unwrap_or_else(DateTime::now_utc);
+            let accounts = self
+                .get_accounts()
+                .await?
+                .into_iter()
+                .filter(|acc| match acc {
+                    account::Account::AssetAccount(_) => true,
+                    account::Account::BookCheckingAccount(acc) => {
+                        acc.kind == Some(account::AccountKind::Liability)
+                    }
+                })
+                .collect::<Vec<_>>();
+            let mut value_data = HashMap::with_capacity(accounts.len());
+            for account in &accounts {
+                value_data.insert(*account.id(), self.value_data_of_account(account).await?);
+            }
+            let mut sums = accounts
+                .iter()
+                .map(|acc| (*acc.id(), Currency::default()))
+                .collect::<HashMap<_, _>>();
+
+            let mut transactions = self.get_transactions_in_timespan((None, Some(end))).await?;
+            transactions.sort_by_key(|t| t.date);
+            let start = timespan
+                .0
+                .or(transactions.first().map(|t| t.date))
+                .unwrap_or(end)
+                .min(end);
+
+            let mut transactions = transactions.into_iter().peekable();
+            let mut series = Vec::new();
+            for date in interval.dates(start, end) {
+                while let Some(transaction) = transactions.next_if(|t| t.date <= date) {
+                    if let Some(sum) = sums.get_mut(&transaction.source) {
+                        *sum -= transaction.amount();
+                    }
+                    if let Some(sum) = sums.get_mut(&transaction.destination) {
+                        *sum += transaction.amount();
+                    }
+                }
+                let mut net_worth = Currency::default();
+                for account in &accounts {
+                    let (trades, prices, valuations) = &value_data[account.id()];
+                    net_worth += account_value(
+                        account,
+                        sums[account.id()].clone(),
+                        date,
+                        trades,
+                        prices,
+                        valuations,
+                    );
+                }
+                series.push((date, net_worth));
+            }
+            Ok::<_, anyhow::Error>(series)
+        }
+        .await
+        .context("Error while calculating the net worth")
+    }
+
+    /// The income and expenses of the asset accounts per period of `interval` in `timespan`.
+    /// See [`reporting::cash_flow`] for how transactions are counted.
+    ///
+    /// Without a start the report starts at the first transaction, without an end it ends now.
+    pub async fn cash_flow(
+        &self,
+        timespan: Timespan,
+        interval: Interval,
+        breakdown: reporting::Breakdown,
+    ) -> Result<Vec<reporting::CashFlowPeriod>> {
+        async {
+            let end = timespan.1.unwrap_or_else(DateTime::now_utc);
+            let own_accounts = self
+                .get_accounts()
+                .await?
+                .iter()
+                .filter(|acc| matches!(acc, account::Account::AssetAccount(_)))
+                .map(|acc| *acc.id())
+                .collect::<std::collections::HashSet<_>>();
+            let start = match timespan.0 {
+                Some(start) => start,
+                None => self
+                    .get_transactions_in_timespan((None, Some(end)))
+                    .await?
+                    .iter()
+                    .map(|t| t.date)
+                    .min()
+                    .unwrap_or(end),
+            }
+            .min(end);
+            let transactions = self
+                .get_transactions_in_timespan((Some(interval.start_of(start)), Some(end)))
+                .await?;
+            Ok::<_, anyhow::Error>(reporting::cash_flow(
+                &transactions,
+                &own_accounts,
+                start,
+                end,
+                interval,
+                breakdown,
+            ))
+        }
+        .await
+        .context("Error while calculating the cash flow")
+    }
+
+    /// The values of the categories of the transactions matching `filter` per period of
+    /// `interval` (see [`reporting::CategoryPivot`]).
+    ///
+    /// The periods cover the total timespan of the filter. Without a start or end it is taken
+    /// from the first or last matching transaction.
+    pub async fn category_pivot(
+        &self,
+        filter: transaction_filter::TransactionFilter,
+        interval: Interval,
+    ) -> Result<reporting::CategoryPivot> {
+        async {
+            let filter = resolve_relative_timespans(filter);
+            let timespan = filter.total_timespan();
+            let transactions = self.get_filtered_transactions(filter).await?;
+            let first = transactions.iter().map(|t| t.date).min();
+            let last = transactions.iter().map(|t| t.date).max();
+            let end = timespan.1.or(last).unwrap_or_else(DateTime::now_utc);
+            let start = timespan.0.or(first).unwrap_or(end).min(end);
+            Ok::<_, anyhow::Error>(reporting::CategoryPivot::new(
+                &transactions,
+                start,
+                end,
+                interval,
+            ))
+        }
+        .await
+        .context("Error while calculating the category pivot")
+    }
+
+    /// The amortization schedule of the loan of a liability account.
+    pub async fn get_amortization_schedule(
+        &self,
+        account: Id,
+    ) -> Result<Vec<loan::AmortizationEntry>> {
+        async {
+            let account = self
+                .get_account(account)
+                .await?
+                .context(format!("Could not find account {account}"))?;
+            Ok::<_, anyhow::Error>(loan_of_account(&account)?.amortization_schedule())
+        }
+        .await
+        .context(format!(
+            "Error while getting the amortization schedule of account {account}"
+        ))
+    }
+
+    /// Splits a payment to a loan account into its principal and interest part.
+    /// The payment is reduced to the principal part. The interest part is a new transaction
+    /// from the source of the payment to the interest account of the loan, which is returned.
+    /// The interest is calculated from the outstanding balance before the payment.
+    pub async fn split_loan_payment(&self, transaction: Id) -> Result<Transaction> {
+        async {
+            let payment = self
+                .get_transaction(transaction)
+                .await?
+                .context(format!("Could not find transaction {transaction}"))?;
+            if payment
+                .metadata
+                .contains_key(loan::METADATA_INTEREST_TRANSACTION)
+            {
+                anyhow::bail!("The payment is already split");
+            }
+            let account = self
+                .get_account(payment.destination)
+                .await?
+                .context(format!("Could not find account {}", payment.destination))?;
+            let loan = loan_of_account(&account)?;
+            let interest_account = loan
+                .interest_account
+                .context("The loan has no account for the interest")?;
+
+            // interest accrues from the value date
+            let outstanding = (self
+                .get_account_sum(&account, payment.date_of(DateKind::Value), DateKind::Value)
+                .await?
+                - payment.amount())
+            .negative();
+            let interest = std::cmp::min(loan.interest(&outstanding), payment.amount().clone());
+
+            let interest_transaction = self
+                .create_transaction(
+                    interest.clone(),
+                    format!("{} (interest)", payment.title),
+                    payment.description.clone(),
+                    payment.source,
+                    interest_account,
+                    None,
+                    payment.date,
+                    HashMap::from([(
+                        loan::METADATA_INTEREST_OF.to_string(),
+                        payment.id.to_string(),
+                    )]),
+                    HashMap::new(),
+                )
+                .await?;
+
+            let mut metadata = payment.metadata.clone();
+            metadata.insert(
+                loan::METADATA_INTEREST_TRANSACTION.to_string(),
+                interest_transaction.id.to_string(),
+            );
+            self.update_transaction(Transaction::new(
+                payment.id,
+                payment.amount().clone() - &interest,
+                payment.title,
+                payment.description,
+                payment.source,
+                payment.destination,
+                payment.budget,
+                payment.date,
+                metadata,
+                payment.categories,
+            )?)
+            .await?;
+            Ok(interest_transaction)
+        }
+        .await
+        .context(format!("Error while splitting loan payment {transaction}"))
+    }
+
+    /// Splits all payments to the loan account that are not split yet, ordered by date.
+    /// Returns the created interest transactions.
+    pub async fn split_loan_payments(&self, account: Id) -> Result<Vec<Transaction>> {
+        async {
+            let mut payments = self
+                .get_transactions_of_account(account, (None, None))
+                .await?
+                .into_iter()
+                .filter(|t| {
+                    t.destination == account
+                        && !t.metadata.contains_key(loan::METADATA_INTEREST_TRANSACTION)
+                })
+                .collect::<Vec<_>>();
+            payments.sort_by_key(|t| t.date);
+            let mut interest_transactions = Vec::with_capacity(payments.len());
+            for payment in payments {
+                interest_transactions.push(self.split_loan_payment(payment.id).await?);
+            }
+            Ok::<_, anyhow::Error>(interest_transactions)
+        }
+        .await
+        .context(format!(
+            "Error while splitting the loan payments of account {account}"
+        ))
+    }
+
+    /// Creates a trade of the securities account `account`.
+    /// Sells cannot exceed the quantity held at the date of the sale.
+    pub async fn create_trade(
+        &self,
+        account: Id,
+        isin: securities::Isin,
+        kind: securities::TradeKind,
+        quantity: bigdecimal::BigDecimal,
+        amount: Currency,
+        date: DateTime,
+    ) -> Result<securities::Trade> {
+        async {
+            match self.get_account(account).await? {
+                Some(account::Account::AssetAccount(acc))
+                    if acc.asset_type == account::AssetType::Securities => {}
+                Some(_) => anyhow::bail!("The account {account} is no securities account"),
+                None => anyhow::bail!("Could not find account {account}"),
+            }
+            securities::Trade::new(
+                0,
+                account,
+                isin.clone(),
+                kind,
+                quantity.clone(),
+                amount.clone(),
+                date,
+            )
+            .validate()?;
+            if kind == securities::TradeKind::Sell {
+                let held = self
+                    .get_securities_performance(account, date)
+                    .await?
+                    .positions
+                    .into_iter()
+                    .find(|p| p.isin == isin)
+                    .map(|p| p.quantity)
+                    .unwrap_or_default();
+                if quantity > held {
+                    anyhow::bail!("Cannot sell {quantity} units of {isin}, only {held} are held");
+                }
+            }
+            self.finance_manager
+                .lock()
+                .await
+                .create_trade(account, isin, kind, quantity, amount, date)
+                .await
+        }
+        .await
+        .context(format!("Error while creating a trade of account {account}"))
+    }
+
+    pub async fn delete_trade(&self, id: Id) -> Result<()> {
+        self.finance_manager
+            .lock()
+            .await
+            .delete_trade(id)
+            .await
+            .context(format!("Error while deleting trade {id}"))
+    }
+
+    pub async fn get_trades(&self, account: Id) -> Result<Vec<securities::Trade>> {
+        self.finance_manager
+            .lock()
+            .await
+            .get_trades(account)
+            .await
+            .context(format!(
+                "Error while getting the trades of account {account}"
+            ))
+    }
+
+    pub async fn add_prices(&self, prices: Vec<securities::Price>) -> Result<()> {
+        self.finance_manager
+            .lock()
+            .await
+            .add_prices(prices)
+            .await
+            .context("Error while adding prices")
+    }
+
+    pub async fn get_prices(&self, isin: securities::Isin) -> Result<Vec<securities::Price>> {
+        self.finance_manager
+            .lock()
+            .await
+            .get_prices(isin.clone())
+            .await
+            .context(format!("Error while getting the prices of {isin}"))
+    }
+
+    /// Positions, market value and gains of the securities account at `date`.
+    pub async fn get_securities_performance(
+        &self,
+        account: Id,
+        date: DateTime,
+    ) -> Result<securities::Performance> {
+        async {
+            let trades = self.get_trades(account).await?;
+            let prices = self.prices_of_trades(&trades).await?;
+            Ok::<_, anyhow::Error>(securities::performance(&trades, &prices, date))
+        }
+        .await
+        .context(format!(
+            "Error while getting the performance of account {account}"
+        ))
+    }
+
+    /// The performance of the securities account at every date in `timespan` where a trade happened
+    /// or a price of a traded security is known, and at the end of the timespan.
+    pub async fn get_securities_performance_over_time(
+        &self,
+        account: Id,
+        timespan: Timespan,
+    ) -> Result<Vec<(DateTime, securities::Performance)>> {
+        async {
+            let trades = self.get_trades(account).await?;
+            let prices = self.prices_of_trades(&trades).await?;
+            let end = timespan.1.unwrap_or_else(DateTime::now_utc);
+            let mut dates = trades
+                .iter()
+                .map(|t| t.date)
+                .chain(prices.values().flatten().map(|p| p.date))
+                .filter(|date| timespan.0.is_none_or(|start| *date >= start) && *date <= end)
+                .collect::<Vec<_>>();
+            dates.push(end);
+            dates.sort();
+            dates.dedup();
+            Ok::<_, anyhow::Error>(
+                dates
+                    .into_iter()
+                    .map(|date| (date, securities::performance(&trades, &prices, date)))
+                    .collect(),
+            )
+        }
+        .await
+        .context(format!(
+            "Error while getting the performance of account {account} over time"
+        ))
+    }
+
+    /// Adds a valuation to the valuation account `account`.
+    pub async fn create_valuation(
+        &self,
+        account: Id,
+        value: Currency,
+        date: DateTime,
+    ) -> Result<valuation::Valuation> {
+        async {
+            match self.get_account(account).await? {
+                Some(account::Account::AssetAccount(acc))
+                    if acc.asset_type == account::AssetType::Valuation => {}
+                Some(_) => anyhow::bail!("The account {account} is no valuation account"),
+                None => anyhow::bail!("Could not find account {account}"),
+            }
+            if value < Currency::default() {
+                anyhow::bail!("A valuation cannot be negative");
+            }
+            self.finance_manager
+                .lock()
+                .await
+                .create_valuation(account, value, date)
+                .await
+        }
+        .await
+        .context(format!(
+            "Error while creating a valuation of account {account}"
+        ))
+    }
+
+    pub async fn delete_valuation(&self, id: Id) -> Result<()> {
+        self.finance_manager
+            .lock()
+            .await
+            .delete_valuation(id)
+            .await
+            .context(format!("Error while deleting valuation {id}"))
+    }
+
+    pub async fn get_valuations(&self, account: Id) -> Result<Vec<valuation::Valuation>> {
+        self.finance_manager
+            .lock()
+            .await
+            .get_valuations(account)
+            .await
+            .context(format!(
+                "Error while getting the valuations of account {account}"
+            ))
+    }
+
+    async fn prices_of_trades(
+        &self,
+        trades: &[securities::Trade],
+    ) -> Result<HashMap<securities::Isin, Vec<securities::Price>>> {
+        let mut prices = HashMap::new();
+        for trade in trades {
+            if !prices.contains_key(&trade.isin) {
+                prices.insert(
+                    trade.isin.clone(),
+                    self.get_prices(trade.isin.clone()).await?,
+                );
+            }
         }
+        Ok(prices)
     }
 
     pub async fn get_transaction(&self, id: Id) -> Result<Option<Transaction>> {
@@ -316,30 +1051,55 @@ where
         .context("Error while creating transaction")
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub async fn update_transaction(&self, transaction: Transaction) -> Result<Transaction> {
-        let t_id = transaction.id;
+    /// Creates the transaction with a new id including fields like the status,
+    /// see [`FinanceManager::create_transaction_from`].
+    pub async fn create_transaction_from(&self, transaction: Transaction) -> Result<Transaction> {
         async {
             if transaction.amount().get_eur_num() < 0.0 {
                 anyhow::bail!("Amount must be positive")
             }
+            for category in transaction.categories.keys() {
+                if self.get_category(*category).await?.is_none() {
+                    anyhow::bail!("Category does not exist!")
+                }
+            }
+
             self.finance_manager
                 .lock()
                 .await
-                .update_transaction(transaction)
+                .create_transaction_from(transaction)
                 .await
         }
         .await
-        .context(format!("Error while updating transaction with id {t_id}"))
+        .context("Error while creating transaction")
     }
 
-    pub async fn create_book_checking_account(
-        &self,
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_transaction(&self, transaction: Transaction) -> Result<Transaction> {
+        let t_id = transaction.id;
+        async {
+            if transaction.amount().get_eur_num() < 0.0 {
+                anyhow::bail!("Amount must be positive")
+            }
+            self.finance_manager
+                .lock()
+                .await
+                .update_transaction(transaction)
+                .await
+        }
+        .await
+        .context(format!("Error while updating transaction with id {t_id}"))
+    }
+
+    pub async fn create_book_checking_account(
+        &self,
         name: String,
         notes: Option<String>,
         iban: Option<AccountId>,
         bic: Option<Bic>,
     ) -> Result<account::BookCheckingAccount> {
+        account::check_iban_bic_country(iban.as_ref(), bic.as_ref())
+            .context("Error while creating book checking account")?;
         self.finance_manager
             .lock()
             .await
@@ -350,9 +1110,31 @@ where
 
     pub async fn update_book_checking_account(
         &self,
-        account: account::BookCheckingAccount,
+        mut account: account::BookCheckingAccount,
     ) -> Result<account::BookCheckingAccount> {
         let acc_id = account.id;
+        if let Some(loan) = &account.loan {
+            loan.validate().context(format!(
+                "Error while updating book checking account with id {acc_id}"
+            ))?;
+            match account.kind {
+                None => account.kind = Some(account::AccountKind::Liability),
+                Some(account::AccountKind::Liability) => {}
+                Some(_) => anyhow::bail!(
+                    "Error while updating book checking account with id {acc_id}: only liabilities can have a loan"
+                ),
+            }
+        }
+        if account.kind == Some(account::AccountKind::Asset) {
+            anyhow::bail!(
+                "Error while updating book checking account with id {acc_id}: own assets have to be asset accounts"
+            );
+        }
+        self.check_changed_iban_bic(acc_id, account.iban.as_ref(), account.bic.as_ref())
+            .await
+            .context(format!(
+                "Error while updating book checking account with id {acc_id}"
+            ))?;
         self.finance_manager
             .lock()
             .await
@@ -500,22 +1282,36 @@ where
         offset: i32,
         timezone: time::UtcOffset,
     ) -> Result<Currency> {
-        let transactions = self
-            .get_budget_transactions(budget, offset, timezone)
-            .await
-            .context(format!(
-                "Error while getting value of budget {} {}",
-                budget.id, budget.name
-            ))?;
-        let mut sum = Currency::default();
-        for transaction in transactions {
-            let sign = transaction.budget.unwrap().1;
-            match sign {
-                Sign::Positive => sum += transaction.amount(),
-                Sign::Negative => sum -= transaction.amount(),
+        async {
+            let timespan = budget::calculate_budget_timespan(
+                budget,
+                offset,
+                time::OffsetDateTime::now_utc().to_offset(timezone),
+            )?;
+            let filter = transaction_filter::TransactionFilter {
+                default_timespan: timespan,
+                ..Default::default()
             }
+            .push_budget(transaction_filter::Filter {
+                negated: false,
+                id: Some(budget.id),
+                include: true,
+                timespan: None,
+                relative_timespan: None,
+            });
+            Ok::<_, anyhow::Error>(
+                self.aggregate_transactions(filter, Some(aggregation::GroupBy::Budget), None)
+                    .await?
+                    .into_iter()
+                    .find(|aggregate| aggregate.group == Some(budget.id))
+                    .map_or_else(Currency::default, |aggregate| aggregate.sum),
+            )
         }
-        Ok(sum)
+        .await
+        .context(format!(
+            "Error while getting value of budget {} {}",
+            budget.id, budget.name
+        ))
     }
 
     pub fn get_accounts_hash_map(
@@ -580,6 +1376,77 @@ where
             .context(format!("Error while deleting category with id {id}"))
     }
 
+    pub async fn get_saved_filters(&self) -> Result<Vec<transaction_filter::SavedFilter>> {
+        self.finance_manager
+            .lock()
+            .await
+            .get_saved_filters()
+            .await
+            .context("Error while getting saved filters")
+    }
+
+    pub async fn get_saved_filter(
+        &self,
+        id: Id,
+    ) -> Result<Option<transaction_filter::SavedFilter>> {
+        self.finance_manager
+            .lock()
+            .await
+            .get_saved_filter(id)
+            .await
+            .context(format!("Error while getting saved filter with id {id}"))
+    }
+
+    pub async fn create_saved_filter(
+        &self,
+        name: String,
+        filter: transaction_filter::TransactionFilter,
+    ) -> Result<transaction_filter::SavedFilter> {
+        self.finance_manager
+            .lock()
+            .await
+            .create_saved_filter(name, filter)
+            .await
+            .context("Error while creating saved filter")
+    }
+
+    pub async fn update_saved_filter(
+        &self,
+        saved_filter: transaction_filter::SavedFilter,
+    ) -> Result<transaction_filter::SavedFilter> {
+        let saved_filter_id = saved_filter.id;
+        self.finance_manager
+            .lock()
+            .await
+            .update_saved_filter(saved_filter)
+            .await
+            .context(format!(
+                "Error while updating saved filter with id {saved_filter_id}"
+            ))
+    }
+
+    /// Applies `update` to every saved filter and stores the filters it changed.
+    async fn update_saved_filters(
+        &self,
+        update: impl Fn(&mut transaction_filter::TransactionFilter) -> bool,
+    ) -> Result<()> {
+        for mut saved_filter in self.get_saved_filters().await? {
+            if update(&mut saved_filter.filter) {
+                self.update_saved_filter(saved_filter).await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn delete_saved_filter(&self, id: Id) -> Result<()> {
+        self.finance_manager
+            .lock()
+            .await
+            .delete_saved_filter(id)
+            .await
+            .context(format!("Error while deleting saved filter with id {id}"))
+    }
+
     pub async fn get_transactions_of_category(
         &self,
         id: Id,
@@ -603,22 +1470,14 @@ where
         id: Id,
         timespan: Timespan,
     ) -> Result<Vec<(DateTime, Currency)>> {
-        Ok(sum_up_transactions_by_day(
-            self.get_transactions_of_category(id, timespan)
-                .await
-                .context(format!(
-                    "Error while getting transactions of category with id {id} in timespan {timespan:?}"
-                ))?,
-            |transaction| {
-                *transaction
-                    .categories
-                    .clone()
-                    .iter()
-                    .find(|(x, _)| **x == id)
-                    .unwrap()
-                    .1
-            },
-        ))
+        self.finance_manager
+            .lock()
+            .await
+            .get_relative_category_values(id, timespan)
+            .await
+            .context(format!(
+                "Error while getting values of category with id {id} in timespan {timespan:?}"
+            ))
     }
 
     pub async fn update_transaction_categories(
@@ -638,6 +1497,168 @@ where
             "Error while updating categories for transaction with id {id}"
         ))
     }
+
+    /// Moves category `from` into category `into` and deletes `from` afterwards.
+    /// If a transaction has both categories with the same sign, the sign is kept.
+    /// If the signs are opposite, they cancel each other out and the transaction gets neither category.
+    pub async fn merge_categories(&self, into: Id, from: Id) -> Result<()> {
+        async {
+            if into == from {
+                anyhow::bail!("A category cannot be merged with itself");
+            }
+            if self.get_category(into).await?.is_none() {
+                anyhow::bail!("Category {into} does not exist");
+            }
+            for mut transaction in self
+                .get_transactions_of_category(from, (None, None))
+                .await?
+            {
+                let from_sign = transaction.categories.remove(&from).unwrap();
+                match transaction.categories.get(&into) {
+                    None => {
+                        transaction.categories.insert(into, from_sign);
+                    }
+                    Some(into_sign) if *into_sign != from_sign => {
+                        transaction.categories.remove(&into);
+                    }
+                    _ => {}
+                }
+                self.update_transaction(transaction).await?;
+            }
+            self.delete_category(from).await?;
+            self.update_saved_filters(|filter| filter.replace_category(from, into))
+                .await
+        }
+        .await
+        .context(format!(
+            "Error while merging category {from} into category {into}"
+        ))
+    }
+
+    /// Sets the category with the sign on every transaction that matches the filter.
+    /// Returns the changed transactions.
+    pub async fn set_category_of_filtered_transactions(
+        &self,
+        filter: transaction_filter::TransactionFilter,
+        category: Id,
+        sign: Sign,
+    ) -> Result<Vec<Transaction>> {
+        async {
+            if self.get_category(category).await?.is_none() {
+                anyhow::bail!("Category {category} does not exist");
+            }
+            let mut changed = Vec::new();
+            for mut transaction in self.get_filtered_transactions(filter).await? {
+                if transaction.categories.insert(category, sign) != Some(sign) {
+                    changed.push(self.update_transaction(transaction).await?);
+                }
+            }
+            Ok(changed)
+        }
+        .await
+        .context(format!(
+            "Error while setting category {category} for filtered transactions"
+        ))
+    }
+
+    /// Removes the category from every transaction that matches the filter.
+    /// Returns the changed transactions.
+    pub async fn remove_category_of_filtered_transactions(
+        &self,
+        filter: transaction_filter::TransactionFilter,
+        category: Id,
+    ) -> Result<Vec<Transaction>> {
+        async {
+            let mut changed = Vec::new();
+            for mut transaction in self.get_filtered_transactions(filter).await? {
+                if transaction.categories.remove(&category).is_some() {
+                    changed.push(self.update_transaction(transaction).await?);
+                }
+            }
+            Ok::<_, anyhow::Error>(changed)
+        }
+        .await
+        .context(format!(
+            "Error while removing category {category} from filtered transactions"
+        ))
+    }
+
+    /// Trains a [`category_suggestion::CategoryModel`] with all categorized transactions.
+    pub async fn get_category_model(&self) -> Result<category_suggestion::CategoryModel> {
+        Ok(category_suggestion::CategoryModel::new(
+            &self
+                .get_transactions_in_timespan((None, None))
+                .await
+                .context("Error while getting transactions for the category model")?,
+        ))
+    }
+
+    /// Suggests categories for a new transaction based on the already categorized transactions.
+    /// At most `limit` suggestions are returned, sorted by confidence.
+    pub async fn suggest_categories(
+        &self,
+        title: &str,
+        source: Option<Id>,
+        destination: Option<Id>,
+        amount: &Currency,
+        limit: usize,
+    ) -> Result<Vec<category_suggestion::CategorySuggestion>> {
+        let mut suggestions = self
+            .get_category_model()
+            .await
+            .context("Error while suggesting categories")?
+            .suggest(title, source, destination, amount);
+        suggestions.truncate(limit);
+        Ok(suggestions)
+    }
+
+    /// Scans all transactions for transfers between two asset accounts that show up twice.
+    /// See [`transfer::find_transfers`].
+    pub async fn find_transfers(
+        &self,
+        max_date_difference: time::Duration,
+    ) -> Result<Vec<transfer::Transfer>> {
+        let accounts = self
+            .get_accounts()
+            .await
+            .context("Error while finding transfers")?;
+        let transactions = self
+            .get_transactions_in_timespan((None, None))
+            .await
+            .context("Error while finding transfers")?;
+        Ok(transfer::find_transfers(
+            &accounts,
+            &transactions,
+            max_date_difference,
+        ))
+    }
+
+    /// Merges the two transactions of the transfer into one transaction between the asset accounts.
+    /// Bills that contain the removed transaction will contain the kept transaction instead.
+    pub async fn merge_transfer(&self, transfer: transfer::Transfer) -> Result<Transaction> {
+        let keep_id = transfer.keep.id;
+        let remove_id = transfer.remove.id;
+        self.finance_manager
+            .lock()
+            .await
+            .merge_transactions(transfer.merged_transaction(), remove_id)
+            .await
+            .context(format!(
+                "Error while merging transaction {remove_id} into transaction {keep_id}"
+            ))
+    }
+
+    /// Finds and merges all transfers. Returns the merged transactions.
+    pub async fn merge_transfers(
+        &self,
+        max_date_difference: time::Duration,
+    ) -> Result<Vec<Transaction>> {
+        let mut transactions = Vec::new();
+        for transfer in self.find_transfers(max_date_difference).await? {
+            transactions.push(self.merge_transfer(transfer).await?);
+        }
+        Ok(transactions)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -648,6 +1669,55 @@ pub enum DeleteAccountError {
     Other(#[from] anyhow::Error),
 }
 
+/// Resolves the relative timespans of the filter at the current local time,
+/// so every caller of the controller gets the same transactions for a saved filter.
+fn resolve_relative_timespans(
+    mut filter: transaction_filter::TransactionFilter,
+) -> transaction_filter::TransactionFilter {
+    filter.resolve_relative_timespans(
+        DateTime::now_utc(),
+        get_local_timezone().unwrap_or(time::UtcOffset::UTC),
+    );
+    filter
+}
+
+fn loan_of_account(account: &account::Account) -> Result<&loan::Loan> {
+    match account {
+        account::Account::BookCheckingAccount(acc) => acc.loan.as_deref(),
+        account::Account::AssetAccount(_) => None,
+    }
+    .context(format!("The account {} has no loan", account.id()))
+}
+
+/// The value of `account` at `date` given the `sum` of its transactions until `date`.
+/// Adds the offset, positions in securities, valuations and loans depending on the account.
+fn account_value(
+    account: &account::Account,
+    sum: Currency,
+    date: DateTime,
+    trades: &[securities::Trade],
+    prices: &HashMap<securities::Isin, Vec<securities::Price>>,
+    valuations: &[valuation::Valuation],
+) -> Currency {
+    match account {
+        account::Account::AssetAccount(asset_account) => match asset_account.asset_type {
+            account::AssetType::Cash => sum + &asset_account.offset,
+            account::AssetType::Securities => {
+                let performance = securities::performance(trades, prices, date);
+                sum + &asset_account.offset + &performance.cash_flow + &performance.market_value
+            }
+            account::AssetType::Valuation => {
+                valuation::value_at(valuations, asset_account.depreciation.as_ref(), date)
+                    .unwrap_or(sum + &asset_account.offset)
+            }
+        },
+        account::Account::BookCheckingAccount(acc) => match &acc.loan {
+            Some(loan) if loan.start <= date => sum - &loan.principal,
+            _ => sum,
+        },
+    }
+}
+
 #[cfg(test)]
 mod test {
     use managers::RamFinanceManager;
@@ -735,4 +1805,1088 @@ mod test {
         let new_bill = fm.get_bill(&bill.id).await.unwrap().unwrap();
         assert!(new_bill.transactions.is_empty());
     }
+
+    #[async_std::test]
+    async fn suggest_categories_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        let acc1 = fm
+            .create_asset_account(
+                "asset_acc".to_string(),
+                None,
+                None,
+                None,
+                Currency::default(),
+            )
+            .await
+            .unwrap();
+        let acc2 = fm
+            .create_book_checking_account("supermarket".to_string(), None, None, None)
+            .await
+            .unwrap();
+        let groceries = fm.create_category("groceries".to_string()).await.unwrap();
+        let salary = fm.create_category("salary".to_string()).await.unwrap();
+        fm.create_transaction(
+            Currency::from(12.5),
+            "Supermarket purchase".to_string(),
+            None,
+            acc1.id,
+            acc2.id,
+            None,
+            time::OffsetDateTime::new_utc(date!(2024 - 01 - 01), time!(10:50)),
+            HashMap::default(),
+            HashMap::from([(groceries.id, Sign::Negative)]),
+        )
+        .await
+        .unwrap();
+        fm.create_transaction(
+            Currency::from(2000.0),
+            "Salary January".to_string(),
+            None,
+            acc2.id,
+            acc1.id,
+            None,
+            time::OffsetDateTime::new_utc(date!(2024 - 01 - 31), time!(10:50)),
+            HashMap::default(),
+            HashMap::from([(salary.id, Sign::Positive)]),
+        )
+        .await
+        .unwrap();
+
+        let suggestions = fm
+            .suggest_categories(
+                "supermarket",
+                Some(acc1.id),
+                Some(acc2.id),
+                &Currency::from(20.0),
+                1,
+            )
+            .await
+            .unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].category, groceries.id);
+        assert_eq!(suggestions[0].sign, Sign::Negative);
+    }
+
+    #[async_std::test]
+    async fn merge_transfers_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        let iban_a: AccountId = "DE89370400440532013000".parse().unwrap();
+        let iban_b: AccountId = "DE02120300000000202051".parse().unwrap();
+        let asset_a = fm
+            .create_asset_account(
+                "a".to_string(),
+                None,
+                Some(iban_a.clone()),
+                None,
+                Currency::default(),
+            )
+            .await
+            .unwrap();
+        let asset_b = fm
+            .create_asset_account(
+                "b".to_string(),
+                None,
+                Some(iban_b.clone()),
+                None,
+                Currency::default(),
+            )
+            .await
+            .unwrap();
+        let book_b = fm
+            .create_book_checking_account("b".to_string(), None, Some(iban_b), None)
+            .await
+            .unwrap();
+        let book_a = fm
+            .create_book_checking_account("a".to_string(), None, Some(iban_a), None)
+            .await
+            .unwrap();
+        let outgoing = fm
+            .create_transaction(
+                Currency::from(100.0),
+                "outgoing".to_string(),
+                None,
+                asset_a.id,
+                book_b.id,
+                None,
+                time::OffsetDateTime::new_utc(date!(2024 - 01 - 01), time!(10:50)),
+                HashMap::default(),
+                HashMap::default(),
+            )
+            .await
+            .unwrap();
+        let incoming = fm
+            .create_transaction(
+                Currency::from(100.0),
+                "incoming".to_string(),
+                None,
+                book_a.id,
+                asset_b.id,
+                None,
+                time::OffsetDateTime::new_utc(date!(2024 - 01 - 02), time!(8:00)),
+                HashMap::default(),
+                HashMap::default(),
+            )
+            .await
+            .unwrap();
+        let bill = fm
+            .create_bill(
+                "bill".to_string(),
+                None,
+                Currency::default(),
+                HashMap::from([(incoming.id, Sign::Negative)]),
+                None,
+                false,
+            )
+            .await
+            .unwrap();
+
+        let merged = fm.merge_transfers(time::Duration::days(3)).await.unwrap();
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].id, outgoing.id);
+        assert_eq!(merged[0].source, asset_a.id);
+        assert_eq!(merged[0].destination, asset_b.id);
+        assert!(fm.get_transaction(incoming.id).await.unwrap().is_none());
+        assert_eq!(
+            fm.get_bill(&bill.id).await.unwrap().unwrap().transactions,
+            HashMap::from([(outgoing.id, Sign::Negative)])
+        );
+        assert!(
+            fm.find_transfers(time::Duration::days(3))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[async_std::test]
+    async fn iban_bic_country_mismatch_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        let iban: AccountId = "DE89370400440532013000".parse().unwrap();
+        assert!(
+            fm.create_book_checking_account(
+                "b".to_string(),
+                None,
+                Some(iban.clone()),
+                Some("BNPAFRPP".parse().unwrap()),
+            )
+            .await
+            .is_err()
+        );
+        let mut account = fm
+            .create_asset_account(
+                "a".to_string(),
+                None,
+                Some(iban),
+                Some("COBADEFFXXX".parse().unwrap()),
+                Currency::default(),
+            )
+            .await
+            .unwrap();
+        account.bic = Some("BNPAFRPP".parse().unwrap());
+        assert!(fm.update_asset_account(account).await.is_err());
+    }
+
+    #[async_std::test]
+    async fn book_checking_account_kind_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        let mut account = fm
+            .create_book_checking_account("b".to_string(), None, None, None)
+            .await
+            .unwrap();
+        account.kind = Some(account::AccountKind::Asset);
+        assert!(
+            fm.update_book_checking_account(account.clone())
+                .await
+                .is_err()
+        );
+        account.kind = Some(account::AccountKind::Expense);
+        assert_eq!(
+            fm.update_book_checking_account(account).await.unwrap().kind,
+            Some(account::AccountKind::Expense)
+        );
+    }
+
+    #[async_std::test]
+    async fn loan_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        let bank = fm
+            .create_asset_account("bank".to_string(), None, None, None, Currency::default())
+            .await
+            .unwrap();
+        let interest = fm
+            .create_book_checking_account("interest".to_string(), None, None, None)
+            .await
+            .unwrap();
+        let mut loan_account = fm
+            .create_book_checking_account("car loan".to_string(), None, None, None)
+            .await
+            .unwrap();
+        loan_account.loan = Some(Box::new(
+            loan::Loan::new(
+                eur("10000"),
+                "6".parse().unwrap(),
+                12,
+                31,
+                datetime!(2024-01-15 00:00 UTC),
+                Some(interest.id),
+            )
+            .unwrap(),
+        ));
+        let loan_account: account::Account = fm
+            .update_book_checking_account(loan_account)
+            .await
+            .unwrap()
+            .into();
+        assert_eq!(loan_account.kind(), Some(account::AccountKind::Liability));
+        assert_eq!(
+            fm.get_account_sum(
+                &loan_account,
+                datetime!(2024-01-01 00:00 UTC),
+                DateKind::Booking
+            )
+            .await
+            .unwrap(),
+            Currency::default()
+        );
+        assert_eq!(
+            fm.get_account_sum(
+                &loan_account,
+                datetime!(2024-02-01 00:00 UTC),
+                DateKind::Booking
+            )
+            .await
+            .unwrap(),
+            eur("-10000")
+        );
+
+        let schedule = fm
+            .get_amortization_schedule(*loan_account.id())
+            .await
+            .unwrap();
+        let mut payments = Vec::new();
+        for entry in &schedule[..2] {
+            payments.push(
+                fm.create_transaction(
+                    entry.payment.clone(),
+                    "rate".to_string(),
+                    None,
+                    bank.id,
+                    *loan_account.id(),
+                    None,
+                    entry.date,
+                    HashMap::new(),
+                    HashMap::new(),
+                )
+                .await
+                .unwrap(),
+            );
+        }
+
+        let interest_transaction = fm.split_loan_payment(payments[0].id).await.unwrap();
+        assert_eq!(interest_transaction.amount(), &schedule[0].interest);
+        assert_eq!(interest_transaction.source, bank.id);
+        assert_eq!(interest_transaction.destination, interest.id);
+        assert_eq!(
+            fm.get_transaction(payments[0].id)
+                .await
+                .unwrap()
+                .unwrap()
+                .amount(),
+            &schedule[0].principal
+        );
+        assert!(fm.split_loan_payment(payments[0].id).await.is_err());
+
+        let interest_transactions = fm.split_loan_payments(*loan_account.id()).await.unwrap();
+        assert_eq!(interest_transactions.len(), 1);
+        assert_eq!(interest_transactions[0].amount(), &schedule[1].interest);
+        assert_eq!(
+            fm.get_account_sum(&loan_account, schedule[1].date, DateKind::Booking)
+                .await
+                .unwrap(),
+            schedule[1].balance.negative()
+        );
+    }
+
+    #[async_std::test]
+    async fn merge_accounts_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        let asset = fm
+            .create_asset_account(
+                "asset_acc".to_string(),
+                None,
+                None,
+                None,
+                Currency::default(),
+            )
+            .await
+            .unwrap();
+        let keep = fm
+            .create_book_checking_account(
+                "Supermarket".to_string(),
+                Some("note".to_string()),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let remove = fm
+            .create_book_checking_account(
+                "SUPERMARKET GMBH".to_string(),
+                Some("other note".to_string()),
+                Some("DE89370400440532013000".parse().unwrap()),
+                Some(Bic::new("COBADEFFXXX".to_string()).unwrap()),
+            )
+            .await
+            .unwrap();
+        let transaction = fm
+            .create_transaction(
+                Currency::from(10.0),
+                "t1".to_string(),
+                None,
+                asset.id,
+                remove.id,
+                None,
+                time::OffsetDateTime::now_utc(),
+                HashMap::default(),
+                HashMap::default(),
+            )
+            .await
+            .unwrap();
+
+        assert!(fm.merge_accounts(keep.id, keep.id).await.is_err());
+        assert!(fm.merge_accounts(keep.id, asset.id).await.is_err());
+
+        let merged = fm.merge_accounts(keep.id, remove.id).await.unwrap();
+        assert_eq!(merged.name(), "Supermarket");
+        assert_eq!(merged.note().unwrap(), "note\nother note");
+        assert_eq!(merged.iban(), remove.iban.as_ref());
+        assert_eq!(merged.bic(), remove.bic.as_ref());
+        assert!(merged.additional_ids().is_empty());
+        assert_eq!(merged.aliases(), ["SUPERMARKET GMBH".to_string()]);
+        assert!(fm.get_account(remove.id).await.unwrap().is_none());
+        assert_eq!(
+            fm.get_transaction(transaction.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .destination,
+            keep.id
+        );
+
+        // transactions between the accounts
+        assert!(fm.merge_accounts(asset.id, keep.id).await.is_err());
+    }
+
+    #[async_std::test]
+    async fn update_account_with_unchanged_iban_bic_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        // stored before the country codes were checked
+        let mut account = fm
+            .raw_fm()
+            .lock()
+            .await
+            .create_book_checking_account(
+                "Supermarket".to_string(),
+                None,
+                Some("DE89370400440532013000".parse().unwrap()),
+                Some(Bic::new("BNPAFRPPXXX".to_string()).unwrap()),
+            )
+            .await
+            .unwrap();
+
+        account.aliases.push("SUPERMARKET GMBH".to_string());
+        let mut account = fm.update_book_checking_account(account).await.unwrap();
+
+        account.bic = Some(Bic::new("RBOSJESH".to_string()).unwrap());
+        assert!(fm.update_book_checking_account(account).await.is_err());
+    }
+
+    #[async_std::test]
+    async fn merge_accounts_iban_bic_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        let keep = fm
+            .create_book_checking_account(
+                "Supermarket".to_string(),
+                None,
+                None,
+                Some(Bic::new("BNPAFRPPXXX".to_string()).unwrap()),
+            )
+            .await
+            .unwrap();
+        let remove = fm
+            .create_book_checking_account(
+                "SUPERMARKET GMBH".to_string(),
+                None,
+                Some("DE89370400440532013000".parse().unwrap()),
+                Some(Bic::new("COBADEFFXXX".to_string()).unwrap()),
+            )
+            .await
+            .unwrap();
+
+        let merged = fm.merge_accounts(keep.id, remove.id).await.unwrap();
+        assert_eq!(merged.iban(), remove.iban.as_ref());
+        assert_eq!(merged.bic(), remove.bic.as_ref());
+    }
+
+    #[async_std::test]
+    async fn merge_categories_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        let acc1 = fm
+            .create_asset_account(
+                "asset_acc".to_string(),
+                None,
+                None,
+                None,
+                Currency::default(),
+            )
+            .await
+            .unwrap();
+        let acc2 = fm
+            .create_book_checking_account("book_checking_acc".to_string(), None, None, None)
+            .await
+            .unwrap();
+        let into = fm.create_category("into".to_string()).await.unwrap();
+        let from = fm.create_category("from".to_string()).await.unwrap();
+        let mut transactions = Vec::new();
+        for categories in [
+            HashMap::from([(from.id, Sign::Negative)]),
+            HashMap::from([(from.id, Sign::Positive), (into.id, Sign::Positive)]),
+            HashMap::from([(from.id, Sign::Positive), (into.id, Sign::Negative)]),
+        ] {
+            transactions.push(
+                fm.create_transaction(
+                    Currency::from(10.0),
+                    "t".to_string(),
+                    None,
+                    acc1.id,
+                    acc2.id,
+                    None,
+                    time::OffsetDateTime::now_utc(),
+                    HashMap::default(),
+                    categories,
+                )
+                .await
+                .unwrap(),
+            );
+        }
+
+        fm.merge_categories(into.id, from.id).await.unwrap();
+
+        assert!(fm.get_category(from.id).await.unwrap().is_none());
+        let expected = [
+            HashMap::from([(into.id, Sign::Negative)]),
+            HashMap::from([(into.id, Sign::Positive)]),
+            HashMap::new(),
+        ];
+        for (transaction, expected) in transactions.iter().zip(expected) {
+            assert_eq!(
+                fm.get_transaction(transaction.id)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .categories,
+                expected
+            );
+        }
+    }
+
+    #[async_std::test]
+    async fn filter_with_relative_timespan_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        let asset = fm
+            .create_asset_account(
+                "asset_acc".to_string(),
+                None,
+                None,
+                None,
+                Currency::default(),
+            )
+            .await
+            .unwrap();
+        let book = fm
+            .create_book_checking_account("book_acc".to_string(), None, None, None)
+            .await
+            .unwrap();
+        let mut transactions = Vec::new();
+        for days in [60, 0] {
+            transactions.push(
+                fm.create_transaction(
+                    Currency::from(10.0),
+                    format!("{days} days ago"),
+                    None,
+                    asset.id,
+                    book.id,
+                    None,
+                    DateTime::now_utc() - time::Duration::days(days),
+                    HashMap::default(),
+                    HashMap::default(),
+                )
+                .await
+                .unwrap(),
+            );
+        }
+        let filter = transaction_filter::TransactionFilter {
+            relative_default_timespan: Some(RelativeTimespan::LastDays(30)),
+            ..Default::default()
+        }
+        .push_account(transaction_filter::Filter {
+            negated: false,
+            id: Some(asset.id),
+            include: true,
+            timespan: None,
+            relative_timespan: None,
+        });
+
+        assert_eq!(
+            fm.get_filtered_transactions(filter.clone()).await.unwrap(),
+            [transactions[1].clone()]
+        );
+        let pivot = fm.category_pivot(filter, Interval::Day).await.unwrap();
+        assert!(pivot.periods[0].0 > transactions[0].date);
+    }
+
+    #[async_std::test]
+    async fn merge_rewrites_saved_filters_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        let keep = fm
+            .create_book_checking_account("Supermarket".to_string(), None, None, None)
+            .await
+            .unwrap();
+        let remove = fm
+            .create_book_checking_account("SUPERMARKET GMBH".to_string(), None, None, None)
+            .await
+            .unwrap();
+        let into = fm.create_category("into".to_string()).await.unwrap();
+        let from = fm.create_category("from".to_string()).await.unwrap();
+        let filter = |id| transaction_filter::Filter {
+            negated: false,
+            id: Some(id),
+            include: true,
+            timespan: None,
+            relative_timespan: None,
+        };
+        let saved_filter = fm
+            .create_saved_filter(
+                "saved".to_string(),
+                transaction_filter::TransactionFilter::default()
+                    .push_account(filter(remove.id))
+                    .push_category(filter(from.id)),
+            )
+            .await
+            .unwrap();
+
+        fm.merge_accounts(keep.id, remove.id).await.unwrap();
+        fm.merge_categories(into.id, from.id).await.unwrap();
+
+        let saved_filter = fm.get_saved_filter(saved_filter.id).await.unwrap().unwrap();
+        assert_eq!(saved_filter.filter.accounts, [filter(keep.id)]);
+        assert_eq!(saved_filter.filter.categories, [filter(into.id)]);
+    }
+
+    #[async_std::test]
+    async fn bulk_category_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        let acc1 = fm
+            .create_asset_account(
+                "asset_acc".to_string(),
+                None,
+                None,
+                None,
+                Currency::default(),
+            )
+            .await
+            .unwrap();
+        let acc2 = fm
+            .create_book_checking_account("book_checking_acc".to_string(), None, None, None)
+            .await
+            .unwrap();
+        let acc3 = fm
+            .create_book_checking_account("other".to_string(), None, None, None)
+            .await
+            .unwrap();
+        let category = fm.create_category("category".to_string()).await.unwrap();
+        let t1 = fm
+            .create_transaction(
+                Currency::from(10.0),
+                "t1".to_string(),
+                None,
+                acc1.id,
+                acc2.id,
+                None,
+                time::OffsetDateTime::now_utc(),
+                HashMap::default(),
+                HashMap::default(),
+            )
+            .await
+            .unwrap();
+        let t2 = fm
+            .create_transaction(
+                Currency::from(10.0),
+                "t2".to_string(),
+                None,
+                acc1.id,
+                acc3.id,
+                None,
+                time::OffsetDateTime::now_utc(),
+                HashMap::default(),
+                HashMap::default(),
+            )
+            .await
+            .unwrap();
+
+        let filter = transaction_filter::TransactionFilter::default().push_account(
+            transaction_filter::Filter {
+                negated: false,
+                id: Some(acc2.id),
+                include: true,
+                timespan: None,
+                relative_timespan: None,
+            },
+        );
+
+        let changed = fm
+            .set_category_of_filtered_transactions(filter.clone(), category.id, Sign::Negative)
+            .await
+            .unwrap();
+        // the equality of transactions only compares the ids
+        assert_eq!(changed, vec![t1.clone()]);
+        assert_eq!(
+            changed[0].categories,
+            HashMap::from([(category.id, Sign::Negative)])
+        );
+        assert_eq!(
+            fm.get_transaction(t1.id).await.unwrap().unwrap().categories,
+            HashMap::from([(category.id, Sign::Negative)])
+        );
+        assert!(
+            fm.get_transaction(t2.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .categories
+                .is_empty()
+        );
+
+        let changed = fm
+            .remove_category_of_filtered_transactions(filter, category.id)
+            .await
+            .unwrap();
+        assert_eq!(changed, vec![t1.clone()]);
+        assert!(changed[0].categories.is_empty());
+        assert!(
+            fm.get_transaction(t1.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .categories
+                .is_empty()
+        );
+    }
+
+    #[async_std::test]
+    async fn securities_account_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        let isin: securities::Isin = "US0378331005".parse().unwrap();
+        let bank = fm
+            .create_asset_account("bank".to_string(), None, None, None, Currency::default())
+            .await
+            .unwrap();
+        let mut depot = fm
+            .create_asset_account("depot".to_string(), None, None, None, Currency::default())
+            .await
+            .unwrap();
+        // trades need a securities account
+        assert!(
+            fm.create_trade(
+                depot.id,
+                isin.clone(),
+                securities::TradeKind::Buy,
+                10.into(),
+                eur("1000"),
+                datetime!(2024-01-10 12:00 UTC),
+            )
+            .await
+            .is_err()
+        );
+        depot.asset_type = account::AssetType::Securities;
+        let depot = fm.update_asset_account(depot).await.unwrap();
+
+        fm.create_transaction(
+            eur("1500"),
+            "deposit".to_string(),
+            None,
+            bank.id,
+            depot.id,
+            None,
+            datetime!(2024-01-01 12:00 UTC),
+            HashMap::new(),
+            HashMap::new(),
+        )
+        .await
+        .unwrap();
+        fm.create_trade(
+            depot.id,
+            isin.clone(),
+            securities::TradeKind::Buy,
+            10.into(),
+            eur("1000"),
+            datetime!(2024-01-10 12:00 UTC),
+        )
+        .await
+        .unwrap();
+        // more than is held
+        assert!(
+            fm.create_trade(
+                depot.id,
+                isin.clone(),
+                securities::TradeKind::Sell,
+                11.into(),
+                eur("1100"),
+                datetime!(2024-02-01 12:00 UTC),
+            )
+            .await
+            .is_err()
+        );
+        fm.create_trade(
+            depot.id,
+            isin.clone(),
+            securities::TradeKind::Sell,
+            4.into(),
+            eur("480"),
+            datetime!(2024-02-01 12:00 UTC),
+        )
+        .await
+        .unwrap();
+        fm.add_prices(vec![securities::Price {
+            isin: isin.clone(),
+            date: datetime!(2024-03-01 00:00 UTC),
+            price: eur("130"),
+        }])
+        .await
+        .unwrap();
+
+        let performance = fm
+            .get_securities_performance(depot.id, datetime!(2024-03-02 00:00 UTC))
+            .await
+            .unwrap();
+        assert_eq!(performance.market_value, eur("780"));
+        assert_eq!(performance.cost_basis, eur("600"));
+        assert_eq!(performance.unrealized_gain, eur("180"));
+        assert_eq!(performance.realized_gain, eur("80"));
+
+        // cash of 1500 - 1000 + 480 and the positions
+        let depot_account: account::Account = depot.clone().into();
+        assert_eq!(
+            fm.get_account_sum(
+                &depot_account,
+                datetime!(2024-03-02 00:00 UTC),
+                DateKind::Booking
+            )
+            .await
+            .unwrap(),
+            eur("1760")
+        );
+        assert_eq!(
+            fm.get_account_sum(
+                &depot_account,
+                datetime!(2024-01-05 00:00 UTC),
+                DateKind::Booking
+            )
+            .await
+            .unwrap(),
+            eur("1500")
+        );
+
+        let over_time = fm
+            .get_securities_performance_over_time(
+                depot.id,
+                (
+                    Some(datetime!(2024-01-01 00:00 UTC)),
+                    Some(datetime!(2024-03-31 00:00 UTC)),
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            over_time.iter().map(|x| x.0).collect::<Vec<_>>(),
+            vec![
+                datetime!(2024-01-10 12:00 UTC),
+                datetime!(2024-02-01 12:00 UTC),
+                datetime!(2024-03-01 00:00 UTC),
+                datetime!(2024-03-31 00:00 UTC),
+            ]
+        );
+        assert_eq!(over_time[0].1.market_value, eur("1000"));
+        assert_eq!(over_time[1].1.market_value, eur("720"));
+        assert_eq!(over_time[2].1.market_value, eur("780"));
+
+        // an account with trades stays a securities account
+        let mut cash_depot = depot.clone();
+        cash_depot.asset_type = account::AssetType::Cash;
+        assert!(fm.update_asset_account(cash_depot).await.is_err());
+    }
+
+    #[async_std::test]
+    async fn valuation_account_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        let mut car = fm
+            .create_asset_account("car".to_string(), None, None, None, eur("100"))
+            .await
+            .unwrap();
+        // valuations need a valuation account
+        assert!(
+            fm.create_valuation(car.id, eur("20000"), datetime!(2021-01-01 00:00 UTC))
+                .await
+                .is_err()
+        );
+        car.asset_type = account::AssetType::Valuation;
+        car.depreciation = Some(valuation::Depreciation::Percentage(120.into()));
+        assert!(fm.update_asset_account(car.clone()).await.is_err());
+        car.depreciation = Some(valuation::Depreciation::Linear(eur("2000")));
+        let car = fm.update_asset_account(car).await.unwrap();
+        assert!(
+            fm.create_valuation(car.id, eur("-1"), datetime!(2021-01-01 00:00 UTC))
+                .await
+                .is_err()
+        );
+
+        // without a valuation the offset is used
+        let car_account: account::Account = car.clone().into();
+        assert_eq!(
+            fm.get_account_sum(
+                &car_account,
+                datetime!(2021-01-01 00:00 UTC),
+                DateKind::Booking
+            )
+            .await
+            .unwrap(),
+            eur("100")
+        );
+
+        fm.create_valuation(car.id, eur("20000"), datetime!(2021-01-01 00:00 UTC))
+            .await
+            .unwrap();
+        let revaluation = fm
+            .create_valuation(car.id, eur("15000"), datetime!(2023-01-01 00:00 UTC))
+            .await
+            .unwrap();
+        assert_eq!(
+            fm.get_account_sum(
+                &car_account,
+                datetime!(2022-01-01 00:00 UTC),
+                DateKind::Booking
+            )
+            .await
+            .unwrap(),
+            eur("18000")
+        );
+        assert_eq!(
+            fm.get_account_sum(
+                &car_account,
+                datetime!(2023-01-01 00:00 UTC),
+                DateKind::Booking
+            )
+            .await
+            .unwrap(),
+            eur("15000")
+        );
+        fm.delete_valuation(revaluation.id).await.unwrap();
+        assert_eq!(fm.get_valuations(car.id).await.unwrap().len(), 1);
+
+        // an account with valuations stays a valuation account
+        let mut cash_car = car.clone();
+        cash_car.asset_type = account::AssetType::Cash;
+        assert!(fm.update_asset_account(cash_car).await.is_err());
+
+        // merging moves the valuations and the type
+        let old_car = fm
+            .create_asset_account("old car".to_string(), None, None, None, Currency::default())
+            .await
+            .unwrap();
+        fm.merge_accounts(old_car.id, car.id).await.unwrap();
+        let merged = fm.get_account(old_car.id).await.unwrap().unwrap();
+        if let account::Account::AssetAccount(merged) = merged {
+            assert_eq!(merged.asset_type, account::AssetType::Valuation);
+            assert_eq!(
+                merged.depreciation,
+                Some(valuation::Depreciation::Linear(eur("2000")))
+            );
+        } else {
+            panic!()
+        }
+        assert_eq!(fm.get_valuations(old_car.id).await.unwrap().len(), 1);
+    }
+
+    #[async_std::test]
+    async fn net_worth_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        let bank = fm
+            .create_asset_account("bank".to_string(), None, None, None, eur("100"))
+            .await
+            .unwrap();
+        let mut debt = fm
+            .create_book_checking_account("debt".to_string(), None, None, None)
+            .await
+            .unwrap();
+        debt.kind = Some(account::AccountKind::Liability);
+        let debt = fm.update_book_checking_account(debt).await.unwrap();
+        let shop = fm
+            .create_book_checking_account("shop".to_string(), None, None, None)
+            .await
+            .unwrap();
+        let mut car = fm
+            .create_asset_account("car".to_string(), None, None, None, Currency::default())
+            .await
+            .unwrap();
+        car.asset_type = account::AssetType::Valuation;
+        let car = fm.update_asset_account(car).await.unwrap();
+        fm.create_valuation(car.id, eur("5000"), datetime!(2024-02-01 00:00 UTC))
+            .await
+            .unwrap();
+        for (amount, source, destination, date) in [
+            ("1000", debt.id, bank.id, datetime!(2024-01-05 12:00 UTC)),
+            ("200", bank.id, shop.id, datetime!(2024-01-20 12:00 UTC)),
+            ("300", bank.id, debt.id, datetime!(2024-02-10 12:00 UTC)),
+        ] {
+            fm.create_transaction(
+                eur(amount),
+                "transaction".to_string(),
+                None,
+                source,
+                destination,
+                None,
+                date,
+                HashMap::new(),
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let net_worth = fm
+            .net_worth(
+                (
+                    Some(datetime!(2024-01-01 00:00 UTC)),
+                    Some(datetime!(2024-03-15 00:00 UTC)),
+                ),
+                Interval::Month,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            net_worth,
+            vec![
+                (datetime!(2024-01-01 00:00 UTC), eur("100")),
+                (datetime!(2024-02-01 00:00 UTC), eur("4900")),
+                (datetime!(2024-03-01 00:00 UTC), eur("4900")),
+                (datetime!(2024-03-15 00:00 UTC), eur("4900")),
+            ]
+        );
+
+        // the same as the sums of the single accounts
+        let accounts: Vec<account::Account> = vec![bank.into(), debt.into(), car.into()];
+        for (date, value) in fm
+            .net_worth(
+                (None, Some(datetime!(2024-02-20 00:00 UTC))),
+                Interval::Week,
+            )
+            .await
+            .unwrap()
+        {
+            let mut sum = Currency::default();
+            for account in &accounts {
+                sum += fm
+                    .get_account_sum(account, date, DateKind::Booking)
+                    .await
+                    .unwrap();
+            }
+            assert_eq!(value, sum);
+        }
+
+        assert_eq!(
+            Interval::Month.nth(datetime!(2024-01-31 00:00 UTC), 1),
+            datetime!(2024-02-29 00:00 UTC)
+        );
+        assert_eq!(
+            Interval::Month.nth(datetime!(2024-01-31 00:00 UTC), 14),
+            datetime!(2025-03-31 00:00 UTC)
+        );
+        assert_eq!(
+            Interval::Year.nth(datetime!(2024-02-29 00:00 UTC), 1),
+            datetime!(2025-02-28 00:00 UTC)
+        );
+        assert_eq!(
+            Interval::Week.start_of(datetime!(2024-03-14 15:00 UTC)),
+            datetime!(2024-03-11 00:00 UTC)
+        );
+    }
+
+    #[async_std::test]
+    async fn cash_flow_test() {
+        let fm = FMController::with_finance_manager(RamFinanceManager::new(()).unwrap());
+        let bank = fm
+            .create_asset_account("bank".to_string(), None, None, None, Currency::default())
+            .await
+            .unwrap();
+        let savings = fm
+            .create_asset_account("savings".to_string(), None, None, None, Currency::default())
+            .await
+            .unwrap();
+        let employer = fm
+            .create_book_checking_account("employer".to_string(), None, None, None)
+            .await
+            .unwrap();
+        let shop = fm
+            .create_book_checking_account("shop".to_string(), None, None, None)
+            .await
+            .unwrap();
+        for (amount, source, destination, date) in [
+            (
+                "2000",
+                employer.id,
+                bank.id,
+                datetime!(2024-01-31 12:00 UTC),
+            ),
+            ("500", bank.id, savings.id, datetime!(2024-02-01 12:00 UTC)),
+            ("120", bank.id, shop.id, datetime!(2024-02-10 12:00 UTC)),
+            ("30", savings.id, shop.id, datetime!(2024-03-05 12:00 UTC)),
+        ] {
+            fm.create_transaction(
+                eur(amount),
+                "transaction".to_string(),
+                None,
+                source,
+                destination,
+                None,
+                date,
+                HashMap::new(),
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let periods = fm
+            .cash_flow(
+                (None, Some(datetime!(2024-03-31 00:00 UTC))),
+                Interval::Month,
+                reporting::Breakdown::Counterparty,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            periods
+                .iter()
+                .map(|p| (p.start, p.income.clone(), p.expenses.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (datetime!(2024-01-01 00:00 UTC), eur("2000"), eur("0")),
+                (datetime!(2024-02-01 00:00 UTC), eur("0"), eur("120")),
+                (datetime!(2024-03-01 00:00 UTC), eur("0"), eur("30")),
+            ]
+        );
+        assert_eq!(periods[1].groups.len(), 1);
+        assert_eq!(periods[1].groups[0].id, Some(shop.id));
+        assert_eq!(periods[1].net(), eur("-120"));
+    }
 }
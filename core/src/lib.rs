@@ -18,19 +18,36 @@ pub use bigdecimal;
 
 mod currency;
 pub use currency::Currency;
+#[cfg(any(test, feature = "test"))]
+pub use currency::eur;
 
 pub mod account;
 pub use account::Bic;
 
+pub mod aggregation;
+
+pub mod filter_expression;
 pub mod managers;
+pub mod pagination;
 pub mod transaction_filter;
 
 pub mod transaction;
 pub use transaction::Transaction;
+pub use transaction::{DateKind, TransactionStatus};
 
 pub mod budget;
 pub use budget::Budget;
 
+pub mod relative_timespan;
+pub use relative_timespan::RelativeTimespan;
+
+pub mod category_suggestion;
+pub mod loan;
+pub mod reporting;
+pub mod securities;
+pub mod transfer;
+pub mod valuation;
+
 mod demo_data;
 pub use demo_data::generate_demo_data;
 
@@ -46,6 +63,16 @@ impl<T: Send> MaybeSend for T {}
 #[cfg(target_arch = "wasm32")]
 impl<T> MaybeSend for T {}
 
+/// A stream of transactions that is `Send` except on wasm
+/// (see [`FinanceManager::stream_transactions`]).
+#[cfg(not(target_arch = "wasm32"))]
+pub type TransactionStream =
+    std::pin::Pin<Box<dyn futures::Stream<Item = anyhow::Result<Transaction>> + Send>>;
+
+#[cfg(target_arch = "wasm32")]
+pub type TransactionStream =
+    std::pin::Pin<Box<dyn futures::Stream<Item = anyhow::Result<Transaction>>>>;
+
 pub type DateTime = time::OffsetDateTime;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -187,11 +214,111 @@ impl Bill {
 
 pub type Timespan = (Option<DateTime>, Option<DateTime>);
 
+/// Distance between the points of a time series.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
+pub enum Interval {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl Interval {
+    pub const ALL: [Interval; 4] = [
+        Interval::Day,
+        Interval::Week,
+        Interval::Month,
+        Interval::Year,
+    ];
+
+    /// The date `count` intervals after `start`.
+    /// Months and years keep the day of the month of `start` and are clamped to the last day of
+    /// shorter months.
+    pub fn nth(&self, start: DateTime, count: u32) -> DateTime {
+        match self {
+            Interval::Day => start + time::Duration::days(count as i64),
+            Interval::Week => start + time::Duration::weeks(count as i64),
+            Interval::Month => {
+                let months = start.month() as i32 - 1 + count as i32;
+                let year = start.year() + months / 12;
+                let month = time::Month::try_from((months % 12 + 1) as u8).unwrap();
+                let day = start.day().min(month.length(year));
+                start
+                    .replace_day(1)
+                    .unwrap()
+                    .replace_year(year)
+                    .unwrap()
+                    .replace_month(month)
+                    .unwrap()
+                    .replace_day(day)
+                    .unwrap()
+            }
+            Interval::Year => {
+                let year = start.year() + count as i32;
+                let day = start.day().min(start.month().length(year));
+                start
+                    .replace_day(1)
+                    .unwrap()
+                    .replace_year(year)
+                    .unwrap()
+                    .replace_day(day)
+                    .unwrap()
+            }
+        }
+    }
+
+    /// The start of the interval that contains `date`.
+    /// Weeks start on monday, all intervals start at midnight.
+    pub fn start_of(&self, date: DateTime) -> DateTime {
+        let date = date.replace_time(time::Time::MIDNIGHT);
+        match self {
+            Interval::Day => date,
+            Interval::Week => {
+                date - time::Duration::days(date.weekday().number_days_from_monday() as i64)
+            }
+            Interval::Month => date.replace_day(1).unwrap(),
+            Interval::Year => date
+                .replace_day(1)
+                .unwrap()
+                .replace_month(time::Month::January)
+                .unwrap(),
+        }
+    }
+
+    /// The dates from `start` to `end` in steps of this interval.
+    /// `end` is always the last date, even if it is not a full interval after the previous date.
+    pub fn dates(&self, start: DateTime, end: DateTime) -> Vec<DateTime> {
+        let mut dates = Vec::new();
+        let mut count = 0;
+        loop {
+            let date = self.nth(start, count);
+            if date >= end {
+                break;
+            }
+            dates.push(date);
+            count += 1;
+        }
+        dates.push(end);
+        dates
+    }
+}
+
+impl std::fmt::Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Interval::Day => write!(f, "Day"),
+            Interval::Week => write!(f, "Week"),
+            Interval::Month => write!(f, "Month"),
+            Interval::Year => write!(f, "Year"),
+        }
+    }
+}
+
 pub fn sum_up_transactions_by_day(
     mut transactions: Vec<Transaction>,
     sign_f: impl Fn(&Transaction) -> Sign,
 ) -> Vec<(DateTime, Currency)> {
-    transactions.sort_by(|a, b| a.date.cmp(&b.date));
+    transactions.sort_by_key(|a| a.date);
 
     let mut values: Vec<(DateTime, Currency)> = Vec::new();
 
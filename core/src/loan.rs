@@ -0,0 +1,218 @@
+//! Loans like mortgages that are paid back in monthly annuities.
+//!
+//! A loan is attached to a [`crate::account::BookCheckingAccount`] of the kind liability.
+//! Like the offset of asset accounts the principal is no transaction.
+//! It is subtracted from the account sum starting at the date the loan was paid out.
+
+use super::{Currency, DateTime, Id};
+use anyhow::Result;
+use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
+
+/// Metadata key of an interest transaction that contains the id of the split payment.
+pub const METADATA_INTEREST_OF: &str = "loan-interest-of";
+/// Metadata key of a split payment that contains the id of its interest transaction.
+pub const METADATA_INTEREST_TRANSACTION: &str = "loan-interest-transaction";
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct Loan {
+    pub principal: Currency,
+    /// Nominal interest rate per year in percent.
+    pub interest_rate: BigDecimal,
+    /// Number of monthly payments.
+    pub term_months: u32,
+    /// Day of the month the payments are due. Clamped to the last day of shorter months.
+    pub payment_day: u8,
+    /// Date the principal was paid out. The first payment is due in the following month.
+    pub start: DateTime,
+    /// Account that receives the interest part of split payments.
+    pub interest_account: Option<Id>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct AmortizationEntry {
+    pub date: DateTime,
+    pub payment: Currency,
+    pub interest: Currency,
+    pub principal: Currency,
+    /// Outstanding balance after the payment.
+    pub balance: Currency,
+}
+
+impl Loan {
+    pub fn new(
+        principal: Currency,
+        interest_rate: BigDecimal,
+        term_months: u32,
+        payment_day: u8,
+        start: DateTime,
+        interest_account: Option<Id>,
+    ) -> Result<Self> {
+        let loan = Self {
+            principal,
+            interest_rate,
+            term_months,
+            payment_day,
+            start,
+            interest_account,
+        };
+        loan.validate()?;
+        Ok(loan)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.principal.get_eur_num() <= 0.0 {
+            anyhow::bail!("The principal of a loan must be positive");
+        }
+        if self.interest_rate < 0 {
+            anyhow::bail!("The interest rate of a loan cannot be negative");
+        }
+        if self.term_months == 0 {
+            anyhow::bail!("A loan needs at least one payment");
+        }
+        if !(1..=31).contains(&self.payment_day) {
+            anyhow::bail!("The payment day must be a day of the month");
+        }
+        Ok(())
+    }
+
+    fn monthly_rate(&self) -> f64 {
+        self.interest_rate.to_f64().unwrap_or_default() / 100.0 / 12.0
+    }
+
+    fn currency(&self, value: f64) -> Currency {
+        Currency::from_currency_id(
+            self.principal.get_currency_id(),
+            BigDecimal::from_f64(value).unwrap_or_default(),
+        )
+        .unwrap()
+    }
+
+    /// The monthly payment of the annuity.
+    pub fn monthly_payment(&self) -> Currency {
+        self.currency(self.monthly_payment_num())
+    }
+
+    fn monthly_payment_num(&self) -> f64 {
+        let principal = self.principal.get_eur_num();
+        let rate = self.monthly_rate();
+        if rate == 0.0 {
+            principal / self.term_months as f64
+        } else {
+            principal * rate / (1.0 - (1.0 + rate).powi(-(self.term_months as i32)))
+        }
+    }
+
+    /// The interest of one month for the `outstanding` amount that is still owed.
+    pub fn interest(&self, outstanding: &Currency) -> Currency {
+        self.currency(round_cents(
+            outstanding.get_eur_num().max(0.0) * self.monthly_rate(),
+        ))
+    }
+
+    /// Due date of the `number`th payment starting at 1.
+    pub fn payment_date(&self, number: u32) -> DateTime {
+        let months = self.start.year() * 12 + self.start.month() as i32 - 1 + number as i32;
+        let year = months.div_euclid(12);
+        let month = time::Month::try_from((months.rem_euclid(12) + 1) as u8).unwrap();
+        let day = self.payment_day.min(month.length(year));
+        self.start
+            .replace_date(time::Date::from_calendar_date(year, month, day).unwrap())
+    }
+
+    /// All payments if every payment is made on time with the monthly payment.
+    /// The last payment pays the remaining balance.
+    pub fn amortization_schedule(&self) -> Vec<AmortizationEntry> {
+        let payment = round_cents(self.monthly_payment_num());
+        let rate = self.monthly_rate();
+        let mut balance = self.principal.get_eur_num();
+        let mut schedule = Vec::with_capacity(self.term_months as usize);
+        for number in 1..=self.term_months {
+            let interest = round_cents(balance * rate);
+            let principal = if number == self.term_months {
+                balance
+            } else {
+                (payment - interest).min(balance)
+            };
+            balance = round_cents(balance - principal);
+            schedule.push(AmortizationEntry {
+                date: self.payment_date(number),
+                payment: self.currency(principal + interest),
+                interest: self.currency(interest),
+                principal: self.currency(principal),
+                balance: self.currency(balance),
+            });
+            if balance <= 0.0 {
+                break;
+            }
+        }
+        schedule
+    }
+}
+
+fn round_cents(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::eur;
+    use time::macros::datetime;
+
+    fn loan(rate: &str) -> Loan {
+        Loan::new(
+            eur("10000.0"),
+            rate.parse().unwrap(),
+            12,
+            31,
+            datetime!(2024-01-15 00:00 UTC),
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn annuity_schedule() {
+        let loan = loan("6");
+        assert_eq!(loan.monthly_payment(), eur("860.66"));
+
+        let schedule = loan.amortization_schedule();
+        assert_eq!(schedule.len(), 12);
+        assert_eq!(schedule[0].date, datetime!(2024-02-29 00:00 UTC));
+        assert_eq!(schedule[1].date, datetime!(2024-03-31 00:00 UTC));
+        assert_eq!(schedule[0].interest, eur("50.0"));
+        assert_eq!(schedule[0].principal, eur("810.66"));
+        assert_eq!(schedule[0].balance, eur("9189.34"));
+        assert_eq!(schedule[11].date, datetime!(2025-01-31 00:00 UTC));
+        assert_eq!(schedule[11].balance, Currency::default());
+
+        let mut total_principal = Currency::default();
+        for entry in &schedule {
+            assert_eq!(entry.payment.clone() - &entry.interest, entry.principal);
+            total_principal += entry.principal.clone();
+        }
+        assert_eq!(total_principal, eur("10000.0"));
+    }
+
+    #[test]
+    fn schedule_without_interest() {
+        let schedule = loan("0").amortization_schedule();
+        assert_eq!(schedule.len(), 12);
+        assert!(
+            schedule
+                .iter()
+                .all(|entry| entry.interest == Currency::default())
+        );
+        assert_eq!(schedule[0].payment, eur("833.33"));
+        assert_eq!(schedule[11].payment, eur("833.37"));
+    }
+
+    #[test]
+    fn invalid_loans() {
+        let start = datetime!(2024-01-15 00:00 UTC);
+        assert!(Loan::new(eur("0.0"), 1.into(), 12, 1, start, None).is_err());
+        assert!(Loan::new(eur("10.0"), (-1).into(), 12, 1, start, None).is_err());
+        assert!(Loan::new(eur("10.0"), 1.into(), 0, 1, start, None).is_err());
+        assert!(Loan::new(eur("10.0"), 1.into(), 12, 32, start, None).is_err());
+    }
+}
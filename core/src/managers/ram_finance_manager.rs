@@ -1,6 +1,6 @@
 use crate::{
     AccountId, Bic, Bill, Budget, Category, Currency, DateTime, FinanceManager, Id, Sign, Timespan,
-    Transaction, account, budget::Recurring,
+    Transaction, account, budget::Recurring, securities, transaction_filter, valuation,
 };
 use anyhow::Result;
 use std::collections::HashMap;
@@ -12,6 +12,10 @@ pub struct RamFinanceManager {
     budgets: HashMap<Id, Budget>,
     categories: Vec<Category>,
     bills: Vec<Bill>,
+    trades: Vec<securities::Trade>,
+    prices: Vec<securities::Price>,
+    valuations: Vec<valuation::Valuation>,
+    saved_filters: Vec<transaction_filter::SavedFilter>,
     last_modified: crate::DateTime,
 }
 
@@ -23,6 +27,10 @@ impl Default for RamFinanceManager {
             budgets: HashMap::default(),
             categories: Vec::default(),
             bills: Vec::default(),
+            trades: Vec::default(),
+            prices: Vec::default(),
+            valuations: Vec::default(),
+            saved_filters: Vec::default(),
             last_modified: crate::DateTime::now_utc(),
         }
     }
@@ -80,6 +88,8 @@ impl FinanceManager for RamFinanceManager {
 
     async fn delete_account(&mut self, id: Id) -> Result<()> {
         self.accounts.remove(&id);
+        self.trades.retain(|x| x.account != id);
+        self.valuations.retain(|x| x.account != id);
         self.modified();
         Ok(())
     }
@@ -170,6 +180,90 @@ impl FinanceManager for RamFinanceManager {
         Ok(())
     }
 
+    async fn create_trade(
+        &mut self,
+        account: Id,
+        isin: securities::Isin,
+        kind: securities::TradeKind,
+        quantity: bigdecimal::BigDecimal,
+        amount: Currency,
+        date: DateTime,
+    ) -> Result<securities::Trade> {
+        let id = uuid::Uuid::new_v4().as_u64_pair().0;
+        let trade = securities::Trade::new(id, account, isin, kind, quantity, amount, date);
+        self.trades.push(trade.clone());
+        self.modified();
+        Ok(trade)
+    }
+
+    async fn delete_trade(&mut self, id: Id) -> Result<()> {
+        self.trades.retain(|x| x.id != id);
+        self.modified();
+        Ok(())
+    }
+
+    async fn get_trades(&self, account: Id) -> Result<Vec<securities::Trade>> {
+        let mut trades = self
+            .trades
+            .iter()
+            .filter(|x| x.account == account)
+            .cloned()
+            .collect::<Vec<_>>();
+        trades.sort_by_key(|x| x.date);
+        Ok(trades)
+    }
+
+    async fn add_prices(&mut self, prices: Vec<securities::Price>) -> Result<()> {
+        for price in prices {
+            self.prices
+                .retain(|x| x.isin != price.isin || x.date != price.date);
+            self.prices.push(price);
+        }
+        self.modified();
+        Ok(())
+    }
+
+    async fn get_prices(&self, isin: securities::Isin) -> Result<Vec<securities::Price>> {
+        let mut prices = self
+            .prices
+            .iter()
+            .filter(|x| x.isin == isin)
+            .cloned()
+            .collect::<Vec<_>>();
+        prices.sort_by_key(|x| x.date);
+        Ok(prices)
+    }
+
+    async fn create_valuation(
+        &mut self,
+        account: Id,
+        value: Currency,
+        date: DateTime,
+    ) -> Result<valuation::Valuation> {
+        let id = uuid::Uuid::new_v4().as_u64_pair().0;
+        let valuation = valuation::Valuation::new(id, account, value, date);
+        self.valuations.push(valuation.clone());
+        self.modified();
+        Ok(valuation)
+    }
+
+    async fn delete_valuation(&mut self, id: Id) -> Result<()> {
+        self.valuations.retain(|x| x.id != id);
+        self.modified();
+        Ok(())
+    }
+
+    async fn get_valuations(&self, account: Id) -> Result<Vec<valuation::Valuation>> {
+        let mut valuations = self
+            .valuations
+            .iter()
+            .filter(|x| x.account == account)
+            .cloned()
+            .collect::<Vec<_>>();
+        valuations.sort_by_key(|x| x.date);
+        Ok(valuations)
+    }
+
     async fn get_accounts(&self) -> Result<Vec<account::Account>> {
         Ok(self
             .accounts
@@ -279,10 +373,8 @@ impl FinanceManager for RamFinanceManager {
         metadata: HashMap<String, String>,
         categories: HashMap<Id, Sign>,
     ) -> Result<Transaction> {
-        let id = uuid::Uuid::new_v4().as_u64_pair().0;
-
-        let new_transaction = Transaction::new(
-            id,
+        self.create_transaction_from(Transaction::new(
+            0,
             amount,
             title,
             description,
@@ -292,13 +384,21 @@ impl FinanceManager for RamFinanceManager {
             date,
             metadata,
             categories,
-        )?;
+        )?)
+        .await
+    }
+
+    async fn create_transaction_from(
+        &mut self,
+        mut transaction: Transaction,
+    ) -> Result<Transaction> {
+        transaction.id = uuid::Uuid::new_v4().as_u64_pair().0;
 
-        self.transactions.push(new_transaction.clone());
+        self.transactions.push(transaction.clone());
 
         self.modified();
 
-        Ok(new_transaction)
+        Ok(transaction)
     }
 
     async fn get_budget(&self, id: Id) -> Result<Option<Budget>> {
@@ -388,6 +488,21 @@ impl FinanceManager for RamFinanceManager {
         Ok(transactions)
     }
 
+    fn stream_transactions(
+        &self,
+        expression: crate::filter_expression::FilterExpression,
+    ) -> crate::TransactionStream {
+        // everything is in memory already, so only the matching transactions are cloned
+        let transactions = self
+            .transactions
+            .iter()
+            .filter(|transaction| expression.matches(transaction, &self.bills))
+            .cloned()
+            .map(Ok)
+            .collect::<Vec<_>>();
+        Box::pin(futures::stream::iter(transactions))
+    }
+
     async fn create_category(&mut self, name: String) -> Result<Category> {
         let id = uuid::Uuid::new_v4().as_u64_pair().0;
 
@@ -471,6 +586,52 @@ impl FinanceManager for RamFinanceManager {
 
         Ok(transactions)
     }
+
+    async fn get_saved_filters(&self) -> Result<Vec<transaction_filter::SavedFilter>> {
+        Ok(self.saved_filters.clone())
+    }
+
+    async fn get_saved_filter(&self, id: Id) -> Result<Option<transaction_filter::SavedFilter>> {
+        Ok(self.saved_filters.iter().find(|x| x.id == id).cloned())
+    }
+
+    async fn create_saved_filter(
+        &mut self,
+        name: String,
+        filter: transaction_filter::TransactionFilter,
+    ) -> Result<transaction_filter::SavedFilter> {
+        let id = uuid::Uuid::new_v4().as_u64_pair().0;
+        let saved_filter = transaction_filter::SavedFilter::new(id, name, filter);
+        self.saved_filters.push(saved_filter.clone());
+        self.modified();
+        Ok(saved_filter)
+    }
+
+    async fn update_saved_filter(
+        &mut self,
+        saved_filter: transaction_filter::SavedFilter,
+    ) -> Result<transaction_filter::SavedFilter> {
+        let Some(old) = self
+            .saved_filters
+            .iter_mut()
+            .find(|x| x.id == saved_filter.id)
+        else {
+            anyhow::bail!("Saved filter does not exist");
+        };
+        *old = saved_filter.clone();
+        self.modified();
+        Ok(saved_filter)
+    }
+
+    async fn delete_saved_filter(&mut self, id: Id) -> Result<()> {
+        let length = self.saved_filters.len();
+        self.saved_filters.retain(|x| x.id != id);
+        if self.saved_filters.len() == length {
+            anyhow::bail!("Saved filter does not exist");
+        }
+        self.modified();
+        Ok(())
+    }
 }
 
 #[cfg(test)]
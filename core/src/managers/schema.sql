@@ -10,7 +10,8 @@ CREATE TABLE IF NOT EXISTS asset_account (
     iban TEXT,
     bic TEXT,
     offset_value REAL NOT NULL,
-    offset_currency INTEGER NOT NULL
+    offset_currency INTEGER NOT NULL,
+    asset_type INTEGER NOT NULL DEFAULT 0 -- 0 cash, 1 securities, 2 valuation
 );
 
 CREATE TABLE IF NOT EXISTS book_checking_account (
@@ -18,7 +19,8 @@ CREATE TABLE IF NOT EXISTS book_checking_account (
     name TEXT NOT NULL,
     notes TEXT,
     iban TEXT,
-    bic TEXT
+    bic TEXT,
+    kind INTEGER -- 1 liability, 2 income, 3 expense, 4 equity, NULL if not classified
 );
 
 CREATE TABLE IF NOT EXISTS account (
@@ -29,6 +31,74 @@ CREATE TABLE IF NOT EXISTS account (
     FOREIGN KEY (book_checking_account) REFERENCES book_checking_account(id)
 );
 
+CREATE TABLE IF NOT EXISTS account_identifier (
+    account_id INTEGER NOT NULL,
+    identifier TEXT NOT NULL,
+    FOREIGN KEY(account_id) REFERENCES account(id)
+);
+
+CREATE TABLE IF NOT EXISTS account_alias (
+    account_id INTEGER NOT NULL,
+    alias TEXT NOT NULL,
+    FOREIGN KEY(account_id) REFERENCES account(id)
+);
+
+CREATE TABLE IF NOT EXISTS loan (
+    account_id INTEGER NOT NULL PRIMARY KEY,
+    principal_value REAL NOT NULL,
+    principal_currency INTEGER NOT NULL,
+    interest_rate TEXT NOT NULL,
+    term_months INTEGER NOT NULL,
+    payment_day INTEGER NOT NULL,
+    start INTEGER NOT NULL,
+    interest_account INTEGER,
+    FOREIGN KEY(account_id) REFERENCES account(id),
+    FOREIGN KEY(interest_account) REFERENCES account(id)
+);
+
+CREATE TABLE IF NOT EXISTS trade (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    account_id INTEGER NOT NULL,
+    isin TEXT NOT NULL,
+    kind INTEGER NOT NULL, -- 0 buy, 1 sell, 2 dividend
+    quantity TEXT NOT NULL,
+    amount_value REAL NOT NULL,
+    amount_currency INTEGER NOT NULL,
+    timestamp INTEGER NOT NULL,
+    FOREIGN KEY(account_id) REFERENCES account(id)
+);
+
+CREATE TABLE IF NOT EXISTS security_price (
+    isin TEXT NOT NULL,
+    timestamp INTEGER NOT NULL,
+    price_value REAL NOT NULL,
+    price_currency INTEGER NOT NULL,
+    PRIMARY KEY (isin, timestamp)
+);
+
+CREATE TABLE IF NOT EXISTS valuation (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    account_id INTEGER NOT NULL,
+    value REAL NOT NULL,
+    currency INTEGER NOT NULL,
+    timestamp INTEGER NOT NULL,
+    FOREIGN KEY(account_id) REFERENCES account(id)
+);
+
+CREATE TABLE IF NOT EXISTS depreciation (
+    account_id INTEGER NOT NULL PRIMARY KEY,
+    kind INTEGER NOT NULL, -- 0 linear, 1 percentage
+    value TEXT NOT NULL, -- amount per year or percentage per year
+    currency INTEGER, -- currency of linear depreciations
+    FOREIGN KEY(account_id) REFERENCES account(id)
+);
+
+CREATE TABLE IF NOT EXISTS saved_filter (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    filter TEXT NOT NULL -- json of the transaction filter
+);
+
 CREATE TABLE IF NOT EXISTS transactions (
     id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
     amount_value INTEGER NOT NULL,
@@ -41,11 +111,31 @@ CREATE TABLE IF NOT EXISTS transactions (
     budget_sign BOOLEAN, -- true for positive and false for negative
     timestamp INTEGER,
     metadata TEXT NOT NULL,
+    status INTEGER NOT NULL DEFAULT 1, -- 0 pending, 1 booked, 2 reconciled
+    value_date INTEGER, -- NULL if it is the booking date
     FOREIGN KEY(source_id) REFERENCES account(id),
     FOREIGN KEY(destination_id) REFERENCES account(id),
     FOREIGN KEY (budget) REFERENCES budget(id)
 );
 
+-- trigram index over the title, description and metadata values for case-insensitive substring searches
+CREATE VIRTUAL TABLE IF NOT EXISTS transaction_search USING fts5(title, description, metadata, tokenize='trigram');
+
+CREATE TRIGGER IF NOT EXISTS transaction_search_insert AFTER INSERT ON transactions BEGIN
+    INSERT INTO transaction_search (rowid, title, description, metadata)
+    VALUES (new.id, new.title, new.description, (SELECT group_concat(value, char(10)) FROM json_each(new.metadata)));
+END;
+
+CREATE TRIGGER IF NOT EXISTS transaction_search_update AFTER UPDATE OF title, description, metadata ON transactions BEGIN
+    UPDATE transaction_search
+    SET title = new.title, description = new.description, metadata = (SELECT group_concat(value, char(10)) FROM json_each(new.metadata))
+    WHERE rowid = new.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transaction_search_delete AFTER DELETE ON transactions BEGIN
+    DELETE FROM transaction_search WHERE rowid = old.id;
+END;
+
 CREATE TABLE IF NOT EXISTS categories (
     id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
     name TEXT NOT NULL
@@ -9,7 +9,10 @@ use async_std::sync::{Mutex, MutexGuard};
 use const_format::formatc;
 use std::sync::Arc;
 
-const TRANSACTION_FIELDS: &str = "id, amount_value, currency, title, description, source_id, destination_id, budget, budget_sign, timestamp, metadata";
+/// The count of transactions loaded at once by [`SqliteFinanceManager::stream_transactions`].
+const STREAM_CHUNK_SIZE: usize = 100;
+
+const TRANSACTION_FIELDS: &str = "id, amount_value, currency, title, description, source_id, destination_id, budget, budget_sign, timestamp, metadata, status, value_date";
 
 impl TryFrom<&rusqlite::Row<'_>> for Transaction {
     type Error = anyhow::Error;
@@ -19,7 +22,7 @@ impl TryFrom<&rusqlite::Row<'_>> for Transaction {
         let budget_id = value.get::<usize, Option<u64>>(7)?;
         let budget_sign = value.get::<usize, Option<bool>>(8)?;
 
-        Transaction::new(
+        let mut transaction = Transaction::new(
             value.get(0)?,
             Currency::from_currency_id(
                 value.get(2)?,
@@ -42,7 +45,36 @@ impl TryFrom<&rusqlite::Row<'_>> for Transaction {
             DateTime::from_unix_timestamp(value.get(9)?).unwrap(),
             serde_json::from_str(&value.get::<usize, String>(10)?)?,
             HashMap::new(),
-        )
+        )?;
+        transaction.status = value.get::<usize, i32>(11)?.try_into()?;
+        transaction.value_date = value
+            .get::<usize, Option<i64>>(12)?
+            .map(DateTime::from_unix_timestamp)
+            .transpose()?;
+        Ok(transaction)
+    }
+}
+
+impl From<TransactionStatus> for i32 {
+    fn from(val: TransactionStatus) -> Self {
+        match val {
+            TransactionStatus::Pending => 0,
+            TransactionStatus::Booked => 1,
+            TransactionStatus::Reconciled => 2,
+        }
+    }
+}
+
+impl TryFrom<i32> for TransactionStatus {
+    type Error = anyhow::Error;
+
+    fn try_from(value: i32) -> Result<Self> {
+        match value {
+            0 => Ok(TransactionStatus::Pending),
+            1 => Ok(TransactionStatus::Booked),
+            2 => Ok(TransactionStatus::Reconciled),
+            _ => anyhow::bail!("invalid transaction status {value}"),
+        }
     }
 }
 
@@ -79,6 +111,79 @@ impl TryFrom<RecurringSignature> for budget::Recurring {
     }
 }
 
+impl From<account::AccountKind> for i32 {
+    fn from(val: account::AccountKind) -> Self {
+        match val {
+            account::AccountKind::Asset => 0,
+            account::AccountKind::Liability => 1,
+            account::AccountKind::Income => 2,
+            account::AccountKind::Expense => 3,
+            account::AccountKind::Equity => 4,
+        }
+    }
+}
+
+impl TryFrom<i32> for account::AccountKind {
+    type Error = anyhow::Error;
+
+    fn try_from(value: i32) -> Result<Self> {
+        match value {
+            0 => Ok(account::AccountKind::Asset),
+            1 => Ok(account::AccountKind::Liability),
+            2 => Ok(account::AccountKind::Income),
+            3 => Ok(account::AccountKind::Expense),
+            4 => Ok(account::AccountKind::Equity),
+            _ => anyhow::bail!("invalid account kind {value}"),
+        }
+    }
+}
+
+impl From<account::AssetType> for i32 {
+    fn from(val: account::AssetType) -> Self {
+        match val {
+            account::AssetType::Cash => 0,
+            account::AssetType::Securities => 1,
+            account::AssetType::Valuation => 2,
+        }
+    }
+}
+
+impl TryFrom<i32> for account::AssetType {
+    type Error = anyhow::Error;
+
+    fn try_from(value: i32) -> Result<Self> {
+        match value {
+            0 => Ok(account::AssetType::Cash),
+            1 => Ok(account::AssetType::Securities),
+            2 => Ok(account::AssetType::Valuation),
+            _ => anyhow::bail!("invalid asset type {value}"),
+        }
+    }
+}
+
+impl From<securities::TradeKind> for i32 {
+    fn from(val: securities::TradeKind) -> Self {
+        match val {
+            securities::TradeKind::Buy => 0,
+            securities::TradeKind::Sell => 1,
+            securities::TradeKind::Dividend => 2,
+        }
+    }
+}
+
+impl TryFrom<i32> for securities::TradeKind {
+    type Error = anyhow::Error;
+
+    fn try_from(value: i32) -> Result<Self> {
+        match value {
+            0 => Ok(securities::TradeKind::Buy),
+            1 => Ok(securities::TradeKind::Sell),
+            2 => Ok(securities::TradeKind::Dividend),
+            _ => anyhow::bail!("invalid trade kind {value}"),
+        }
+    }
+}
+
 const BUDGET_FIELDS: &str =
     "id, name, description, value, currency, timespan_type, timespan_field1, timespan_field2";
 
@@ -124,6 +229,63 @@ impl TryFrom<&rusqlite::Row<'_>> for Bill {
     }
 }
 
+const TRADE_FIELDS: &str =
+    "id, account_id, isin, kind, quantity, amount_value, amount_currency, timestamp";
+
+impl TryFrom<&rusqlite::Row<'_>> for securities::Trade {
+    type Error = anyhow::Error;
+
+    /// Expects the rows content to be [`TRADE_FIELDS`]
+    fn try_from(value: &rusqlite::Row<'_>) -> Result<Self> {
+        Ok(securities::Trade::new(
+            value.get(0)?,
+            value.get(1)?,
+            securities::Isin::new(value.get(2)?)?,
+            securities::TradeKind::try_from(value.get::<usize, i32>(3)?)?,
+            value.get::<usize, String>(4)?.parse()?,
+            Currency::from_currency_id(
+                value.get(6)?,
+                BigDecimal::from_f64(value.get(5)?).unwrap(),
+            )?,
+            DateTime::from_unix_timestamp(value.get(7)?)?,
+        ))
+    }
+}
+
+const VALUATION_FIELDS: &str = "id, account_id, value, currency, timestamp";
+
+impl TryFrom<&rusqlite::Row<'_>> for valuation::Valuation {
+    type Error = anyhow::Error;
+
+    /// Expects the rows content to be [`VALUATION_FIELDS`]
+    fn try_from(value: &rusqlite::Row<'_>) -> Result<Self> {
+        Ok(valuation::Valuation::new(
+            value.get(0)?,
+            value.get(1)?,
+            Currency::from_currency_id(
+                value.get(3)?,
+                BigDecimal::from_f64(value.get(2)?).unwrap(),
+            )?,
+            DateTime::from_unix_timestamp(value.get(4)?)?,
+        ))
+    }
+}
+
+const SAVED_FILTER_FIELDS: &str = "id, name, filter";
+
+impl TryFrom<&rusqlite::Row<'_>> for transaction_filter::SavedFilter {
+    type Error = anyhow::Error;
+
+    /// Expects the rows content to be [`SAVED_FILTER_FIELDS`]
+    fn try_from(value: &rusqlite::Row<'_>) -> Result<Self> {
+        Ok(transaction_filter::SavedFilter::new(
+            value.get(0)?,
+            value.get(1)?,
+            serde_json::from_str(&value.get::<usize, String>(2)?)?,
+        ))
+    }
+}
+
 async fn migrate_db(connection: MutexGuard<'_, rusqlite::Connection>) -> Result<()> {
     let version_result: Option<i32> = connection
         .query_row(
@@ -136,26 +298,87 @@ async fn migrate_db(connection: MutexGuard<'_, rusqlite::Connection>) -> Result<
         .map(|x: String| x.parse().unwrap());
 
     if let Some(version) = version_result {
-        match version {
-            0 => {
-                connection.execute(
-                    "ALTER TABLE bill ADD closed BOOLEAN NOT NULL DEFAULT false;",
-                    (),
-                )?;
-                connection.execute("UPDATE database_info SET value=1 WHERE tag='version'", ())?;
-            }
-            1 => {}
-            _ => panic!("unknown database version"),
+        if version > 7 {
+            panic!("unknown database version");
+        }
+        if version < 1 {
+            connection.execute(
+                "ALTER TABLE bill ADD closed BOOLEAN NOT NULL DEFAULT false;",
+                (),
+            )?;
+            connection.execute("UPDATE database_info SET value=1 WHERE tag='version'", ())?;
+        }
+        if version < 2 {
+            normalize_account_ids(&connection)?;
+            connection.execute("UPDATE database_info SET value=2 WHERE tag='version'", ())?;
+        }
+        if version < 3 {
+            connection.execute("ALTER TABLE book_checking_account ADD kind INTEGER;", ())?;
+            connection.execute("UPDATE database_info SET value=3 WHERE tag='version'", ())?;
+        }
+        if version < 4 {
+            connection.execute(
+                "ALTER TABLE asset_account ADD asset_type INTEGER NOT NULL DEFAULT 0;",
+                (),
+            )?;
+            connection.execute("UPDATE database_info SET value=4 WHERE tag='version'", ())?;
+        }
+        if version < 5 {
+            // the triggers only index new transactions
+            connection.execute(
+                "INSERT INTO transaction_search (rowid, title, description, metadata) \
+                SELECT id, title, description, (SELECT group_concat(value, char(10)) FROM json_each(metadata)) FROM transactions",
+                (),
+            )?;
+            connection.execute("UPDATE database_info SET value=5 WHERE tag='version'", ())?;
+        }
+        if version < 6 {
+            connection.execute(
+                "ALTER TABLE transactions ADD status INTEGER NOT NULL DEFAULT 1;",
+                (),
+            )?;
+            connection.execute("UPDATE database_info SET value=6 WHERE tag='version'", ())?;
+        }
+        if version < 7 {
+            connection.execute("ALTER TABLE transactions ADD value_date INTEGER;", ())?;
+            connection.execute("UPDATE database_info SET value=7 WHERE tag='version'", ())?;
         }
     } else {
         connection.execute(
-            "INSERT INTO database_info (tag, value) VALUES ('version', '1')",
+            "INSERT INTO database_info (tag, value) VALUES ('version', '7')",
             (),
         )?;
     }
     Ok(())
 }
 
+/// Rewrites the stored account ids in their normalized form.
+/// Before typed account ids existed, everything that was no IBAN was stored as it was entered.
+fn normalize_account_ids(connection: &rusqlite::Connection) -> Result<()> {
+    for (table, column) in [
+        ("asset_account", "iban"),
+        ("book_checking_account", "iban"),
+        ("account_identifier", "identifier"),
+    ] {
+        let values = connection
+            .prepare(&format!(
+                "SELECT rowid, {column} FROM {table} WHERE {column} IS NOT NULL"
+            ))?
+            .query_map((), |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<std::result::Result<Vec<(i64, String)>, _>>()?;
+        for (rowid, value) in values {
+            let account_id: AccountId = value.parse()?;
+            if account_id.electronic_str() != value {
+                connection.execute(
+                    &format!("UPDATE {table} SET {column}=?1 WHERE rowid=?2"),
+                    (account_id.electronic_str(), rowid),
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
 #[derive(Clone, Debug)]
 pub struct SqliteFinanceManager {
     path: String,
@@ -166,6 +389,7 @@ pub struct SqliteFinanceManager {
 impl SqliteFinanceManager {
     async fn init_db(&self) -> Result<()> {
         let connection = self.connect().await;
+        register_regexp(&connection)?;
         connection.execute_batch(include_str!("schema.sql"))?;
         migrate_db(connection).await?;
         Ok(())
@@ -192,6 +416,45 @@ impl SqliteFinanceManager {
     fn modified(&mut self) {
         self.last_modified = crate::DateTime::now_utc();
     }
+
+    /// Gets the transactions matching the condition on the transactions aliased as `t`.
+    /// `condition` binds its parameters to the given vector.
+    async fn get_transactions_where(
+        &self,
+        condition: impl FnOnce(&mut Vec<rusqlite::types::Value>) -> String,
+    ) -> Result<Vec<Transaction>> {
+        let connection = self.connect().await;
+        let mut parameters = Vec::new();
+        let condition = condition(&mut parameters);
+        query_transactions(
+            &connection,
+            &format!("SELECT {TRANSACTION_FIELDS} FROM transactions t WHERE {condition}"),
+            parameters,
+        )
+    }
+}
+
+/// Runs a query selecting [`TRANSACTION_FIELDS`] and loads the categories of the transactions.
+fn query_transactions(
+    connection: &rusqlite::Connection,
+    query: &str,
+    parameters: Vec<rusqlite::types::Value>,
+) -> Result<Vec<Transaction>> {
+    let result: Vec<Result<Transaction>> = connection
+        .prepare(query)?
+        .query_and_then(rusqlite::params_from_iter(parameters), |row| row.try_into())?
+        .collect();
+
+    let mut transactions: Vec<Transaction> = Vec::new();
+    for transaction in result {
+        let mut transaction = transaction?;
+        transaction.categories = get_categories_of_transaction(connection, transaction.id)?
+            .iter()
+            .map(|x| (x.0.id, x.1))
+            .collect();
+        transactions.push(transaction);
+    }
+    Ok(transactions)
 }
 
 impl FinanceManager for SqliteFinanceManager {
@@ -245,53 +508,49 @@ impl FinanceManager for SqliteFinanceManager {
     ) -> Result<account::AssetAccount> {
         self.modified();
         let connection = self.connect().await;
-
-        let asset_account_id = get_asset_account_id(&connection, account.id)?;
-
-        connection.execute(
-            "UPDATE asset_account SET name=?1, notes=?2, iban=?3, bic=?4, offset_value=?5, offset_currency=?6 WHERE id=?7",
-            (
-                &account.name,
-                &account.note,
-                account.iban.clone().map(|x| x.electronic_str().to_owned()),
-                account.bic.as_ref().map(|x|x.to_string()),
-                account.offset.get_eur_num(),
-                account.offset.get_currency_id(),
-                asset_account_id
-            ),
-        )?;
+        update_asset_account(&connection, &account)?;
         Ok(account)
     }
 
     async fn delete_account(&mut self, id: Id) -> Result<()> {
         self.modified();
         let connection = self.connect().await;
+        delete_account(&connection, id)
+    }
 
-        let account_result: (Option<Id>, Option<Id>) = connection
-            .query_row(
-                "SELECT asset_account, book_checking_account FROM account WHERE id=?1",
-                (id,),
-                |row| Ok((row.get(0)?, row.get(1)?)),
-            )
-            .context("could not query entry from account table")?;
-        connection.execute("DELETE FROM account WHERE id=?1", (id,))?;
-        match account_result {
-            (Some(asset_account_id), None) => {
-                connection
-                    .execute("DELETE FROM asset_account WHERE id=?1", (asset_account_id,))
-                    .context("could not delete from asset_account table")?;
-            }
-            (None, Some(book_checking_account_id)) => {
-                connection
-                    .execute(
-                        "DELETE FROM book_checking_account WHERE id=?1",
-                        (book_checking_account_id,),
-                    )
-                    .context("could not delete from book_checking_account table")?;
+    async fn merge_accounts(
+        &mut self,
+        keep: account::Account,
+        remove: Id,
+    ) -> Result<account::Account> {
+        self.modified();
+        let connection = self.connect().await;
+        let transaction = connection.unchecked_transaction()?;
+        match &keep {
+            account::Account::AssetAccount(acc) => update_asset_account(&transaction, acc)?,
+            account::Account::BookCheckingAccount(acc) => {
+                update_book_checking_account(&transaction, acc)?
             }
-            _ => anyhow::bail!("can not find account with id {}", id),
         }
-        Ok(())
+        transaction.execute(
+            "UPDATE transactions SET source_id=?1 WHERE source_id=?2",
+            (keep.id(), remove),
+        )?;
+        transaction.execute(
+            "UPDATE transactions SET destination_id=?1 WHERE destination_id=?2",
+            (keep.id(), remove),
+        )?;
+        transaction.execute(
+            "UPDATE trade SET account_id=?1 WHERE account_id=?2",
+            (keep.id(), remove),
+        )?;
+        transaction.execute(
+            "UPDATE valuation SET account_id=?1 WHERE account_id=?2",
+            (keep.id(), remove),
+        )?;
+        delete_account(&transaction, remove)?;
+        transaction.commit()?;
+        Ok(keep)
     }
 
     async fn create_book_checking_account(
@@ -312,17 +571,7 @@ impl FinanceManager for SqliteFinanceManager {
     ) -> Result<account::BookCheckingAccount> {
         self.modified();
         let connection = self.connect().await;
-        let account_id = get_book_checking_account_id(&connection, account.id)?;
-        connection.execute(
-            "UPDATE book_checking_account SET name=?1, notes=?2, iban=?3, bic=?4 WHERE id=?5",
-            (
-                &account.name,
-                &account.note,
-                account.iban.clone().map(|x| x.electronic_str().to_owned()),
-                account.bic.as_ref().map(|x| x.to_string()),
-                account_id,
-            ),
-        )?;
+        update_book_checking_account(&connection, &account)?;
         Ok(account)
     }
 
@@ -458,6 +707,140 @@ impl FinanceManager for SqliteFinanceManager {
         Ok(())
     }
 
+    async fn create_trade(
+        &mut self,
+        account: Id,
+        isin: securities::Isin,
+        kind: securities::TradeKind,
+        quantity: BigDecimal,
+        amount: Currency,
+        date: DateTime,
+    ) -> Result<securities::Trade> {
+        self.modified();
+        let connection = self.connect().await;
+        connection.execute(
+            "INSERT INTO trade (account_id, isin, kind, quantity, amount_value, amount_currency, timestamp) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            (
+                account,
+                isin.to_string(),
+                i32::from(kind),
+                quantity.to_string(),
+                amount.get_eur_num(),
+                amount.get_currency_id(),
+                date.unix_timestamp(),
+            ),
+        )?;
+        Ok(securities::Trade::new(
+            connection.last_insert_rowid() as Id,
+            account,
+            isin,
+            kind,
+            quantity,
+            amount,
+            date,
+        ))
+    }
+
+    async fn delete_trade(&mut self, id: Id) -> Result<()> {
+        self.modified();
+        let connection = self.connect().await;
+        connection.execute("DELETE FROM trade WHERE id=?1", (id,))?;
+        Ok(())
+    }
+
+    async fn get_trades(&self, account: Id) -> Result<Vec<securities::Trade>> {
+        let connection = self.connect().await;
+        connection
+            .prepare(formatc!(
+                "SELECT {} FROM trade WHERE account_id=?1 ORDER BY timestamp",
+                TRADE_FIELDS
+            ))?
+            .query_and_then((account,), |row| row.try_into())?
+            .collect()
+    }
+
+    async fn add_prices(&mut self, prices: Vec<securities::Price>) -> Result<()> {
+        self.modified();
+        let connection = self.connect().await;
+        let transaction = connection.unchecked_transaction()?;
+        for price in prices {
+            transaction.execute(
+                "INSERT OR REPLACE INTO security_price (isin, timestamp, price_value, price_currency) VALUES (?1, ?2, ?3, ?4)",
+                (
+                    price.isin.to_string(),
+                    price.date.unix_timestamp(),
+                    price.price.get_eur_num(),
+                    price.price.get_currency_id(),
+                ),
+            )?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    async fn get_prices(&self, isin: securities::Isin) -> Result<Vec<securities::Price>> {
+        let connection = self.connect().await;
+        let mut statement = connection.prepare(
+            "SELECT timestamp, price_value, price_currency FROM security_price WHERE isin=?1 ORDER BY timestamp",
+        )?;
+        let rows = statement.query_map((isin.to_string(),), |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?;
+        let mut prices = Vec::new();
+        for row in rows {
+            let (timestamp, value, currency): (i64, f64, i32) = row?;
+            prices.push(securities::Price {
+                isin: isin.clone(),
+                date: DateTime::from_unix_timestamp(timestamp)?,
+                price: Currency::from_currency_id(currency, BigDecimal::from_f64(value).unwrap())?,
+            });
+        }
+        Ok(prices)
+    }
+
+    async fn create_valuation(
+        &mut self,
+        account: Id,
+        value: Currency,
+        date: DateTime,
+    ) -> Result<valuation::Valuation> {
+        self.modified();
+        let connection = self.connect().await;
+        connection.execute(
+            "INSERT INTO valuation (account_id, value, currency, timestamp) VALUES (?1, ?2, ?3, ?4)",
+            (
+                account,
+                value.get_eur_num(),
+                value.get_currency_id(),
+                date.unix_timestamp(),
+            ),
+        )?;
+        Ok(valuation::Valuation::new(
+            connection.last_insert_rowid() as Id,
+            account,
+            value,
+            date,
+        ))
+    }
+
+    async fn delete_valuation(&mut self, id: Id) -> Result<()> {
+        self.modified();
+        let connection = self.connect().await;
+        connection.execute("DELETE FROM valuation WHERE id=?1", (id,))?;
+        Ok(())
+    }
+
+    async fn get_valuations(&self, account: Id) -> Result<Vec<valuation::Valuation>> {
+        let connection = self.connect().await;
+        connection
+            .prepare(formatc!(
+                "SELECT {} FROM valuation WHERE account_id=?1 ORDER BY timestamp",
+                VALUATION_FIELDS
+            ))?
+            .query_and_then((account,), |row| row.try_into())?
+            .collect()
+    }
+
     async fn get_accounts(&self) -> Result<Vec<account::Account>> {
         let connection = self.connect().await;
 
@@ -482,6 +865,35 @@ impl FinanceManager for SqliteFinanceManager {
         get_account(&connection, id)
     }
 
+    async fn get_accounts_by_account_id(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<account::Account>> {
+        // masked card numbers can match with different stored values
+        if let AccountId::Card(_) = account_id {
+            return Ok(self
+                .get_accounts()
+                .await?
+                .into_iter()
+                .filter(|x| x.has_account_id(&account_id))
+                .collect());
+        }
+        let connection = self.connect().await;
+        let ids = connection
+            .prepare(
+                "SELECT id FROM account WHERE asset_account IN (SELECT id FROM asset_account WHERE iban=?1)
+                    OR book_checking_account IN (SELECT id FROM book_checking_account WHERE iban=?1)
+                UNION SELECT account_id FROM account_identifier WHERE identifier=?1",
+            )?
+            .query_map((account_id.electronic_str(),), |row| row.get(0))?
+            .collect::<std::result::Result<Vec<Id>, _>>()?;
+        let mut accounts = Vec::with_capacity(ids.len());
+        for id in ids {
+            accounts.push(get_account(&connection, id)?.context("could not find account")?);
+        }
+        Ok(accounts)
+    }
+
     async fn get_transaction(&self, id: Id) -> Result<Option<Transaction>> {
         let connection = self.connect().await;
         let mut transaction: Transaction = connection.query_row_and_then(
@@ -499,6 +911,33 @@ impl FinanceManager for SqliteFinanceManager {
         Ok(Some(transaction))
     }
 
+    async fn get_account_sum(
+        &self,
+        account: &account::Account,
+        date: DateTime,
+        date_kind: DateKind,
+    ) -> Result<Currency> {
+        let account_id = i64::try_from(*account.id())?;
+        // the value date can be before or after the booking date
+        let date_sql = match date_kind {
+            DateKind::Booking => "t.timestamp",
+            DateKind::Value => "COALESCE(t.value_date, t.timestamp)",
+        };
+        let transactions = self
+            .get_transactions_where(|parameters| {
+                parameters.push(account_id.into());
+                parameters.push(date.unix_timestamp().into());
+                format!("(t.source_id=?1 OR t.destination_id=?1) AND {date_sql} <= ?2")
+            })
+            .await?;
+        Ok(finance_manager::account_sum(
+            *account.id(),
+            transactions,
+            date,
+            date_kind,
+        ))
+    }
+
     async fn get_transactions_of_account(
         &self,
         account: Id,
@@ -538,11 +977,31 @@ impl FinanceManager for SqliteFinanceManager {
         date: DateTime,
         metadata: HashMap<String, String>,
         categories: HashMap<Id, Sign>,
+    ) -> Result<Transaction> {
+        self.create_transaction_from(Transaction::new(
+            0,
+            amount,
+            title,
+            description,
+            source,
+            destination,
+            budget,
+            date,
+            metadata,
+            categories,
+        )?)
+        .await
+    }
+
+    async fn create_transaction_from(
+        &mut self,
+        mut transaction: Transaction,
     ) -> Result<Transaction> {
         self.modified();
-        let connection = self.connect().await;
+        let mut connection = self.connect().await;
+        let db_transaction = connection.transaction()?;
 
-        connection.execute(
+        db_transaction.execute(
             "
             INSERT INTO transactions (
                 amount_value,
@@ -554,43 +1013,37 @@ impl FinanceManager for SqliteFinanceManager {
                 budget,
                 budget_sign,
                 timestamp,
-                metadata
+                metadata,
+                status,
+                value_date
             ) VALUES (
-                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10
+                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12
             )
             ",
             (
-                amount.get_eur_num(),
-                amount.get_currency_id(),
-                &title,
-                &description,
-                &source,
-                &destination,
-                &budget.map(|x| x.0),
-                &budget.map(|x| match x.1 {
+                transaction.amount().get_eur_num(),
+                transaction.amount().get_currency_id(),
+                &transaction.title,
+                &transaction.description,
+                &transaction.source,
+                &transaction.destination,
+                &transaction.budget.map(|x| x.0),
+                &transaction.budget.map(|x| match x.1 {
                     Sign::Positive => true,
                     Sign::Negative => false,
                 }),
-                &date.unix_timestamp(),
-                serde_json::to_string(&metadata)?,
+                &transaction.date.unix_timestamp(),
+                serde_json::to_string(&transaction.metadata)?,
+                i32::from(transaction.status),
+                transaction.value_date.map(|x| x.unix_timestamp()),
             ),
         )?;
-        let transaction_id = connection.last_insert_rowid();
+        transaction.id = db_transaction.last_insert_rowid() as Id;
 
-        set_categories_for_transaction(&connection, transaction_id as Id, &categories)?; // set categories for transaction
+        set_categories_for_transaction(&db_transaction, transaction.id, &transaction.categories)?; // set categories for transaction
+        db_transaction.commit()?;
 
-        Transaction::new(
-            transaction_id as Id,
-            amount,
-            title,
-            description,
-            source,
-            destination,
-            budget,
-            date,
-            metadata,
-            categories,
-        )
+        Ok(transaction)
     }
 
     async fn create_budget(
@@ -690,42 +1143,28 @@ impl FinanceManager for SqliteFinanceManager {
     async fn update_transaction(&mut self, transaction: Transaction) -> Result<Transaction> {
         self.modified();
         let connection = self.connect().await;
-
-        connection.execute(
-            "UPDATE transactions SET amount_value=?1, currency=?2, title=?3, description=?4, source_id=?5, destination_id=?6, budget=?7, budget_sign=?8, timestamp=?9, metadata=?10 WHERE id=?11", 
-            (
-                transaction.amount().get_eur_num(),
-                transaction.amount().get_currency_id(),
-                &transaction.title,
-                &transaction.description,
-                transaction.source,
-                transaction.destination,
-                transaction.budget.map(|x| x.0),
-                transaction.budget.map(|x| match x.1 {Sign::Positive => true, Sign::Negative => false}),
-                transaction.date.unix_timestamp(),
-                serde_json::to_string(&transaction.metadata)?,
-                transaction.id
-            )
-        )?;
-
-        set_categories_for_transaction(&connection, transaction.id, &transaction.categories)?; // set categories for transaction
-
+        update_transaction(&connection, &transaction)?;
         Ok(transaction)
     }
 
     async fn delete_transaction(&mut self, id: Id) -> Result<()> {
         self.modified();
         let connection = self.connect().await;
-        connection.execute(
-            "DELETE FROM transaction_category WHERE transaction_id=?1",
-            (id,),
-        )?;
-        connection.execute(
-            "DELETE FROM bill_transaction WHERE transaction_id=?1",
-            (id,),
+        delete_transaction(&connection, id)
+    }
+
+    async fn merge_transactions(&mut self, keep: Transaction, remove: Id) -> Result<Transaction> {
+        self.modified();
+        let connection = self.connect().await;
+        let transaction = connection.unchecked_transaction()?;
+        update_transaction(&transaction, &keep)?;
+        transaction.execute(
+            "UPDATE bill_transaction SET transaction_id=?1 WHERE transaction_id=?2 AND bill_id NOT IN (SELECT bill_id FROM bill_transaction WHERE transaction_id=?1)",
+            (keep.id, remove),
         )?;
-        connection.execute("DELETE FROM transactions WHERE id=?1", (id,))?;
-        Ok(())
+        delete_transaction(&transaction, remove)?;
+        transaction.commit()?;
+        Ok(keep)
     }
 
     async fn get_transactions_of_budget(
@@ -825,6 +1264,175 @@ impl FinanceManager for SqliteFinanceManager {
         Ok(transactions)
     }
 
+    async fn get_filtered_transactions(
+        &self,
+        filter: transaction_filter::TransactionFilter,
+    ) -> Result<Vec<Transaction>> {
+        self.get_transactions_where(|parameters| transaction_filter_sql(&filter, parameters))
+            .await
+    }
+
+    async fn get_transactions_matching(
+        &self,
+        expression: filter_expression::FilterExpression,
+    ) -> Result<Vec<Transaction>> {
+        self.get_transactions_where(|parameters| expression_sql(&expression, parameters))
+            .await
+    }
+
+    fn stream_transactions(
+        &self,
+        expression: filter_expression::FilterExpression,
+    ) -> TransactionStream {
+        use futures::TryStreamExt;
+
+        let manager = self.clone();
+        // the id of the last streamed transaction or `None` after the last chunk
+        let chunks = futures::stream::try_unfold(Some(0), move |after| {
+            let manager = manager.clone();
+            let expression = expression.clone();
+            async move {
+                let Some(after) = after else {
+                    return Ok(None);
+                };
+                let connection = manager.connect().await;
+                let mut parameters = Vec::new();
+                let condition = expression_sql(&expression, &mut parameters);
+                parameters.push(i64::try_from(after)?.into());
+                let chunk = query_transactions(
+                    &connection,
+                    &format!(
+                        "SELECT {TRANSACTION_FIELDS} FROM transactions t WHERE {condition} AND t.id > ?{} ORDER BY t.id LIMIT {STREAM_CHUNK_SIZE}",
+                        parameters.len()
+                    ),
+                    parameters,
+                )?;
+                let next = if chunk.len() < STREAM_CHUNK_SIZE {
+                    None
+                } else {
+                    chunk.last().map(|transaction| transaction.id)
+                };
+                anyhow::Ok(Some((chunk, next)))
+            }
+        });
+        Box::pin(
+            chunks
+                .map_ok(|chunk| futures::stream::iter(chunk.into_iter().map(Ok)))
+                .try_flatten(),
+        )
+    }
+
+    async fn get_transactions_page(
+        &self,
+        expression: filter_expression::FilterExpression,
+        page: pagination::PageRequest,
+    ) -> Result<pagination::Page<Transaction>> {
+        let connection = self.connect().await;
+        let mut parameters = Vec::new();
+        let condition = expression_sql(&expression, &mut parameters);
+
+        let total: i64 = connection.query_row(
+            &format!("SELECT COUNT(*) FROM transactions t WHERE {condition}"),
+            rusqlite::params_from_iter(parameters.iter()),
+            |row| row.get(0),
+        )?;
+
+        let direction = if page.descending { "DESC" } else { "ASC" };
+        let order = match page.sort {
+            pagination::SortKey::Date => format!("t.timestamp {direction}"),
+            // the same order as the `Ord` implementation of `Currency`
+            pagination::SortKey::Amount => format!("t.amount_value {direction}"),
+            pagination::SortKey::Title => format!("t.title {direction}"),
+        };
+        parameters.push(i64::try_from(page.limit).unwrap_or(i64::MAX).into());
+        let limit = parameters.len();
+        parameters.push(i64::try_from(page.offset)?.into());
+        let offset = parameters.len();
+        let items = query_transactions(
+            &connection,
+            &format!(
+                "SELECT {TRANSACTION_FIELDS} FROM transactions t WHERE {condition} ORDER BY {order}, t.id {direction} LIMIT ?{limit} OFFSET ?{offset}"
+            ),
+            parameters,
+        )?;
+
+        Ok(pagination::Page {
+            items,
+            total: total as usize,
+        })
+    }
+
+    async fn aggregate_transactions(
+        &self,
+        filter: transaction_filter::TransactionFilter,
+        group_by: Option<aggregation::GroupBy>,
+        period: Option<aggregation::Period>,
+    ) -> Result<Vec<aggregation::Aggregate>> {
+        let connection = self.connect().await;
+        let mut parameters = Vec::new();
+        let condition = transaction_filter_sql(&filter, &mut parameters);
+
+        // every variant selects the group, the signed amount, the currency and the timestamp
+        let grouped = match group_by {
+            None => format!(
+                "SELECT NULL AS grp, t.amount_value AS amount, t.currency AS currency, t.timestamp AS timestamp FROM transactions t WHERE {condition}"
+            ),
+            Some(aggregation::GroupBy::Category) => format!(
+                "SELECT c.category_id AS grp, CASE WHEN c.sign THEN t.amount_value ELSE -t.amount_value END AS amount, t.currency AS currency, t.timestamp AS timestamp FROM transactions t JOIN transaction_category c ON c.transaction_id = t.id WHERE {condition}"
+            ),
+            Some(aggregation::GroupBy::Account) => format!(
+                "SELECT t.destination_id AS grp, t.amount_value AS amount, t.currency AS currency, t.timestamp AS timestamp FROM transactions t WHERE {condition} \
+                UNION ALL SELECT t.source_id, -t.amount_value, t.currency, t.timestamp FROM transactions t WHERE {condition}"
+            ),
+            Some(aggregation::GroupBy::Budget) => format!(
+                "SELECT t.budget AS grp, CASE WHEN t.budget_sign THEN t.amount_value ELSE -t.amount_value END AS amount, t.currency AS currency, t.timestamp AS timestamp FROM transactions t WHERE t.budget IS NOT NULL AND {condition}"
+            ),
+        };
+        // the first day of the period as julian day number
+        let period_sql = match period {
+            None => String::from("NULL"),
+            Some(aggregation::Period::Day(offset)) => format!(
+                "CAST(julianday(date(timestamp + {}, 'unixepoch')) + 0.5 AS INTEGER)",
+                offset.whole_seconds()
+            ),
+            Some(aggregation::Period::Month(offset)) => format!(
+                "CAST(julianday(date(timestamp + {}, 'unixepoch', 'start of month')) + 0.5 AS INTEGER)",
+                offset.whole_seconds()
+            ),
+        };
+
+        let mut statement = connection.prepare(&format!(
+            "SELECT grp, {period_sql}, currency, SUM(amount), COUNT(*) FROM ({grouped}) GROUP BY 1, 2, 3"
+        ))?;
+        let mut rows = statement.query(rusqlite::params_from_iter(parameters))?;
+
+        // different currencies of the same group are summed up like in the in-memory implementation
+        let mut groups: std::collections::BTreeMap<
+            (Option<Id>, Option<time::Date>),
+            (Currency, usize),
+        > = std::collections::BTreeMap::new();
+        while let Some(row) = rows.next()? {
+            let group: Option<Id> = row.get(0)?;
+            let julian_day: Option<i32> = row.get(1)?;
+            let currency: i32 = row.get(2)?;
+            let sum: f64 = row.get(3)?;
+            let count: usize = row.get(4)?;
+            let period = julian_day.map(time::Date::from_julian_day).transpose()?;
+            let (total, total_count) = groups.entry((group, period)).or_default();
+            *total += Currency::from_currency_id(currency, BigDecimal::from_f64(sum).unwrap())?;
+            *total_count += count;
+        }
+        Ok(groups
+            .into_iter()
+            .map(|((group, period), (sum, count))| aggregation::Aggregate {
+                group,
+                period,
+                sum,
+                count,
+            })
+            .collect())
+    }
+
     async fn get_categories(&self) -> Result<Vec<Category>> {
         let connection = self.connect().await;
         let mut categories = Vec::new();
@@ -911,6 +1519,75 @@ impl FinanceManager for SqliteFinanceManager {
 
         Ok(transactions)
     }
+    async fn get_saved_filters(&self) -> Result<Vec<transaction_filter::SavedFilter>> {
+        let connection = self.connect().await;
+        connection
+            .prepare(formatc!("SELECT {} FROM saved_filter", SAVED_FILTER_FIELDS))?
+            .query_and_then((), |row| row.try_into())?
+            .collect()
+    }
+
+    async fn get_saved_filter(&self, id: Id) -> Result<Option<transaction_filter::SavedFilter>> {
+        let connection = self.connect().await;
+        connection
+            .query_row(
+                formatc!(
+                    "SELECT {} FROM saved_filter WHERE id=?1",
+                    SAVED_FILTER_FIELDS
+                ),
+                (id,),
+                |row| Ok(row.try_into()),
+            )
+            .optional()?
+            .transpose()
+    }
+
+    async fn create_saved_filter(
+        &mut self,
+        name: String,
+        filter: transaction_filter::TransactionFilter,
+    ) -> Result<transaction_filter::SavedFilter> {
+        self.modified();
+        let connection = self.connect().await;
+        connection.execute(
+            "INSERT INTO saved_filter (name, filter) VALUES (?1, ?2)",
+            (&name, serde_json::to_string(&filter)?),
+        )?;
+        Ok(transaction_filter::SavedFilter::new(
+            connection.last_insert_rowid() as Id,
+            name,
+            filter,
+        ))
+    }
+
+    async fn update_saved_filter(
+        &mut self,
+        saved_filter: transaction_filter::SavedFilter,
+    ) -> Result<transaction_filter::SavedFilter> {
+        self.modified();
+        let connection = self.connect().await;
+        let changed = connection.execute(
+            "UPDATE saved_filter SET name=?1, filter=?2 WHERE id=?3",
+            (
+                &saved_filter.name,
+                serde_json::to_string(&saved_filter.filter)?,
+                saved_filter.id,
+            ),
+        )?;
+        if changed == 0 {
+            anyhow::bail!("Saved filter does not exist");
+        }
+        Ok(saved_filter)
+    }
+
+    async fn delete_saved_filter(&mut self, id: Id) -> Result<()> {
+        self.modified();
+        let connection = self.connect().await;
+        if connection.execute("DELETE FROM saved_filter WHERE id=?1", (id,))? == 0 {
+            anyhow::bail!("Saved filter does not exist");
+        }
+        Ok(())
+    }
 }
 
 fn get_asset_account_id(connection: &rusqlite::Connection, account_id: Id) -> Result<i32> {
@@ -940,6 +1617,50 @@ fn get_book_checking_account_id(connection: &rusqlite::Connection, account_id: I
 fn get_account(
     connection: &rusqlite::Connection,
     account_id: Id,
+) -> Result<Option<account::Account>> {
+    let mut account = match get_account_without_aliases(connection, account_id)? {
+        Some(x) => x,
+        None => return Ok(None),
+    };
+    let additional_ids = connection
+        .prepare("SELECT identifier FROM account_identifier WHERE account_id=?1 ORDER BY rowid")?
+        .query_and_then((account_id,), |row| -> Result<AccountId> {
+            row.get::<_, String>(0)?.parse()
+        })?
+        .collect::<Result<Vec<_>>>()?;
+    let aliases = connection
+        .prepare("SELECT alias FROM account_alias WHERE account_id=?1 ORDER BY rowid")?
+        .query_map((account_id,), |row| row.get(0))?
+        .collect::<std::result::Result<Vec<String>, _>>()?;
+    match &mut account {
+        account::Account::AssetAccount(acc) => {
+            acc.additional_ids = additional_ids;
+            acc.aliases = aliases;
+            acc.depreciation = get_depreciation(connection, account_id)?;
+        }
+        account::Account::BookCheckingAccount(acc) => {
+            acc.additional_ids = additional_ids;
+            acc.aliases = aliases;
+            acc.loan = get_loan(connection, account_id)?.map(Box::new);
+        }
+    }
+    Ok(Some(account))
+}
+
+/// name, notes, iban, bic, offset value, offset currency, asset type
+type AssetAccountRow = (
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    f64,
+    i32,
+    i32,
+);
+
+fn get_account_without_aliases(
+    connection: &rusqlite::Connection,
+    account_id: Id,
 ) -> Result<Option<account::Account>> {
     let account_result: (Option<Id>, Option<Id>) = match connection
         .query_row(
@@ -954,56 +1675,301 @@ fn get_account(
         None => return Ok(None),
     };
     if let Some(id) = account_result.0 {
-        let asset_account_result: (String, Option<String>, Option<String>, Option<String>, f64, i32) =
-            connection.query_row(
-                "SELECT name, notes, iban, bic, offset_value, offset_currency FROM asset_account WHERE id=?1",
-                (id,),
-                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?)),
-            )?;
-        Ok(Some(
-            account::AssetAccount::new(
-                account_id,
-                asset_account_result.0,
-                asset_account_result.1,
-                if let Some(iban_str) = asset_account_result.2 {
-                    Some(iban_str.parse()?)
-                } else {
-                    None
-                },
-                asset_account_result.3.map(|x| x.into()),
-                Currency::from_currency_id(
-                    asset_account_result.5,
-                    BigDecimal::from_f64(asset_account_result.4).unwrap(),
-                )?,
-            )
-            .into(),
-        ))
+        let asset_account_result: AssetAccountRow = connection.query_row(
+            "SELECT name, notes, iban, bic, offset_value, offset_currency, asset_type FROM asset_account WHERE id=?1",
+            (id,),
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                    row.get(6)?,
+                ))
+            },
+        )?;
+        let mut account = account::AssetAccount::new(
+            account_id,
+            asset_account_result.0,
+            asset_account_result.1,
+            if let Some(iban_str) = asset_account_result.2 {
+                Some(iban_str.parse()?)
+            } else {
+                None
+            },
+            asset_account_result.3.map(Bic::new_unchecked),
+            Currency::from_currency_id(
+                asset_account_result.5,
+                BigDecimal::from_f64(asset_account_result.4).unwrap(),
+            )?,
+        );
+        account.asset_type = account::AssetType::try_from(asset_account_result.6)?;
+        Ok(Some(account.into()))
     } else if let Some(id) = account_result.1 {
-        let book_checking_account_result: (String, Option<String>, Option<String>, Option<String>) =
-            connection.query_row(
-                "SELECT name, notes, iban, bic FROM book_checking_account WHERE id=?1",
-                (id,),
-                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
-            )?;
-        Ok(Some(
-            account::BookCheckingAccount::new(
-                account_id,
-                book_checking_account_result.0,
-                book_checking_account_result.1,
-                if let Some(iban_str) = book_checking_account_result.2 {
-                    Some(iban_str.parse()?)
-                } else {
-                    None
-                },
-                book_checking_account_result.3.map(|x| x.into()),
-            )
-            .into(),
-        ))
+        let book_checking_account_result: (
+            String,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+            Option<i32>,
+        ) = connection.query_row(
+            "SELECT name, notes, iban, bic, kind FROM book_checking_account WHERE id=?1",
+            (id,),
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            },
+        )?;
+        let mut account = account::BookCheckingAccount::new(
+            account_id,
+            book_checking_account_result.0,
+            book_checking_account_result.1,
+            if let Some(iban_str) = book_checking_account_result.2 {
+                Some(iban_str.parse()?)
+            } else {
+                None
+            },
+            book_checking_account_result.3.map(Bic::new_unchecked),
+        );
+        account.kind = book_checking_account_result
+            .4
+            .map(account::AccountKind::try_from)
+            .transpose()?;
+        Ok(Some(account.into()))
     } else {
         anyhow::bail!("could not find the account");
     }
 }
 
+fn update_asset_account(
+    connection: &rusqlite::Connection,
+    account: &account::AssetAccount,
+) -> Result<()> {
+    let asset_account_id = get_asset_account_id(connection, account.id)?;
+
+    connection.execute(
+        "UPDATE asset_account SET name=?1, notes=?2, iban=?3, bic=?4, offset_value=?5, offset_currency=?6, asset_type=?7 WHERE id=?8",
+        (
+            &account.name,
+            &account.note,
+            account.iban.clone().map(|x| x.electronic_str().to_owned()),
+            account.bic.as_ref().map(|x|x.to_string()),
+            account.offset.get_eur_num(),
+            account.offset.get_currency_id(),
+            i32::from(account.asset_type),
+            asset_account_id
+        ),
+    )?;
+    set_depreciation(connection, account.id, account.depreciation.as_ref())?;
+    set_account_ids_and_aliases(
+        connection,
+        account.id,
+        &account.additional_ids,
+        &account.aliases,
+    )
+}
+
+fn update_book_checking_account(
+    connection: &rusqlite::Connection,
+    account: &account::BookCheckingAccount,
+) -> Result<()> {
+    let account_id = get_book_checking_account_id(connection, account.id)?;
+    connection.execute(
+        "UPDATE book_checking_account SET name=?1, notes=?2, iban=?3, bic=?4, kind=?5 WHERE id=?6",
+        (
+            &account.name,
+            &account.note,
+            account.iban.clone().map(|x| x.electronic_str().to_owned()),
+            account.bic.as_ref().map(|x| x.to_string()),
+            account.kind.map(i32::from),
+            account_id,
+        ),
+    )?;
+    set_loan(connection, account.id, account.loan.as_deref())?;
+    set_account_ids_and_aliases(
+        connection,
+        account.id,
+        &account.additional_ids,
+        &account.aliases,
+    )
+}
+
+/// principal value, principal currency, interest rate, term, payment day, start, interest account
+type LoanRow = (f64, i32, String, u32, u8, i64, Option<Id>);
+
+fn get_loan(connection: &rusqlite::Connection, account_id: Id) -> Result<Option<loan::Loan>> {
+    let result: Option<LoanRow> = connection
+        .query_row(
+            "SELECT principal_value, principal_currency, interest_rate, term_months, payment_day, start, interest_account FROM loan WHERE account_id=?1",
+            (account_id,),
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                    row.get(6)?,
+                ))
+            },
+        )
+        .optional()?;
+    let Some(result) = result else {
+        return Ok(None);
+    };
+    Ok(Some(loan::Loan {
+        principal: Currency::from_currency_id(result.1, BigDecimal::from_f64(result.0).unwrap())?,
+        interest_rate: result.2.parse()?,
+        term_months: result.3,
+        payment_day: result.4,
+        start: DateTime::from_unix_timestamp(result.5)?,
+        interest_account: result.6,
+    }))
+}
+
+fn set_loan(
+    connection: &rusqlite::Connection,
+    account_id: Id,
+    loan: Option<&loan::Loan>,
+) -> Result<()> {
+    connection.execute("DELETE FROM loan WHERE account_id=?1", (account_id,))?;
+    if let Some(loan) = loan {
+        connection.execute(
+            "INSERT INTO loan (account_id, principal_value, principal_currency, interest_rate, term_months, payment_day, start, interest_account) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            (
+                account_id,
+                loan.principal.get_eur_num(),
+                loan.principal.get_currency_id(),
+                loan.interest_rate.to_string(),
+                loan.term_months,
+                loan.payment_day,
+                loan.start.unix_timestamp(),
+                loan.interest_account,
+            ),
+        )?;
+    }
+    Ok(())
+}
+
+fn get_depreciation(
+    connection: &rusqlite::Connection,
+    account_id: Id,
+) -> Result<Option<valuation::Depreciation>> {
+    let result: Option<(i32, String, Option<i32>)> = connection
+        .query_row(
+            "SELECT kind, value, currency FROM depreciation WHERE account_id=?1",
+            (account_id,),
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()?;
+    let Some((kind, value, currency)) = result else {
+        return Ok(None);
+    };
+    match kind {
+        0 => Ok(Some(valuation::Depreciation::Linear(
+            Currency::from_currency_id(
+                currency.context("a linear depreciation needs a currency")?,
+                value.parse()?,
+            )?,
+        ))),
+        1 => Ok(Some(valuation::Depreciation::Percentage(value.parse()?))),
+        _ => anyhow::bail!("invalid depreciation kind {kind}"),
+    }
+}
+
+fn set_depreciation(
+    connection: &rusqlite::Connection,
+    account_id: Id,
+    depreciation: Option<&valuation::Depreciation>,
+) -> Result<()> {
+    connection.execute(
+        "DELETE FROM depreciation WHERE account_id=?1",
+        (account_id,),
+    )?;
+    let values = match depreciation {
+        Some(valuation::Depreciation::Linear(amount)) => {
+            (0, amount.to_num_string(), Some(amount.get_currency_id()))
+        }
+        Some(valuation::Depreciation::Percentage(rate)) => (1, rate.to_string(), None),
+        None => return Ok(()),
+    };
+    connection.execute(
+        "INSERT INTO depreciation (account_id, kind, value, currency) VALUES (?1, ?2, ?3, ?4)",
+        (account_id, values.0, values.1, values.2),
+    )?;
+    Ok(())
+}
+
+fn set_account_ids_and_aliases(
+    connection: &rusqlite::Connection,
+    account_id: Id,
+    additional_ids: &[AccountId],
+    aliases: &[String],
+) -> Result<()> {
+    connection.execute(
+        "DELETE FROM account_identifier WHERE account_id=?1",
+        (account_id,),
+    )?;
+    for additional_id in additional_ids {
+        connection.execute(
+            "INSERT INTO account_identifier (account_id, identifier) VALUES (?1, ?2)",
+            (account_id, additional_id.electronic_str()),
+        )?;
+    }
+    connection.execute(
+        "DELETE FROM account_alias WHERE account_id=?1",
+        (account_id,),
+    )?;
+    for alias in aliases {
+        connection.execute(
+            "INSERT INTO account_alias (account_id, alias) VALUES (?1, ?2)",
+            (account_id, alias),
+        )?;
+    }
+    Ok(())
+}
+
+fn delete_account(connection: &rusqlite::Connection, id: Id) -> Result<()> {
+    let account_result: (Option<Id>, Option<Id>) = connection
+        .query_row(
+            "SELECT asset_account, book_checking_account FROM account WHERE id=?1",
+            (id,),
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .context("could not query entry from account table")?;
+    set_account_ids_and_aliases(connection, id, &[], &[])?;
+    set_loan(connection, id, None)?;
+    connection.execute("DELETE FROM trade WHERE account_id=?1", (id,))?;
+    connection.execute("DELETE FROM valuation WHERE account_id=?1", (id,))?;
+    set_depreciation(connection, id, None)?;
+    connection.execute("DELETE FROM account WHERE id=?1", (id,))?;
+    match account_result {
+        (Some(asset_account_id), None) => {
+            connection
+                .execute("DELETE FROM asset_account WHERE id=?1", (asset_account_id,))
+                .context("could not delete from asset_account table")?;
+        }
+        (None, Some(book_checking_account_id)) => {
+            connection
+                .execute(
+                    "DELETE FROM book_checking_account WHERE id=?1",
+                    (book_checking_account_id,),
+                )
+                .context("could not delete from book_checking_account table")?;
+        }
+        _ => anyhow::bail!("can not find account with id {}", id),
+    }
+    Ok(())
+}
+
 fn create_book_checking_account(
     connection: &rusqlite::Connection,
     name: String,
@@ -1033,6 +1999,209 @@ fn create_book_checking_account(
     ))
 }
 
+/// Registers `regexp(pattern, text)`, which is used for regex text filters.
+/// `NULL` never matches.
+fn register_regexp(connection: &rusqlite::Connection) -> Result<()> {
+    connection.create_scalar_function(
+        "regexp",
+        2,
+        rusqlite::functions::FunctionFlags::SQLITE_UTF8
+            | rusqlite::functions::FunctionFlags::SQLITE_DETERMINISTIC,
+        |context| {
+            // the compiled regex is cached by sqlite as long as the pattern does not change
+            let regex: Arc<regex::Regex> = context
+                .get_or_create_aux(0, |pattern| -> anyhow::Result<_> {
+                    Ok(regex::Regex::new(pattern.as_str()?)?)
+                })?;
+            let text = context
+                .get_raw(1)
+                .as_str_or_null()
+                .map_err(|error| rusqlite::Error::UserFunctionError(error.into()))?;
+            Ok(text.is_some_and(|text| regex.is_match(text)))
+        },
+    )?;
+    Ok(())
+}
+
+/// Translates the text search into a condition on the transactions aliased as `t`.
+/// The search text is bound as parameter to `parameters`.
+fn text_search_sql(
+    search: &transaction_filter::TextSearch,
+    parameters: &mut Vec<rusqlite::types::Value>,
+) -> String {
+    let Some(regex) = search.regex() else {
+        return String::from("0");
+    };
+    match search {
+        // the trigram index can only be used for at least three characters
+        transaction_filter::TextSearch::Substring(text) if text.chars().count() >= 3 => {
+            parameters.push(format!("\"{}\"", text.replace('"', "\"\"")).into());
+            format!(
+                "t.id IN (SELECT rowid FROM transaction_search WHERE transaction_search MATCH ?{})",
+                parameters.len()
+            )
+        }
+        _ => {
+            parameters.push(regex.as_str().to_string().into());
+            let parameter = parameters.len();
+            format!(
+                "regexp(?{parameter}, t.title) OR regexp(?{parameter}, t.description) \
+                OR EXISTS (SELECT 1 FROM json_each(t.metadata) WHERE regexp(?{parameter}, value))"
+            )
+        }
+    }
+}
+
+/// Translates the filter into a condition on the transactions aliased as `t`.
+/// Texts are bound as parameters to `parameters`.
+/// Like `get_filtered_transactions` only transactions in the total timespan of the filter match.
+fn transaction_filter_sql(
+    filter: &transaction_filter::TransactionFilter,
+    parameters: &mut Vec<rusqlite::types::Value>,
+) -> String {
+    expression_sql(
+        &(filter_expression::FilterExpression::from(filter.clone())
+            & filter_expression::Condition::Timespan(filter.total_timespan()).into()),
+        parameters,
+    )
+}
+
+/// Translates the expression into a condition on the transactions aliased as `t`.
+/// Texts and amounts are bound as parameters to `parameters`.
+/// Has the same semantics as [`filter_expression::FilterExpression::matches`].
+fn expression_sql(
+    expression: &filter_expression::FilterExpression,
+    parameters: &mut Vec<rusqlite::types::Value>,
+) -> String {
+    use filter_expression::FilterExpression;
+
+    let mut join = |expressions: &Vec<FilterExpression>, operator: &str, empty: &str| {
+        if expressions.is_empty() {
+            return String::from(empty);
+        }
+        let expressions = expressions
+            .iter()
+            .map(|expression| expression_sql(expression, parameters))
+            .collect::<Vec<_>>();
+        format!("({})", expressions.join(operator))
+    };
+    match expression {
+        FilterExpression::Condition(condition) => {
+            format!("({})", condition_sql(condition, parameters))
+        }
+        FilterExpression::And(expressions) => join(expressions, " AND ", "1"),
+        FilterExpression::Or(expressions) => join(expressions, " OR ", "0"),
+        FilterExpression::Not(expression) => {
+            format!("(NOT {})", expression_sql(expression, parameters))
+        }
+    }
+}
+
+fn condition_sql(
+    condition: &filter_expression::Condition,
+    parameters: &mut Vec<rusqlite::types::Value>,
+) -> String {
+    use filter_expression::Condition;
+
+    match condition {
+        Condition::Account(Some(id)) => format!("t.source_id = {id} OR t.destination_id = {id}"),
+        Condition::Account(None) => String::from("1"),
+        Condition::Category(Some(id)) => format!(
+            "EXISTS (SELECT 1 FROM transaction_category WHERE transaction_id = t.id AND category_id = {id})"
+        ),
+        Condition::Category(None) => {
+            String::from("EXISTS (SELECT 1 FROM transaction_category WHERE transaction_id = t.id)")
+        }
+        Condition::Bill(Some(id)) => format!(
+            "EXISTS (SELECT 1 FROM bill_transaction WHERE transaction_id = t.id AND bill_id = {id})"
+        ),
+        Condition::Bill(None) => {
+            String::from("EXISTS (SELECT 1 FROM bill_transaction WHERE transaction_id = t.id)")
+        }
+        Condition::Budget(Some(id)) => format!("t.budget IS {id}"),
+        Condition::Budget(None) => String::from("t.budget IS NOT NULL"),
+        Condition::Text(search) => text_search_sql(search, parameters),
+        Condition::Amount(range) => {
+            let mut bounds = Vec::new();
+            for (bound, operator) in [(&range.min, ">="), (&range.max, "<=")] {
+                if let Some(bound) = bound {
+                    parameters.push(bound.get_eur_num().into());
+                    bounds.push(format!(
+                        "t.currency = {} AND t.amount_value {operator} ?{}",
+                        bound.get_currency_id(),
+                        parameters.len()
+                    ));
+                }
+            }
+            if bounds.is_empty() {
+                String::from("1")
+            } else {
+                bounds.join(" AND ")
+            }
+        }
+        Condition::Metadata(predicate) => {
+            parameters.push(predicate.key.clone().into());
+            let mut condition = format!(
+                "EXISTS (SELECT 1 FROM json_each(t.metadata) WHERE key = ?{}",
+                parameters.len()
+            );
+            if let Some(value) = &predicate.value {
+                parameters.push(value.clone().into());
+                condition += &format!(" AND value = ?{}", parameters.len());
+            }
+            condition + ")"
+        }
+        Condition::Timespan((start, end)) => {
+            let mut bounds = vec![String::from("1")];
+            if let Some(start) = start {
+                // timestamps are stored in whole seconds
+                let start = start.unix_timestamp() + i64::from(start.nanosecond() > 0);
+                bounds.push(format!("t.timestamp >= {start}"));
+            }
+            if let Some(end) = end {
+                bounds.push(format!("t.timestamp <= {}", end.unix_timestamp()));
+            }
+            bounds.join(" AND ")
+        }
+    }
+}
+
+fn update_transaction(connection: &rusqlite::Connection, transaction: &Transaction) -> Result<()> {
+    connection.execute(
+        "UPDATE transactions SET amount_value=?1, currency=?2, title=?3, description=?4, source_id=?5, destination_id=?6, budget=?7, budget_sign=?8, timestamp=?9, metadata=?10, status=?11, value_date=?12 WHERE id=?13", 
+        (
+            transaction.amount().get_eur_num(),
+            transaction.amount().get_currency_id(),
+            &transaction.title,
+            &transaction.description,
+            transaction.source,
+            transaction.destination,
+            transaction.budget.map(|x| x.0),
+            transaction.budget.map(|x| match x.1 {Sign::Positive => true, Sign::Negative => false}),
+            transaction.date.unix_timestamp(),
+            serde_json::to_string(&transaction.metadata)?,
+            i32::from(transaction.status),
+            transaction.value_date.map(|x| x.unix_timestamp()),
+            transaction.id
+        )
+    )?;
+
+    set_categories_for_transaction(connection, transaction.id, &transaction.categories) // set categories for transaction
+}
+
+fn delete_transaction(connection: &rusqlite::Connection, id: Id) -> Result<()> {
+    connection.execute(
+        "DELETE FROM transaction_category WHERE transaction_id=?1",
+        (id,),
+    )?;
+    connection.execute(
+        "DELETE FROM bill_transaction WHERE transaction_id=?1",
+        (id,),
+    )?;
+    connection.execute("DELETE FROM transactions WHERE id=?1", (id,))?;
+    Ok(())
+}
+
 fn set_categories_for_transaction(
     connection: &rusqlite::Connection,
     transaction_id: Id,
@@ -1124,4 +2293,158 @@ mod test {
     }
 
     crate::finance_manager_test::unit_tests!(test_runner);
+
+    #[async_std::test]
+    async fn migrate_from_version_1() {
+        let connection =
+            async_std::sync::Mutex::new(rusqlite::Connection::open_in_memory().unwrap());
+        {
+            let connection = connection.lock().await;
+            connection
+                .execute_batch(include_str!("schema.sql"))
+                .unwrap();
+            // columns that did not exist in version 1
+            connection
+                .execute("ALTER TABLE book_checking_account DROP COLUMN kind", ())
+                .unwrap();
+            connection
+                .execute("ALTER TABLE asset_account DROP COLUMN asset_type", ())
+                .unwrap();
+            connection
+                .execute("ALTER TABLE transactions DROP COLUMN status", ())
+                .unwrap();
+            connection
+                .execute("ALTER TABLE transactions DROP COLUMN value_date", ())
+                .unwrap();
+            connection
+                .execute(
+                    "INSERT INTO database_info (tag, value) VALUES ('version', '1')",
+                    (),
+                )
+                .unwrap();
+            connection
+                .execute(
+                    "INSERT INTO book_checking_account (name, iban) VALUES ('PayPal', ' John.Doe@Example.com'), ('Bank', 'DE89370400440532013000')",
+                    (),
+                )
+                .unwrap();
+        }
+        super::migrate_db(connection.lock().await).await.unwrap();
+
+        let connection = connection.lock().await;
+        let ibans = connection
+            .prepare("SELECT iban FROM book_checking_account ORDER BY id")
+            .unwrap()
+            .query_map((), |row| row.get(0))
+            .unwrap()
+            .collect::<Result<Vec<String>, _>>()
+            .unwrap();
+        assert_eq!(
+            ibans,
+            vec!["john.doe@example.com", "DE89370400440532013000"]
+        );
+        let version: String = connection
+            .query_row(
+                "SELECT value FROM database_info WHERE tag='version'",
+                (),
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(version, "7");
+    }
+
+    #[async_std::test]
+    async fn migrate_from_version_4() {
+        let connection =
+            async_std::sync::Mutex::new(rusqlite::Connection::open_in_memory().unwrap());
+        {
+            let connection = connection.lock().await;
+            connection
+                .execute_batch(include_str!("schema.sql"))
+                .unwrap();
+            connection
+                .execute("ALTER TABLE transactions DROP COLUMN status", ())
+                .unwrap();
+            connection
+                .execute("ALTER TABLE transactions DROP COLUMN value_date", ())
+                .unwrap();
+            connection
+                .execute(
+                    "INSERT INTO database_info (tag, value) VALUES ('version', '4')",
+                    (),
+                )
+                .unwrap();
+            // the accounts are irrelevant for the index
+            connection
+                .execute_batch("PRAGMA foreign_keys = OFF")
+                .unwrap();
+            connection
+                .execute(
+                    "INSERT INTO transactions (amount_value, currency, title, source_id, destination_id, timestamp, metadata) VALUES (1, 1, 'Amazon', 1, 2, 0, '{\"invoice\":\"0815\"}')",
+                    (),
+                )
+                .unwrap();
+            // transactions of version 4 are not indexed
+            connection
+                .execute("DELETE FROM transaction_search", ())
+                .unwrap();
+        }
+        super::migrate_db(connection.lock().await).await.unwrap();
+
+        let connection = connection.lock().await;
+        let indexed: (String, String) = connection
+            .query_row(
+                "SELECT title, metadata FROM transaction_search WHERE transaction_search MATCH '\"amaz\"'",
+                (),
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(indexed, ("Amazon".to_string(), "0815".to_string()));
+    }
+    #[async_std::test]
+    async fn migrate_from_version_5() {
+        let connection =
+            async_std::sync::Mutex::new(rusqlite::Connection::open_in_memory().unwrap());
+        {
+            let connection = connection.lock().await;
+            connection
+                .execute_batch(include_str!("schema.sql"))
+                .unwrap();
+            connection
+                .execute("ALTER TABLE transactions DROP COLUMN status", ())
+                .unwrap();
+            connection
+                .execute("ALTER TABLE transactions DROP COLUMN value_date", ())
+                .unwrap();
+            connection
+                .execute(
+                    "INSERT INTO database_info (tag, value) VALUES ('version', '5')",
+                    (),
+                )
+                .unwrap();
+            connection
+                .execute_batch("PRAGMA foreign_keys = OFF")
+                .unwrap();
+            connection
+                .execute(
+                    "INSERT INTO transactions (amount_value, currency, title, source_id, destination_id, timestamp, metadata) VALUES (1, 1, 'Rent', 1, 2, 0, '{}')",
+                    (),
+                )
+                .unwrap();
+        }
+        super::migrate_db(connection.lock().await).await.unwrap();
+
+        // the existing transactions were booked
+        let connection = connection.lock().await;
+        let transaction: crate::Transaction = connection
+            .query_row(
+                &format!("SELECT {} FROM transactions", super::TRANSACTION_FIELDS),
+                (),
+                |row| Ok(row.try_into()),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(transaction.status, crate::TransactionStatus::Booked);
+        assert_eq!(transaction.value_date, None);
+    }
 }
@@ -0,0 +1,85 @@
+//! Sorted pages of transactions, so that views only load the transactions they show.
+
+use super::Transaction;
+
+/// The property transactions are sorted by. Ties are broken by the id.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize,
+)]
+pub enum SortKey {
+    #[default]
+    Date,
+    Amount,
+    Title,
+}
+
+/// A window into the sorted transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct PageRequest {
+    /// The count of transactions skipped.
+    pub offset: usize,
+    /// The maximal count of transactions in the page.
+    pub limit: usize,
+    pub sort: SortKey,
+    pub descending: bool,
+}
+
+impl PageRequest {
+    /// The page sorted ascending by date.
+    pub fn new(offset: usize, limit: usize) -> Self {
+        Self {
+            offset,
+            limit,
+            sort: SortKey::default(),
+            descending: false,
+        }
+    }
+
+    pub fn sorted_by(mut self, sort: SortKey, descending: bool) -> Self {
+        self.sort = sort;
+        self.descending = descending;
+        self
+    }
+}
+
+/// The items of a page and the count of all items across all pages.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: usize,
+}
+
+impl<T> Page<T> {
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+        }
+    }
+}
+
+/// Sorts all transactions as requested and cuts out the page.
+pub fn paginate(mut transactions: Vec<Transaction>, request: &PageRequest) -> Page<Transaction> {
+    transactions.sort_by(|a, b| {
+        let ordering = match request.sort {
+            SortKey::Date => a.date.cmp(&b.date),
+            SortKey::Amount => a.amount().cmp(b.amount()),
+            SortKey::Title => a.title.cmp(&b.title),
+        }
+        .then(a.id.cmp(&b.id));
+        if request.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+    let total = transactions.len();
+    Page {
+        items: transactions
+            .into_iter()
+            .skip(request.offset)
+            .take(request.limit)
+            .collect(),
+        total,
+    }
+}
@@ -0,0 +1,198 @@
+//! Timespans relative to the current date like "this month", so that stored filters do not go
+//! stale. They are resolved to an absolute [`Timespan`] whenever they are used.
+
+use super::{DateTime, Timespan};
+
+/// A timespan of whole days relative to today.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum RelativeTimespan {
+    /// The last days including today.
+    LastDays(u16),
+    ThisMonth,
+    PreviousMonth,
+    ThisQuarter,
+    PreviousQuarter,
+    ThisYear,
+    PreviousYear,
+    /// From the start of the year until today.
+    YearToDate,
+}
+
+impl RelativeTimespan {
+    /// The timespans offered by inputs.
+    pub const PRESETS: [RelativeTimespan; 9] = [
+        RelativeTimespan::LastDays(7),
+        RelativeTimespan::LastDays(30),
+        RelativeTimespan::ThisMonth,
+        RelativeTimespan::PreviousMonth,
+        RelativeTimespan::ThisQuarter,
+        RelativeTimespan::PreviousQuarter,
+        RelativeTimespan::ThisYear,
+        RelativeTimespan::PreviousYear,
+        RelativeTimespan::YearToDate,
+    ];
+
+    /// The first and the last day of the timespan.
+    pub fn dates(self, today: time::Date) -> (time::Date, time::Date) {
+        let year = today.year();
+        let quarter_start = month_start(
+            year,
+            time::Month::try_from((u8::from(today.month()) - 1) / 3 * 3 + 1).unwrap(),
+        );
+        match self {
+            RelativeTimespan::LastDays(days) => (
+                today.saturating_sub(time::Duration::days(i64::from(days.max(1)) - 1)),
+                today,
+            ),
+            RelativeTimespan::ThisMonth => {
+                let start = month_start(year, today.month());
+                (start, month_end(start))
+            }
+            RelativeTimespan::PreviousMonth => {
+                let end = month_start(year, today.month()).previous_day().unwrap();
+                (month_start(end.year(), end.month()), end)
+            }
+            RelativeTimespan::ThisQuarter => {
+                (quarter_start, month_end(shift_months(quarter_start, 2)))
+            }
+            RelativeTimespan::PreviousQuarter => (
+                shift_months(quarter_start, -3),
+                quarter_start.previous_day().unwrap(),
+            ),
+            RelativeTimespan::ThisYear => (
+                month_start(year, time::Month::January),
+                month_end(month_start(year, time::Month::December)),
+            ),
+            RelativeTimespan::PreviousYear => (
+                month_start(year - 1, time::Month::January),
+                month_end(month_start(year - 1, time::Month::December)),
+            ),
+            RelativeTimespan::YearToDate => (month_start(year, time::Month::January), today),
+        }
+    }
+
+    /// Resolves the timespan from the start of its first day until the end of its last day,
+    /// where today is the date of `now` in `utc_offset`.
+    pub fn resolve(self, now: DateTime, utc_offset: time::UtcOffset) -> Timespan {
+        let (first, last) = self.dates(now.to_offset(utc_offset).date());
+        (
+            Some(
+                first
+                    .with_time(time::Time::MIDNIGHT)
+                    .assume_offset(utc_offset),
+            ),
+            Some(
+                last.with_time(time::Time::from_hms(23, 59, 59).unwrap())
+                    .assume_offset(utc_offset),
+            ),
+        )
+    }
+}
+
+impl std::fmt::Display for RelativeTimespan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RelativeTimespan::LastDays(1) => write!(f, "Today"),
+            RelativeTimespan::LastDays(days) => write!(f, "Last {days} days"),
+            RelativeTimespan::ThisMonth => write!(f, "This month"),
+            RelativeTimespan::PreviousMonth => write!(f, "Previous month"),
+            RelativeTimespan::ThisQuarter => write!(f, "This quarter"),
+            RelativeTimespan::PreviousQuarter => write!(f, "Previous quarter"),
+            RelativeTimespan::ThisYear => write!(f, "This year"),
+            RelativeTimespan::PreviousYear => write!(f, "Previous year"),
+            RelativeTimespan::YearToDate => write!(f, "Year to date"),
+        }
+    }
+}
+
+fn month_start(year: i32, month: time::Month) -> time::Date {
+    time::Date::from_calendar_date(year, month, 1).unwrap()
+}
+
+fn month_end(start: time::Date) -> time::Date {
+    start
+        .replace_day(start.month().length(start.year()))
+        .unwrap()
+}
+
+/// Shifts the first day of a month by whole months.
+fn shift_months(start: time::Date, months: i32) -> time::Date {
+    let index = start.year() * 12 + i32::from(u8::from(start.month())) - 1 + months;
+    month_start(
+        index.div_euclid(12),
+        time::Month::try_from(index.rem_euclid(12) as u8 + 1).unwrap(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use time::macros::*;
+
+    #[test]
+    fn dates() {
+        let today = date!(2024 - 02 - 15);
+        for (timespan, expected) in [
+            (
+                RelativeTimespan::LastDays(30),
+                (date!(2024 - 01 - 17), today),
+            ),
+            (RelativeTimespan::LastDays(1), (today, today)),
+            (
+                RelativeTimespan::ThisMonth,
+                (date!(2024 - 02 - 01), date!(2024 - 02 - 29)),
+            ),
+            (
+                RelativeTimespan::PreviousMonth,
+                (date!(2024 - 01 - 01), date!(2024 - 01 - 31)),
+            ),
+            (
+                RelativeTimespan::ThisQuarter,
+                (date!(2024 - 01 - 01), date!(2024 - 03 - 31)),
+            ),
+            (
+                RelativeTimespan::PreviousQuarter,
+                (date!(2023 - 10 - 01), date!(2023 - 12 - 31)),
+            ),
+            (
+                RelativeTimespan::ThisYear,
+                (date!(2024 - 01 - 01), date!(2024 - 12 - 31)),
+            ),
+            (
+                RelativeTimespan::PreviousYear,
+                (date!(2023 - 01 - 01), date!(2023 - 12 - 31)),
+            ),
+            (RelativeTimespan::YearToDate, (date!(2024 - 01 - 01), today)),
+        ] {
+            assert_eq!(timespan.dates(today), expected, "{timespan}");
+        }
+        assert_eq!(
+            RelativeTimespan::PreviousMonth.dates(date!(2024 - 01 - 31)),
+            (date!(2023 - 12 - 01), date!(2023 - 12 - 31))
+        );
+        assert_eq!(
+            RelativeTimespan::ThisQuarter.dates(date!(2024 - 11 - 30)),
+            (date!(2024 - 10 - 01), date!(2024 - 12 - 31))
+        );
+    }
+
+    #[test]
+    fn resolve_in_offset() {
+        // already the first of march in UTC+2
+        let now = datetime!(2024-02-29 23:00 UTC);
+        assert_eq!(
+            RelativeTimespan::ThisMonth.resolve(now, offset!(+2)),
+            (
+                Some(datetime!(2024-03-01 0:00 +2)),
+                Some(datetime!(2024-03-31 23:59:59 +2))
+            )
+        );
+        assert_eq!(
+            RelativeTimespan::ThisMonth.resolve(now, offset!(UTC)),
+            (
+                Some(datetime!(2024-02-01 0:00 UTC)),
+                Some(datetime!(2024-02-29 23:59:59 UTC))
+            )
+        );
+    }
+}
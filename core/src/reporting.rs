@@ -0,0 +1,526 @@
+//! Reports that summarize transactions over periods of time.
+//!
+//! The periods of a report are aligned to the start of their [`Interval`],
+//! e.g. the first day of a month.
+
+use super::{Category, Currency, DateTime, Id, Interval, Sign, Transaction};
+use bigdecimal::BigDecimal;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// The start (inclusive) and end (exclusive) of the periods that overlap with `start` to `end`.
+pub fn periods(start: DateTime, end: DateTime, interval: Interval) -> Vec<(DateTime, DateTime)> {
+    let first = interval.start_of(start);
+    let mut periods = Vec::new();
+    let mut count = 0;
+    loop {
+        let period_start = interval.nth(first, count);
+        if period_start > end {
+            break;
+        }
+        count += 1;
+        periods.push((period_start, interval.nth(first, count)));
+    }
+    periods
+}
+
+/// How the income and expenses of a period are split up.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
+pub enum Breakdown {
+    None,
+    /// Split by category. A transaction with multiple categories counts for each of them.
+    Category,
+    /// Split by the account on the other side of the own account.
+    Counterparty,
+}
+
+impl Breakdown {
+    pub const ALL: [Breakdown; 3] = [
+        Breakdown::None,
+        Breakdown::Category,
+        Breakdown::Counterparty,
+    ];
+}
+
+impl std::fmt::Display for Breakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Breakdown::None => write!(f, "None"),
+            Breakdown::Category => write!(f, "Category"),
+            Breakdown::Counterparty => write!(f, "Counterparty"),
+        }
+    }
+}
+
+/// Income and expenses of a group of a [`Breakdown`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct CashFlowGroup {
+    /// The category or counterparty account.
+    /// `None` for transactions without a category.
+    pub id: Option<Id>,
+    pub income: Currency,
+    /// Expenses are positive.
+    pub expenses: Currency,
+}
+
+impl CashFlowGroup {
+    pub fn net(&self) -> Currency {
+        self.income.clone() - self.expenses.clone()
+    }
+}
+
+/// Income and expenses from `start` (inclusive) to `end` (exclusive).
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct CashFlowPeriod {
+    pub start: DateTime,
+    pub end: DateTime,
+    pub income: Currency,
+    /// Expenses are positive.
+    pub expenses: Currency,
+    /// Empty if there is no breakdown.
+    pub groups: Vec<CashFlowGroup>,
+}
+
+impl CashFlowPeriod {
+    pub fn net(&self) -> Currency {
+        self.income.clone() - self.expenses.clone()
+    }
+}
+
+/// Calculates the cash flow of the periods of `interval` that overlap with `start` to `end`.
+///
+/// Only transactions that cross the boundary of the own accounts are counted.
+/// Money flowing into an own account is income, money flowing out of it is an expense.
+/// Transfers between two own accounts are excluded.
+pub fn cash_flow(
+    transactions: &[Transaction],
+    own_accounts: &HashSet<Id>,
+    start: DateTime,
+    end: DateTime,
+    interval: Interval,
+    breakdown: Breakdown,
+) -> Vec<CashFlowPeriod> {
+    let mut periods = periods(start, end, interval)
+        .into_iter()
+        .map(|(start, end)| {
+            (
+                CashFlowPeriod {
+                    start,
+                    end,
+                    income: Currency::default(),
+                    expenses: Currency::default(),
+                    groups: Vec::new(),
+                },
+                BTreeMap::<Option<Id>, (Currency, Currency)>::new(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    for transaction in transactions {
+        let outgoing = own_accounts.contains(&transaction.source);
+        let incoming = own_accounts.contains(&transaction.destination);
+        if outgoing == incoming {
+            continue;
+        }
+        let Some((period, groups)) = periods
+            .iter_mut()
+            .find(|(period, _)| period.start <= transaction.date && transaction.date < period.end)
+        else {
+            continue;
+        };
+        let ids = match breakdown {
+            Breakdown::None => Vec::new(),
+            Breakdown::Category if transaction.categories.is_empty() => vec![None],
+            Breakdown::Category => transaction.categories.keys().map(|id| Some(*id)).collect(),
+            Breakdown::Counterparty => vec![Some(if incoming {
+                transaction.source
+            } else {
+                transaction.destination
+            })],
+        };
+        if incoming {
+            period.income += transaction.amount();
+        } else {
+            period.expenses += transaction.amount();
+        }
+        for id in ids {
+            let (income, expenses) = groups.entry(id).or_default();
+            if incoming {
+                *income += transaction.amount();
+            } else {
+                *expenses += transaction.amount();
+            }
+        }
+    }
+
+    periods
+        .into_iter()
+        .map(|(mut period, groups)| {
+            period.groups = groups
+                .into_iter()
+                .map(|(id, (income, expenses))| CashFlowGroup {
+                    id,
+                    income,
+                    expenses,
+                })
+                .collect();
+            period
+        })
+        .collect()
+}
+
+/// The values of a category in the periods of a [`CategoryPivot`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct PivotRow {
+    /// `None` for transactions without a category.
+    pub category: Option<Id>,
+    pub values: Vec<Currency>,
+}
+
+impl PivotRow {
+    pub fn total(&self) -> Currency {
+        sum(&self.values)
+    }
+
+    /// The average value per period.
+    pub fn average(&self) -> Currency {
+        average(&self.values)
+    }
+
+    /// The difference of the value in `period` to the value in the period before.
+    /// `None` for the first period.
+    pub fn change(&self, period: usize) -> Option<Currency> {
+        change(&self.values, period)
+    }
+}
+
+/// A matrix of categories and periods.
+/// Every transaction counts with the sign of each of its categories (see
+/// [`crate::transactions_category_distribution`]). Transactions without a category are summed up
+/// in a row without a category.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct CategoryPivot {
+    /// The start (inclusive) and end (exclusive) of the columns.
+    pub periods: Vec<(DateTime, DateTime)>,
+    /// Sorted by category id, the row without a category is the last one.
+    pub rows: Vec<PivotRow>,
+}
+
+impl CategoryPivot {
+    pub fn new(
+        transactions: &[Transaction],
+        start: DateTime,
+        end: DateTime,
+        interval: Interval,
+    ) -> Self {
+        let periods = periods(start, end, interval);
+        let mut rows: BTreeMap<Option<Id>, Vec<Currency>> = BTreeMap::new();
+        for transaction in transactions {
+            let Some(period) = periods
+                .iter()
+                .position(|(start, end)| *start <= transaction.date && transaction.date < *end)
+            else {
+                continue;
+            };
+            let categories = if transaction.categories.is_empty() {
+                vec![(None, Sign::Positive)]
+            } else {
+                transaction
+                    .categories
+                    .iter()
+                    .map(|(id, sign)| (Some(*id), *sign))
+                    .collect()
+            };
+            for (category, sign) in categories {
+                let values = rows
+                    .entry(category)
+                    .or_insert_with(|| vec![Currency::default(); periods.len()]);
+                match sign {
+                    Sign::Positive => values[period] += transaction.amount(),
+                    Sign::Negative => values[period] -= transaction.amount(),
+                }
+            }
+        }
+
+        let mut rows = rows
+            .into_iter()
+            .map(|(category, values)| PivotRow { category, values })
+            .collect::<Vec<_>>();
+        // `None` is sorted first by the map
+        if rows.first().is_some_and(|row| row.category.is_none()) {
+            rows.rotate_left(1);
+        }
+        Self { periods, rows }
+    }
+
+    /// The sum of all rows per period.
+    pub fn column_totals(&self) -> Vec<Currency> {
+        (0..self.periods.len())
+            .map(|period| {
+                self.rows
+                    .iter()
+                    .fold(Currency::default(), |sum, row| sum + &row.values[period])
+            })
+            .collect()
+    }
+
+    pub fn total(&self) -> Currency {
+        sum(&self.column_totals())
+    }
+
+    /// The average of the column totals.
+    pub fn average(&self) -> Currency {
+        average(&self.column_totals())
+    }
+
+    /// The difference of the column total of `period` to the total of the period before.
+    /// `None` for the first period.
+    pub fn change(&self, period: usize) -> Option<Currency> {
+        change(&self.column_totals(), period)
+    }
+
+    /// Writes the matrix with the row and column totals and the averages as CSV.
+    /// The columns are separated by `,` and the periods are named by their start date.
+    pub fn to_csv(&self, categories: &[Category]) -> String {
+        let names = categories
+            .iter()
+            .map(|category| (category.id, category.name.as_str()))
+            .collect::<HashMap<_, _>>();
+        let mut csv = String::from("Category");
+        for (start, _) in &self.periods {
+            csv += &format!(",{}", start.date());
+        }
+        csv += ",Total,Average\n";
+
+        let line = |name: &str, values: &[Currency]| {
+            let mut line = csv_field(name);
+            for value in values {
+                line += &format!(",{}", csv_amount(value));
+            }
+            line + &format!(
+                ",{},{}\n",
+                csv_amount(&sum(values)),
+                csv_amount(&average(values))
+            )
+        };
+        for row in &self.rows {
+            let name = match row.category {
+                Some(id) => names.get(&id).copied().unwrap_or_default(),
+                None => "No category",
+            };
+            csv += &line(name, &row.values);
+        }
+        csv += &line("Total", &self.column_totals());
+        csv
+    }
+}
+
+fn sum(values: &[Currency]) -> Currency {
+    values
+        .iter()
+        .fold(Currency::default(), |sum, value| sum + value)
+}
+
+fn average(values: &[Currency]) -> Currency {
+    if values.is_empty() {
+        return Currency::default();
+    }
+    sum(values).scale(&(BigDecimal::from(1) / BigDecimal::from(values.len() as u64)))
+}
+
+fn change(values: &[Currency], period: usize) -> Option<Currency> {
+    if period == 0 {
+        return None;
+    }
+    Some(values.get(period)?.clone() - values.get(period - 1)?.clone())
+}
+
+/// The amount with two decimal places and a decimal point.
+fn csv_amount(value: &Currency) -> String {
+    match value {
+        Currency::Eur(value) => format!("{value:.2}"),
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::eur;
+    use std::collections::HashMap;
+    use time::macros::datetime;
+
+    fn transaction(
+        id: Id,
+        amount: &str,
+        source: Id,
+        destination: Id,
+        date: DateTime,
+        categories: &[Id],
+    ) -> Transaction {
+        Transaction::new(
+            id,
+            eur(amount),
+            String::new(),
+            None,
+            source,
+            destination,
+            None,
+            date,
+            HashMap::new(),
+            categories
+                .iter()
+                .map(|id| (*id, crate::Sign::Positive))
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn monthly_cash_flow() {
+        let own = HashSet::from([1, 2]);
+        let transactions = vec![
+            transaction(1, "1000", 10, 1, datetime!(2024-01-15 0:00 UTC), &[]),
+            transaction(2, "300", 1, 11, datetime!(2024-01-20 0:00 UTC), &[100]),
+            transaction(3, "500", 1, 2, datetime!(2024-01-25 0:00 UTC), &[]),
+            transaction(4, "50", 2, 11, datetime!(2024-02-01 0:00 UTC), &[100, 101]),
+            transaction(5, "20", 10, 11, datetime!(2024-02-02 0:00 UTC), &[]),
+        ];
+        let periods = cash_flow(
+            &transactions,
+            &own,
+            datetime!(2024-01-10 0:00 UTC),
+            datetime!(2024-02-10 0:00 UTC),
+            Interval::Month,
+            Breakdown::None,
+        );
+        assert_eq!(periods.len(), 2);
+        assert_eq!(periods[0].start, datetime!(2024-01-01 0:00 UTC));
+        assert_eq!(periods[0].end, datetime!(2024-02-01 0:00 UTC));
+        assert_eq!(periods[0].income, eur("1000"));
+        assert_eq!(periods[0].expenses, eur("300"));
+        assert_eq!(periods[0].net(), eur("700"));
+        assert!(periods[0].groups.is_empty());
+        assert_eq!(periods[1].income, eur("0"));
+        assert_eq!(periods[1].expenses, eur("50"));
+
+        let periods = cash_flow(
+            &transactions,
+            &own,
+            datetime!(2024-01-10 0:00 UTC),
+            datetime!(2024-02-10 0:00 UTC),
+            Interval::Month,
+            Breakdown::Category,
+        );
+        assert_eq!(
+            periods[0].groups,
+            vec![
+                CashFlowGroup {
+                    id: None,
+                    income: eur("1000"),
+                    expenses: eur("0"),
+                },
+                CashFlowGroup {
+                    id: Some(100),
+                    income: eur("0"),
+                    expenses: eur("300"),
+                },
+            ]
+        );
+        assert_eq!(periods[1].groups.len(), 2);
+
+        let periods = cash_flow(
+            &transactions,
+            &own,
+            datetime!(2024-01-10 0:00 UTC),
+            datetime!(2024-02-10 0:00 UTC),
+            Interval::Year,
+            Breakdown::Counterparty,
+        );
+        assert_eq!(periods.len(), 1);
+        assert_eq!(
+            periods[0].groups,
+            vec![
+                CashFlowGroup {
+                    id: Some(10),
+                    income: eur("1000"),
+                    expenses: eur("0"),
+                },
+                CashFlowGroup {
+                    id: Some(11),
+                    income: eur("0"),
+                    expenses: eur("350"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn category_pivot() {
+        let transactions = vec![
+            transaction(1, "100", 1, 10, datetime!(2024-01-15 0:00 UTC), &[100]),
+            transaction(2, "40", 1, 10, datetime!(2024-02-15 0:00 UTC), &[100, 101]),
+            transaction(3, "10", 1, 10, datetime!(2024-02-20 0:00 UTC), &[]),
+            transaction(4, "5", 1, 10, datetime!(2024-03-01 0:00 UTC), &[101]),
+        ];
+        let pivot = CategoryPivot::new(
+            &transactions,
+            datetime!(2024-01-01 0:00 UTC),
+            datetime!(2024-03-31 0:00 UTC),
+            Interval::Month,
+        );
+        assert_eq!(pivot.periods.len(), 3);
+        assert_eq!(
+            pivot.rows,
+            vec![
+                PivotRow {
+                    category: Some(100),
+                    values: vec![eur("100"), eur("40"), eur("0")],
+                },
+                PivotRow {
+                    category: Some(101),
+                    values: vec![eur("0"), eur("40"), eur("5")],
+                },
+                PivotRow {
+                    category: None,
+                    values: vec![eur("0"), eur("10"), eur("0")],
+                },
+            ]
+        );
+        assert_eq!(pivot.rows[0].total(), eur("140"));
+        assert_eq!(pivot.rows[0].average(), eur("46.67"));
+        assert_eq!(pivot.rows[0].change(0), None);
+        assert_eq!(pivot.rows[0].change(1), Some(eur("-60")));
+        assert_eq!(pivot.column_totals(), vec![eur("100"), eur("90"), eur("5")]);
+        assert_eq!(pivot.total(), eur("195"));
+        assert_eq!(pivot.change(2), Some(eur("-85")));
+
+        let categories = vec![
+            Category {
+                id: 100,
+                name: "Food".to_string(),
+            },
+            Category {
+                id: 101,
+                name: "Rent, Flat".to_string(),
+            },
+        ];
+        let csv = pivot.to_csv(&categories);
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "Category,2024-01-01,2024-02-01,2024-03-01,Total,Average"
+        );
+        assert_eq!(lines[1], "Food,100.00,40.00,0.00,140.00,46.67");
+        assert_eq!(lines[2], "\"Rent, Flat\",0.00,40.00,5.00,45.00,15.00");
+        assert_eq!(lines[3], "No category,0.00,10.00,0.00,10.00,3.33");
+        assert_eq!(lines[4], "Total,100.00,90.00,5.00,195.00,65.00");
+    }
+}
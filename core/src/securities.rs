@@ -0,0 +1,385 @@
+//! Positions in securities like stocks and funds held by asset accounts of the type [`crate::account::AssetType::Securities`].
+//!
+//! Trades convert the cash of a securities account into positions and back.
+//! Cash has to be transferred into the account with normal transactions.
+//! Positions are valued with the latest known price and use the average cost method for the cost basis.
+
+use super::{Currency, DateTime, Id};
+use anyhow::Result;
+use bigdecimal::BigDecimal;
+use std::collections::HashMap;
+
+/// International Securities Identification Number like `US0378331005`.
+#[derive(
+    Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+pub struct Isin(String);
+
+impl Isin {
+    pub fn new(isin: String) -> Result<Self> {
+        let isin = isin.trim().to_uppercase().replace(' ', "");
+        if isin.len() != 12
+            || !isin[..2].chars().all(|c| c.is_ascii_alphabetic())
+            || !isin[2..11].chars().all(|c| c.is_ascii_alphanumeric())
+            || !isin[11..].chars().all(|c| c.is_ascii_digit())
+        {
+            anyhow::bail!("{isin} is not an ISIN");
+        }
+        if !luhn_valid(&isin) {
+            anyhow::bail!("The check digit of the ISIN {isin} is wrong");
+        }
+        Ok(Self(isin))
+    }
+
+    pub fn country_code(&self) -> &str {
+        &self.0[..2]
+    }
+}
+
+/// Letters count as two digit numbers (A = 10) before the luhn algorithm is applied.
+fn luhn_valid(isin: &str) -> bool {
+    let digits = isin
+        .chars()
+        .flat_map(|c| {
+            let value = c.to_digit(36).unwrap();
+            if value >= 10 {
+                vec![value / 10, value % 10]
+            } else {
+                vec![value]
+            }
+        })
+        .collect::<Vec<_>>();
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, digit)| {
+            if i % 2 == 1 {
+                let doubled = digit * 2;
+                doubled / 10 + doubled % 10
+            } else {
+                *digit
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+impl std::fmt::Display for Isin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for Isin {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
+pub enum TradeKind {
+    Buy,
+    Sell,
+    Dividend,
+}
+
+impl TradeKind {
+    pub const ALL: [TradeKind; 3] = [TradeKind::Buy, TradeKind::Sell, TradeKind::Dividend];
+}
+
+impl std::fmt::Display for TradeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TradeKind::Buy => write!(f, "Buy"),
+            TradeKind::Sell => write!(f, "Sell"),
+            TradeKind::Dividend => write!(f, "Dividend"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct Trade {
+    pub id: Id,
+    /// The securities account that holds the position.
+    pub account: Id,
+    pub isin: Isin,
+    pub kind: TradeKind,
+    /// Number of units bought or sold. Zero for dividends.
+    pub quantity: BigDecimal,
+    /// Cash paid for a buy or received for a sell or dividend, including fees.
+    pub amount: Currency,
+    pub date: DateTime,
+}
+
+impl Trade {
+    pub fn new(
+        id: Id,
+        account: Id,
+        isin: Isin,
+        kind: TradeKind,
+        quantity: BigDecimal,
+        amount: Currency,
+        date: DateTime,
+    ) -> Self {
+        Self {
+            id,
+            account,
+            isin,
+            kind,
+            quantity,
+            amount,
+            date,
+        }
+    }
+
+    /// Checks that the quantity and amount fit to the kind of the trade.
+    pub fn validate(&self) -> Result<()> {
+        if self.amount < Currency::default() {
+            anyhow::bail!("The amount of a trade cannot be negative");
+        }
+        match self.kind {
+            TradeKind::Buy | TradeKind::Sell if self.quantity <= 0 => {
+                anyhow::bail!("The quantity of a {} must be positive", self.kind)
+            }
+            TradeKind::Dividend if self.quantity != 0 => {
+                anyhow::bail!("A dividend has no quantity")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The change of the cash of the account caused by this trade.
+    pub fn cash_flow(&self) -> Currency {
+        match self.kind {
+            TradeKind::Buy => self.amount.negative(),
+            TradeKind::Sell | TradeKind::Dividend => self.amount.clone(),
+        }
+    }
+}
+
+/// Price of one unit of a security at a date.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct Price {
+    pub isin: Isin,
+    pub date: DateTime,
+    pub price: Currency,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct Position {
+    pub isin: Isin,
+    pub quantity: BigDecimal,
+    /// What was paid for the units that are still held.
+    pub cost_basis: Currency,
+    pub market_value: Currency,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Default)]
+pub struct Performance {
+    /// Open positions sorted by ISIN.
+    pub positions: Vec<Position>,
+    /// Sum of the cash flows of all trades.
+    pub cash_flow: Currency,
+    pub market_value: Currency,
+    pub cost_basis: Currency,
+    pub unrealized_gain: Currency,
+    /// Gains of sold units compared to their cost basis.
+    pub realized_gain: Currency,
+    pub dividends: Currency,
+}
+
+/// Computes the positions and gains of `trades` at `date`.
+///
+/// Positions are valued with the latest price at or before `date`.
+/// Without a price the unit price of the latest buy or sell is used.
+/// `prices` has to be sorted by date.
+pub fn performance(
+    trades: &[Trade],
+    prices: &HashMap<Isin, Vec<Price>>,
+    date: DateTime,
+) -> Performance {
+    let mut trades = trades.iter().filter(|t| t.date <= date).collect::<Vec<_>>();
+    trades.sort_by_key(|t| t.date);
+
+    let mut performance = Performance::default();
+    // quantity, cost basis and the unit price of the last trade
+    let mut holdings: HashMap<&Isin, (BigDecimal, Currency, Option<BigDecimal>)> = HashMap::new();
+    for trade in trades {
+        performance.cash_flow += trade.cash_flow();
+        let holding = holdings
+            .entry(&trade.isin)
+            .or_insert_with(|| (BigDecimal::from(0), Currency::default(), None));
+        match trade.kind {
+            TradeKind::Buy => {
+                holding.0 += &trade.quantity;
+                holding.1 += &trade.amount;
+                holding.2 = Some(amount_value(&trade.amount) / &trade.quantity);
+            }
+            TradeKind::Sell => {
+                let sold = trade.quantity.clone().min(holding.0.clone());
+                let sold_cost = if holding.0 > 0 {
+                    holding.1.scale(&(sold / &holding.0))
+                } else {
+                    Currency::default()
+                };
+                performance.realized_gain += trade.amount.clone() - &sold_cost;
+                holding.0 -= &trade.quantity;
+                holding.1 -= &sold_cost;
+                if holding.0 <= 0 {
+                    holding.0 = BigDecimal::from(0);
+                    holding.1 = Currency::default();
+                }
+                holding.2 = Some(amount_value(&trade.amount) / &trade.quantity);
+            }
+            TradeKind::Dividend => performance.dividends += &trade.amount,
+        }
+    }
+
+    for (isin, (quantity, cost_basis, last_trade_price)) in holdings {
+        if quantity <= 0 {
+            continue;
+        }
+        let price = prices
+            .get(isin)
+            .and_then(|prices| prices.iter().rev().find(|p| p.date <= date))
+            .map(|p| amount_value(&p.price))
+            .or(last_trade_price)
+            .unwrap_or_default();
+        let market_value =
+            Currency::from_currency_id(cost_basis.get_currency_id(), price * &quantity).unwrap();
+        performance.market_value += &market_value;
+        performance.cost_basis += &cost_basis;
+        performance.positions.push(Position {
+            isin: isin.clone(),
+            quantity,
+            cost_basis,
+            market_value,
+        });
+    }
+    performance.positions.sort_by(|a, b| a.isin.cmp(&b.isin));
+    performance.unrealized_gain = performance.market_value.clone() - &performance.cost_basis;
+    performance
+}
+
+fn amount_value(currency: &Currency) -> BigDecimal {
+    match currency {
+        Currency::Eur(value) => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::eur;
+    use time::macros::datetime;
+
+    fn trade(kind: TradeKind, quantity: &str, amount: &str, date: DateTime) -> Trade {
+        Trade::new(
+            0,
+            0,
+            "US0378331005".parse().unwrap(),
+            kind,
+            quantity.parse().unwrap(),
+            eur(amount),
+            date,
+        )
+    }
+
+    #[test]
+    fn isin_validation() {
+        assert_eq!(
+            Isin::new(" us0378331005".to_string()).unwrap().to_string(),
+            "US0378331005"
+        );
+        assert!(Isin::new("DE0005140008".to_string()).is_ok());
+        assert_eq!("DE0005140008".parse::<Isin>().unwrap().country_code(), "DE");
+        // wrong check digit
+        assert!(Isin::new("US0378331006".to_string()).is_err());
+        assert!(Isin::new("US037833100".to_string()).is_err());
+        assert!(Isin::new("120378331005".to_string()).is_err());
+    }
+
+    #[test]
+    fn trade_validation() {
+        let date = datetime!(2024-01-01 12:00 UTC);
+        assert!(trade(TradeKind::Buy, "1", "10", date).validate().is_ok());
+        assert!(trade(TradeKind::Buy, "0", "10", date).validate().is_err());
+        assert!(trade(TradeKind::Sell, "-1", "10", date).validate().is_err());
+        assert!(trade(TradeKind::Buy, "1", "-10", date).validate().is_err());
+        assert!(
+            trade(TradeKind::Dividend, "0", "1", date)
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            trade(TradeKind::Dividend, "1", "1", date)
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn average_cost_performance() {
+        let trades = vec![
+            trade(
+                TradeKind::Buy,
+                "10",
+                "1000",
+                datetime!(2024-01-01 12:00 UTC),
+            ),
+            trade(
+                TradeKind::Buy,
+                "10",
+                "1200",
+                datetime!(2024-02-01 12:00 UTC),
+            ),
+            trade(TradeKind::Sell, "5", "650", datetime!(2024-03-01 12:00 UTC)),
+            trade(
+                TradeKind::Dividend,
+                "0",
+                "20",
+                datetime!(2024-04-01 12:00 UTC),
+            ),
+        ];
+        let isin: Isin = "US0378331005".parse().unwrap();
+        let prices = HashMap::from([(
+            isin.clone(),
+            vec![Price {
+                isin: isin.clone(),
+                date: datetime!(2024-03-15 00:00 UTC),
+                price: eur("140"),
+            }],
+        )]);
+
+        // before the price is known the last trade price is used
+        let performance = performance(&trades, &prices, datetime!(2024-02-15 00:00 UTC));
+        assert_eq!(performance.cost_basis, eur("2200"));
+        assert_eq!(performance.market_value, eur("2400"));
+        assert_eq!(performance.cash_flow, eur("-2200"));
+
+        let performance = super::performance(&trades, &prices, datetime!(2024-05-01 00:00 UTC));
+        assert_eq!(performance.positions.len(), 1);
+        assert_eq!(performance.positions[0].quantity, BigDecimal::from(15));
+        assert_eq!(performance.cost_basis, eur("1650"));
+        assert_eq!(performance.market_value, eur("2100"));
+        assert_eq!(performance.unrealized_gain, eur("450"));
+        assert_eq!(performance.realized_gain, eur("100"));
+        assert_eq!(performance.dividends, eur("20"));
+        assert_eq!(performance.cash_flow, eur("-1530"));
+    }
+
+    #[test]
+    fn sold_out_position() {
+        let trades = vec![
+            trade(TradeKind::Buy, "2", "100", datetime!(2024-01-01 12:00 UTC)),
+            trade(TradeKind::Sell, "2", "90", datetime!(2024-02-01 12:00 UTC)),
+        ];
+        let performance = performance(&trades, &HashMap::new(), datetime!(2024-03-01 00:00 UTC));
+        assert!(performance.positions.is_empty());
+        assert_eq!(performance.market_value, Currency::default());
+        assert_eq!(performance.realized_gain, eur("-10"));
+    }
+}
@@ -3,6 +3,49 @@ use anyhow::Result;
 use super::{Currency, DateTime, Id, Sign};
 use std::collections::HashMap;
 
+/// The state of a transaction at the bank.
+#[derive(
+    Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash,
+)]
+pub enum TransactionStatus {
+    /// Announced by the bank but not booked yet, like a card payment. The amount can still change.
+    Pending,
+    #[default]
+    Booked,
+    /// Booked and checked against a statement.
+    Reconciled,
+}
+
+impl TransactionStatus {
+    pub const ALL: [TransactionStatus; 3] = [
+        TransactionStatus::Pending,
+        TransactionStatus::Booked,
+        TransactionStatus::Reconciled,
+    ];
+}
+
+impl std::fmt::Display for TransactionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransactionStatus::Pending => write!(f, "Pending"),
+            TransactionStatus::Booked => write!(f, "Booked"),
+            TransactionStatus::Reconciled => write!(f, "Reconciled"),
+        }
+    }
+}
+
+/// Which date of a transaction is used, e.g. when summing up an account.
+#[derive(
+    Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash,
+)]
+pub enum DateKind {
+    /// The date the bank booked the transaction. Statements are sorted by it.
+    #[default]
+    Booking,
+    /// The date from which the amount bears interest. Falls back to the booking date.
+    Value,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Transaction {
     pub id: Id,
@@ -13,9 +56,16 @@ pub struct Transaction {
     pub source: Id,
     pub destination: Id,
     pub budget: Option<(Id, Sign)>,
+    /// The booking date.
     pub date: DateTime,
+    /// Only set if it differs from the booking date, like the Valutadatum of a bank.
+    #[serde(default)]
+    pub value_date: Option<DateTime>,
     pub metadata: HashMap<String, String>,
     pub categories: HashMap<Id, Sign>,
+    /// New transactions are booked.
+    #[serde(default)]
+    pub status: TransactionStatus,
 }
 
 impl PartialEq for Transaction {
@@ -51,15 +101,32 @@ impl Transaction {
             destination,
             budget,
             date,
+            value_date: None,
             metadata,
             categories,
+            status: TransactionStatus::default(),
         })
     }
 
+    pub fn date_of(&self, kind: DateKind) -> DateTime {
+        match kind {
+            DateKind::Booking => self.date,
+            DateKind::Value => self.value_date.unwrap_or(self.date),
+        }
+    }
+
     pub fn amount(&self) -> &Currency {
         &self.amount
     }
 
+    pub fn set_amount(&mut self, amount: Currency) -> Result<()> {
+        if amount.get_eur_num().is_sign_negative() {
+            anyhow::bail!("Amount of transaction cannot be negative")
+        }
+        self.amount = amount;
+        Ok(())
+    }
+
     pub(super) fn connection_with_account(&self, account: Id) -> bool {
         if account == self.source {
             return true;
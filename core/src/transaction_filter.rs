@@ -1,4 +1,6 @@
-use super::{Bill, Id, Timespan, Transaction};
+pub mod query;
+
+use super::{Bill, Currency, DateTime, Id, RelativeTimespan, Timespan, Transaction};
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash)]
 pub struct Filter<I: Clone + std::fmt::Debug> {
@@ -8,6 +10,89 @@ pub struct Filter<I: Clone + std::fmt::Debug> {
     pub id: Option<I>,
     pub include: bool,
     pub timespan: Option<Timespan>,
+    /// Replaces `timespan` when resolved (see [`TransactionFilter::resolve_relative_timespans`]).
+    #[serde(default)]
+    pub relative_timespan: Option<RelativeTimespan>,
+}
+
+/// A case-insensitive search in the title, the description and the metadata values of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash)]
+pub enum TextSearch {
+    Substring(String),
+    /// An invalid regex matches nothing.
+    Regex(String),
+}
+
+impl TextSearch {
+    /// The case-insensitive regex of the search or `None` if the regex is invalid.
+    pub fn regex(&self) -> Option<regex::Regex> {
+        let pattern = match self {
+            TextSearch::Substring(text) => regex::escape(text),
+            TextSearch::Regex(pattern) => pattern.clone(),
+        };
+        regex::Regex::new(&format!("(?i){pattern}")).ok()
+    }
+
+    pub fn text(&self) -> &str {
+        match self {
+            TextSearch::Substring(text) | TextSearch::Regex(text) => text,
+        }
+    }
+
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        Self::regex_matches(&self.regex(), transaction)
+    }
+
+    fn regex_matches(regex: &Option<regex::Regex>, transaction: &Transaction) -> bool {
+        let Some(regex) = regex else {
+            return false;
+        };
+        regex.is_match(&transaction.title)
+            || transaction
+                .description
+                .as_ref()
+                .is_some_and(|description| regex.is_match(description))
+            || transaction
+                .metadata
+                .values()
+                .any(|value| regex.is_match(value))
+    }
+}
+
+/// An inclusive range of the amount of a transaction.
+/// Amounts in another currency than the bounds are never in the range.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash)]
+pub struct AmountRange {
+    pub min: Option<Currency>,
+    pub max: Option<Currency>,
+}
+
+impl AmountRange {
+    pub fn contains(&self, amount: &Currency) -> bool {
+        self.min
+            .as_ref()
+            .is_none_or(|min| min.get_currency_id() == amount.get_currency_id() && amount >= min)
+            && self.max.as_ref().is_none_or(|max| {
+                max.get_currency_id() == amount.get_currency_id() && amount <= max
+            })
+    }
+}
+
+/// Matches transactions that have the metadata key and, if given, the exact value for it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash)]
+pub struct MetadataFilter {
+    pub key: String,
+    pub value: Option<String>,
+}
+
+impl MetadataFilter {
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        match (transaction.metadata.get(&self.key), &self.value) {
+            (Some(value), Some(expected)) => value == expected,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
 }
 
 /// exclude > include
@@ -15,13 +100,25 @@ pub struct Filter<I: Clone + std::fmt::Debug> {
 /// Default timespan is used as default for every selected category and account.
 /// Included timespan includes for that account/category transactions that are in range (by default nothing is included).
 /// Exclude timespan excludes only transaction in that range but does not include everything else and has higher priority than includes.
-#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq)]
 pub struct TransactionFilter {
     pub default_timespan: Timespan,
+    /// Replaces `default_timespan` when resolved (see [`TransactionFilter::resolve_relative_timespans`]).
+    #[serde(default)]
+    pub relative_default_timespan: Option<RelativeTimespan>,
     pub accounts: Vec<Filter<Id>>,
     pub categories: Vec<Filter<Id>>,
     pub bills: Vec<Filter<Bill>>,
     pub budgets: Vec<Filter<Id>>,
+    /// A text filter without a search matches every transaction.
+    #[serde(default)]
+    pub texts: Vec<Filter<TextSearch>>,
+    /// An amount filter without a range matches every transaction.
+    #[serde(default)]
+    pub amounts: Vec<Filter<AmountRange>>,
+    /// A metadata filter without a predicate matches every transaction.
+    #[serde(default)]
+    pub metadata: Vec<Filter<MetadataFilter>>,
 }
 
 impl TransactionFilter {
@@ -44,6 +141,12 @@ impl TransactionFilter {
         }
     }
 
+    /// Replaces the account `from` with the account `to`, e.g. after merging the accounts.
+    /// Returns if the filter changed.
+    pub fn replace_account(&mut self, from: Id, to: Id) -> bool {
+        replace_id(&mut self.accounts, from, to)
+    }
+
     pub fn push_category(self, filter: Filter<Id>) -> Self {
         let mut new = self;
         new.categories.push(filter);
@@ -63,6 +166,12 @@ impl TransactionFilter {
         }
     }
 
+    /// Replaces the category `from` with the category `to`, e.g. after merging the categories.
+    /// Returns if the filter changed.
+    pub fn replace_category(&mut self, from: Id, to: Id) -> bool {
+        replace_id(&mut self.categories, from, to)
+    }
+
     pub fn push_bill(self, filter: Filter<Bill>) -> Self {
         let mut new = self;
         new.bills.push(filter);
@@ -101,6 +210,89 @@ impl TransactionFilter {
         }
     }
 
+    pub fn push_text(self, filter: Filter<TextSearch>) -> Self {
+        let mut new = self;
+        new.texts.push(filter);
+        new
+    }
+
+    pub fn delete_text(&mut self, filter: Filter<TextSearch>) {
+        self.texts.retain(|x| *x != filter);
+    }
+
+    pub fn edit_text(&mut self, old: Filter<TextSearch>, new: Filter<TextSearch>) {
+        for f in self.texts.iter_mut() {
+            if *f == old {
+                *f = new;
+                return;
+            }
+        }
+    }
+
+    pub fn push_amount(self, filter: Filter<AmountRange>) -> Self {
+        let mut new = self;
+        new.amounts.push(filter);
+        new
+    }
+
+    pub fn delete_amount(&mut self, filter: Filter<AmountRange>) {
+        self.amounts.retain(|x| *x != filter);
+    }
+
+    pub fn edit_amount(&mut self, old: Filter<AmountRange>, new: Filter<AmountRange>) {
+        for f in self.amounts.iter_mut() {
+            if *f == old {
+                *f = new;
+                return;
+            }
+        }
+    }
+
+    pub fn push_metadata(self, filter: Filter<MetadataFilter>) -> Self {
+        let mut new = self;
+        new.metadata.push(filter);
+        new
+    }
+
+    pub fn delete_metadata(&mut self, filter: Filter<MetadataFilter>) {
+        self.metadata.retain(|x| *x != filter);
+    }
+
+    pub fn edit_metadata(&mut self, old: Filter<MetadataFilter>, new: Filter<MetadataFilter>) {
+        for f in self.metadata.iter_mut() {
+            if *f == old {
+                *f = new;
+                return;
+            }
+        }
+    }
+
+    /// Sets the absolute timespans of the filter from its relative timespans at `now`.
+    pub fn resolve_relative_timespans(&mut self, now: DateTime, utc_offset: time::UtcOffset) {
+        fn resolve<I: Clone + std::fmt::Debug>(
+            filters: &mut [Filter<I>],
+            now: DateTime,
+            utc_offset: time::UtcOffset,
+        ) {
+            for filter in filters {
+                if let Some(relative) = filter.relative_timespan {
+                    filter.timespan = Some(relative.resolve(now, utc_offset));
+                }
+            }
+        }
+
+        if let Some(relative) = self.relative_default_timespan {
+            self.default_timespan = relative.resolve(now, utc_offset);
+        }
+        resolve(&mut self.accounts, now, utc_offset);
+        resolve(&mut self.categories, now, utc_offset);
+        resolve(&mut self.bills, now, utc_offset);
+        resolve(&mut self.budgets, now, utc_offset);
+        resolve(&mut self.texts, now, utc_offset);
+        resolve(&mut self.amounts, now, utc_offset);
+        resolve(&mut self.metadata, now, utc_offset);
+    }
+
     pub fn total_timespan(&self) -> Timespan {
         let mut timespan = self.default_timespan;
         for timespan_iteration in self
@@ -110,6 +302,9 @@ impl TransactionFilter {
             .chain(self.categories.iter().map(|x| x.timespan))
             .chain(self.bills.iter().map(|x| x.timespan))
             .chain(self.budgets.iter().map(|x| x.timespan))
+            .chain(self.texts.iter().map(|x| x.timespan))
+            .chain(self.amounts.iter().map(|x| x.timespan))
+            .chain(self.metadata.iter().map(|x| x.timespan))
         {
             if timespan_iteration.is_none() {
                 continue;
@@ -151,6 +346,12 @@ impl TransactionFilter {
         mut transactions: Vec<Transaction>,
         bills: &Vec<Bill>,
     ) -> Vec<Transaction> {
+        // compile the regexes only once
+        let text_regexes = self
+            .texts
+            .iter()
+            .map(|text| text.id.as_ref().map(TextSearch::regex))
+            .collect::<Vec<_>>();
         transactions.retain(|transaction| {
             // create iterators from filters with include/exclude and timespan
             let account_filter_iterator = self
@@ -213,6 +414,40 @@ impl TransactionFilter {
                     }
                 })
                 .map(|x| (x.include, x.timespan));
+            let text_filter_iterator = self
+                .texts
+                .iter()
+                .zip(&text_regexes)
+                .filter(|(text_filter, regex)| {
+                    if let Some(regex) = regex {
+                        TextSearch::regex_matches(regex, transaction) != text_filter.negated
+                    } else {
+                        !text_filter.negated
+                    }
+                })
+                .map(|(x, _)| (x.include, x.timespan));
+            let amount_filter_iterator = self
+                .amounts
+                .iter()
+                .filter(|amount_filter| {
+                    if let Some(range) = &amount_filter.id {
+                        range.contains(transaction.amount()) != amount_filter.negated
+                    } else {
+                        !amount_filter.negated
+                    }
+                })
+                .map(|x| (x.include, x.timespan));
+            let metadata_filter_iterator = self
+                .metadata
+                .iter()
+                .filter(|metadata_filter| {
+                    if let Some(predicate) = &metadata_filter.id {
+                        predicate.matches(transaction) != metadata_filter.negated
+                    } else {
+                        !metadata_filter.negated
+                    }
+                })
+                .map(|x| (x.include, x.timespan));
 
             // if the transaction should stay or get removed
             let mut stay = false;
@@ -222,6 +457,9 @@ impl TransactionFilter {
                 .chain(category_filter_iterator)
                 .chain(bill_filter_iterator)
                 .chain(budget_filter_iterator)
+                .chain(text_filter_iterator)
+                .chain(amount_filter_iterator)
+                .chain(metadata_filter_iterator)
                 .map(|(x, y)| (x, y.unwrap_or(self.default_timespan)))
             {
                 // check if it is in the timespan
@@ -253,10 +491,39 @@ impl TransactionFilter {
     }
 }
 
+fn replace_id(filters: &mut [Filter<Id>], from: Id, to: Id) -> bool {
+    let mut changed = false;
+    for filter in filters.iter_mut().filter(|filter| filter.id == Some(from)) {
+        filter.id = Some(to);
+        changed = true;
+    }
+    changed
+}
+
+/// A filter stored in the finance manager under a name.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct SavedFilter {
+    pub id: Id,
+    pub name: String,
+    pub filter: TransactionFilter,
+}
+
+impl SavedFilter {
+    pub fn new(id: Id, name: String, filter: TransactionFilter) -> Self {
+        Self { id, name, filter }
+    }
+}
+
+impl std::fmt::Display for SavedFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{Bill, Currency, Sign};
+    use crate::{Bill, Currency, Sign, eur};
     use std::collections::HashMap;
     use time::macros::*;
 
@@ -369,6 +636,7 @@ mod test {
             id: Some(bill.clone()),
             include: true,
             timespan: None,
+            relative_timespan: None,
         });
         let result = filter.filter_transactions(transactions, &vec![bill]);
         assert_eq!(result.len(), 2);
@@ -384,12 +652,14 @@ mod test {
             id: Some(generate_test_bill_1()),
             include: true,
             timespan: None,
+            relative_timespan: None,
         });
         filter.bills.push(Filter {
             negated: false,
             id: Some(generate_test_bill_2()),
             include: false,
             timespan: None,
+            relative_timespan: None,
         });
         let result = filter.filter_transactions(
             generate_test_transactions_1(),
@@ -407,6 +677,7 @@ mod test {
                 id: Some(1),
                 include: true,
                 timespan: None,
+                relative_timespan: None,
             })
             .filter_transactions(generate_test_transactions_1(), &Vec::new());
         assert_eq!(result.len(), 2);
@@ -422,12 +693,14 @@ mod test {
                 id: Some(1),
                 include: true,
                 timespan: None,
+                relative_timespan: None,
             })
             .push_account(Filter {
                 negated: false,
                 id: Some(5),
                 include: false,
                 timespan: None,
+                relative_timespan: None,
             })
             .filter_transactions(generate_test_transactions_1(), &Vec::new());
         assert_eq!(result.len(), 1);
@@ -449,6 +722,7 @@ mod test {
                 id: Some(2),
                 include: true,
                 timespan: None,
+                relative_timespan: None,
             })
             .filter_transactions(transactions, &Vec::new());
         assert_eq!(result.len(), 2);
@@ -471,12 +745,14 @@ mod test {
                 id: Some(1),
                 include: true,
                 timespan: None,
+                relative_timespan: None,
             })
             .push_budget(Filter {
                 negated: false,
                 id: Some(2),
                 include: false,
                 timespan: None,
+                relative_timespan: None,
             })
             .filter_transactions(transactions, &Vec::new());
         assert_eq!(result.len(), 2);
@@ -505,6 +781,7 @@ mod test {
                 id: Some(1),
                 include: true,
                 timespan: None,
+                relative_timespan: None,
             })
             .filter_transactions(transactions, &Vec::new());
         assert_eq!(result.len(), 2);
@@ -533,12 +810,14 @@ mod test {
                 id: Some(1),
                 include: true,
                 timespan: None,
+                relative_timespan: None,
             })
             .push_category(Filter {
                 negated: false,
                 id: Some(2),
                 include: false,
                 timespan: None,
+                relative_timespan: None,
             })
             .filter_transactions(transactions, &Vec::new());
         assert_eq!(result.len(), 1);
@@ -553,24 +832,28 @@ mod test {
             id: Some(generate_test_bill_1()),
             include: true,
             timespan: None,
+            relative_timespan: None,
         });
         filter.accounts.push(Filter {
             negated: false,
             id: Some(2),
             include: true,
             timespan: None,
+            relative_timespan: None,
         });
         filter.categories.push(Filter {
             negated: false,
             id: Some(1),
             include: true,
             timespan: None,
+            relative_timespan: None,
         });
         filter.budgets.push(Filter {
             negated: false,
             id: Some(1),
             include: true,
             timespan: None,
+            relative_timespan: None,
         });
         assert_eq!(filter.total_timespan(), (None, None));
     }
@@ -594,7 +877,8 @@ mod test {
                     negated: false,
                     id: Some(generate_test_bill_1()),
                     include: true,
-                    timespan: Some(timespan.clone()),
+                    timespan: Some(timespan),
+                    relative_timespan: None,
                 })
                 .total_timespan(),
             timespan.clone()
@@ -605,7 +889,8 @@ mod test {
                     negated: false,
                     id: Some(1),
                     include: true,
-                    timespan: Some(timespan.clone()),
+                    timespan: Some(timespan),
+                    relative_timespan: None,
                 })
                 .total_timespan(),
             timespan.clone()
@@ -616,7 +901,8 @@ mod test {
                     negated: false,
                     id: Some(2),
                     include: true,
-                    timespan: Some(timespan.clone()),
+                    timespan: Some(timespan),
+                    relative_timespan: None,
                 })
                 .total_timespan(),
             timespan.clone()
@@ -627,7 +913,8 @@ mod test {
                     negated: false,
                     id: Some(2),
                     include: true,
-                    timespan: Some(timespan.clone()),
+                    timespan: Some(timespan),
+                    relative_timespan: None,
                 })
                 .total_timespan(),
             timespan.clone()
@@ -642,12 +929,14 @@ mod test {
                 include: false,
                 id: None,
                 timespan: None,
+                relative_timespan: None,
             })
             .push_account(Filter {
                 negated: false,
                 include: true,
                 id: Some(1),
                 timespan: None,
+                relative_timespan: None,
             });
         let transactions = vec![
             generate_advanced_transaction(
@@ -673,12 +962,14 @@ mod test {
                 include: false,
                 id: None,
                 timespan: None,
+                relative_timespan: None,
             })
             .push_account(Filter {
                 negated: false,
                 include: true,
                 id: Some(1),
                 timespan: None,
+                relative_timespan: None,
             });
         let transactions = vec![
             generate_advanced_transaction(
@@ -704,12 +995,14 @@ mod test {
                 id: Some(1),
                 include: true,
                 timespan: None,
+                relative_timespan: None,
             })
             .push_budget(Filter {
                 negated: false,
                 id: None,
                 include: false,
                 timespan: None,
+                relative_timespan: None,
             });
         let result = filter.filter_transactions(
             vec![
@@ -730,12 +1023,14 @@ mod test {
                 id: Some(1),
                 include: true,
                 timespan: None,
+                relative_timespan: None,
             })
             .push_budget(Filter {
                 negated: true,
                 id: None,
                 include: false,
                 timespan: None,
+                relative_timespan: None,
             });
         let result = filter.filter_transactions(
             vec![
@@ -756,12 +1051,14 @@ mod test {
                 id: Some(1),
                 include: true,
                 timespan: None,
+                relative_timespan: None,
             })
             .push_bill(Filter {
                 negated: false,
                 id: None,
                 include: false,
                 timespan: None,
+                relative_timespan: None,
             });
         let result = filter.filter_transactions(
             vec![
@@ -785,12 +1082,14 @@ mod test {
                 id: Some(1),
                 include: true,
                 timespan: None,
+                relative_timespan: None,
             })
             .push_bill(Filter {
                 negated: true,
                 id: None,
                 include: false,
                 timespan: None,
+                relative_timespan: None,
             });
         let result = filter.filter_transactions(
             vec![
@@ -813,6 +1112,7 @@ mod test {
             id: None,
             include: false,
             timespan: None,
+            relative_timespan: None,
         });
         let result = filter.filter_transactions(
             vec![
@@ -832,12 +1132,14 @@ mod test {
                 id: None,
                 include: false,
                 timespan: None,
+                relative_timespan: None,
             })
             .push_account(Filter {
                 negated: false,
                 id: Some(2),
                 include: true,
                 timespan: None,
+                relative_timespan: None,
             });
         let result = filter.filter_transactions(
             vec![
@@ -905,6 +1207,7 @@ mod test {
                     time!(10:50),
                 )),
             )),
+            relative_timespan: None,
         });
 
         let mut result = vec![t1.clone(), t2.clone(), t3.clone()];
@@ -913,4 +1216,188 @@ mod test {
         assert!(result.iter().find(|x| x.id == 1).is_some());
         assert!(result.iter().find(|x| x.id == 3).is_some());
     }
+
+    fn generate_text_transaction(
+        id: Id,
+        title: &str,
+        description: Option<&str>,
+        metadata: &[(&str, &str)],
+    ) -> Transaction {
+        Transaction::new(
+            id,
+            Currency::default(),
+            title.to_string(),
+            description.map(str::to_string),
+            1,
+            2,
+            None,
+            time::OffsetDateTime::now_utc(),
+            metadata
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            HashMap::new(),
+        )
+        .unwrap()
+    }
+
+    fn filter_text_ids(search: Option<TextSearch>, negated: bool) -> Vec<Id> {
+        let transactions = vec![
+            generate_text_transaction(1, "AMAZON Marketplace", None, &[]),
+            generate_text_transaction(2, "Rent", Some("Flat amazon.de"), &[]),
+            generate_text_transaction(3, "Invoice", None, &[("invoice", "INV-2024-0815")]),
+            generate_text_transaction(4, "Groceries", Some("Übersee Markt"), &[]),
+        ];
+        let mut ids: Vec<Id> = TransactionFilter::default()
+            .push_text(Filter {
+                negated,
+                id: search,
+                include: true,
+                timespan: None,
+                relative_timespan: None,
+            })
+            .filter_transactions(transactions, &Vec::default())
+            .iter()
+            .map(|transaction| transaction.id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn text_filter_substring() {
+        let substring = |text: &str| Some(TextSearch::Substring(text.to_string()));
+        assert_eq!(filter_text_ids(substring("amazon"), false), vec![1, 2]);
+        assert_eq!(filter_text_ids(substring("amazon"), true), vec![3, 4]);
+        assert_eq!(filter_text_ids(substring("0815"), false), vec![3]);
+        assert_eq!(filter_text_ids(substring("invoice"), false), vec![3]);
+        assert_eq!(filter_text_ids(substring("übersee"), false), vec![4]);
+        // special characters are no regex
+        assert_eq!(filter_text_ids(substring("amazon.de"), false), vec![2]);
+        assert_eq!(filter_text_ids(substring("."), false), vec![2]);
+        assert_eq!(filter_text_ids(None, false), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn text_filter_regex() {
+        let regex = |pattern: &str| Some(TextSearch::Regex(pattern.to_string()));
+        assert_eq!(filter_text_ids(regex("^amazon"), false), vec![1]);
+        assert_eq!(filter_text_ids(regex(r"INV-\d{4}-\d+"), false), vec![3]);
+        assert_eq!(filter_text_ids(regex("markt?"), false), vec![1, 4]);
+        // an invalid regex matches nothing
+        assert!(filter_text_ids(regex("(unclosed"), false).is_empty());
+        assert_eq!(filter_text_ids(regex("(unclosed"), true), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn amount_range() {
+        let range = AmountRange {
+            min: Some(eur("10")),
+            max: Some(eur("20.50")),
+        };
+        assert!(!range.contains(&eur("9.99")));
+        assert!(range.contains(&eur("10")));
+        assert!(range.contains(&eur("20.50")));
+        assert!(!range.contains(&eur("20.51")));
+        assert!(
+            AmountRange {
+                min: None,
+                max: Some(eur("5"))
+            }
+            .contains(&eur("0"))
+        );
+        assert!(
+            AmountRange {
+                min: None,
+                max: None
+            }
+            .contains(&eur("1000"))
+        );
+    }
+
+    #[test]
+    fn metadata_filter() {
+        let transactions = vec![
+            generate_text_transaction(1, "CSV", None, &[("importer-import-format", "CSV_CAMT_V2")]),
+            generate_text_transaction(2, "CAMT", None, &[("importer-import-format", "CAMT")]),
+            generate_text_transaction(3, "None", None, &[]),
+        ];
+        let filter_ids = |key: &str, value: Option<&str>, negated: bool| {
+            TransactionFilter::default()
+                .push_metadata(Filter {
+                    negated,
+                    id: Some(MetadataFilter {
+                        key: key.to_string(),
+                        value: value.map(str::to_string),
+                    }),
+                    include: true,
+                    timespan: None,
+                    relative_timespan: None,
+                })
+                .filter_transactions(transactions.clone(), &Vec::default())
+                .iter()
+                .map(|transaction| transaction.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            filter_ids("importer-import-format", Some("CSV_CAMT_V2"), false),
+            vec![1]
+        );
+        assert_eq!(
+            filter_ids("importer-import-format", None, false),
+            vec![1, 2]
+        );
+        assert_eq!(filter_ids("importer-import-format", None, true), vec![3]);
+        assert!(filter_ids("importer", None, false).is_empty());
+    }
+
+    #[test]
+    fn relative_timespans() {
+        let mut filter = TransactionFilter {
+            default_timespan: (Some(datetime!(2020-01-01 0:00 UTC)), None),
+            relative_default_timespan: Some(RelativeTimespan::PreviousMonth),
+            ..Default::default()
+        }
+        .push_account(Filter {
+            negated: false,
+            id: Some(1),
+            include: true,
+            timespan: None,
+            relative_timespan: Some(RelativeTimespan::LastDays(2)),
+        })
+        .push_category(Filter {
+            negated: false,
+            id: Some(2),
+            include: true,
+            timespan: Some((None, Some(datetime!(2020-01-01 0:00 UTC)))),
+            relative_timespan: None,
+        });
+        filter.resolve_relative_timespans(datetime!(2024-03-10 12:00 UTC), offset!(+1));
+        assert_eq!(
+            filter.default_timespan,
+            (
+                Some(datetime!(2024-02-01 0:00 +1)),
+                Some(datetime!(2024-02-29 23:59:59 +1))
+            )
+        );
+        assert_eq!(
+            filter.accounts[0].timespan,
+            Some((
+                Some(datetime!(2024-03-09 0:00 +1)),
+                Some(datetime!(2024-03-10 23:59:59 +1))
+            ))
+        );
+        assert_eq!(
+            filter.categories[0].timespan,
+            Some((None, Some(datetime!(2020-01-01 0:00 UTC))))
+        );
+
+        // filters stored before relative timespans existed
+        let stored: TransactionFilter = serde_json::from_str(
+            r#"{"default_timespan":[null,null],"accounts":[{"negated":false,"id":1,"include":true,"timespan":null}],"categories":[],"bills":[],"budgets":[]}"#,
+        )
+        .unwrap();
+        assert_eq!(stored.relative_default_timespan, None);
+        assert_eq!(stored.accounts[0].relative_timespan, None);
+    }
 }
@@ -0,0 +1,856 @@
+//! A textual query language for [`FilterExpression`]s, e.g.
+//! `category:Food -account:Savings amount>50 date:2024-01..2024-03 "amazon"`.
+//!
+//! Terms separated by whitespace (or `AND`) must all match, `OR` combines alternatives and binds
+//! weaker than AND, `-` or `NOT` negates a term and parentheses group terms.
+//!
+//! The terms are:
+//! - `account:NAME`, `category:NAME`, `bill:NAME` and `budget:NAME` match by the case-insensitive
+//!   name (every object with that name). `#ID` matches by id and `*` matches any.
+//! - `amount>50`, `amount>=50`, `amount<50`, `amount<=50`, `amount:50` and ranges like
+//!   `amount:10..50`, `amount:10..` or `amount:..50`.
+//! - `date:2024`, `date:2024-01`, `date:2024-01-15` and ranges like `date:2024-01..2024-03`,
+//!   `date:2024-01..` or `date:..2024-03`, including the whole first and last period.
+//! - `meta:KEY` and `meta:KEY=VALUE` for metadata.
+//! - `regex:PATTERN` and every other word or `"quoted text"` search the text of transactions.
+//!
+//! Values containing whitespace or special characters are quoted with `"`,
+//! inside quotes `\"` and `\\` escape.
+
+use super::{AmountRange, MetadataFilter, TextSearch};
+use crate::filter_expression::{Condition, FilterExpression};
+use crate::{Bill, Budget, Category, Currency, FinanceManager, Id, Timespan, account};
+use anyhow::Result;
+use std::str::FromStr;
+
+/// An invalid query. The position is the index of the character where the error occurred.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{message} at position {position}")]
+pub struct QueryError {
+    pub position: usize,
+    pub message: String,
+}
+
+/// The names of the objects that can be referenced in a query.
+#[derive(Debug, Clone, Default)]
+pub struct Names {
+    accounts: Vec<(Id, String)>,
+    categories: Vec<(Id, String)>,
+    bills: Vec<(Id, String)>,
+    budgets: Vec<(Id, String)>,
+}
+
+impl Names {
+    pub fn new(
+        accounts: &[account::Account],
+        categories: &[Category],
+        bills: &[Bill],
+        budgets: &[Budget],
+    ) -> Self {
+        Self {
+            accounts: accounts
+                .iter()
+                .map(|x| (*x.id(), x.name().to_string()))
+                .collect(),
+            categories: categories.iter().map(|x| (x.id, x.name.clone())).collect(),
+            bills: bills.iter().map(|x| (x.id, x.name.clone())).collect(),
+            budgets: budgets.iter().map(|x| (x.id, x.name.clone())).collect(),
+        }
+    }
+
+    /// Loads the names of all accounts, categories, bills and budgets of the finance manager.
+    pub async fn load(finance_manager: &impl FinanceManager) -> Result<Self> {
+        Ok(Self::new(
+            &finance_manager.get_accounts().await?,
+            &finance_manager.get_categories().await?,
+            &finance_manager.get_bills(None).await?,
+            &finance_manager.get_budgets().await?,
+        ))
+    }
+
+    fn of(&self, kind: Kind) -> &[(Id, String)] {
+        match kind {
+            Kind::Account => &self.accounts,
+            Kind::Category => &self.categories,
+            Kind::Bill => &self.bills,
+            Kind::Budget => &self.budgets,
+        }
+    }
+
+    fn ids(&self, kind: Kind, name: &str) -> Vec<Id> {
+        let name = name.to_lowercase();
+        self.of(kind)
+            .iter()
+            .filter(|(_, x)| x.to_lowercase() == name)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Account,
+    Category,
+    Bill,
+    Budget,
+}
+
+impl Kind {
+    fn key(self) -> &'static str {
+        match self {
+            Kind::Account => "account",
+            Kind::Category => "category",
+            Kind::Bill => "bill",
+            Kind::Budget => "budget",
+        }
+    }
+
+    fn condition(self, id: Option<Id>) -> Condition {
+        match self {
+            Kind::Account => Condition::Account(id),
+            Kind::Category => Condition::Category(id),
+            Kind::Bill => Condition::Bill(id),
+            Kind::Budget => Condition::Budget(id),
+        }
+    }
+}
+
+/// Parses the query into an expression. An empty query matches every transaction.
+///
+/// Dates are interpreted in `utc_offset`.
+pub fn parse(
+    query: &str,
+    names: &Names,
+    utc_offset: time::UtcOffset,
+) -> Result<FilterExpression, QueryError> {
+    let mut parser = Parser {
+        chars: query.chars().collect(),
+        position: 0,
+        names,
+        utc_offset,
+    };
+    let expression = parser.parse_or()?;
+    if parser.peek().is_some() {
+        return Err(parser.error(parser.position, "unexpected `)`"));
+    }
+    Ok(expression)
+}
+
+/// Formats the expression as a query that [`parse`]s to an equivalent expression.
+///
+/// Names that do not identify a single object are written as ids and timespans are written
+/// as whole days in `utc_offset`.
+pub fn to_query(
+    expression: &FilterExpression,
+    names: &Names,
+    utc_offset: time::UtcOffset,
+) -> String {
+    Printer { names, utc_offset }.expression(expression, Context::Top)
+}
+
+struct Parser<'a> {
+    chars: Vec<char>,
+    position: usize,
+    names: &'a Names,
+    utc_offset: time::UtcOffset,
+}
+
+impl Parser<'_> {
+    fn error(&self, position: usize, message: impl Into<String>) -> QueryError {
+        QueryError {
+            position,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn at_keyword(&self, keyword: &str) -> bool {
+        let end = self.position + keyword.len();
+        end <= self.chars.len()
+            && self.chars[self.position..end]
+                .iter()
+                .copied()
+                .eq(keyword.chars())
+            && self
+                .chars
+                .get(end)
+                .is_none_or(|c| c.is_whitespace() || matches!(c, '(' | ')'))
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpression, QueryError> {
+        let Some(first) = self.parse_and()? else {
+            self.skip_whitespace();
+            if self.at_keyword("OR") {
+                return Err(self.error(self.position, "expected a term before `OR`"));
+            }
+            return Ok(FilterExpression::And(Vec::new()));
+        };
+        let mut alternatives = vec![first];
+        self.skip_whitespace();
+        while self.at_keyword("OR") {
+            self.position += 2;
+            let position = self.position;
+            let Some(alternative) = self.parse_and()? else {
+                return Err(self.error(position, "expected a term after `OR`"));
+            };
+            alternatives.push(alternative);
+            self.skip_whitespace();
+        }
+        Ok(if alternatives.len() == 1 {
+            alternatives.pop().unwrap()
+        } else {
+            FilterExpression::Or(alternatives)
+        })
+    }
+
+    /// Parses terms up to the next `OR` or `)`. Returns `None` if there are no terms.
+    fn parse_and(&mut self) -> Result<Option<FilterExpression>, QueryError> {
+        let mut terms = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek().is_none_or(|c| c == ')') || self.at_keyword("OR") {
+                break;
+            }
+            if self.at_keyword("AND") {
+                self.position += 3;
+                self.skip_whitespace();
+                if terms.is_empty()
+                    || self.peek().is_none_or(|c| c == ')')
+                    || self.at_keyword("OR")
+                    || self.at_keyword("AND")
+                {
+                    return Err(self.error(self.position, "expected a term around `AND`"));
+                }
+            }
+            terms.push(self.parse_unary()?);
+        }
+        Ok(match terms.len() {
+            0 => None,
+            1 => terms.pop(),
+            _ => Some(FilterExpression::And(terms)),
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterExpression, QueryError> {
+        let start = self.position;
+        if self.at_keyword("NOT") {
+            self.position += 3;
+            self.skip_whitespace();
+        } else if self.eat('-') {
+            if self.peek().is_none_or(char::is_whitespace) {
+                return Err(self.error(self.position, "expected a term after `-`"));
+            }
+        } else if self.eat('(') {
+            let expression = self.parse_or()?;
+            if !self.eat(')') {
+                return Err(self.error(start, "unclosed `(`"));
+            }
+            return Ok(expression);
+        } else {
+            return self.parse_term();
+        }
+        if self.peek().is_none_or(|c| c == ')') {
+            return Err(self.error(self.position, "expected a term to negate"));
+        }
+        Ok(!self.parse_unary()?)
+    }
+
+    fn parse_term(&mut self) -> Result<FilterExpression, QueryError> {
+        let start = self.position;
+        if self.peek() == Some('"') {
+            let text = self.quoted()?;
+            return Ok(Condition::Text(TextSearch::Substring(text)).into());
+        }
+        let word = self.bare(true);
+        if word.is_empty() {
+            return Err(self.error(start, format!("unexpected `{}`", self.peek().unwrap())));
+        }
+        if word.eq_ignore_ascii_case("amount") && matches!(self.peek(), Some('<' | '>' | '=')) {
+            return self.parse_comparison();
+        }
+        if !self.eat(':') {
+            return Ok(Condition::Text(TextSearch::Substring(word)).into());
+        }
+        let kind = match word.to_lowercase().as_str() {
+            "account" => Kind::Account,
+            "category" => Kind::Category,
+            "bill" => Kind::Bill,
+            "budget" => Kind::Budget,
+            "amount" => return self.parse_amount_range(),
+            "date" => return self.parse_date_range(),
+            "meta" | "metadata" => return self.parse_metadata(),
+            "regex" => {
+                let position = self.position;
+                let (pattern, _) = self.value(false)?;
+                let search = TextSearch::Regex(pattern);
+                if search.regex().is_none() {
+                    return Err(self.error(position, "invalid regex"));
+                }
+                return Ok(Condition::Text(search).into());
+            }
+            "text" => {
+                return Ok(Condition::Text(TextSearch::Substring(self.value(false)?.0)).into());
+            }
+            _ => return Err(self.error(start, format!("unknown key `{word}`"))),
+        };
+        self.parse_reference(kind)
+    }
+
+    /// Reads characters up to whitespace, a parenthesis, a quote or, if `stop_at_operators`,
+    /// one of `:<>=`.
+    fn bare(&mut self, stop_at_operators: bool) -> String {
+        let mut word = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_whitespace()
+                || matches!(c, '(' | ')' | '"')
+                || (stop_at_operators && matches!(c, ':' | '<' | '>' | '='))
+            {
+                break;
+            }
+            word.push(c);
+            self.position += 1;
+        }
+        word
+    }
+
+    fn quoted(&mut self) -> Result<String, QueryError> {
+        let start = self.position;
+        self.position += 1;
+        let mut text = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error(start, "unterminated `\"`")),
+                Some('"') => {
+                    self.position += 1;
+                    return Ok(text);
+                }
+                Some('\\') if self.chars.get(self.position + 1).is_some() => {
+                    text.push(self.chars[self.position + 1]);
+                    self.position += 2;
+                }
+                Some(c) => {
+                    text.push(c);
+                    self.position += 1;
+                }
+            }
+        }
+    }
+
+    /// Reads a quoted or bare value. The bool is true if the value was quoted.
+    fn value(&mut self, stop_at_equals: bool) -> Result<(String, bool), QueryError> {
+        if self.peek() == Some('"') {
+            return Ok((self.quoted()?, true));
+        }
+        let start = self.position;
+        let mut value = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || matches!(c, '(' | ')' | '"') || (stop_at_equals && c == '=') {
+                break;
+            }
+            value.push(c);
+            self.position += 1;
+        }
+        if value.is_empty() {
+            return Err(self.error(start, "expected a value"));
+        }
+        Ok((value, false))
+    }
+
+    fn parse_reference(&mut self, kind: Kind) -> Result<FilterExpression, QueryError> {
+        let position = self.position;
+        let (value, quoted) = self.value(false)?;
+        if !quoted && value == "*" {
+            return Ok(kind.condition(None).into());
+        }
+        if let Some(id) = value.strip_prefix('#').filter(|_| !quoted) {
+            return match id.parse() {
+                Ok(id) => Ok(kind.condition(Some(id)).into()),
+                Err(_) => Err(self.error(position, format!("invalid id `{id}`"))),
+            };
+        }
+        let mut conditions = self
+            .names
+            .ids(kind, &value)
+            .into_iter()
+            .map(|id| FilterExpression::from(kind.condition(Some(id))))
+            .collect::<Vec<_>>();
+        match conditions.len() {
+            0 => Err(self.error(position, format!("unknown {} `{value}`", kind.key()))),
+            1 => Ok(conditions.pop().unwrap()),
+            _ => Ok(FilterExpression::Or(conditions)),
+        }
+    }
+
+    fn number(&self, text: &str, position: usize) -> Result<bigdecimal::BigDecimal, QueryError> {
+        bigdecimal::BigDecimal::from_str(text)
+            .map_err(|_| self.error(position, format!("invalid amount `{text}`")))
+    }
+
+    fn parse_comparison(&mut self) -> Result<FilterExpression, QueryError> {
+        let operator = if self.eat('<') {
+            if self.eat('=') { "<=" } else { "<" }
+        } else if self.eat('>') {
+            if self.eat('=') { ">=" } else { ">" }
+        } else {
+            self.position += 1;
+            "="
+        };
+        let position = self.position;
+        let (text, _) = self.value(false)?;
+        let number = self.number(&text, position)?;
+        // Amounts are rounded to cents, so strict comparisons are inclusive ones a cent further.
+        let cent = bigdecimal::BigDecimal::new(1.into(), 2);
+        let currency = |number: bigdecimal::BigDecimal| Some(Currency::Eur(number.round(2)));
+        let range = match operator {
+            "<" => AmountRange {
+                min: None,
+                max: currency(number - cent),
+            },
+            "<=" => AmountRange {
+                min: None,
+                max: currency(number),
+            },
+            ">" => AmountRange {
+                min: currency(number + cent),
+                max: None,
+            },
+            ">=" => AmountRange {
+                min: currency(number),
+                max: None,
+            },
+            _ => AmountRange {
+                min: currency(number.clone()),
+                max: currency(number),
+            },
+        };
+        Ok(Condition::Amount(range).into())
+    }
+
+    fn parse_amount_range(&mut self) -> Result<FilterExpression, QueryError> {
+        let position = self.position;
+        let (text, _) = self.value(false)?;
+        let bound = |text: &str, offset: usize| -> Result<Option<Currency>, QueryError> {
+            if text.is_empty() {
+                return Ok(None);
+            }
+            Ok(Some(Currency::Eur(
+                self.number(text, position + offset)?.round(2),
+            )))
+        };
+        let range = match text.split_once("..") {
+            Some((min, max)) => AmountRange {
+                min: bound(min, 0)?,
+                max: bound(max, min.chars().count() + 2)?,
+            },
+            None => {
+                let amount = bound(&text, 0)?;
+                AmountRange {
+                    min: amount.clone(),
+                    max: amount,
+                }
+            }
+        };
+        Ok(Condition::Amount(range).into())
+    }
+
+    fn parse_date_range(&mut self) -> Result<FilterExpression, QueryError> {
+        let position = self.position;
+        let (text, _) = self.value(false)?;
+        let timespan: Timespan = match text.split_once("..") {
+            Some((start, end)) => (
+                self.period(start, position)?.map(|x| x.0),
+                self.period(end, position + start.chars().count() + 2)?
+                    .map(|x| x.1),
+            ),
+            None => match self.period(&text, position)? {
+                Some((start, end)) => (Some(start), Some(end)),
+                None => (None, None),
+            },
+        };
+        Ok(Condition::Timespan(timespan).into())
+    }
+
+    /// The first and last moment of a year, month or day like `2024`, `2024-01` or `2024-01-15`.
+    fn period(
+        &self,
+        text: &str,
+        position: usize,
+    ) -> Result<Option<(crate::DateTime, crate::DateTime)>, QueryError> {
+        if text.is_empty() {
+            return Ok(None);
+        }
+        let error = || self.error(position, format!("invalid date `{text}`"));
+        let mut parts = text.split('-');
+        let year: i32 = parts
+            .next()
+            .and_then(|x| x.parse().ok())
+            .ok_or_else(error)?;
+        let month: Option<time::Month> = match parts.next() {
+            Some(month) => Some(
+                month
+                    .parse::<u8>()
+                    .ok()
+                    .and_then(|x| time::Month::try_from(x).ok())
+                    .ok_or_else(error)?,
+            ),
+            None => None,
+        };
+        let day: Option<u8> = match parts.next() {
+            Some(day) => Some(day.parse().map_err(|_| error())?),
+            None => None,
+        };
+        if parts.next().is_some() {
+            return Err(error());
+        }
+        let (first, last) = match (month, day) {
+            (None, _) => (
+                time::Date::from_calendar_date(year, time::Month::January, 1),
+                time::Date::from_calendar_date(year, time::Month::December, 31),
+            ),
+            (Some(month), None) => (
+                time::Date::from_calendar_date(year, month, 1),
+                time::Date::from_calendar_date(year, month, month.length(year)),
+            ),
+            (Some(month), Some(day)) => {
+                let date = time::Date::from_calendar_date(year, month, day);
+                (date, date)
+            }
+        };
+        let (first, last) = (first.map_err(|_| error())?, last.map_err(|_| error())?);
+        Ok(Some((
+            first
+                .with_time(time::Time::MIDNIGHT)
+                .assume_offset(self.utc_offset),
+            last.with_time(time::Time::from_hms(23, 59, 59).unwrap())
+                .assume_offset(self.utc_offset),
+        )))
+    }
+
+    fn parse_metadata(&mut self) -> Result<FilterExpression, QueryError> {
+        let (key, _) = self.value(true)?;
+        let value = if self.eat('=') {
+            Some(self.value(false)?.0)
+        } else {
+            None
+        };
+        Ok(Condition::Metadata(MetadataFilter { key, value }).into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Context {
+    Top,
+    And,
+    Or,
+    Not,
+}
+
+struct Printer<'a> {
+    names: &'a Names,
+    utc_offset: time::UtcOffset,
+}
+
+impl Printer<'_> {
+    fn expression(&self, expression: &FilterExpression, context: Context) -> String {
+        match expression {
+            FilterExpression::Condition(condition) => self.condition(condition),
+            FilterExpression::And(expressions) | FilterExpression::Or(expressions)
+                if expressions.len() == 1 =>
+            {
+                self.expression(&expressions[0], context)
+            }
+            FilterExpression::And(expressions) if expressions.is_empty() => {
+                if context == Context::Top {
+                    String::new()
+                } else {
+                    "()".to_string()
+                }
+            }
+            FilterExpression::Or(expressions) if expressions.is_empty() => "-()".to_string(),
+            FilterExpression::And(expressions) => {
+                let text = expressions
+                    .iter()
+                    .map(|x| self.expression(x, Context::And))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                if matches!(context, Context::And | Context::Not) {
+                    format!("({text})")
+                } else {
+                    text
+                }
+            }
+            FilterExpression::Or(expressions) => {
+                let text = expressions
+                    .iter()
+                    .map(|x| self.expression(x, Context::Or))
+                    .collect::<Vec<_>>()
+                    .join(" OR ");
+                if context == Context::Top {
+                    text
+                } else {
+                    format!("({text})")
+                }
+            }
+            FilterExpression::Not(expression) => {
+                format!("-{}", self.expression(expression, Context::Not))
+            }
+        }
+    }
+
+    fn condition(&self, condition: &Condition) -> String {
+        match condition {
+            Condition::Account(id) => self.reference(Kind::Account, *id),
+            Condition::Category(id) => self.reference(Kind::Category, *id),
+            Condition::Bill(id) => self.reference(Kind::Bill, *id),
+            Condition::Budget(id) => self.reference(Kind::Budget, *id),
+            Condition::Text(TextSearch::Substring(text)) => quote(text),
+            Condition::Text(TextSearch::Regex(pattern)) => format!("regex:{}", quote(pattern)),
+            Condition::Amount(AmountRange { min, max }) => match (min, max) {
+                (Some(min), Some(max)) if min == max => format!("amount:{}", min.to_num_string()),
+                (Some(min), None) => format!("amount>={}", min.to_num_string()),
+                (None, Some(max)) => format!("amount<={}", max.to_num_string()),
+                (min, max) => format!(
+                    "amount:{}..{}",
+                    min.as_ref().map_or(String::new(), Currency::to_num_string),
+                    max.as_ref().map_or(String::new(), Currency::to_num_string)
+                ),
+            },
+            Condition::Metadata(MetadataFilter { key, value }) => match value {
+                Some(value) => format!("meta:{}={}", quote(key), quote(value)),
+                None => format!("meta:{}", quote(key)),
+            },
+            Condition::Timespan((start, end)) => {
+                let date = |date: &Option<crate::DateTime>| {
+                    date.map_or(String::new(), |x| {
+                        let x = x.to_offset(self.utc_offset).date();
+                        format!("{:04}-{:02}-{:02}", x.year(), u8::from(x.month()), x.day())
+                    })
+                };
+                format!("date:{}..{}", date(start), date(end))
+            }
+        }
+    }
+
+    fn reference(&self, kind: Kind, id: Option<Id>) -> String {
+        let Some(id) = id else {
+            return format!("{}:*", kind.key());
+        };
+        match self.names.of(kind).iter().find(|x| x.0 == id) {
+            Some((_, name)) if self.names.ids(kind, name).len() == 1 => {
+                format!("{}:{}", kind.key(), quote(name))
+            }
+            _ => format!("{}:#{id}", kind.key()),
+        }
+    }
+}
+
+/// Quotes the text if it would not be read back as a single plain word.
+fn quote(text: &str) -> String {
+    let plain = !text.is_empty()
+        && !text.starts_with(['-', '#', '*'])
+        && !text.chars().any(|c| {
+            c.is_whitespace() || matches!(c, '"' | '\\' | '(' | ')' | ':' | '<' | '>' | '=')
+        })
+        && !["OR", "AND", "NOT"].contains(&text);
+    if plain {
+        text.to_string()
+    } else {
+        format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::eur;
+    use time::macros::*;
+
+    fn names() -> Names {
+        Names {
+            accounts: vec![(1, "Savings".to_string()), (2, "Daily".to_string())],
+            categories: vec![
+                (3, "Food".to_string()),
+                (4, "Eating Out".to_string()),
+                (5, "Food".to_string()),
+            ],
+            bills: vec![(6, "Rent".to_string())],
+            budgets: vec![(7, "Groceries".to_string())],
+        }
+    }
+
+    fn parse_utc(query: &str) -> Result<FilterExpression, QueryError> {
+        parse(query, &names(), offset!(UTC))
+    }
+
+    #[test]
+    fn example() {
+        let expression =
+            parse_utc("account:daily -account:Savings amount>50 date:2024-01..2024-03 \"amazon\"")
+                .unwrap();
+        assert_eq!(
+            expression,
+            FilterExpression::And(vec![
+                Condition::Account(Some(2)).into(),
+                !FilterExpression::from(Condition::Account(Some(1))),
+                Condition::Amount(AmountRange {
+                    min: Some(Currency::Eur(bigdecimal::BigDecimal::new(5001.into(), 2))),
+                    max: None
+                })
+                .into(),
+                Condition::Timespan((
+                    Some(datetime!(2024-01-01 0:00 UTC)),
+                    Some(datetime!(2024-03-31 23:59:59 UTC))
+                ))
+                .into(),
+                Condition::Text(TextSearch::Substring("amazon".to_string())).into(),
+            ])
+        );
+    }
+
+    #[test]
+    fn operators() {
+        assert_eq!(
+            parse_utc("budget:Groceries OR NOT (bill:Rent AND category:\"Eating Out\")").unwrap(),
+            FilterExpression::Or(vec![
+                Condition::Budget(Some(7)).into(),
+                FilterExpression::Not(Box::new(FilterExpression::And(vec![
+                    Condition::Bill(Some(6)).into(),
+                    Condition::Category(Some(4)).into(),
+                ]))),
+            ])
+        );
+        assert_eq!(
+            parse_utc("category:food account:#9 bill:*").unwrap(),
+            FilterExpression::And(vec![
+                FilterExpression::Or(vec![
+                    Condition::Category(Some(3)).into(),
+                    Condition::Category(Some(5)).into(),
+                ]),
+                Condition::Account(Some(9)).into(),
+                Condition::Bill(None).into(),
+            ])
+        );
+        assert_eq!(parse_utc("  ").unwrap(), FilterExpression::And(Vec::new()));
+        assert_eq!(
+            parse_utc("--meta:key").unwrap(),
+            Condition::Metadata(MetadataFilter {
+                key: "key".to_string(),
+                value: None
+            })
+            .into()
+        );
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(
+            parse_utc("amount:10..").unwrap(),
+            Condition::Amount(AmountRange {
+                min: Some(eur("10")),
+                max: None
+            })
+            .into()
+        );
+        assert_eq!(
+            parse_utc("amount<=20").unwrap(),
+            Condition::Amount(AmountRange {
+                min: None,
+                max: Some(eur("20"))
+            })
+            .into()
+        );
+        assert_eq!(
+            parse("date:2024-02-29 date:..2023", &names(), offset!(+2)).unwrap(),
+            FilterExpression::And(vec![
+                Condition::Timespan((
+                    Some(datetime!(2024-02-29 0:00 +2)),
+                    Some(datetime!(2024-02-29 23:59:59 +2))
+                ))
+                .into(),
+                Condition::Timespan((None, Some(datetime!(2023-12-31 23:59:59 +2)))).into(),
+            ])
+        );
+        assert_eq!(
+            parse_utc("meta:\"order id\"=\"a \\\"b\\\"\" regex:^a.c").unwrap(),
+            FilterExpression::And(vec![
+                Condition::Metadata(MetadataFilter {
+                    key: "order id".to_string(),
+                    value: Some("a \"b\"".to_string())
+                })
+                .into(),
+                Condition::Text(TextSearch::Regex("^a.c".to_string())).into(),
+            ])
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = |query: &str| parse_utc(query).unwrap_err().position;
+        assert_eq!(error("category:Unknown"), 9);
+        assert_eq!(error("foo (bar"), 4);
+        assert_eq!(error("foo)"), 3);
+        assert_eq!(error("a \"b"), 2);
+        assert_eq!(error("date:2024-13"), 5);
+        assert_eq!(error("date:2024..2024-02-30"), 11);
+        assert_eq!(error("amount>x"), 7);
+        assert_eq!(error("amount:1..x"), 10);
+        assert_eq!(error("colour:red"), 0);
+        assert_eq!(error("regex:\"(\""), 6);
+        assert_eq!(error("a OR"), 4);
+        assert_eq!(error("OR a"), 0);
+        assert_eq!(error("- a"), 1);
+        assert_eq!(error("account:#x"), 8);
+        assert_eq!(
+            parse_utc("account:").unwrap_err().to_string(),
+            "expected a value at position 8"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let names = names();
+        for query in [
+            "category:Food -account:Savings amount>50 date:2024-01..2024-03 \"amazon\"",
+            "budget:Groceries OR NOT (bill:Rent category:\"Eating Out\" OR bill:*)",
+            "-(a b) (c OR d) -(e OR f) \"OR\" \"-x\" \"\\\"#\\\\\"",
+            "amount:-5..5 amount:.. amount:3 amount<1 date:.. date:2024 meta:a=\"b c\" regex:\"x y\"",
+            "() -() account:#42",
+            "() OR x OR (() OR -()) OR (y OR z) a (b c)",
+            "",
+        ] {
+            let expression = parse(query, &names, offset!(+1)).unwrap();
+            let printed = to_query(&expression, &names, offset!(+1));
+            assert_eq!(
+                parse(&printed, &names, offset!(+1)).unwrap(),
+                expression,
+                "{query} printed as {printed}"
+            );
+        }
+        assert_eq!(
+            to_query(
+                &parse("CATEGORY:food  -ACCOUNT:savings", &names, offset!(UTC)).unwrap(),
+                &names,
+                offset!(UTC)
+            ),
+            "(category:#3 OR category:#5) -account:Savings"
+        );
+    }
+}
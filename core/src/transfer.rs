@@ -0,0 +1,312 @@
+//! Detection of transfers between two own asset accounts.
+//!
+//! If the statements of two own asset accounts are imported, the same transfer shows up twice.
+//! Once as outgoing transaction of the first account and once as incoming transaction of the second account.
+//! The other side is in both cases a book checking account with the iban of the other asset account.
+
+use super::{Id, Transaction, account::Account};
+use std::collections::{HashMap, HashSet};
+
+/// Suffix for metadata keys of the removed transaction that already exist in the kept transaction.
+pub const MERGED_METADATA_SUFFIX: &str = "-merged";
+
+#[derive(Debug, Clone)]
+pub struct Transfer {
+    /// The transaction that stays and is changed to be between the two asset accounts.
+    pub keep: Transaction,
+    /// The transaction that is merged into `keep` and deleted afterwards.
+    pub remove: Transaction,
+    /// Asset account the money is sent from.
+    pub source: Id,
+    /// Asset account the money is sent to.
+    pub destination: Id,
+}
+
+impl Transfer {
+    /// Returns `keep` between the two asset accounts with the data of `remove` merged into it.
+    pub fn merged_transaction(&self) -> Transaction {
+        let mut transaction = self.keep.clone();
+        transaction.source = self.source;
+        transaction.destination = self.destination;
+        if transaction.description.is_none() {
+            transaction.description = self.remove.description.clone();
+        }
+        if transaction.budget.is_none() {
+            transaction.budget = self.remove.budget;
+        }
+        for (category, sign) in &self.remove.categories {
+            transaction.categories.entry(*category).or_insert(*sign);
+        }
+        for (key, value) in &self.remove.metadata {
+            match transaction.metadata.get(key) {
+                None => {
+                    transaction.metadata.insert(key.clone(), value.clone());
+                }
+                Some(existing) if existing != value => {
+                    transaction
+                        .metadata
+                        .insert(format!("{key}{MERGED_METADATA_SUFFIX}"), value.clone());
+                }
+                _ => {}
+            }
+        }
+        transaction
+    }
+}
+
+/// Finds all pairs of transactions that are the same transfer between two asset accounts.
+/// The dates of the two transactions can differ at most by `max_date_difference`.
+/// Every transaction is part of at most one pair, closer dates are preferred.
+pub fn find_transfers(
+    accounts: &[Account],
+    transactions: &[Transaction],
+    max_date_difference: time::Duration,
+) -> Vec<Transfer> {
+    let assets = AssetLookup::new(accounts);
+
+    let mut groups: HashMap<(Id, Id), Vec<&Transaction>> = HashMap::new();
+    for transaction in transactions {
+        if let Some(flow) = assets.flow(transaction) {
+            groups.entry(flow).or_default().push(transaction);
+        }
+    }
+
+    let mut candidates = Vec::new();
+    for group in groups.values() {
+        for (i, a) in group.iter().enumerate() {
+            for b in &group[i + 1..] {
+                if let Some(transfer) = assets.transfer(a, b, max_date_difference) {
+                    candidates.push(((a.date - b.date).abs(), transfer));
+                }
+            }
+        }
+    }
+    candidates.sort_by_key(|(difference, _)| *difference);
+
+    let mut used = HashSet::new();
+    let mut transfers = Vec::new();
+    for (_, transfer) in candidates {
+        if used.contains(&transfer.keep.id) || used.contains(&transfer.remove.id) {
+            continue;
+        }
+        used.insert(transfer.keep.id);
+        used.insert(transfer.remove.id);
+        transfers.push(transfer);
+    }
+    transfers
+}
+
+/// Finds the transaction in `transactions` that is the same transfer as `transaction`.
+pub fn find_transfer(
+    transaction: &Transaction,
+    accounts: &[Account],
+    transactions: &[Transaction],
+    max_date_difference: time::Duration,
+) -> Option<Transfer> {
+    let assets = AssetLookup::new(accounts);
+    transactions
+        .iter()
+        .filter(|other| other.id != transaction.id)
+        .filter_map(|other| assets.transfer(transaction, other, max_date_difference))
+        .min_by_key(|transfer| (transfer.keep.date - transfer.remove.date).abs())
+}
+
+struct AssetLookup<'a> {
+    accounts: HashMap<Id, &'a Account>,
+    assets: Vec<&'a Account>,
+}
+
+impl<'a> AssetLookup<'a> {
+    fn new(accounts: &'a [Account]) -> Self {
+        Self {
+            accounts: accounts.iter().map(|a| (*a.id(), a)).collect(),
+            assets: accounts
+                .iter()
+                .filter(|a| matches!(a, Account::AssetAccount(_)))
+                .collect(),
+        }
+    }
+
+    fn is_asset(&self, id: Id) -> bool {
+        matches!(self.accounts.get(&id), Some(Account::AssetAccount(_)))
+    }
+
+    /// Returns the asset account the account stands for.
+    /// That is the account itself or the asset account that shares an iban or additional id.
+    fn resolve(&self, id: Id) -> Option<Id> {
+        let account = self.accounts.get(&id)?;
+        if let Account::AssetAccount(_) = account {
+            return Some(id);
+        }
+        self.assets
+            .iter()
+            .find(|asset| account.account_ids().any(|x| asset.has_account_id(x)))
+            .map(|asset| *asset.id())
+    }
+
+    /// Returns the asset accounts the money flows between.
+    fn flow(&self, transaction: &Transaction) -> Option<(Id, Id)> {
+        if !self.is_asset(transaction.source) && !self.is_asset(transaction.destination) {
+            return None;
+        }
+        let source = self.resolve(transaction.source)?;
+        let destination = self.resolve(transaction.destination)?;
+        if source == destination {
+            return None;
+        }
+        Some((source, destination))
+    }
+
+    fn transfer(
+        &self,
+        a: &Transaction,
+        b: &Transaction,
+        max_date_difference: time::Duration,
+    ) -> Option<Transfer> {
+        let flow = self.flow(a)?;
+        if self.flow(b)? != flow
+            || a.amount() != b.amount()
+            || (a.date - b.date).abs() > max_date_difference
+        {
+            return None;
+        }
+        let direct = |t: &Transaction| (self.is_asset(t.source), self.is_asset(t.destination));
+        // both come from the same statement and are therefore different transfers
+        if direct(a) == direct(b) {
+            return None;
+        }
+        // keep the one that already is between the asset accounts or else the outgoing one
+        let (keep, remove) = match (direct(a), direct(b)) {
+            ((true, true), _) => (a, b),
+            (_, (true, true)) => (b, a),
+            ((true, false), _) => (a, b),
+            _ => (b, a),
+        };
+        Some(Transfer {
+            keep: keep.clone(),
+            remove: remove.clone(),
+            source: flow.0,
+            destination: flow.1,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{AccountId, Currency, Sign, account};
+    use time::macros::datetime;
+
+    fn accounts() -> Vec<Account> {
+        let iban_a: AccountId = "DE89370400440532013000".parse().unwrap();
+        let iban_b: AccountId = "DE02120300000000202051".parse().unwrap();
+        vec![
+            account::AssetAccount::new(
+                1,
+                "A".to_string(),
+                None,
+                Some(iban_a.clone()),
+                None,
+                Currency::default(),
+            )
+            .into(),
+            account::AssetAccount::new(
+                2,
+                "B".to_string(),
+                None,
+                Some(iban_b.clone()),
+                None,
+                Currency::default(),
+            )
+            .into(),
+            account::BookCheckingAccount::new(3, "B".to_string(), None, Some(iban_b), None).into(),
+            account::BookCheckingAccount::new(4, "A".to_string(), None, Some(iban_a), None).into(),
+            account::BookCheckingAccount::new(5, "Shop".to_string(), None, None, None).into(),
+        ]
+    }
+
+    fn transaction(
+        id: Id,
+        source: Id,
+        destination: Id,
+        amount: f64,
+        date: crate::DateTime,
+    ) -> Transaction {
+        Transaction::new(
+            id,
+            Currency::from(amount),
+            format!("t{id}"),
+            None,
+            source,
+            destination,
+            None,
+            date,
+            HashMap::from([("raw".to_string(), format!("raw{id}"))]),
+            HashMap::new(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn find_transfer_pairs() {
+        let transactions = vec![
+            // outgoing in statement of A
+            transaction(1, 1, 3, 100.0, datetime!(2024-01-01 10:00 UTC)),
+            // incoming in statement of B
+            transaction(2, 4, 2, 100.0, datetime!(2024-01-02 10:00 UTC)),
+            // different amount
+            transaction(3, 4, 2, 50.0, datetime!(2024-01-02 10:00 UTC)),
+            // not a transfer
+            transaction(4, 1, 5, 100.0, datetime!(2024-01-01 10:00 UTC)),
+            // too far away
+            transaction(5, 1, 3, 20.0, datetime!(2024-02-01 10:00 UTC)),
+            transaction(6, 4, 2, 20.0, datetime!(2024-02-10 10:00 UTC)),
+        ];
+        let transfers = find_transfers(&accounts(), &transactions, time::Duration::days(3));
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].keep.id, 1);
+        assert_eq!(transfers[0].remove.id, 2);
+        assert_eq!(transfers[0].source, 1);
+        assert_eq!(transfers[0].destination, 2);
+
+        let merged = transfers[0].merged_transaction();
+        assert_eq!(merged.id, 1);
+        assert_eq!(merged.source, 1);
+        assert_eq!(merged.destination, 2);
+        assert_eq!(merged.metadata.get("raw").unwrap(), "raw1");
+        assert_eq!(
+            merged
+                .metadata
+                .get(&format!("raw{MERGED_METADATA_SUFFIX}"))
+                .unwrap(),
+            "raw2"
+        );
+    }
+
+    #[test]
+    fn same_statement_is_no_transfer() {
+        let transactions = vec![
+            transaction(1, 1, 3, 100.0, datetime!(2024-01-01 10:00 UTC)),
+            transaction(2, 1, 3, 100.0, datetime!(2024-01-01 10:00 UTC)),
+        ];
+        assert!(find_transfers(&accounts(), &transactions, time::Duration::days(3)).is_empty());
+    }
+
+    #[test]
+    fn find_transfer_keeps_direct_transaction() {
+        let mut direct = transaction(2, 1, 2, 100.0, datetime!(2024-01-02 10:00 UTC));
+        direct.categories.insert(1, Sign::Negative);
+        let transactions = vec![
+            transaction(1, 1, 3, 100.0, datetime!(2024-01-01 10:00 UTC)),
+            direct.clone(),
+        ];
+        let transfer =
+            find_transfer(&direct, &accounts(), &transactions, time::Duration::days(3)).unwrap();
+        assert_eq!(transfer.keep.id, 2);
+        assert_eq!(transfer.remove.id, 1);
+        assert_eq!(
+            transfer.merged_transaction().categories,
+            HashMap::from([(1, Sign::Negative)])
+        );
+    }
+}
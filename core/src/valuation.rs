@@ -0,0 +1,165 @@
+//! Assets like houses or cars that are valued manually, held by asset accounts of the type
+//! [`crate::account::AssetType::Valuation`].
+//!
+//! The value of such an account is the latest valuation, not the sum of its transactions.
+//! An optional depreciation lowers the value over the time since the latest valuation.
+
+use super::{Currency, DateTime, Id};
+use anyhow::Result;
+use bigdecimal::BigDecimal;
+
+/// The value of an asset at a date.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct Valuation {
+    pub id: Id,
+    pub account: Id,
+    pub value: Currency,
+    pub date: DateTime,
+}
+
+impl Valuation {
+    pub fn new(id: Id, account: Id, value: Currency, date: DateTime) -> Self {
+        Self {
+            id,
+            account,
+            value,
+            date,
+        }
+    }
+}
+
+/// How the value of an asset decreases per year after a valuation.
+/// The value never drops below zero.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub enum Depreciation {
+    /// A fixed amount per year.
+    Linear(Currency),
+    /// A percentage of the remaining value per year.
+    Percentage(BigDecimal),
+}
+
+impl Depreciation {
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Depreciation::Linear(amount) if *amount < Currency::default() => {
+                anyhow::bail!("A linear depreciation cannot be negative")
+            }
+            Depreciation::Percentage(rate) if *rate < 0 || *rate > 100 => {
+                anyhow::bail!("A depreciation rate has to be between 0 and 100 percent")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Depreciates `value` over the time from `from` to `to`.
+    /// Partial years are depreciated proportionally.
+    pub fn apply(&self, value: &Currency, from: DateTime, to: DateTime) -> Currency {
+        let days = (to - from).whole_days().max(0);
+        let full_years = days / 365;
+        let partial_year = BigDecimal::from(days % 365) / BigDecimal::from(365);
+        let depreciated = match self {
+            Depreciation::Linear(amount) => {
+                value.clone() - &amount.scale(&(BigDecimal::from(full_years) + partial_year))
+            }
+            Depreciation::Percentage(rate) => {
+                let remaining = BigDecimal::from(1) - rate / BigDecimal::from(100);
+                let mut factor =
+                    BigDecimal::from(1) - (rate / BigDecimal::from(100)) * partial_year;
+                for _ in 0..full_years {
+                    factor *= &remaining;
+                }
+                value.scale(&factor)
+            }
+        };
+        if depreciated < Currency::default() {
+            Currency::from_currency_id(value.get_currency_id(), BigDecimal::from(0)).unwrap()
+        } else {
+            depreciated
+        }
+    }
+}
+
+/// The value at `date` based on the latest valuation at or before `date`.
+/// `None` if there is no such valuation.
+pub fn value_at(
+    valuations: &[Valuation],
+    depreciation: Option<&Depreciation>,
+    date: DateTime,
+) -> Option<Currency> {
+    let latest = valuations
+        .iter()
+        .filter(|v| v.date <= date)
+        .max_by_key(|v| v.date)?;
+    Some(match depreciation {
+        Some(depreciation) => depreciation.apply(&latest.value, latest.date, date),
+        None => latest.value.clone(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::eur;
+    use time::macros::datetime;
+
+    #[test]
+    fn latest_valuation() {
+        let valuations = vec![
+            Valuation::new(1, 1, eur("20000"), datetime!(2020-01-01 0:00 UTC)),
+            Valuation::new(2, 1, eur("15000"), datetime!(2022-01-01 0:00 UTC)),
+        ];
+        assert_eq!(
+            value_at(&valuations, None, datetime!(2019-06-01 0:00 UTC)),
+            None
+        );
+        assert_eq!(
+            value_at(&valuations, None, datetime!(2021-06-01 0:00 UTC)),
+            Some(eur("20000"))
+        );
+        assert_eq!(
+            value_at(&valuations, None, datetime!(2023-06-01 0:00 UTC)),
+            Some(eur("15000"))
+        );
+    }
+
+    #[test]
+    fn linear_depreciation() {
+        let depreciation = Depreciation::Linear(eur("1000"));
+        let start = datetime!(2020-01-01 0:00 UTC);
+        assert_eq!(
+            depreciation.apply(&eur("5000"), start, start + time::Duration::days(365 * 2)),
+            eur("3000")
+        );
+        assert_eq!(
+            depreciation.apply(
+                &eur("5000"),
+                start,
+                start + time::Duration::days(365 * 2 + 73)
+            ),
+            eur("2800")
+        );
+        assert_eq!(
+            depreciation.apply(&eur("5000"), start, start + time::Duration::days(365 * 10)),
+            eur("0")
+        );
+    }
+
+    #[test]
+    fn percentage_depreciation() {
+        let depreciation = Depreciation::Percentage(BigDecimal::from(20));
+        let start = datetime!(2020-01-01 0:00 UTC);
+        assert_eq!(
+            depreciation.apply(&eur("10000"), start, start + time::Duration::days(365 * 2)),
+            eur("6400")
+        );
+        assert_eq!(
+            depreciation.apply(&eur("10000"), start, start + time::Duration::days(365 + 73)),
+            eur("7680")
+        );
+        assert!(
+            Depreciation::Percentage(BigDecimal::from(120))
+                .validate()
+                .is_err()
+        );
+    }
+}
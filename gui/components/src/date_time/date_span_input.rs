@@ -7,6 +7,7 @@ pub enum Action {
     ChangeStart(super::date_input::Action),
     ChangeEnd(super::date_input::Action),
     Shift { shift: super::Shift, positive: bool },
+    Preset(fm_core::RelativeTimespan),
     ToggleDropdown,
 }
 
@@ -14,6 +15,8 @@ pub enum Action {
 pub struct State {
     start: super::date_input::State,
     end: super::date_input::State,
+    /// The preset the dates were set by, until they are changed.
+    relative: Option<fm_core::RelativeTimespan>,
     drop_down: bool,
 }
 
@@ -22,10 +25,21 @@ impl State {
         Self {
             start: super::date_input::State::new(if let Some(t) = timespan { t.0 } else { None }),
             end: super::date_input::State::new(if let Some(t) = timespan { t.1 } else { None }),
+            relative: None,
             drop_down: false,
         }
     }
 
+    /// Keeps the relative timespan the dates were resolved from.
+    pub fn with_relative(mut self, relative: Option<fm_core::RelativeTimespan>) -> Self {
+        self.relative = relative;
+        self
+    }
+
+    pub fn relative(&self) -> Option<fm_core::RelativeTimespan> {
+        self.relative
+    }
+
     pub fn timespan(&self) -> (Option<time::Date>, Option<Date>) {
         (self.start.date(), self.end.date())
     }
@@ -34,11 +48,24 @@ impl State {
         match action {
             Action::ChangeStart(start) => {
                 self.start.perform(start);
+                self.relative = None;
             }
             Action::ChangeEnd(end) => {
                 self.end.perform(end);
+                self.relative = None;
+            }
+            Action::Preset(relative) => {
+                let today = time::OffsetDateTime::now_utc()
+                    .to_offset(fm_core::get_local_timezone().unwrap_or(time::UtcOffset::UTC))
+                    .date();
+                let (start, end) = relative.dates(today);
+                self.start = super::date_input::State::new(Some(start));
+                self.end = super::date_input::State::new(Some(end));
+                self.relative = Some(relative);
+                self.drop_down = false;
             }
             Action::Shift { shift, positive } => {
+                self.relative = None;
                 if let Some(before) = self.start.date() {
                     self.start = super::date_input::State::new(Some(super::apply_date_shift(
                         before, shift, positive,
@@ -76,59 +103,67 @@ impl<'a> DateSpanInput<'a> {
                 super::date_input::date_input(&self.state.end, "End", false)
                     .view()
                     .map(Action::ChangeEnd),
-                widget::button(icons::pencil_fill()).on_press_maybe(
-                    if self.state.start.date().is_some() && self.state.end.date().is_some() {
-                        Some(Action::ToggleDropdown)
-                    } else {
-                        None
-                    }
-                )
+                widget::button(icons::pencil_fill()).on_press(Action::ToggleDropdown)
             ]
+            .push_maybe(
+                self.state
+                    .relative
+                    .map(|relative| widget::text!("({relative})")),
+            )
             .align_y(iced::Alignment::Center),
-            crate::spal_column![
-                crate::spal_row![
-                    widget::button("-").on_press(Action::Shift {
-                        shift: Shift::Duration(time::Duration::DAY),
-                        positive: false
-                    }),
-                    "Day",
-                    widget::button("+").on_press(Action::Shift {
-                        shift: Shift::Duration(time::Duration::DAY),
-                        positive: true
-                    }),
-                ],
-                crate::spal_row![
-                    widget::button("-").on_press(Action::Shift {
-                        shift: Shift::Duration(time::Duration::WEEK),
-                        positive: false
-                    }),
-                    "Week",
-                    widget::button("+").on_press(Action::Shift {
-                        shift: Shift::Duration(time::Duration::WEEK),
-                        positive: true
-                    }),
-                ],
-                crate::spal_row![
-                    widget::button("-").on_press(Action::Shift {
-                        shift: Shift::Month,
-                        positive: false
-                    }),
-                    "Month",
-                    widget::button("+").on_press(Action::Shift {
-                        shift: Shift::Month,
-                        positive: true
-                    }),
-                ],
-                crate::spal_row![
-                    widget::button("-").on_press(Action::Shift {
-                        shift: Shift::Year,
-                        positive: false
-                    }),
-                    "Year",
-                    widget::button("+").on_press(Action::Shift {
-                        shift: Shift::Year,
-                        positive: true
-                    }),
+            crate::spal_row![
+                widget::Column::with_children(fm_core::RelativeTimespan::PRESETS.map(|preset| {
+                    widget::button(widget::text(preset.to_string()))
+                        .on_press(Action::Preset(preset))
+                        .style(widget::button::text)
+                        .into()
+                }))
+                .spacing(style::COLUMN_SPACING),
+                crate::spal_column![
+                    crate::spal_row![
+                        widget::button("-").on_press(Action::Shift {
+                            shift: Shift::Duration(time::Duration::DAY),
+                            positive: false
+                        }),
+                        "Day",
+                        widget::button("+").on_press(Action::Shift {
+                            shift: Shift::Duration(time::Duration::DAY),
+                            positive: true
+                        }),
+                    ],
+                    crate::spal_row![
+                        widget::button("-").on_press(Action::Shift {
+                            shift: Shift::Duration(time::Duration::WEEK),
+                            positive: false
+                        }),
+                        "Week",
+                        widget::button("+").on_press(Action::Shift {
+                            shift: Shift::Duration(time::Duration::WEEK),
+                            positive: true
+                        }),
+                    ],
+                    crate::spal_row![
+                        widget::button("-").on_press(Action::Shift {
+                            shift: Shift::Month,
+                            positive: false
+                        }),
+                        "Month",
+                        widget::button("+").on_press(Action::Shift {
+                            shift: Shift::Month,
+                            positive: true
+                        }),
+                    ],
+                    crate::spal_row![
+                        widget::button("-").on_press(Action::Shift {
+                            shift: Shift::Year,
+                            positive: false
+                        }),
+                        "Year",
+                        widget::button("+").on_press(Action::Shift {
+                            shift: Shift::Year,
+                            positive: true
+                        }),
+                    ]
                 ]
             ],
             self.state.drop_down,
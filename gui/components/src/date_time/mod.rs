@@ -109,21 +109,33 @@ pub fn parse_date_str(date: &str) -> Result<time::Date> {
 }
 
 pub fn to_date_string(date: time::Date) -> String {
-    date.format(&time::format_description::parse("[day].[month].[year]").unwrap())
+    date.format(&time::format_description::parse_borrowed::<1>("[day].[month].[year]").unwrap())
         .unwrap()
 }
 
 pub fn to_date_time_string(date_time: time::PrimitiveDateTime) -> String {
     date_time
-        .format(&time::format_description::parse("[day].[month].[year] [hour]:[minute]").unwrap())
+        .format(
+            &time::format_description::parse_borrowed::<1>("[day].[month].[year] [hour]:[minute]")
+                .unwrap(),
+        )
         .unwrap()
 }
 
 pub fn to_time_string(t: time::Time) -> String {
-    t.format(&time::format_description::parse("[hour]:[minute]").unwrap())
+    t.format(&time::format_description::parse_borrowed::<1>("[hour]:[minute]").unwrap())
         .unwrap()
 }
 
+/// Names the period of `interval` that starts at `start`, e.g. `03.2024` for a month.
+pub fn to_period_string(start: time::Date, interval: fm_core::Interval) -> String {
+    match interval {
+        fm_core::Interval::Day | fm_core::Interval::Week => to_date_string(start),
+        fm_core::Interval::Month => format!("{:02}.{}", start.month() as u8, start.year()),
+        fm_core::Interval::Year => start.year().to_string(),
+    }
+}
+
 pub fn add_months(date_time: time::OffsetDateTime, months: i32) -> time::OffsetDateTime {
     let mut months = date_time.date().month() as i32 + months;
     let mut year = date_time.year();
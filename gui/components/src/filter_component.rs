@@ -5,7 +5,7 @@ use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub enum Action {
-    Submit(fm_core::transaction_filter::TransactionFilter),
+    Submit(Box<fm_core::transaction_filter::TransactionFilter>),
     None,
 }
 
@@ -17,6 +17,9 @@ pub enum InnerMessage {
     NewBillFilter,
     NewCategoryFilter,
     NewBudgetFilter,
+    NewTextFilter,
+    NewAmountFilter,
+    NewMetadataFilter,
     AccountEntryMessage(
         usize,
         filter_entry::MessageContainer<fm_core::account::Account>,
@@ -24,6 +27,9 @@ pub enum InnerMessage {
     BillEntryMessage(usize, filter_entry::MessageContainer<Arc<fm_core::Bill>>),
     CategoryEntryMessage(usize, filter_entry::MessageContainer<fm_core::Category>),
     BudgetEntryMessage(usize, filter_entry::MessageContainer<fm_core::Budget>),
+    TextEntryMessage(usize, custom_entry::Message<custom_entry::TextMessage>),
+    AmountEntryMessage(usize, custom_entry::Message<custom_entry::AmountMessage>),
+    MetadataEntryMessage(usize, custom_entry::Message<custom_entry::MetadataMessage>),
 }
 
 #[derive(Debug)]
@@ -37,6 +43,9 @@ pub struct FilterComponent {
     account_filter_entries: Vec<filter_entry::FilterEntry<fm_core::account::Account, fm_core::Id>>,
     category_filter_entries: Vec<filter_entry::FilterEntry<fm_core::Category, fm_core::Id>>,
     budget_filter_entries: Vec<filter_entry::FilterEntry<fm_core::Budget, fm_core::Id>>,
+    text_filter_entries: Vec<custom_entry::CustomEntry<custom_entry::TextEditor>>,
+    amount_filter_entries: Vec<custom_entry::CustomEntry<custom_entry::AmountEditor>>,
+    metadata_filter_entries: Vec<custom_entry::CustomEntry<custom_entry::MetadataEditor>>,
 }
 
 impl FilterComponent {
@@ -119,6 +128,9 @@ impl FilterComponent {
             account_filter_entries: Vec::default(),
             category_filter_entries: Vec::default(),
             budget_filter_entries: Vec::default(),
+            text_filter_entries: Vec::default(),
+            amount_filter_entries: Vec::default(),
+            metadata_filter_entries: Vec::default(),
         }
     }
 
@@ -126,7 +138,8 @@ impl FilterComponent {
         self.default_transaction_input = date_span_input::State::new(Some((
             new_filter.default_timespan.0.map(|x| x.date()),
             new_filter.default_timespan.1.map(|x| x.date()),
-        )));
+        )))
+        .with_relative(new_filter.relative_default_timespan);
 
         fn set_inputs<
             T: Clone + std::fmt::Debug + std::fmt::Display + 'static,
@@ -178,11 +191,28 @@ impl FilterComponent {
                     id: x.id.map(Arc::new),
                     include: x.include,
                     timespan: x.timespan,
+                    relative_timespan: x.relative_timespan,
                 })
                 .collect(),
             self.bills.clone(),
             |bill| bill.clone(),
         );
+
+        self.text_filter_entries = new_filter
+            .texts
+            .into_iter()
+            .map(custom_entry::CustomEntry::new)
+            .collect();
+        self.amount_filter_entries = new_filter
+            .amounts
+            .into_iter()
+            .map(custom_entry::CustomEntry::new)
+            .collect();
+        self.metadata_filter_entries = new_filter
+            .metadata
+            .into_iter()
+            .map(custom_entry::CustomEntry::new)
+            .collect();
     }
 
     pub fn with_filter(
@@ -213,28 +243,39 @@ impl FilterComponent {
                             )
                         }),
                     ),
+                    relative_default_timespan: self.default_transaction_input.relative(),
                     ..Default::default()
                 };
                 for bill_entry in &self.bill_filter_entries {
-                    let bill_filter = bill_entry.get_filter().clone();
+                    let bill_filter = bill_entry.filter(utc_offset);
                     let bill_filter = Filter {
                         negated: bill_filter.negated,
                         id: bill_filter.id.map(|x| (*x).clone()),
                         include: bill_filter.include,
                         timespan: bill_filter.timespan,
+                        relative_timespan: bill_filter.relative_timespan,
                     };
                     filter.bills.push(bill_filter);
                 }
                 for acc_entry in &self.account_filter_entries {
-                    filter.accounts.push(acc_entry.get_filter().clone());
+                    filter.accounts.push(acc_entry.filter(utc_offset));
                 }
                 for category_entry in &self.category_filter_entries {
-                    filter.categories.push(category_entry.get_filter().clone());
+                    filter.categories.push(category_entry.filter(utc_offset));
                 }
                 for budget_entry in &self.budget_filter_entries {
-                    filter.budgets.push(budget_entry.get_filter().clone());
+                    filter.budgets.push(budget_entry.filter(utc_offset));
+                }
+                for text_entry in &self.text_filter_entries {
+                    filter.texts.push(text_entry.get_filter());
+                }
+                for amount_entry in &self.amount_filter_entries {
+                    filter.amounts.push(amount_entry.get_filter());
                 }
-                return Action::Submit(filter);
+                for metadata_entry in &self.metadata_filter_entries {
+                    filter.metadata.push(metadata_entry.get_filter());
+                }
+                return Action::Submit(Box::new(filter));
             }
             InnerMessage::ChangeDefaultTimespan(action) => {
                 self.default_transaction_input.perform(action);
@@ -248,6 +289,7 @@ impl FilterComponent {
                                 id: Some(*self.accounts[0].id()),
                                 include: true,
                                 timespan: None,
+                                relative_timespan: None,
                             },
                             self.accounts.clone(),
                             |x| *x.id(),
@@ -263,6 +305,7 @@ impl FilterComponent {
                                 id: Some(bill.clone()),
                                 include: true,
                                 timespan: None,
+                                relative_timespan: None,
                             },
                             self.bills.clone(),
                             |x| x.clone(),
@@ -278,6 +321,7 @@ impl FilterComponent {
                                 id: Some(self.categories[0].id),
                                 include: true,
                                 timespan: None,
+                                relative_timespan: None,
                             },
                             self.categories.clone(),
                             |x| x.id,
@@ -293,12 +337,25 @@ impl FilterComponent {
                                 id: Some(self.budgets[0].id),
                                 include: true,
                                 timespan: None,
+                                relative_timespan: None,
                             },
                             self.budgets.clone(),
                             |x| x.id,
                         ));
                 }
             }
+            InnerMessage::NewTextFilter => {
+                self.text_filter_entries
+                    .push(custom_entry::CustomEntry::default());
+            }
+            InnerMessage::NewAmountFilter => {
+                self.amount_filter_entries
+                    .push(custom_entry::CustomEntry::default());
+            }
+            InnerMessage::NewMetadataFilter => {
+                self.metadata_filter_entries
+                    .push(custom_entry::CustomEntry::default());
+            }
             InnerMessage::AccountEntryMessage(index, m) => {
                 match self
                     .account_filter_entries
@@ -341,6 +398,35 @@ impl FilterComponent {
                     filter_entry::Action::None => {}
                 }
             }
+            InnerMessage::TextEntryMessage(index, m) => {
+                match self.text_filter_entries.get_mut(index).unwrap().update(m) {
+                    filter_entry::Action::Delete => {
+                        self.text_filter_entries.remove(index);
+                    }
+                    filter_entry::Action::None => {}
+                }
+            }
+            InnerMessage::AmountEntryMessage(index, m) => {
+                match self.amount_filter_entries.get_mut(index).unwrap().update(m) {
+                    filter_entry::Action::Delete => {
+                        self.amount_filter_entries.remove(index);
+                    }
+                    filter_entry::Action::None => {}
+                }
+            }
+            InnerMessage::MetadataEntryMessage(index, m) => {
+                match self
+                    .metadata_filter_entries
+                    .get_mut(index)
+                    .unwrap()
+                    .update(m)
+                {
+                    filter_entry::Action::Delete => {
+                        self.metadata_filter_entries.remove(index);
+                    }
+                    filter_entry::Action::None => {}
+                }
+            }
         }
         Action::None
     }
@@ -354,6 +440,39 @@ impl FilterComponent {
                     .view()
                     .map(InnerMessage::ChangeDefaultTimespan),
             ],
+            // text filters
+            super::spal_row![
+                "Search",
+                super::button::new("New", Some(InnerMessage::NewTextFilter)),
+                widget::horizontal_rule(3)
+            ]
+            .align_y(iced::Alignment::Center),
+            generate_custom_filter_column(
+                &self.text_filter_entries,
+                InnerMessage::TextEntryMessage
+            ),
+            // amount filters
+            super::spal_row![
+                "Amounts",
+                super::button::new("New", Some(InnerMessage::NewAmountFilter)),
+                widget::horizontal_rule(3)
+            ]
+            .align_y(iced::Alignment::Center),
+            generate_custom_filter_column(
+                &self.amount_filter_entries,
+                InnerMessage::AmountEntryMessage
+            ),
+            // metadata filters
+            super::spal_row![
+                "Metadata",
+                super::button::new("New", Some(InnerMessage::NewMetadataFilter)),
+                widget::horizontal_rule(3)
+            ]
+            .align_y(iced::Alignment::Center),
+            generate_custom_filter_column(
+                &self.metadata_filter_entries,
+                InnerMessage::MetadataEntryMessage
+            ),
             // account filters
             super::spal_row![
                 "Accounts",
@@ -419,6 +538,17 @@ fn generate_filter_column<
     col.into()
 }
 
+fn generate_custom_filter_column<E: custom_entry::Editor>(
+    entries: &[custom_entry::CustomEntry<E>],
+    f: fn(usize, custom_entry::Message<E::Message>) -> InnerMessage,
+) -> iced::Element<'_, InnerMessage> {
+    let mut col = crate::spaced_column!();
+    for (index, entry) in entries.iter().enumerate() {
+        col = col.push(entry.view().map(move |m| (f)(index, m)));
+    }
+    col.into()
+}
+
 mod filter_entry {
     use crate::date_time::date_span_input;
     use fm_core::transaction_filter::Filter;
@@ -517,7 +647,8 @@ mod filter_entry {
                                 .map(|offset_date_time| offset_date_time.date()),
                         )
                     },
-                )),
+                ))
+                .with_relative(filter.relative_timespan),
                 filter,
                 t_to_id: Box::new(t_to_id),
             }
@@ -527,6 +658,21 @@ mod filter_entry {
             &self.filter
         }
 
+        /// The filter with the timespan of the input.
+        pub fn filter(&self, utc_offset: time::UtcOffset) -> Filter<ID> {
+            let mut filter = self.filter.clone();
+            if filter.timespan.is_some() {
+                filter.timespan = Some(crate::date_time::date_span_to_time_span(
+                    self.timespan_input.timespan(),
+                    utc_offset,
+                ));
+                filter.relative_timespan = self.timespan_input.relative();
+            } else {
+                filter.relative_timespan = None;
+            }
+            filter
+        }
+
         pub fn update(&mut self, message: MessageContainer<T>) -> Action {
             let message = message.0;
             match message {
@@ -595,3 +741,275 @@ mod filter_entry {
         }
     }
 }
+
+/// Filter entries whose filter value is entered instead of selected.
+mod custom_entry {
+    use super::filter_entry::Action;
+    use crate::{CurrencyInput, ValidationTextInput, currency_input};
+    use fm_core::transaction_filter::{AmountRange, Filter, MetadataFilter, TextSearch};
+    use iced::widget;
+
+    /// The input of the filter value of a [`CustomEntry`].
+    pub trait Editor: std::fmt::Debug {
+        type Id: Clone + std::fmt::Debug;
+        type Message: Clone + std::fmt::Debug + 'static;
+
+        fn new(id: Option<&Self::Id>) -> Self;
+
+        fn id(&self) -> Self::Id;
+
+        fn update(&mut self, message: Self::Message);
+
+        fn view(&self) -> iced::Element<'_, Self::Message>;
+    }
+
+    #[derive(Debug, Clone)]
+    pub enum Message<M> {
+        Delete,
+        Negate(bool),
+        Exclude(bool),
+        Editor(M),
+    }
+
+    #[derive(Debug)]
+    pub struct CustomEntry<E: Editor> {
+        filter: Filter<E::Id>,
+        editor: E,
+    }
+
+    impl<E: Editor> Default for CustomEntry<E> {
+        fn default() -> Self {
+            Self::new(Filter {
+                negated: false,
+                id: None,
+                include: true,
+                timespan: None,
+                relative_timespan: None,
+            })
+        }
+    }
+
+    impl<E: Editor> CustomEntry<E> {
+        pub fn new(filter: Filter<E::Id>) -> Self {
+            Self {
+                editor: E::new(filter.id.as_ref()),
+                filter,
+            }
+        }
+
+        pub fn get_filter(&self) -> Filter<E::Id> {
+            Filter {
+                id: Some(self.editor.id()),
+                ..self.filter.clone()
+            }
+        }
+
+        pub fn update(&mut self, message: Message<E::Message>) -> Action {
+            match message {
+                Message::Delete => return Action::Delete,
+                Message::Negate(new_value) => self.filter.negated = new_value,
+                Message::Exclude(new_value) => self.filter.include = !new_value,
+                Message::Editor(message) => self.editor.update(message),
+            }
+            Action::None
+        }
+
+        pub fn view(&self) -> iced::Element<'_, Message<E::Message>> {
+            widget::row![
+                self.editor.view().map(Message::Editor),
+                widget::checkbox("Negate", self.filter.negated).on_toggle(Message::Negate),
+                widget::checkbox("Exclude", !self.filter.include).on_toggle(Message::Exclude),
+                widget::horizontal_space(),
+                crate::button::delete(Some(Message::Delete))
+            ]
+            .align_y(iced::Alignment::Center)
+            .spacing(style::ROW_SPACING)
+            .into()
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub enum TextMessage {
+        Input(String),
+        Regex(bool),
+    }
+
+    /// A search field for a [`TextSearch`].
+    #[derive(Debug)]
+    pub struct TextEditor {
+        input: ValidationTextInput,
+        regex: bool,
+    }
+
+    impl TextEditor {
+        fn input(text: String, regex: bool) -> ValidationTextInput {
+            ValidationTextInput::new(text).validation(move |text| {
+                if regex && TextSearch::Regex(text.clone()).regex().is_none() {
+                    Some("invalid regex".to_string())
+                } else {
+                    None
+                }
+            })
+        }
+    }
+
+    impl Editor for TextEditor {
+        type Id = TextSearch;
+        type Message = TextMessage;
+
+        fn new(id: Option<&TextSearch>) -> Self {
+            let regex = matches!(id, Some(TextSearch::Regex(_)));
+            Self {
+                input: Self::input(id.map_or(String::new(), |x| x.text().to_string()), regex),
+                regex,
+            }
+        }
+
+        fn id(&self) -> TextSearch {
+            let text = self.input.value().clone();
+            if self.regex {
+                TextSearch::Regex(text)
+            } else {
+                TextSearch::Substring(text)
+            }
+        }
+
+        fn update(&mut self, message: TextMessage) {
+            match message {
+                TextMessage::Input(text) => self.input.edit_content(text),
+                TextMessage::Regex(regex) => {
+                    self.regex = regex;
+                    self.input = Self::input(self.input.value().clone(), regex);
+                    self.input.input_changed(true);
+                }
+            }
+        }
+
+        fn view(&self) -> iced::Element<'_, TextMessage> {
+            crate::spal_row![
+                widget::container(self.input.view("Search", Some(TextMessage::Input))).width(300),
+                widget::checkbox("Regex", self.regex).on_toggle(TextMessage::Regex),
+            ]
+            .align_y(iced::Alignment::Center)
+            .into()
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub enum AmountMessage {
+        Min(currency_input::Action),
+        Max(currency_input::Action),
+    }
+
+    /// Optional minimum and maximum of an [`AmountRange`].
+    #[derive(Debug)]
+    pub struct AmountEditor {
+        min: CurrencyInput,
+        max: CurrencyInput,
+    }
+
+    impl Editor for AmountEditor {
+        type Id = AmountRange;
+        type Message = AmountMessage;
+
+        fn new(id: Option<&AmountRange>) -> Self {
+            let input = |bound: Option<&fm_core::Currency>| match bound {
+                Some(bound) => CurrencyInput::new(bound.clone(), false),
+                None => {
+                    let mut input = CurrencyInput::new(fm_core::Currency::default(), false);
+                    input.clear();
+                    input
+                }
+            };
+            Self {
+                min: input(id.and_then(|range| range.min.as_ref())),
+                max: input(id.and_then(|range| range.max.as_ref())),
+            }
+        }
+
+        fn id(&self) -> AmountRange {
+            AmountRange {
+                min: self.min.currency(),
+                max: self.max.currency(),
+            }
+        }
+
+        fn update(&mut self, message: AmountMessage) {
+            match message {
+                AmountMessage::Min(action) => self.min.perform(action),
+                AmountMessage::Max(action) => self.max.perform(action),
+            }
+        }
+
+        fn view(&self) -> iced::Element<'_, AmountMessage> {
+            crate::spal_row![
+                "Min",
+                widget::container(self.min.view().map(AmountMessage::Min)).width(150),
+                "Max",
+                widget::container(self.max.view().map(AmountMessage::Max)).width(150),
+            ]
+            .align_y(iced::Alignment::Center)
+            .into()
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub enum MetadataMessage {
+        Key(String),
+        HasValue(bool),
+        Value(String),
+    }
+
+    /// A key and an optional value of a [`MetadataFilter`].
+    #[derive(Debug)]
+    pub struct MetadataEditor {
+        key: String,
+        value: Option<String>,
+    }
+
+    impl Editor for MetadataEditor {
+        type Id = MetadataFilter;
+        type Message = MetadataMessage;
+
+        fn new(id: Option<&MetadataFilter>) -> Self {
+            Self {
+                key: id.map_or(String::new(), |x| x.key.clone()),
+                value: id.and_then(|x| x.value.clone()),
+            }
+        }
+
+        fn id(&self) -> MetadataFilter {
+            MetadataFilter {
+                key: self.key.clone(),
+                value: self.value.clone(),
+            }
+        }
+
+        fn update(&mut self, message: MetadataMessage) {
+            match message {
+                MetadataMessage::Key(key) => self.key = key,
+                MetadataMessage::HasValue(has_value) => {
+                    self.value = if has_value { Some(String::new()) } else { None };
+                }
+                MetadataMessage::Value(value) => self.value = Some(value),
+            }
+        }
+
+        fn view(&self) -> iced::Element<'_, MetadataMessage> {
+            crate::spal_row![
+                widget::text_input("Key", &self.key)
+                    .on_input(MetadataMessage::Key)
+                    .width(200),
+                widget::checkbox("Value", self.value.is_some())
+                    .on_toggle(MetadataMessage::HasValue),
+            ]
+            .push_maybe(self.value.as_ref().map(|value| {
+                widget::text_input("Value", value)
+                    .on_input(MetadataMessage::Value)
+                    .width(200)
+            }))
+            .align_y(iced::Alignment::Center)
+            .into()
+        }
+    }
+}
@@ -6,6 +6,8 @@ pub mod currency_input;
 pub use currency_input::CurrencyInput;
 pub mod filter_component;
 pub mod key_value_editor;
+mod line_chart;
+pub use line_chart::LineChart;
 pub mod scrollable;
 pub mod table_view;
 pub mod transaction_table;
@@ -35,6 +37,30 @@ pub fn labeled_entry<'a, Message: 'a + Clone>(
     spal_row![name, input.view(place_holder, on_input)].into()
 }
 
+/// Validation for an optional BIC input.
+/// Also checks that the country code of the BIC matches the one of `iban` if `iban` is a valid IBAN.
+pub fn bic_validation(iban: &str) -> impl Fn(&String) -> Option<String> + 'static {
+    let iban = iban.parse::<fm_core::AccountId>().ok();
+    move |bic: &String| {
+        if bic.trim().is_empty() {
+            return None;
+        }
+        bic.parse::<fm_core::Bic>()
+            .and_then(|bic| fm_core::account::check_iban_bic_country(iban.as_ref(), Some(&bic)))
+            .err()
+            .map(|error| error.to_string())
+    }
+}
+
+/// Returns the trimmed lines of `text` that are not empty.
+pub fn non_empty_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
 pub fn colored_currency_display<Message>(
     value: &fm_core::Currency,
 ) -> iced::Element<'static, Message> {
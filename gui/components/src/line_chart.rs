@@ -0,0 +1,169 @@
+use iced::widget::canvas;
+
+const PADDING: f32 = 10.0;
+const LABEL_SIZE: f32 = 14.0;
+
+/// A line chart of values over time.
+///
+/// The dates are spaced by time, the y-axis spans from the smallest to the largest value.
+/// Hovering the chart shows the date and value of the closest point.
+#[derive(Debug, Clone, Default)]
+pub struct LineChart {
+    values: Vec<(fm_core::DateTime, fm_core::Currency)>,
+}
+
+impl LineChart {
+    pub fn new(values: Vec<(fm_core::DateTime, fm_core::Currency)>) -> Self {
+        Self { values }
+    }
+
+    pub fn set_values(&mut self, values: Vec<(fm_core::DateTime, fm_core::Currency)>) {
+        self.values = values;
+    }
+
+    pub fn view<Message: 'static>(&self) -> iced::Element<'_, Message> {
+        canvas(self).width(iced::Fill).height(iced::Fill).into()
+    }
+
+    fn value_range(&self) -> (f64, f64) {
+        let min = self
+            .values
+            .iter()
+            .map(|(_, value)| value.get_eur_num())
+            .fold(0.0, f64::min);
+        let max = self
+            .values
+            .iter()
+            .map(|(_, value)| value.get_eur_num())
+            .fold(0.0, f64::max);
+        if min == max {
+            (min, min + 1.0)
+        } else {
+            (min, max)
+        }
+    }
+}
+
+impl<Message> canvas::Program<Message> for LineChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &iced::Renderer,
+        theme: &iced::Theme,
+        bounds: iced::Rectangle,
+        cursor: iced::mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let palette = theme.palette();
+        let (first, last) = match (self.values.first(), self.values.last()) {
+            (Some(first), Some(last)) => (first.0, last.0),
+            _ => {
+                frame.fill_text(canvas::Text {
+                    content: "No data".to_string(),
+                    position: frame.center(),
+                    color: palette.text,
+                    horizontal_alignment: iced::alignment::Horizontal::Center,
+                    vertical_alignment: iced::alignment::Vertical::Center,
+                    ..Default::default()
+                });
+                return vec![frame.into_geometry()];
+            }
+        };
+
+        let (min, max) = self.value_range();
+        let plot = iced::Rectangle {
+            x: PADDING,
+            y: PADDING + LABEL_SIZE,
+            width: (bounds.width - 2.0 * PADDING).max(0.0),
+            height: (bounds.height - 2.0 * (PADDING + LABEL_SIZE)).max(0.0),
+        };
+        let duration = (last - first).whole_seconds().max(1) as f32;
+        let to_point = |date: fm_core::DateTime, value: f64| iced::Point {
+            x: plot.x + plot.width * (date - first).whole_seconds() as f32 / duration,
+            y: plot.y + plot.height * ((max - value) / (max - min)) as f32,
+        };
+
+        let axis_stroke = canvas::Stroke::default()
+            .with_color(iced::Color {
+                a: 0.5,
+                ..palette.text
+            })
+            .with_width(1.0);
+        let zero = to_point(first, 0.0).y;
+        frame.stroke(
+            &canvas::Path::line(
+                iced::Point::new(plot.x, zero),
+                iced::Point::new(plot.x + plot.width, zero),
+            ),
+            axis_stroke,
+        );
+
+        let line = canvas::Path::new(|builder| {
+            for (index, (date, value)) in self.values.iter().enumerate() {
+                let point = to_point(*date, value.get_eur_num());
+                if index == 0 {
+                    builder.move_to(point);
+                } else {
+                    builder.line_to(point);
+                }
+            }
+        });
+        frame.stroke(
+            &line,
+            canvas::Stroke::default()
+                .with_color(palette.primary)
+                .with_width(2.0),
+        );
+
+        let label = |content: String, position: iced::Point, alignment| canvas::Text {
+            content,
+            position,
+            color: palette.text,
+            size: LABEL_SIZE.into(),
+            horizontal_alignment: alignment,
+            ..Default::default()
+        };
+        frame.fill_text(label(
+            crate::date_time::to_date_string(first.date()),
+            iced::Point::new(plot.x, plot.y + plot.height + PADDING),
+            iced::alignment::Horizontal::Left,
+        ));
+        frame.fill_text(label(
+            crate::date_time::to_date_string(last.date()),
+            iced::Point::new(plot.x + plot.width, plot.y + plot.height + PADDING),
+            iced::alignment::Horizontal::Right,
+        ));
+
+        if let Some(position) = cursor.position_in(bounds) {
+            let closest = self.values.iter().min_by(|a, b| {
+                let a = (to_point(a.0, 0.0).x - position.x).abs();
+                let b = (to_point(b.0, 0.0).x - position.x).abs();
+                a.total_cmp(&b)
+            });
+            if let Some((date, value)) = closest {
+                let point = to_point(*date, value.get_eur_num());
+                frame.stroke(
+                    &canvas::Path::line(
+                        iced::Point::new(point.x, plot.y),
+                        iced::Point::new(point.x, plot.y + plot.height),
+                    ),
+                    axis_stroke,
+                );
+                frame.fill(&canvas::Path::circle(point, 4.0), palette.primary);
+                frame.fill_text(label(
+                    format!(
+                        "{}: {}",
+                        crate::date_time::to_date_string(date.date()),
+                        value
+                    ),
+                    iced::Point::new(plot.x + plot.width / 2.0, PADDING / 2.0),
+                    iced::alignment::Horizontal::Center,
+                ));
+            }
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
@@ -6,6 +6,9 @@ mod inner_table_view;
 pub enum Action<Message> {
     OuterMessage(Message),
     Task(iced::Task<InnerMessage<Message>>),
+    /// The page or the sorting of a remotely paged table changed and the owner should load the
+    /// items of the current page (see [`State::paged_remotely`]).
+    LoadPage,
     None,
 }
 
@@ -24,6 +27,8 @@ pub struct State<T, C> {
     context: C,
     page_size: usize,
     page: usize,
+    /// The count of all items if only the items of the current page are held.
+    remote_total: Option<usize>,
     sort_column: Option<usize>,
     sort_reverse: bool,
     sortable: HashSet<usize>,
@@ -54,6 +59,7 @@ impl<T, C> State<T, C> {
             context,
             page_size: 50,
             page: 0,
+            remote_total: None,
             sort_column: None,
             sort_reverse: false,
             sortable: HashSet::default(),
@@ -83,8 +89,16 @@ impl<T, C> State<T, C> {
         self
     }
 
+    /// Leaves sorting and paging to the owner: the items are only the current page of `total` items.
+    /// The owner is asked to load pages with [`Action::LoadPage`] and sets them with [`State::set_page`].
+    pub fn paged_remotely(mut self, total: usize) -> Self {
+        self.remote_total = Some(total);
+        self
+    }
+
     fn apply_sort(&mut self) {
-        if let Some(sort_by_callback) = &self.sort_by_callback
+        if self.remote_total.is_none()
+            && let Some(sort_by_callback) = &self.sort_by_callback
             && let Some(sort_column) = self.sort_column
         {
             self.items.sort_by(|a, b| {
@@ -118,7 +132,21 @@ impl<T, C> State<T, C> {
     }
 
     fn max_page(&self) -> usize {
-        self.items.len() / self.page_size
+        self.remote_total.unwrap_or(self.items.len()) / self.page_size
+    }
+
+    /// The count of items before the current page.
+    pub fn offset(&self) -> usize {
+        self.page * self.page_size
+    }
+
+    pub fn limit(&self) -> usize {
+        self.page_size
+    }
+
+    /// The sort column and whether the sorting is reversed.
+    pub fn sorting(&self) -> Option<(usize, bool)> {
+        self.sort_column.map(|column| (column, self.sort_reverse))
     }
 
     pub fn perform<Message>(&mut self, message: InnerMessage<Message>) -> Action<Message> {
@@ -127,6 +155,9 @@ impl<T, C> State<T, C> {
                 let new_page = (self.page as i32 + value as i32).max(0) as usize;
                 if new_page <= self.max_page() {
                     self.page = new_page;
+                    if self.remote_total.is_some() {
+                        return Action::LoadPage;
+                    }
                     Action::Task(widget::scrollable::snap_to(
                         self.scrollable_id.clone(),
                         widget::scrollable::RelativeOffset { x: 0.0, y: 0.0 },
@@ -146,6 +177,9 @@ impl<T, C> State<T, C> {
                     },
                 );
                 self.page = 0;
+                if self.remote_total.is_some() {
+                    return Action::LoadPage;
+                }
                 Action::Task(widget::scrollable::snap_to(
                     self.scrollable_id.clone(),
                     widget::scrollable::RelativeOffset { x: 0.0, y: 0.0 },
@@ -171,6 +205,14 @@ impl<T, C> State<T, C> {
         self.sort_reverse = false;
     }
 
+    /// Sets the items of the page starting at `offset` of a remotely paged table with `total` items.
+    pub fn set_page(&mut self, items: Vec<T>, offset: usize, total: usize) {
+        self.items = items;
+        self.inner_layout_id = uuid::Uuid::new_v4().as_u128() as isize;
+        self.remote_total = Some(total);
+        self.page = (offset / self.page_size).min(self.max_page());
+    }
+
     /// will edit items and resort
     pub fn edit_items(&mut self, update: impl FnOnce(&mut Vec<T>)) {
         (update)(&mut self.items);
@@ -283,13 +325,17 @@ impl<'a, T, C, const COLUMNS: usize> TableView<'a, T, C, COLUMNS> {
         }
 
         let mut cell_elements = Vec::new();
-        for item_index in (self.state.page * self.state.page_size)
-            ..(self
-                .state
-                .items
-                .len()
-                .min(self.state.page * self.state.page_size + self.state.page_size))
-        {
+        let page_range = if self.state.remote_total.is_some() {
+            0..self.state.items.len()
+        } else {
+            (self.state.page * self.state.page_size)
+                ..(self
+                    .state
+                    .items
+                    .len()
+                    .min(self.state.page * self.state.page_size + self.state.page_size))
+        };
+        for item_index in page_range {
             cell_elements.extend(
                 (to_row)(&self.state.items()[item_index], &self.state.context)
                     .map(|element| element.map(|x| InnerMessage::OuterMessage(Box::new(x)))),
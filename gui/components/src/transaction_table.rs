@@ -6,6 +6,9 @@ pub enum Action {
     ViewTransaction(fm_core::Id),
     ViewAccount(fm_core::Id),
     Task(iced::Task<Message>),
+    /// Only for tables created with [`TransactionTable::new_paged`]: load the requested page and
+    /// set it with [`TransactionTable::set_page`].
+    LoadPage(fm_core::pagination::PageRequest),
     None,
 }
 
@@ -55,6 +58,8 @@ impl std::fmt::Debug for TransactionTable {
     }
 }
 
+const PAGE_SIZE: usize = 50;
+
 fn hash_categories(map: &std::collections::HashMap<fm_core::Id, fm_core::Sign>) -> u64 {
     let mut hasher = std::hash::DefaultHasher::new();
     map.keys().collect::<Vec<_>>().hash(&mut hasher);
@@ -125,6 +130,114 @@ impl TransactionTable {
         }
     }
 
+    /// Create a table of transactions that only holds the current page,
+    /// while sorting and paging are done by the finance manager.
+    /// Transactions can be sorted by title, date and amount, where amounts are sorted by their value regardless of `amount_positive`.
+    ///
+    /// # Arguments
+    /// - `transactions`: The page requested with [`TransactionTable::first_page_request`]
+    /// - for the other arguments see [`TransactionTable::new`]
+    pub fn new_paged(
+        transactions: fm_core::pagination::Page<(
+            fm_core::Transaction,
+            fm_core::account::Account,
+            fm_core::account::Account,
+        )>,
+        categories: Vec<fm_core::Category>,
+        budgets: Vec<fm_core::Budget>,
+        amount_positive: impl Fn(fm_core::Transaction) -> Option<bool> + 'static,
+    ) -> Self {
+        let mut categories = categories;
+        categories.sort();
+        let mut transaction_table =
+            crate::table_view::State::new(transactions.items, (categories.clone(), budgets))
+                .page_size(PAGE_SIZE)
+                .sortable_columns([0, 1, 2])
+                .paged_remotely(transactions.total);
+        transaction_table.sort(1, true);
+        Self {
+            categories,
+            amount_positive: Box::new(amount_positive),
+            transaction_table,
+            category_popup: None,
+        }
+    }
+
+    /// The first page of a table created with [`TransactionTable::new_paged`].
+    pub fn first_page_request() -> fm_core::pagination::PageRequest {
+        fm_core::pagination::PageRequest::new(0, PAGE_SIZE)
+            .sorted_by(fm_core::pagination::SortKey::Date, true)
+    }
+
+    /// The current page and sorting of a table created with [`TransactionTable::new_paged`].
+    pub fn page_request(&self) -> fm_core::pagination::PageRequest {
+        let (sort, descending) = match self.transaction_table.sorting() {
+            Some((0, reverse)) => (fm_core::pagination::SortKey::Title, reverse),
+            Some((2, reverse)) => (fm_core::pagination::SortKey::Amount, reverse),
+            Some((_, reverse)) => (fm_core::pagination::SortKey::Date, reverse),
+            None => (fm_core::pagination::SortKey::Date, true),
+        };
+        fm_core::pagination::PageRequest::new(
+            self.transaction_table.offset(),
+            self.transaction_table.limit(),
+        )
+        .sorted_by(sort, descending)
+    }
+
+    /// Sets the page loaded for `request` of a table created with [`TransactionTable::new_paged`]
+    /// and scrolls to its top.
+    pub fn set_page(
+        &mut self,
+        transactions: fm_core::pagination::Page<(
+            fm_core::Transaction,
+            fm_core::account::Account,
+            fm_core::account::Account,
+        )>,
+        request: &fm_core::pagination::PageRequest,
+    ) -> iced::Task<Message> {
+        self.replace_page(transactions, request);
+        iced::Task::done(Message::TransactionTable(
+            crate::table_view::InnerMessage::ScrollToTop,
+        ))
+    }
+
+    fn replace_page(
+        &mut self,
+        transactions: fm_core::pagination::Page<(
+            fm_core::Transaction,
+            fm_core::account::Account,
+            fm_core::account::Account,
+        )>,
+        request: &fm_core::pagination::PageRequest,
+    ) {
+        if let Some(id) = self.category_popup
+            && !transactions.items.iter().any(|pair| pair.0.id == id)
+        {
+            self.category_popup = None;
+        }
+        self.transaction_table
+            .set_page(transactions.items, request.offset, transactions.total);
+    }
+
+    /// Like [`TransactionTable::reload`] for a table created with [`TransactionTable::new_paged`].
+    pub fn reload_page(
+        &mut self,
+        transactions: fm_core::pagination::Page<(
+            fm_core::Transaction,
+            fm_core::account::Account,
+            fm_core::account::Account,
+        )>,
+        request: &fm_core::pagination::PageRequest,
+        categories: Vec<fm_core::Category>,
+        budgets: Vec<fm_core::Budget>,
+    ) {
+        self.categories = categories;
+        self.categories.sort();
+        self.transaction_table
+            .set_context((self.categories.clone(), budgets));
+        self.replace_page(transactions, request);
+    }
+
     pub fn reload(
         &mut self,
         transactions: Vec<(
@@ -255,6 +368,9 @@ impl TransactionTable {
                         return self.update(m, finance_controller);
                     }
                     crate::table_view::Action::None => {}
+                    crate::table_view::Action::LoadPage => {
+                        return Action::LoadPage(self.page_request());
+                    }
                     crate::table_view::Action::Task(task) => {
                         return Action::Task(task.map(Message::TransactionTable));
                     }
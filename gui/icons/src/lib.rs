@@ -186,3 +186,17 @@ pub static DOWNLOAD: LazyLock<Handle> =
 pub fn download() -> Svg<'static> {
     Svg::new(DOWNLOAD.clone()).width(iced::Shrink)
 }
+
+pub static GRAPH_UP: LazyLock<Handle> =
+    LazyLock::new(|| Handle::from_memory(include_bytes!("../assets/graph-up.svg")));
+
+pub fn graph_up() -> Svg<'static> {
+    Svg::new(GRAPH_UP.clone()).width(iced::Shrink)
+}
+
+pub static BAR_CHART_FILL: LazyLock<Handle> =
+    LazyLock::new(|| Handle::from_memory(include_bytes!("../assets/bar-chart-fill.svg")));
+
+pub fn bar_chart_fill() -> Svg<'static> {
+    Svg::new(BAR_CHART_FILL.clone()).width(iced::Shrink)
+}
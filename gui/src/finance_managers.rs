@@ -2,6 +2,7 @@ use anyhow::Result;
 use std::collections::HashMap;
 
 #[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum FinanceManagers {
     Server(fm_server::client::Client),
     #[cfg(feature = "native")]
@@ -102,6 +103,14 @@ impl fm_core::FinanceManager for FinanceManagers {
         fm_match!(self, delete_account, id)
     }
 
+    async fn merge_accounts(
+        &mut self,
+        keep: fm_core::account::Account,
+        remove: fm_core::Id,
+    ) -> Result<fm_core::account::Account> {
+        fm_match!(self, merge_accounts, keep, remove)
+    }
+
     async fn create_book_checking_account(
         &mut self,
         name: String,
@@ -116,8 +125,9 @@ impl fm_core::FinanceManager for FinanceManagers {
         &self,
         account: &fm_core::account::Account,
         date: fm_core::DateTime,
+        date_kind: fm_core::DateKind,
     ) -> Result<fm_core::Currency> {
-        fm_match!(self, get_account_sum, account, date)
+        fm_match!(self, get_account_sum, account, date, date_kind)
     }
 
     async fn update_asset_account(
@@ -171,6 +181,66 @@ impl fm_core::FinanceManager for FinanceManagers {
         fm_match!(self, delete_bill, id)
     }
 
+    async fn create_trade(
+        &mut self,
+        account: fm_core::Id,
+        isin: fm_core::securities::Isin,
+        kind: fm_core::securities::TradeKind,
+        quantity: fm_core::bigdecimal::BigDecimal,
+        amount: fm_core::Currency,
+        date: fm_core::DateTime,
+    ) -> Result<fm_core::securities::Trade> {
+        fm_match!(
+            self,
+            create_trade,
+            account,
+            isin,
+            kind,
+            quantity,
+            amount,
+            date
+        )
+    }
+
+    async fn delete_trade(&mut self, id: fm_core::Id) -> Result<()> {
+        fm_match!(self, delete_trade, id)
+    }
+
+    async fn get_trades(&self, account: fm_core::Id) -> Result<Vec<fm_core::securities::Trade>> {
+        fm_match!(self, get_trades, account)
+    }
+
+    async fn add_prices(&mut self, prices: Vec<fm_core::securities::Price>) -> Result<()> {
+        fm_match!(self, add_prices, prices)
+    }
+
+    async fn get_prices(
+        &self,
+        isin: fm_core::securities::Isin,
+    ) -> Result<Vec<fm_core::securities::Price>> {
+        fm_match!(self, get_prices, isin)
+    }
+
+    async fn create_valuation(
+        &mut self,
+        account: fm_core::Id,
+        value: fm_core::Currency,
+        date: fm_core::DateTime,
+    ) -> Result<fm_core::valuation::Valuation> {
+        fm_match!(self, create_valuation, account, value, date)
+    }
+
+    async fn delete_valuation(&mut self, id: fm_core::Id) -> Result<()> {
+        fm_match!(self, delete_valuation, id)
+    }
+
+    async fn get_valuations(
+        &self,
+        account: fm_core::Id,
+    ) -> Result<Vec<fm_core::valuation::Valuation>> {
+        fm_match!(self, get_valuations, account)
+    }
+
     async fn get_filtered_transactions(
         &self,
         filter: fm_core::transaction_filter::TransactionFilter,
@@ -178,6 +248,42 @@ impl fm_core::FinanceManager for FinanceManagers {
         fm_match!(self, get_filtered_transactions, filter)
     }
 
+    async fn get_transactions_matching(
+        &self,
+        expression: fm_core::filter_expression::FilterExpression,
+    ) -> Result<Vec<fm_core::Transaction>> {
+        fm_match!(self, get_transactions_matching, expression)
+    }
+
+    fn stream_transactions(
+        &self,
+        expression: fm_core::filter_expression::FilterExpression,
+    ) -> fm_core::TransactionStream {
+        match self {
+            FinanceManagers::Server(client) => client.stream_transactions(expression),
+            #[cfg(feature = "native")]
+            FinanceManagers::Sqlite(sqlite) => sqlite.stream_transactions(expression),
+            FinanceManagers::Ram(ram) => ram.stream_transactions(expression),
+        }
+    }
+
+    async fn get_transactions_page(
+        &self,
+        expression: fm_core::filter_expression::FilterExpression,
+        page: fm_core::pagination::PageRequest,
+    ) -> Result<fm_core::pagination::Page<fm_core::Transaction>> {
+        fm_match!(self, get_transactions_page, expression, page)
+    }
+
+    async fn aggregate_transactions(
+        &self,
+        filter: fm_core::transaction_filter::TransactionFilter,
+        group_by: Option<fm_core::aggregation::GroupBy>,
+        period: Option<fm_core::aggregation::Period>,
+    ) -> Result<Vec<fm_core::aggregation::Aggregate>> {
+        fm_match!(self, aggregate_transactions, filter, group_by, period)
+    }
+
     async fn create_budget(
         &mut self,
         name: String,
@@ -230,6 +336,13 @@ impl fm_core::FinanceManager for FinanceManagers {
         )
     }
 
+    async fn create_transaction_from(
+        &mut self,
+        transaction: fm_core::Transaction,
+    ) -> Result<fm_core::Transaction> {
+        fm_match!(self, create_transaction_from, transaction)
+    }
+
     async fn delete_category(&mut self, id: fm_core::Id) -> Result<()> {
         fm_match!(self, delete_category, id)
     }
@@ -238,6 +351,14 @@ impl fm_core::FinanceManager for FinanceManagers {
         fm_match!(self, delete_transaction, id)
     }
 
+    async fn merge_transactions(
+        &mut self,
+        keep: fm_core::Transaction,
+        remove: fm_core::Id,
+    ) -> Result<fm_core::Transaction> {
+        fm_match!(self, merge_transactions, keep, remove)
+    }
+
     async fn get_account(&self, id: fm_core::Id) -> Result<Option<fm_core::account::Account>> {
         fm_match!(self, get_account, id)
     }
@@ -246,6 +367,17 @@ impl fm_core::FinanceManager for FinanceManagers {
         fm_match!(self, get_accounts,)
     }
 
+    async fn get_accounts_by_account_id(
+        &self,
+        account_id: fm_core::AccountId,
+    ) -> Result<Vec<fm_core::account::Account>> {
+        fm_match!(self, get_accounts_by_account_id, account_id)
+    }
+
+    async fn get_accounts_by_name(&self, name: String) -> Result<Vec<fm_core::account::Account>> {
+        fm_match!(self, get_accounts_by_name, name)
+    }
+
     async fn get_budget(&self, id: fm_core::Id) -> Result<Option<fm_core::Budget>> {
         fm_match!(self, get_budget, id)
     }
@@ -329,4 +461,34 @@ impl fm_core::FinanceManager for FinanceManagers {
     ) -> Result<fm_core::Transaction> {
         fm_match!(self, update_transaction, transaction)
     }
+
+    async fn get_saved_filters(&self) -> Result<Vec<fm_core::transaction_filter::SavedFilter>> {
+        fm_match!(self, get_saved_filters,)
+    }
+
+    async fn get_saved_filter(
+        &self,
+        id: fm_core::Id,
+    ) -> Result<Option<fm_core::transaction_filter::SavedFilter>> {
+        fm_match!(self, get_saved_filter, id)
+    }
+
+    async fn create_saved_filter(
+        &mut self,
+        name: String,
+        filter: fm_core::transaction_filter::TransactionFilter,
+    ) -> Result<fm_core::transaction_filter::SavedFilter> {
+        fm_match!(self, create_saved_filter, name, filter)
+    }
+
+    async fn update_saved_filter(
+        &mut self,
+        saved_filter: fm_core::transaction_filter::SavedFilter,
+    ) -> Result<fm_core::transaction_filter::SavedFilter> {
+        fm_match!(self, update_saved_filter, saved_filter)
+    }
+
+    async fn delete_saved_filter(&mut self, id: fm_core::Id) -> Result<()> {
+        fm_match!(self, delete_saved_filter, id)
+    }
 }
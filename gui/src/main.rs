@@ -13,6 +13,7 @@ type Fm = finance_managers::FinanceManagers;
 type Fc = fm_core::FMController<Fm>;
 
 #[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
 enum Message {
     Ignore,
     PaneViewMessage(
@@ -42,6 +43,7 @@ pub struct App {
 impl App {
     fn new(finance_controller: Fc, settings: settings::Settings) -> (Self, iced::Task<Message>) {
         let (sidebar_state, sidebar_task) = sidebar::Sidebar::new();
+        let saved_filters_task = sidebar_state.reload(finance_controller.clone());
         let (pane_grid, focused_pane) = widget::pane_grid::State::new(view::View::Markdown(
             "Finance Manager".to_string(),
             widget::markdown::parse(include_str!("view/tutorial.md")).collect(),
@@ -55,7 +57,10 @@ impl App {
                 pane_grid,
                 focused_pane,
             },
-            sidebar_task.map(Message::SidebarMessage),
+            iced::Task::batch([
+                sidebar_task.map(Message::SidebarMessage),
+                saved_filters_task.map(Message::SidebarMessage),
+            ]),
         )
     }
 
@@ -63,7 +68,11 @@ impl App {
         match message {
             Message::Ignore => {}
             Message::FCModified => {
-                let mut tasks = Vec::new();
+                let mut tasks = vec![
+                    self.side_bar
+                        .reload(self.finance_controller.clone())
+                        .map(Message::SidebarMessage),
+                ];
                 for (pane, view) in self.pane_grid.panes.iter_mut() {
                     let pane = *pane;
                     tasks.push(
@@ -151,6 +160,20 @@ impl App {
                         .transaction_filter(self.finance_controller.clone())
                         .map(move |x| Message::PaneViewMessage(pane, x.into()));
                 }
+                sidebar::Action::SwitchToSavedFilter(saved_filter) => {
+                    let pane = self.focused_pane;
+                    return self
+                        .pane_grid
+                        .get_mut(self.focused_pane)
+                        .unwrap()
+                        .saved_filter(
+                            self.finance_controller.clone(),
+                            *saved_filter,
+                            time::UtcOffset::from_whole_seconds(self.settings.utc_seconds_offset)
+                                .unwrap(),
+                        )
+                        .map(move |x| Message::PaneViewMessage(pane, x.into()));
+                }
                 sidebar::Action::SwitchToBillOverview => {
                     let pane = self.focused_pane;
                     return self
@@ -160,6 +183,24 @@ impl App {
                         .bill_overview(self.finance_controller.clone())
                         .map(move |x| Message::PaneViewMessage(pane, x.into()));
                 }
+                sidebar::Action::SwitchToNetWorth => {
+                    let pane = self.focused_pane;
+                    return self
+                        .pane_grid
+                        .get_mut(self.focused_pane)
+                        .unwrap()
+                        .net_worth(self.finance_controller.clone())
+                        .map(move |x| Message::PaneViewMessage(pane, x.into()));
+                }
+                sidebar::Action::SwitchToCashFlow => {
+                    let pane = self.focused_pane;
+                    return self
+                        .pane_grid
+                        .get_mut(self.focused_pane)
+                        .unwrap()
+                        .cash_flow(self.finance_controller.clone())
+                        .map(move |x| Message::PaneViewMessage(pane, x.into()));
+                }
                 sidebar::Action::SwitchToLicense => {
                     *self.pane_grid.get_mut(self.focused_pane).unwrap() = view::View::License;
                 }
@@ -380,10 +421,15 @@ impl App {
         if valid_settings {
             self.settings = new_settings.clone();
             let future = settings::write_settings(new_settings);
-            iced::Task::future(async move {
-                future.await.unwrap();
-                Message::Ignore
-            })
+            iced::Task::batch([
+                iced::Task::future(async move {
+                    future.await.unwrap();
+                    Message::Ignore
+                }),
+                self.side_bar
+                    .reload(self.finance_controller.clone())
+                    .map(Message::SidebarMessage),
+            ])
         } else {
             iced::Task::none()
         }
@@ -7,9 +7,12 @@ pub enum Action {
     SwitchToBudgetOverview,
     SwitchToCategoryOverview,
     SwitchToFilterTransactionView,
+    SwitchToSavedFilter(Box<fm_core::transaction_filter::SavedFilter>),
     SwitchToSettingsView,
     SwitchToLicense,
     SwitchToBillOverview,
+    SwitchToNetWorth,
+    SwitchToCashFlow,
     #[cfg(feature = "native")]
     SwitchToImporter,
     CreateTransaction,
@@ -24,26 +27,46 @@ pub enum Message {
     BudgetOverview,
     CategoryOverview,
     FilterTransactionView,
+    SavedFilter(fm_core::Id),
+    SavedFilters(Vec<fm_core::transaction_filter::SavedFilter>),
     SettingsView,
     License,
     CreateTransaction,
     BillOverview,
+    NetWorth,
+    CashFlow,
     #[cfg(feature = "native")]
     Importer,
 }
 
 pub struct Sidebar {
     collapsed: bool,
+    saved_filters: Vec<fm_core::transaction_filter::SavedFilter>,
 }
 
 impl Sidebar {
     pub fn new() -> (Self, iced::Task<Message>) {
         (
-            Self { collapsed: false },
+            Self {
+                collapsed: false,
+                saved_filters: Vec::new(),
+            },
             error::failing_task(read_collapsed_config()).map(Message::Collapse),
         )
     }
 
+    /// Fetches the saved filters listed below the transactions.
+    pub fn reload(
+        &self,
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+    ) -> iced::Task<Message> {
+        error::failing_task(async move {
+            let mut saved_filters = finance_controller.get_saved_filters().await?;
+            saved_filters.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(Message::SavedFilters(saved_filters))
+        })
+    }
+
     pub fn update(&mut self, message: Message) -> Action {
         match message {
             Message::Collapse(collapse) => {
@@ -55,9 +78,19 @@ impl Sidebar {
             Message::BudgetOverview => Action::SwitchToBudgetOverview,
             Message::CategoryOverview => Action::SwitchToCategoryOverview,
             Message::FilterTransactionView => Action::SwitchToFilterTransactionView,
+            Message::SavedFilter(id) => match self.saved_filters.iter().find(|x| x.id == id) {
+                Some(saved_filter) => Action::SwitchToSavedFilter(Box::new(saved_filter.clone())),
+                None => Action::Task(iced::Task::none()),
+            },
+            Message::SavedFilters(saved_filters) => {
+                self.saved_filters = saved_filters;
+                Action::Task(iced::Task::none())
+            }
             Message::License => Action::SwitchToLicense,
             Message::SettingsView => Action::SwitchToSettingsView,
             Message::BillOverview => Action::SwitchToBillOverview,
+            Message::NetWorth => Action::SwitchToNetWorth,
+            Message::CashFlow => Action::SwitchToCashFlow,
             Message::CreateTransaction => Action::CreateTransaction,
             #[cfg(feature = "native")]
             Message::Importer => Action::SwitchToImporter,
@@ -115,12 +148,34 @@ impl Sidebar {
                     Message::BillOverview,
                     self.collapsed
                 ),
+                icon_menu_item(
+                    "Net Worth",
+                    icons::GRAPH_UP.clone(),
+                    Message::NetWorth,
+                    self.collapsed
+                ),
+                icon_menu_item(
+                    "Cash Flow",
+                    icons::BAR_CHART_FILL.clone(),
+                    Message::CashFlow,
+                    self.collapsed
+                ),
                 icon_menu_item(
                     "Transactions",
                     icons::SEND_FILL.clone(),
                     Message::FilterTransactionView,
                     self.collapsed
                 ),
+                widget::Column::with_children(self.saved_filters.iter().map(|saved_filter| {
+                    icon_menu_item(
+                        &saved_filter.name,
+                        icons::FILTER.clone(),
+                        Message::SavedFilter(saved_filter.id),
+                        self.collapsed,
+                    )
+                }))
+                .spacing(style::COLUMN_SPACING)
+                .padding(iced::Padding::ZERO.left(if self.collapsed { 0 } else { 20 })),
                 icon_menu_item(
                     "Create Transaction",
                     icons::PLUS_CIRCLE_FILL.clone(),
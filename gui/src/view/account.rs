@@ -4,6 +4,9 @@ use iced::widget;
 use iced_aw::widget::LabeledFrame;
 use std::sync::Arc;
 
+mod securities;
+mod valuations;
+
 pub enum AccountType {
     AssetAccount,
     BookCheckingAccount,
@@ -23,15 +26,24 @@ pub enum Action {
 struct Init {
     account: fm_core::account::Account,
     value: fm_core::Currency,
-    transactions: Vec<(
-        fm_core::Transaction,
-        fm_core::account::Account,
-        fm_core::account::Account,
-    )>,
+    transactions: TransactionPage,
+    page: fm_core::pagination::PageRequest,
     categories: Vec<fm_core::Category>,
     budgets: Vec<fm_core::Budget>,
+    book_checking_accounts: Vec<fm_core::account::BookCheckingAccount>,
+    securities: Option<(
+        Vec<fm_core::securities::Trade>,
+        fm_core::securities::Performance,
+    )>,
+    valuations: Option<Vec<fm_core::valuation::Valuation>>,
 }
 
+type TransactionPage = fm_core::pagination::Page<(
+    fm_core::Transaction,
+    fm_core::account::Account,
+    fm_core::account::Account,
+)>;
+
 #[derive(Debug, Clone)]
 pub struct MessageContainer(Message);
 
@@ -39,18 +51,18 @@ pub struct MessageContainer(Message);
 enum Message {
     Edit,
     ChangeTransactionTimespan(date_span_input::Action),
-    SetTransactions(
-        Vec<(
-            fm_core::Transaction,
-            fm_core::account::Account,
-            fm_core::account::Account,
-        )>,
-    ),
+    SetPage(TransactionPage, fm_core::pagination::PageRequest),
     Initialize(Box<Init>),
     Reload(Option<Box<Init>>),
     Delete,
     Deleted(Arc<std::result::Result<(), fm_core::DeleteAccountError>>),
+    Merge(fm_core::account::BookCheckingAccount),
+    Merged,
+    SplitLoanPayments,
+    LoanPaymentsSplit(usize),
     TransactionTable(components::transaction_table::Message),
+    Securities(securities::Message),
+    Valuations(valuations::Message),
 }
 
 #[derive(Debug)]
@@ -62,6 +74,9 @@ pub enum View {
         current_value: fm_core::Currency,
         transaction_table: components::TransactionTable,
         timespan_input: date_span_input::State,
+        merge_state: widget::combo_box::State<fm_core::account::BookCheckingAccount>,
+        securities: Option<securities::Securities>,
+        valuations: Option<valuations::Valuations>,
     },
 }
 
@@ -75,10 +90,12 @@ impl View {
         if let Self::Loaded {
             account,
             timespan_input,
+            transaction_table,
             ..
         } = self
         {
             let account_id = *account.id();
+            let page = transaction_table.page_request();
             let timespan = components::date_time::date_span_to_time_span(
                 timespan_input.timespan(),
                 utc_offset,
@@ -90,35 +107,31 @@ impl View {
                     return Ok(Message::Reload(None));
                 };
                 let account_sum = finance_controller
-                    .get_account_sum(&account, time::OffsetDateTime::now_utc())
+                    .get_account_sum(
+                        &account,
+                        time::OffsetDateTime::now_utc(),
+                        fm_core::DateKind::Booking,
+                    )
                     .await?;
                 let transactions = finance_controller
-                    .get_transactions_of_account(*account.id(), timespan)
+                    .get_transactions_page(of_account(account_id, timespan), page)
                     .await?;
                 let accounts = finance_controller.get_accounts_hash_map().await?;
-                let mut transaction_tuples = Vec::with_capacity(transactions.len());
-                for transaction in transactions {
-                    let source = accounts
-                        .get(&transaction.source)
-                        .context(format!("Could not find account {}", transaction.source))?
-                        .clone();
-                    let destination = accounts
-                        .get(&transaction.destination)
-                        .context(format!(
-                            "Could not find account {}",
-                            transaction.destination
-                        ))?
-                        .clone();
-                    transaction_tuples.push((transaction, source, destination));
-                }
+                let transactions = with_accounts(transactions, &accounts)?;
                 let categories = finance_controller.get_categories().await?;
                 let budgets = finance_controller.get_budgets().await?;
+                let securities = securities_of(&finance_controller, &account).await?;
+                let valuations = valuations_of(&finance_controller, &account).await?;
                 Ok(Message::Reload(Some(Box::new(Init {
                     account,
                     value: account_sum,
-                    transactions: transaction_tuples,
+                    transactions,
+                    page,
                     categories,
                     budgets,
+                    book_checking_accounts: book_checking_accounts(accounts),
+                    securities,
+                    valuations,
                 }))))
             })
             .map(MessageContainer);
@@ -126,6 +139,43 @@ impl View {
         task
     }
 
+    /// Loads the page of the transactions in the selected timespan.
+    fn load_page(
+        &self,
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+        utc_offset: time::UtcOffset,
+        page: fm_core::pagination::PageRequest,
+    ) -> iced::Task<MessageContainer> {
+        let Self::Loaded {
+            account,
+            timespan_input,
+            ..
+        } = self
+        else {
+            return iced::Task::none();
+        };
+        let account_id = *account.id();
+        let timespan =
+            components::date_time::date_span_to_time_span(timespan_input.timespan(), utc_offset);
+        error::failing_task(async move {
+            let transactions = finance_controller
+                .get_transactions_page(of_account(account_id, timespan), page)
+                .await
+                .context(format!(
+                    "Error while fetching transactions of account {account_id}."
+                ))?;
+            let accounts = finance_controller
+                .get_accounts_hash_map()
+                .await
+                .context("Error while fetching accounts")?;
+            Ok(Message::SetPage(
+                with_accounts(transactions, &accounts)?,
+                page,
+            ))
+        })
+        .map(MessageContainer)
+    }
+
     pub fn fetch(
         finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
         account_id: fm_core::Id,
@@ -138,35 +188,32 @@ impl View {
                     .await?
                     .context("Account could not be found")?;
                 let account_sum = finance_controller
-                    .get_account_sum(&account, time::OffsetDateTime::now_utc())
+                    .get_account_sum(
+                        &account,
+                        time::OffsetDateTime::now_utc(),
+                        fm_core::DateKind::Booking,
+                    )
                     .await?;
+                let page = components::TransactionTable::first_page_request();
                 let transactions = finance_controller
-                    .get_transactions_of_account(*account.id(), (None, None))
+                    .get_transactions_page(of_account(account_id, (None, None)), page)
                     .await?;
                 let accounts = finance_controller.get_accounts_hash_map().await?;
-                let mut transaction_tuples = Vec::with_capacity(transactions.len());
-                for transaction in transactions {
-                    let source = accounts
-                        .get(&transaction.source)
-                        .context(format!("Could not find account {}", transaction.source))?
-                        .clone();
-                    let destination = accounts
-                        .get(&transaction.destination)
-                        .context(format!(
-                            "Could not find account {}",
-                            transaction.destination
-                        ))?
-                        .clone();
-                    transaction_tuples.push((transaction, source, destination));
-                }
+                let transactions = with_accounts(transactions, &accounts)?;
                 let categories = finance_controller.get_categories().await?;
                 let budgets = finance_controller.get_budgets().await?;
+                let securities = securities_of(&finance_controller, &account).await?;
+                let valuations = valuations_of(&finance_controller, &account).await?;
                 Ok(Message::Initialize(Box::new(Init {
                     account,
                     value: account_sum,
-                    transactions: transaction_tuples,
+                    transactions,
+                    page,
                     categories,
                     budgets,
+                    book_checking_accounts: book_checking_accounts(accounts),
+                    securities,
+                    valuations,
                 })))
             })
             .map(MessageContainer),
@@ -186,12 +233,39 @@ impl View {
                         account,
                         current_value,
                         transaction_table,
+                        merge_state,
+                        securities,
+                        valuations,
                         ..
                     } = self
                     {
+                        *merge_state =
+                            merge_state_of(init.book_checking_accounts, *init.account.id());
+                        *securities = match (securities.take(), init.securities) {
+                            (Some(mut state), Some((trades, performance))) => {
+                                state.reload(trades, performance);
+                                Some(state)
+                            }
+                            (_, data) => data.map(|(trades, performance)| {
+                                securities::Securities::new(*init.account.id(), trades, performance)
+                            }),
+                        };
+                        *valuations = match (valuations.take(), init.valuations) {
+                            (Some(mut state), Some(data)) => {
+                                state.reload(data);
+                                Some(state)
+                            }
+                            (_, data) => data
+                                .map(|data| valuations::Valuations::new(*init.account.id(), data)),
+                        };
                         *account = init.account;
                         *current_value = init.value;
-                        transaction_table.reload(init.transactions, init.categories, init.budgets);
+                        transaction_table.reload_page(
+                            init.transactions,
+                            &init.page,
+                            init.categories,
+                            init.budgets,
+                        );
                     }
                 } else {
                     *self = Self::NotLoaded;
@@ -203,13 +277,20 @@ impl View {
                 *self = Self::Loaded {
                     account: init.account,
                     current_value: init.value,
-                    transaction_table: components::TransactionTable::new(
+                    transaction_table: components::TransactionTable::new_paged(
                         init.transactions,
                         init.categories,
                         init.budgets,
                         move |transaction| Some(transaction.destination == account_id),
                     ),
                     timespan_input: date_span_input::State::default(),
+                    merge_state: merge_state_of(init.book_checking_accounts, account_id),
+                    securities: init.securities.map(|(trades, performance)| {
+                        securities::Securities::new(account_id, trades, performance)
+                    }),
+                    valuations: init
+                        .valuations
+                        .map(|data| valuations::Valuations::new(account_id, data)),
                 };
                 Action::None
             }
@@ -227,61 +308,36 @@ impl View {
                     Action::None
                 }
             }
-            Message::SetTransactions(new_transactions) => {
+            Message::SetPage(transactions, page) => {
                 if let Self::Loaded {
                     transaction_table, ..
                 } = self
                 {
-                    transaction_table.change_transactions(new_transactions);
+                    return Action::Task(
+                        transaction_table
+                            .set_page(transactions, &page)
+                            .map(Message::TransactionTable)
+                            .map(MessageContainer),
+                    );
                 }
                 Action::None
             }
             Message::ChangeTransactionTimespan(action) => {
-                let timespan = if let Self::Loaded { timespan_input, .. } = self {
+                let page = if let Self::Loaded {
+                    timespan_input,
+                    transaction_table,
+                    ..
+                } = self
+                {
                     timespan_input.perform(action);
-                    timespan_input.timespan()
-                } else {
-                    return Action::None;
-                };
-                let account_id = if let Self::Loaded { account, .. } = self {
-                    *account.id()
+                    fm_core::pagination::PageRequest {
+                        offset: 0,
+                        ..transaction_table.page_request()
+                    }
                 } else {
                     return Action::None;
                 };
-                Action::Task(
-                    error::failing_task(async move {
-                        let transactions = finance_controller
-                            .get_transactions_of_account(
-                                account_id,
-                                components::date_time::date_span_to_time_span(timespan, utc_offset),
-                            )
-                            .await
-                            .context(format!(
-                                "Error while fetching transactions of account {account_id}."
-                            ))?;
-                        let accounts = finance_controller
-                            .get_accounts_hash_map()
-                            .await
-                            .context("Error while fetching accounts")?;
-                        let mut transaction_tuples = Vec::with_capacity(transactions.len());
-                        for transaction in transactions {
-                            let source = accounts
-                                .get(&transaction.source)
-                                .context(format!("Could not find account {}", transaction.source))?
-                                .clone();
-                            let destination = accounts
-                                .get(&transaction.destination)
-                                .context(format!(
-                                    "Could not find account {}",
-                                    &transaction.destination
-                                ))?
-                                .clone();
-                            transaction_tuples.push((transaction, source, destination));
-                        }
-                        Ok(Message::SetTransactions(transaction_tuples))
-                    })
-                    .map(MessageContainer),
-                )
+                Action::Task(self.load_page(finance_controller, utc_offset, page))
             }
             Message::Delete => {
                 if let Self::Loaded { account, .. } = self {
@@ -310,6 +366,62 @@ impl View {
                     Action::None
                 }
             }
+            Message::Merge(other) => {
+                if let Self::Loaded { account, .. } = self {
+                    if let rfd::MessageDialogResult::No = rfd::MessageDialog::new()
+                        .set_title("Merge Accounts?")
+                        .set_description(format!(
+                            "Do you really want to merge the account {} into {}? All transactions of {} are moved to {} and {} is deleted.",
+                            other.name,
+                            account.name(),
+                            other.name,
+                            account.name(),
+                            other.name
+                        ))
+                        .set_level(rfd::MessageLevel::Warning)
+                        .set_buttons(rfd::MessageButtons::YesNo)
+                        .show()
+                    {
+                        return Action::None;
+                    }
+
+                    let acc_id = *account.id();
+                    Action::Task(
+                        error::failing_task(async move {
+                            finance_controller.merge_accounts(acc_id, other.id).await?;
+                            Ok(Message::Merged)
+                        })
+                        .map(MessageContainer),
+                    )
+                } else {
+                    Action::None
+                }
+            }
+            Message::Merged => Action::Task(self.reload(finance_controller, utc_offset)),
+            Message::SplitLoanPayments => {
+                if let Self::Loaded { account, .. } = self {
+                    let acc_id = *account.id();
+                    Action::Task(
+                        error::failing_task(async move {
+                            let interest_transactions =
+                                finance_controller.split_loan_payments(acc_id).await?;
+                            Ok(Message::LoanPaymentsSplit(interest_transactions.len()))
+                        })
+                        .map(MessageContainer),
+                    )
+                } else {
+                    Action::None
+                }
+            }
+            Message::LoanPaymentsSplit(count) => {
+                rfd::MessageDialog::new()
+                    .set_title("Loan payments split")
+                    .set_description(format!("Split {count} loan payments."))
+                    .set_level(rfd::MessageLevel::Info)
+                    .set_buttons(rfd::MessageButtons::Ok)
+                    .show();
+                Action::Task(self.reload(finance_controller, utc_offset))
+            }
             Message::Deleted(result) => match &*result {
                 Ok(_) => {
                     if let Self::Loaded { account, .. } = self {
@@ -344,7 +456,7 @@ impl View {
                     transaction_table, ..
                 } = self
                 {
-                    return match transaction_table.update(msg, finance_controller) {
+                    return match transaction_table.update(msg, finance_controller.clone()) {
                         components::transaction_table::Action::None => Action::None,
                         components::transaction_table::Action::ViewTransaction(id) => {
                             Action::ViewTransaction(id)
@@ -355,10 +467,53 @@ impl View {
                         components::transaction_table::Action::Task(task) => {
                             Action::Task(task.map(Message::TransactionTable).map(MessageContainer))
                         }
+                        components::transaction_table::Action::LoadPage(page) => {
+                            Action::Task(self.load_page(finance_controller, utc_offset, page))
+                        }
                     };
                 }
                 Action::None
             }
+            Message::Securities(msg) => {
+                let action = if let Self::Loaded {
+                    securities: Some(securities),
+                    ..
+                } = self
+                {
+                    securities.update(msg, finance_controller.clone(), utc_offset)
+                } else {
+                    return Action::None;
+                };
+                match action {
+                    securities::Action::None => Action::None,
+                    securities::Action::Changed => {
+                        Action::Task(self.reload(finance_controller, utc_offset))
+                    }
+                    securities::Action::Task(task) => {
+                        Action::Task(task.map(Message::Securities).map(MessageContainer))
+                    }
+                }
+            }
+            Message::Valuations(msg) => {
+                let action = if let Self::Loaded {
+                    valuations: Some(valuations),
+                    ..
+                } = self
+                {
+                    valuations.update(msg, finance_controller.clone(), utc_offset)
+                } else {
+                    return Action::None;
+                };
+                match action {
+                    valuations::Action::None => Action::None,
+                    valuations::Action::Changed => {
+                        Action::Task(self.reload(finance_controller, utc_offset))
+                    }
+                    valuations::Action::Task(task) => {
+                        Action::Task(task.map(Message::Valuations).map(MessageContainer))
+                    }
+                }
+            }
         }
     }
 
@@ -368,18 +523,27 @@ impl View {
             transaction_table,
             current_value,
             timespan_input,
+            merge_state,
+            securities,
+            valuations,
         } = self
         {
             match account {
-                fm_core::account::Account::AssetAccount(acc) => {
-                    asset_account_view(acc, transaction_table, current_value, timespan_input)
-                        .map(MessageContainer)
-                }
+                fm_core::account::Account::AssetAccount(acc) => asset_account_view(
+                    acc,
+                    transaction_table,
+                    current_value,
+                    timespan_input,
+                    securities.as_ref(),
+                    valuations.as_ref(),
+                )
+                .map(MessageContainer),
                 fm_core::account::Account::BookCheckingAccount(acc) => book_checking_account_view(
                     acc,
                     transaction_table,
                     current_value,
                     timespan_input,
+                    merge_state,
                 )
                 .map(MessageContainer),
             }
@@ -394,11 +558,14 @@ fn asset_account_view<'a>(
     transaction_table: &'a components::TransactionTable,
     current_value: &fm_core::Currency,
     timespan_input: &'a date_span_input::State,
+    securities: Option<&'a securities::Securities>,
+    valuations: Option<&'a valuations::Valuations>,
 ) -> iced::Element<'a, Message> {
     components::spaced_column![
         widget::row![
             widget::column![
                 widget::text!("Account: {}", account.name),
+                widget::text!("Type: {}", account.asset_type),
                 widget::text!("Notes: {}", account.note.clone().unwrap_or_default()),
                 widget::text!(
                     "IBAN: {}",
@@ -415,6 +582,16 @@ fn asset_account_view<'a>(
                         .map(|x| x.to_string())
                         .unwrap_or_default()
                 ),
+                widget::text!(
+                    "Additional IDs: {}",
+                    account
+                        .additional_ids
+                        .iter()
+                        .map(|x| x.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                widget::text!("Aliases: {}", account.aliases.join(", ")),
                 widget::text!("Offset: {}", account.offset),
                 widget::row![
                     "Current Amount: ",
@@ -427,6 +604,12 @@ fn asset_account_view<'a>(
                 components::button::delete(Some(Message::Delete))
             ]
         ],
+        securities.map_or(widget::Space::new(0, 0).into(), |x| x
+            .view()
+            .map(Message::Securities)),
+        valuations.map_or(widget::Space::new(0, 0).into(), |x| x
+            .view(account.depreciation.as_ref())
+            .map(Message::Valuations)),
         LabeledFrame::new(
             "Transactions",
             components::spaced_column![
@@ -446,11 +629,18 @@ fn book_checking_account_view<'a>(
     transaction_table: &'a components::TransactionTable,
     current_value: &fm_core::Currency,
     timespan_input: &'a date_span_input::State,
+    merge_state: &'a widget::combo_box::State<fm_core::account::BookCheckingAccount>,
 ) -> iced::Element<'a, Message> {
     components::spaced_column![
         widget::row![
             widget::column![
                 widget::text!("Account: {}", account.name),
+                widget::text!(
+                    "Kind: {}",
+                    account
+                        .kind
+                        .map_or("Not classified".to_string(), |kind| kind.to_string())
+                ),
                 widget::text!("Notes: {}", account.note.clone().unwrap_or_default()),
                 widget::text!(
                     "IBAN: {}",
@@ -467,10 +657,24 @@ fn book_checking_account_view<'a>(
                         .map(|x| x.to_string())
                         .unwrap_or_default()
                 ),
+                widget::text!(
+                    "Additional IDs: {}",
+                    account
+                        .additional_ids
+                        .iter()
+                        .map(|x| x.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                widget::text!("Aliases: {}", account.aliases.join(", ")),
                 widget::row![
                     "Current Amount: ",
                     components::colored_currency_display(current_value)
                 ],
+                components::spal_row![
+                    "Merge into this account: ",
+                    widget::ComboBox::new(merge_state, "Account", None, Message::Merge).width(300)
+                ],
             ],
             widget::Space::with_width(iced::Length::Fill),
             components::spaced_column![
@@ -478,6 +682,7 @@ fn book_checking_account_view<'a>(
                 components::button::delete(Some(Message::Delete))
             ]
         ],
+        loan_view(account),
         LabeledFrame::new(
             "Transactions",
             components::spaced_column![
@@ -492,3 +697,140 @@ fn book_checking_account_view<'a>(
     .height(iced::Fill)
     .into()
 }
+
+fn loan_view(account: &fm_core::account::BookCheckingAccount) -> iced::Element<'_, Message> {
+    let loan = if let Some(loan) = &account.loan {
+        loan
+    } else {
+        return widget::Space::new(0, 0).into();
+    };
+    let mut schedule = widget::column![components::spaced_row![
+        widget::text("Date").width(150),
+        widget::text("Payment").width(150),
+        widget::text("Interest").width(150),
+        widget::text("Principal").width(150),
+        widget::text("Balance").width(150),
+    ]];
+    for entry in loan.amortization_schedule() {
+        schedule = schedule.push(components::spaced_row![
+            widget::text(components::date_time::to_date_string(entry.date.date())).width(150),
+            widget::text(entry.payment.to_string()).width(150),
+            widget::text(entry.interest.to_string()).width(150),
+            widget::text(entry.principal.to_string()).width(150),
+            widget::text(entry.balance.to_string()).width(150),
+        ]);
+    }
+    LabeledFrame::new(
+        "Loan",
+        components::spaced_column![
+            widget::row![
+                widget::column![
+                    widget::text!("Principal: {}", loan.principal),
+                    widget::text!("Interest Rate: {} % p.a.", loan.interest_rate),
+                    widget::text!("Term: {} months", loan.term_months),
+                    widget::text!("Monthly Payment: {}", loan.monthly_payment()),
+                ],
+                widget::Space::with_width(iced::Fill),
+                widget::button("Split loan payments")
+                    .on_press_maybe(loan.interest_account.map(|_| Message::SplitLoanPayments)),
+            ],
+            widget::scrollable(schedule).height(200),
+        ],
+    )
+    .width(iced::Fill)
+    .into()
+}
+
+async fn securities_of(
+    finance_controller: &fm_core::FMController<impl fm_core::FinanceManager>,
+    account: &fm_core::account::Account,
+) -> anyhow::Result<
+    Option<(
+        Vec<fm_core::securities::Trade>,
+        fm_core::securities::Performance,
+    )>,
+> {
+    match account {
+        fm_core::account::Account::AssetAccount(acc)
+            if acc.asset_type == fm_core::account::AssetType::Securities =>
+        {
+            Ok(Some((
+                finance_controller.get_trades(acc.id).await?,
+                finance_controller
+                    .get_securities_performance(acc.id, time::OffsetDateTime::now_utc())
+                    .await?,
+            )))
+        }
+        _ => Ok(None),
+    }
+}
+
+async fn valuations_of(
+    finance_controller: &fm_core::FMController<impl fm_core::FinanceManager>,
+    account: &fm_core::account::Account,
+) -> anyhow::Result<Option<Vec<fm_core::valuation::Valuation>>> {
+    match account {
+        fm_core::account::Account::AssetAccount(acc)
+            if acc.asset_type == fm_core::account::AssetType::Valuation =>
+        {
+            Ok(Some(finance_controller.get_valuations(acc.id).await?))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn of_account(
+    account_id: fm_core::Id,
+    timespan: fm_core::Timespan,
+) -> fm_core::filter_expression::FilterExpression {
+    fm_core::filter_expression::FilterExpression::from(
+        fm_core::filter_expression::Condition::Account(Some(account_id)),
+    ) & fm_core::filter_expression::Condition::Timespan(timespan).into()
+}
+
+/// Pairs the transactions with their source and destination accounts.
+fn with_accounts(
+    transactions: fm_core::pagination::Page<fm_core::Transaction>,
+    accounts: &std::collections::HashMap<fm_core::Id, fm_core::account::Account>,
+) -> anyhow::Result<TransactionPage> {
+    let mut items = Vec::with_capacity(transactions.items.len());
+    for transaction in transactions.items {
+        let source = accounts
+            .get(&transaction.source)
+            .context(format!("Could not find account {}", transaction.source))?
+            .clone();
+        let destination = accounts
+            .get(&transaction.destination)
+            .context(format!(
+                "Could not find account {}",
+                transaction.destination
+            ))?
+            .clone();
+        items.push((transaction, source, destination));
+    }
+    Ok(fm_core::pagination::Page {
+        items,
+        total: transactions.total,
+    })
+}
+
+fn book_checking_accounts(
+    accounts: std::collections::HashMap<fm_core::Id, fm_core::account::Account>,
+) -> Vec<fm_core::account::BookCheckingAccount> {
+    accounts
+        .into_values()
+        .filter_map(|account| match account {
+            fm_core::account::Account::BookCheckingAccount(acc) => Some(acc),
+            _ => None,
+        })
+        .collect()
+}
+
+fn merge_state_of(
+    mut accounts: Vec<fm_core::account::BookCheckingAccount>,
+    account_id: fm_core::Id,
+) -> widget::combo_box::State<fm_core::account::BookCheckingAccount> {
+    accounts.retain(|acc| acc.id != account_id);
+    accounts.sort_by(|a, b| a.name.cmp(&b.name));
+    widget::combo_box::State::new(accounts)
+}
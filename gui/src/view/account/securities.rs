@@ -0,0 +1,279 @@
+use components::ValidationTextInput;
+use components::date_time::date_input;
+use iced::widget;
+use iced_aw::widget::LabeledFrame;
+
+pub enum Action {
+    None,
+    Changed,
+    Task(iced::Task<Message>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    IsinInput(String),
+    KindSelected(fm_core::securities::TradeKind),
+    QuantityInput(String),
+    AmountInput(components::currency_input::Action),
+    DateInput(date_input::Action),
+    AddTrade,
+    DeleteTrade(fm_core::Id),
+    #[cfg(feature = "native")]
+    ImportPrices,
+    #[cfg(feature = "native")]
+    PricesImported(usize),
+    Changed,
+}
+
+#[derive(Debug)]
+pub struct Securities {
+    account: fm_core::Id,
+    trades: Vec<fm_core::securities::Trade>,
+    performance: fm_core::securities::Performance,
+    isin_input: ValidationTextInput,
+    kind: fm_core::securities::TradeKind,
+    quantity_input: ValidationTextInput,
+    amount_input: components::CurrencyInput,
+    date_input: date_input::State,
+}
+
+impl Securities {
+    pub fn new(
+        account: fm_core::Id,
+        trades: Vec<fm_core::securities::Trade>,
+        performance: fm_core::securities::Performance,
+    ) -> Self {
+        Self {
+            account,
+            trades,
+            performance,
+            isin_input: ValidationTextInput::default()
+                .required(true)
+                .validation(|x| {
+                    x.parse::<fm_core::securities::Isin>()
+                        .err()
+                        .map(|_| "invalid ISIN".to_string())
+                }),
+            kind: fm_core::securities::TradeKind::Buy,
+            quantity_input: ValidationTextInput::default()
+                .required(true)
+                .validation(|x| {
+                    x.replace(',', ".")
+                        .parse::<fm_core::bigdecimal::BigDecimal>()
+                        .err()
+                        .map(|_| "invalid number".to_string())
+                }),
+            amount_input: components::CurrencyInput::default(),
+            date_input: date_input::State::default(),
+        }
+    }
+
+    pub fn reload(
+        &mut self,
+        trades: Vec<fm_core::securities::Trade>,
+        performance: fm_core::securities::Performance,
+    ) {
+        self.trades = trades;
+        self.performance = performance;
+    }
+
+    pub fn update(
+        &mut self,
+        message: Message,
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+        utc_offset: time::UtcOffset,
+    ) -> Action {
+        match message {
+            Message::IsinInput(input) => self.isin_input.edit_content(input),
+            Message::KindSelected(kind) => {
+                self.kind = kind;
+                if kind == fm_core::securities::TradeKind::Dividend {
+                    self.quantity_input.set_content("0".to_string());
+                }
+            }
+            Message::QuantityInput(input) => self.quantity_input.edit_content(input),
+            Message::AmountInput(action) => self.amount_input.perform(action),
+            Message::DateInput(action) => self.date_input.perform(action),
+            Message::AddTrade => {
+                if !self.submittable() {
+                    return Action::None;
+                }
+                let account = self.account;
+                let isin = self.isin_input.value().parse().unwrap();
+                let kind = self.kind;
+                let quantity = self
+                    .quantity_input
+                    .value()
+                    .replace(',', ".")
+                    .parse()
+                    .unwrap();
+                let amount = self.amount_input.currency().unwrap();
+                let date = components::date_time::primitive_to_offset(
+                    time::PrimitiveDateTime::new(
+                        self.date_input.date().unwrap(),
+                        time::Time::MIDNIGHT,
+                    ),
+                    utc_offset,
+                );
+                self.isin_input.set_content(String::new());
+                self.quantity_input.set_content(String::new());
+                self.amount_input.clear();
+                return Action::Task(error::failing_task(async move {
+                    finance_controller
+                        .create_trade(account, isin, kind, quantity, amount, date)
+                        .await?;
+                    Ok(Message::Changed)
+                }));
+            }
+            Message::DeleteTrade(id) => {
+                if let rfd::MessageDialogResult::No = rfd::MessageDialog::new()
+                    .set_title("Delete Trade?")
+                    .set_description("Do you really want to delete the trade?")
+                    .set_level(rfd::MessageLevel::Warning)
+                    .set_buttons(rfd::MessageButtons::YesNo)
+                    .show()
+                {
+                    return Action::None;
+                }
+                return Action::Task(error::failing_task(async move {
+                    finance_controller.delete_trade(id).await?;
+                    Ok(Message::Changed)
+                }));
+            }
+            #[cfg(feature = "native")]
+            Message::ImportPrices => {
+                return Action::Task(error::failing_task(async move {
+                    if let Some(file) = rfd::AsyncFileDialog::new()
+                        .set_title("Select Price CSV File")
+                        .add_filter("CSV", &["csv"])
+                        .pick_file()
+                        .await
+                    {
+                        let count = fm_importer::price_csv::import_prices(
+                            std::fs::File::open(file.path())?,
+                            finance_controller,
+                        )
+                        .await?;
+                        Ok(Message::PricesImported(count))
+                    } else {
+                        Ok(Message::PricesImported(0))
+                    }
+                }));
+            }
+            #[cfg(feature = "native")]
+            Message::PricesImported(count) => {
+                if count == 0 {
+                    return Action::None;
+                }
+                rfd::MessageDialog::new()
+                    .set_title("Prices imported")
+                    .set_description(format!("Imported {count} prices."))
+                    .set_level(rfd::MessageLevel::Info)
+                    .set_buttons(rfd::MessageButtons::Ok)
+                    .show();
+                return Action::Changed;
+            }
+            Message::Changed => return Action::Changed,
+        }
+        Action::None
+    }
+
+    pub fn view(&self) -> iced::Element<'_, Message> {
+        let mut positions = widget::column![components::spaced_row![
+            widget::text("ISIN").width(150),
+            widget::text("Quantity").width(100),
+            widget::text("Cost Basis").width(150),
+            widget::text("Market Value").width(150),
+            widget::text("Gain").width(150),
+        ]];
+        for position in &self.performance.positions {
+            positions = positions.push(components::spaced_row![
+                widget::text(position.isin.to_string()).width(150),
+                widget::text(position.quantity.to_string()).width(100),
+                widget::text(position.cost_basis.to_string()).width(150),
+                widget::text(position.market_value.to_string()).width(150),
+                widget::container(components::colored_currency_display(
+                    &(position.market_value.clone() - position.cost_basis.clone())
+                ))
+                .width(150),
+            ]);
+        }
+
+        let mut trades = widget::column![components::spaced_row![
+            widget::text("Date").width(100),
+            widget::text("Kind").width(100),
+            widget::text("ISIN").width(150),
+            widget::text("Quantity").width(100),
+            widget::text("Amount").width(150),
+        ]];
+        for trade in &self.trades {
+            trades = trades.push(components::spaced_row![
+                widget::text(components::date_time::to_date_string(trade.date.date())).width(100),
+                widget::text(trade.kind.to_string()).width(100),
+                widget::text(trade.isin.to_string()).width(150),
+                widget::text(trade.quantity.to_string()).width(100),
+                widget::text(trade.amount.to_string()).width(150),
+                components::button::delete(Some(Message::DeleteTrade(trade.id))),
+            ]);
+        }
+
+        #[cfg(feature = "native")]
+        let import_button = widget::button("Import prices").on_press(Message::ImportPrices);
+        #[cfg(not(feature = "native"))]
+        let import_button = widget::Space::new(0, 0);
+
+        LabeledFrame::new(
+            "Securities",
+            components::spaced_column![
+                widget::row![
+                    widget::column![
+                        widget::text!("Market Value: {}", self.performance.market_value),
+                        widget::text!("Cost Basis: {}", self.performance.cost_basis),
+                        widget::row![
+                            "Unrealized Gain: ",
+                            components::colored_currency_display(&self.performance.unrealized_gain)
+                        ],
+                        widget::row![
+                            "Realized Gain: ",
+                            components::colored_currency_display(&self.performance.realized_gain)
+                        ],
+                        widget::text!("Dividends: {}", self.performance.dividends),
+                    ],
+                    widget::Space::with_width(iced::Fill),
+                    import_button,
+                ],
+                widget::scrollable(positions).height(150),
+                widget::scrollable(trades).height(150),
+                components::spaced_row![
+                    widget::container(self.isin_input.view("ISIN", Some(Message::IsinInput)))
+                        .width(200),
+                    widget::pick_list(
+                        fm_core::securities::TradeKind::ALL,
+                        Some(self.kind),
+                        Message::KindSelected
+                    ),
+                    widget::container(
+                        self.quantity_input
+                            .view("Quantity", Some(Message::QuantityInput))
+                    )
+                    .width(150),
+                    self.amount_input.view().map(Message::AmountInput),
+                    date_input::date_input(&self.date_input, "", true)
+                        .view()
+                        .map(Message::DateInput),
+                    widget::button("Add Trade")
+                        .on_press_maybe(self.submittable().then_some(Message::AddTrade)),
+                ],
+            ],
+        )
+        .width(iced::Fill)
+        .into()
+    }
+
+    fn submittable(&self) -> bool {
+        self.isin_input.is_valid()
+            && self.quantity_input.is_valid()
+            && self.amount_input.currency().is_some()
+            && self.date_input.date().is_some()
+    }
+}
@@ -0,0 +1,140 @@
+use components::date_time::date_input;
+use iced::widget;
+use iced_aw::widget::LabeledFrame;
+
+pub enum Action {
+    None,
+    Changed,
+    Task(iced::Task<Message>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ValueInput(components::currency_input::Action),
+    DateInput(date_input::Action),
+    AddValuation,
+    DeleteValuation(fm_core::Id),
+    Changed,
+}
+
+#[derive(Debug)]
+pub struct Valuations {
+    account: fm_core::Id,
+    valuations: Vec<fm_core::valuation::Valuation>,
+    value_input: components::CurrencyInput,
+    date_input: date_input::State,
+}
+
+impl Valuations {
+    pub fn new(account: fm_core::Id, valuations: Vec<fm_core::valuation::Valuation>) -> Self {
+        Self {
+            account,
+            valuations,
+            value_input: components::CurrencyInput::default(),
+            date_input: date_input::State::default(),
+        }
+    }
+
+    pub fn reload(&mut self, valuations: Vec<fm_core::valuation::Valuation>) {
+        self.valuations = valuations;
+    }
+
+    pub fn update(
+        &mut self,
+        message: Message,
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+        utc_offset: time::UtcOffset,
+    ) -> Action {
+        match message {
+            Message::ValueInput(action) => self.value_input.perform(action),
+            Message::DateInput(action) => self.date_input.perform(action),
+            Message::AddValuation => {
+                if !self.submittable() {
+                    return Action::None;
+                }
+                let account = self.account;
+                let value = self.value_input.currency().unwrap();
+                let date = components::date_time::primitive_to_offset(
+                    time::PrimitiveDateTime::new(
+                        self.date_input.date().unwrap(),
+                        time::Time::MIDNIGHT,
+                    ),
+                    utc_offset,
+                );
+                self.value_input.clear();
+                return Action::Task(error::failing_task(async move {
+                    finance_controller
+                        .create_valuation(account, value, date)
+                        .await?;
+                    Ok(Message::Changed)
+                }));
+            }
+            Message::DeleteValuation(id) => {
+                if let rfd::MessageDialogResult::No = rfd::MessageDialog::new()
+                    .set_title("Delete Valuation?")
+                    .set_description("Do you really want to delete the valuation?")
+                    .set_level(rfd::MessageLevel::Warning)
+                    .set_buttons(rfd::MessageButtons::YesNo)
+                    .show()
+                {
+                    return Action::None;
+                }
+                return Action::Task(error::failing_task(async move {
+                    finance_controller.delete_valuation(id).await?;
+                    Ok(Message::Changed)
+                }));
+            }
+            Message::Changed => return Action::Changed,
+        }
+        Action::None
+    }
+
+    pub fn view<'a>(
+        &'a self,
+        depreciation: Option<&fm_core::valuation::Depreciation>,
+    ) -> iced::Element<'a, Message> {
+        let mut valuations = widget::column![components::spaced_row![
+            widget::text("Date").width(100),
+            widget::text("Value").width(150),
+        ]];
+        for valuation in self.valuations.iter().rev() {
+            valuations = valuations.push(components::spaced_row![
+                widget::text(components::date_time::to_date_string(valuation.date.date()))
+                    .width(100),
+                widget::text(valuation.value.to_string()).width(150),
+                components::button::delete(Some(Message::DeleteValuation(valuation.id))),
+            ]);
+        }
+
+        LabeledFrame::new(
+            "Valuations",
+            components::spaced_column![
+                widget::text!(
+                    "Depreciation: {}",
+                    match depreciation {
+                        Some(fm_core::valuation::Depreciation::Linear(amount)) =>
+                            format!("{amount} per year"),
+                        Some(fm_core::valuation::Depreciation::Percentage(rate)) =>
+                            format!("{rate} % per year"),
+                        None => "None".to_string(),
+                    }
+                ),
+                widget::scrollable(valuations).height(150),
+                components::spaced_row![
+                    self.value_input.view().map(Message::ValueInput),
+                    date_input::date_input(&self.date_input, "", true)
+                        .view()
+                        .map(Message::DateInput),
+                    widget::button("Add Valuation")
+                        .on_press_maybe(self.submittable().then_some(Message::AddValuation)),
+                ],
+            ],
+        )
+        .width(iced::Fill)
+        .into()
+    }
+
+    fn submittable(&self) -> bool {
+        self.value_input.currency().is_some() && self.date_input.date().is_some()
+    }
+}
@@ -9,6 +9,7 @@ pub enum Action {
 }
 
 #[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum Message {
     CreateAssetAccount,
     AccountView(fm_core::account::AssetAccount),
@@ -122,7 +123,11 @@ async fn init_future(
     let mut tuples = Vec::new();
     for account in accounts {
         let amount = finance_controller
-            .get_account_sum(&account.clone().into(), time::OffsetDateTime::now_utc())
+            .get_account_sum(
+                &account.clone().into(),
+                time::OffsetDateTime::now_utc(),
+                fm_core::DateKind::Booking,
+            )
             .await?;
         tuples.push((account, amount));
     }
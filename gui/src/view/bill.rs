@@ -191,7 +191,8 @@ impl View {
                 } = self
                 {
                     match transaction_table.perform(inner) {
-                        components::table_view::Action::None => {}
+                        components::table_view::Action::None
+                        | components::table_view::Action::LoadPage => {}
                         components::table_view::Action::OuterMessage(m) => {
                             return self.update(MessageContainer(m), finance_controller);
                         }
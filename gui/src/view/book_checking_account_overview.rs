@@ -1,4 +1,5 @@
 use anyhow::Result;
+use iced::widget;
 
 pub enum Action {
     None,
@@ -30,10 +31,11 @@ impl View {
             accounts_table: components::table_view::State::new(accounts, ())
                 .sort_by(|a, b, column| match column {
                     0 => b.0.name.cmp(&a.0.name),
-                    1 => a.1.cmp(&b.1),
+                    1 => kind_name(&b.0).cmp(&kind_name(&a.0)),
+                    2 => a.1.cmp(&b.1),
                     _ => std::cmp::Ordering::Equal,
                 })
-                .sortable_columns([0, 1]),
+                .sortable_columns([0, 1, 2]),
         }
     }
 
@@ -84,12 +86,13 @@ impl View {
     pub fn view(&self) -> iced::Element<'_, Message> {
         components::overlap_bottom_right(
             components::table_view::table_view(&self.accounts_table)
-                .headers(["Account".to_string(), "Sum".to_string()])
+                .headers(["Account".to_string(), "Kind".to_string(), "Sum".to_string()])
                 .view(|(account, sum), _| {
                     [
                         components::link(account.name.as_str())
                             .on_press(Message::ViewAccount(account.id))
                             .into(),
+                        widget::text(kind_name(account)).into(),
                         components::colored_currency_display(sum),
                     ]
                 })
@@ -102,6 +105,13 @@ impl View {
     }
 }
 
+fn kind_name(account: &fm_core::account::BookCheckingAccount) -> String {
+    account
+        .kind
+        .map(|kind| kind.to_string())
+        .unwrap_or_default()
+}
+
 async fn fetch_future(
     finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
 ) -> Result<Vec<(fm_core::account::BookCheckingAccount, fm_core::Currency)>> {
@@ -117,7 +127,11 @@ async fn fetch_future(
     let mut accounts_with_sums = Vec::new();
     for account in accounts {
         let sum = finance_controller
-            .get_account_sum(&account.clone().into(), time::OffsetDateTime::now_utc())
+            .get_account_sum(
+                &account.clone().into(),
+                time::OffsetDateTime::now_utc(),
+                fm_core::DateKind::Booking,
+            )
             .await?;
         accounts_with_sums.push((account, sum));
     }
@@ -244,7 +244,8 @@ impl View {
                 } = self
                 {
                     match transaction_table.update(*msg, finance_controller) {
-                        components::transaction_table::Action::None => Action::None,
+                        components::transaction_table::Action::None
+                        | components::transaction_table::Action::LoadPage(_) => Action::None,
                         components::transaction_table::Action::ViewTransaction(id) => {
                             Action::ViewTransaction(id)
                         }
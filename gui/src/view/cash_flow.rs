@@ -0,0 +1,213 @@
+use components::date_time::date_span_input;
+use iced::widget;
+
+pub enum Action {
+    None,
+    Task(iced::Task<Message>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ChangedTimespan(date_span_input::Action),
+    IntervalSelected(fm_core::Interval),
+    BreakdownSelected(fm_core::reporting::Breakdown),
+    Set(Vec<Row>),
+    Table(components::table_view::InnerMessage<Message>),
+}
+
+/// A period or, indented below it, a group of its breakdown.
+#[derive(Debug, Clone)]
+pub struct Row {
+    label: String,
+    group: bool,
+    income: fm_core::Currency,
+    expenses: fm_core::Currency,
+    net: fm_core::Currency,
+}
+
+#[derive(Debug)]
+pub struct View {
+    loaded: bool,
+    table: components::table_view::State<Row, ()>,
+    timespan_input: date_span_input::State,
+    interval: fm_core::Interval,
+    breakdown: fm_core::reporting::Breakdown,
+}
+
+impl View {
+    pub fn fetch(
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+    ) -> (Self, iced::Task<Message>) {
+        let interval = fm_core::Interval::Month;
+        let breakdown = fm_core::reporting::Breakdown::None;
+        (
+            Self {
+                loaded: false,
+                table: components::table_view::State::new(Vec::new(), ()),
+                timespan_input: date_span_input::State::default(),
+                interval,
+                breakdown,
+            },
+            Self::load(finance_controller, (None, None), interval, breakdown),
+        )
+    }
+
+    pub fn reload(
+        &mut self,
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+        utc_offset: time::UtcOffset,
+    ) -> iced::Task<Message> {
+        Self::load(
+            finance_controller,
+            components::date_time::date_span_to_time_span(
+                self.timespan_input.timespan(),
+                utc_offset,
+            ),
+            self.interval,
+            self.breakdown,
+        )
+    }
+
+    pub fn update(
+        &mut self,
+        message: Message,
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+        utc_offset: time::UtcOffset,
+    ) -> Action {
+        match message {
+            Message::ChangedTimespan(action) => {
+                self.timespan_input.perform(action);
+                Action::Task(self.reload(finance_controller, utc_offset))
+            }
+            Message::IntervalSelected(interval) => {
+                self.interval = interval;
+                Action::Task(self.reload(finance_controller, utc_offset))
+            }
+            Message::BreakdownSelected(breakdown) => {
+                self.breakdown = breakdown;
+                Action::Task(self.reload(finance_controller, utc_offset))
+            }
+            Message::Set(rows) => {
+                self.loaded = true;
+                self.table.set_items(rows);
+                Action::None
+            }
+            Message::Table(inner) => match self.table.perform(inner) {
+                components::table_view::Action::OuterMessage(m) => {
+                    self.update(m, finance_controller, utc_offset)
+                }
+                components::table_view::Action::Task(task) => {
+                    Action::Task(task.map(Message::Table))
+                }
+                components::table_view::Action::None | components::table_view::Action::LoadPage => {
+                    Action::None
+                }
+            },
+        }
+    }
+
+    pub fn view(&self) -> iced::Element<'_, Message> {
+        if !self.loaded {
+            return widget::text("Loading...").into();
+        }
+        components::spaced_column![
+            components::heading("Cash Flow", components::HeadingLevel::H1),
+            components::spal_row![
+                date_span_input::date_span_input(&self.timespan_input)
+                    .view()
+                    .map(Message::ChangedTimespan),
+                widget::pick_list(
+                    fm_core::Interval::ALL,
+                    Some(self.interval),
+                    Message::IntervalSelected
+                ),
+                "Breakdown",
+                widget::pick_list(
+                    fm_core::reporting::Breakdown::ALL,
+                    Some(self.breakdown),
+                    Message::BreakdownSelected
+                ),
+            ],
+            components::table_view::table_view(&self.table)
+                .headers([
+                    "Period".to_string(),
+                    "Income".to_string(),
+                    "Expenses".to_string(),
+                    "Net".to_string(),
+                ])
+                .view(|row, _| {
+                    [
+                        if row.group {
+                            widget::text!("    {}", row.label).into()
+                        } else {
+                            widget::text(row.label.as_str())
+                                .font(iced::Font {
+                                    weight: iced::font::Weight::Bold,
+                                    ..Default::default()
+                                })
+                                .into()
+                        },
+                        widget::text!("{}", row.income).into(),
+                        widget::text!("{}", row.expenses).into(),
+                        components::colored_currency_display(&row.net),
+                    ]
+                })
+                .map(Message::Table),
+        ]
+        .height(iced::Fill)
+        .width(iced::Fill)
+        .into()
+    }
+
+    fn load(
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+        timespan: fm_core::Timespan,
+        interval: fm_core::Interval,
+        breakdown: fm_core::reporting::Breakdown,
+    ) -> iced::Task<Message> {
+        error::failing_task(async move {
+            let periods = finance_controller
+                .cash_flow(timespan, interval, breakdown)
+                .await?;
+            let names = match breakdown {
+                fm_core::reporting::Breakdown::None => Default::default(),
+                fm_core::reporting::Breakdown::Category => finance_controller
+                    .get_categories()
+                    .await?
+                    .into_iter()
+                    .map(|category| (category.id, category.name))
+                    .collect(),
+                fm_core::reporting::Breakdown::Counterparty => finance_controller
+                    .get_accounts()
+                    .await?
+                    .into_iter()
+                    .map(|account| (*account.id(), account.name().to_string()))
+                    .collect::<std::collections::HashMap<_, _>>(),
+            };
+
+            let mut rows = Vec::new();
+            for period in periods {
+                rows.push(Row {
+                    label: components::date_time::to_period_string(period.start.date(), interval),
+                    group: false,
+                    income: period.income.clone(),
+                    expenses: period.expenses.clone(),
+                    net: period.net(),
+                });
+                for group in &period.groups {
+                    rows.push(Row {
+                        label: match group.id {
+                            Some(id) => names.get(&id).cloned().unwrap_or_default(),
+                            None => "No category".to_string(),
+                        },
+                        group: true,
+                        income: group.income.clone(),
+                        expenses: group.expenses.clone(),
+                        net: group.net(),
+                    });
+                }
+            }
+            Ok(Message::Set(rows))
+        })
+    }
+}
@@ -0,0 +1,212 @@
+use fm_core::transaction_filter::TransactionFilter;
+use iced::widget;
+
+const CATEGORY_WIDTH: f32 = 200.0;
+const VALUE_WIDTH: f32 = 120.0;
+
+pub enum Action {
+    None,
+    Task(iced::Task<Message>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    IntervalSelected(fm_core::Interval),
+    ShowChanges(bool),
+    Set(fm_core::reporting::CategoryPivot, Vec<fm_core::Category>),
+    #[cfg(feature = "native")]
+    ExportCsv,
+}
+
+/// A table of the category values of filtered transactions per period.
+#[derive(Debug)]
+pub struct View {
+    filter: TransactionFilter,
+    interval: fm_core::Interval,
+    show_changes: bool,
+    pivot: Option<fm_core::reporting::CategoryPivot>,
+    categories: Vec<fm_core::Category>,
+}
+
+impl View {
+    pub fn fetch(
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+        filter: TransactionFilter,
+    ) -> (Self, iced::Task<Message>) {
+        let mut view = Self {
+            filter,
+            interval: fm_core::Interval::Month,
+            show_changes: false,
+            pivot: None,
+            categories: Vec::new(),
+        };
+        let task = view.reload(finance_controller);
+        (view, task)
+    }
+
+    pub fn reload(
+        &mut self,
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+    ) -> iced::Task<Message> {
+        let filter = self.filter.clone();
+        let interval = self.interval;
+        error::failing_task(async move {
+            let pivot = finance_controller.category_pivot(filter, interval).await?;
+            let categories = finance_controller.get_categories().await?;
+            Ok(Message::Set(pivot, categories))
+        })
+    }
+
+    pub fn update(
+        &mut self,
+        message: Message,
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+    ) -> Action {
+        match message {
+            Message::IntervalSelected(interval) => {
+                self.interval = interval;
+                return Action::Task(self.reload(finance_controller));
+            }
+            Message::ShowChanges(show_changes) => self.show_changes = show_changes,
+            Message::Set(pivot, categories) => {
+                self.pivot = Some(pivot);
+                self.categories = categories;
+            }
+            #[cfg(feature = "native")]
+            Message::ExportCsv => {
+                if let Some(pivot) = &self.pivot {
+                    let csv = pivot.to_csv(&self.categories);
+                    return Action::Task(
+                        error::failing_task(async move {
+                            if let Some(file) = rfd::AsyncFileDialog::new()
+                                .set_title("Export Category Pivot")
+                                .add_filter("CSV", &["csv"])
+                                .set_file_name("category_pivot.csv")
+                                .save_file()
+                                .await
+                            {
+                                file.write(csv.as_bytes()).await?;
+                            }
+                            Ok(())
+                        })
+                        .discard(),
+                    );
+                }
+            }
+        }
+        Action::None
+    }
+
+    pub fn view(&self) -> iced::Element<'_, Message> {
+        let Some(pivot) = &self.pivot else {
+            return widget::text("Loading...").into();
+        };
+
+        #[cfg(feature = "native")]
+        let export_button = widget::button("Export CSV").on_press(Message::ExportCsv);
+        #[cfg(not(feature = "native"))]
+        let export_button = widget::Space::new(0, 0);
+
+        let mut header = components::spaced_row![widget::text("Category").width(CATEGORY_WIDTH)];
+        for (start, _) in &pivot.periods {
+            header = header.push(
+                widget::text(components::date_time::to_period_string(
+                    start.date(),
+                    self.interval,
+                ))
+                .width(VALUE_WIDTH),
+            );
+        }
+        header = header
+            .push(widget::text("Total").width(VALUE_WIDTH))
+            .push(widget::text("Average").width(VALUE_WIDTH));
+
+        let mut table =
+            widget::column![header, widget::horizontal_rule(2)].spacing(style::COLUMN_SPACING);
+        for row in &pivot.rows {
+            let name = match row.category {
+                Some(id) => self
+                    .categories
+                    .iter()
+                    .find(|category| category.id == id)
+                    .map_or(String::new(), |category| category.name.clone()),
+                None => "No category".to_string(),
+            };
+            table = table.push(self.table_row(
+                widget::text(name).into(),
+                &row.values,
+                |period| row.change(period),
+                row.total(),
+                row.average(),
+            ));
+        }
+        let totals = pivot.column_totals();
+        table = table.push(widget::horizontal_rule(2)).push(
+            self.table_row(
+                widget::text("Total")
+                    .font(iced::Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    })
+                    .into(),
+                &totals,
+                |period| pivot.change(period),
+                pivot.total(),
+                pivot.average(),
+            ),
+        );
+
+        components::spaced_column![
+            components::heading("Category Pivot", components::HeadingLevel::H1),
+            components::spal_row![
+                widget::pick_list(
+                    fm_core::Interval::ALL,
+                    Some(self.interval),
+                    Message::IntervalSelected
+                ),
+                widget::checkbox("Change to previous period", self.show_changes)
+                    .on_toggle(Message::ShowChanges),
+                widget::horizontal_space(),
+                export_button,
+            ],
+            widget::scrollable(table)
+                .direction(widget::scrollable::Direction::Both {
+                    vertical: Default::default(),
+                    horizontal: Default::default(),
+                })
+                .height(iced::Fill)
+                .width(iced::Fill),
+        ]
+        .height(iced::Fill)
+        .width(iced::Fill)
+        .into()
+    }
+
+    /// A row with the value or the change to the previous period of every period.
+    fn table_row<'a>(
+        &self,
+        name: iced::Element<'a, Message>,
+        values: &[fm_core::Currency],
+        change: impl Fn(usize) -> Option<fm_core::Currency>,
+        total: fm_core::Currency,
+        average: fm_core::Currency,
+    ) -> iced::Element<'a, Message> {
+        let mut row = components::spaced_row![widget::container(name).width(CATEGORY_WIDTH)];
+        for (period, value) in values.iter().enumerate() {
+            row = row.push(
+                widget::container(if self.show_changes {
+                    match change(period) {
+                        Some(change) => components::colored_currency_display(&change),
+                        None => widget::text("-").into(),
+                    }
+                } else {
+                    widget::text(value.to_string()).into()
+                })
+                .width(VALUE_WIDTH),
+            );
+        }
+        row.push(widget::text(total.to_string()).width(VALUE_WIDTH))
+            .push(widget::text(average.to_string()).width(VALUE_WIDTH))
+            .into()
+    }
+}
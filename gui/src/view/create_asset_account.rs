@@ -11,13 +11,44 @@ pub enum Action {
     Cancel,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepreciationKind {
+    None,
+    Linear,
+    Percentage,
+}
+
+impl DepreciationKind {
+    const ALL: [DepreciationKind; 3] = [
+        DepreciationKind::None,
+        DepreciationKind::Linear,
+        DepreciationKind::Percentage,
+    ];
+}
+
+impl std::fmt::Display for DepreciationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DepreciationKind::None => write!(f, "No depreciation"),
+            DepreciationKind::Linear => write!(f, "Linear (amount per year)"),
+            DepreciationKind::Percentage => write!(f, "Percentage per year"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum Message {
     NameInput(String),
     NoteInput(widget::text_editor::Action),
     IbanInput(String),
     BicInput(String),
+    AdditionalIdsInput(widget::text_editor::Action),
+    AliasesInput(widget::text_editor::Action),
     OffsetInput(components::currency_input::Action),
+    AssetTypeSelected(fm_core::account::AssetType),
+    DepreciationKindSelected(DepreciationKind),
+    DepreciationInput(String),
     Submit,
     AssetAccountCreated(fm_core::Id),
     Initialize(fm_core::account::AssetAccount),
@@ -32,7 +63,12 @@ pub struct View {
     note_input: widget::text_editor::Content,
     iban_input: ValidationTextInput,
     bic_input: ValidationTextInput,
+    additional_ids_input: widget::text_editor::Content,
+    aliases_input: widget::text_editor::Content,
     offset_input: components::CurrencyInput,
+    asset_type: fm_core::account::AssetType,
+    depreciation_kind: DepreciationKind,
+    depreciation_input: ValidationTextInput,
     submitted: bool,
 }
 
@@ -44,7 +80,20 @@ impl std::default::Default for View {
             name_input: ValidationTextInput::new(String::default()).required(true),
             note_input: widget::text_editor::Content::default(),
             iban_input: ValidationTextInput::new(String::default()),
-            bic_input: ValidationTextInput::new(String::default()),
+            bic_input: ValidationTextInput::default().validation(components::bic_validation("")),
+            additional_ids_input: widget::text_editor::Content::default(),
+            aliases_input: widget::text_editor::Content::default(),
+            asset_type: fm_core::account::AssetType::default(),
+            depreciation_kind: DepreciationKind::None,
+            depreciation_input: ValidationTextInput::default().required(true).validation(
+                |x| match x
+                    .replace(',', ".")
+                    .parse::<fm_core::bigdecimal::BigDecimal>()
+                {
+                    Ok(value) if value >= 0 => None,
+                    _ => Some("invalid number".to_string()),
+                },
+            ),
             submitted: false,
         }
     }
@@ -121,16 +170,47 @@ impl View {
                     widget::text_editor::Content::with_text(&account.note.unwrap_or_default());
                 self.iban_input
                     .set_content(account.iban.map_or(String::new(), |iban| iban.to_string()));
+                self.update_bic_validation();
                 self.bic_input
                     .set_content(account.bic.map(|x| x.to_string()).unwrap_or_default());
+                self.additional_ids_input = widget::text_editor::Content::with_text(
+                    &account
+                        .additional_ids
+                        .iter()
+                        .map(|x| x.to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                );
+                self.aliases_input =
+                    widget::text_editor::Content::with_text(&account.aliases.join("\n"));
                 self.offset_input.set_value(account.offset);
+                self.asset_type = account.asset_type;
+                match account.depreciation {
+                    Some(fm_core::valuation::Depreciation::Linear(amount)) => {
+                        self.depreciation_kind = DepreciationKind::Linear;
+                        self.depreciation_input.set_content(amount.to_num_string());
+                    }
+                    Some(fm_core::valuation::Depreciation::Percentage(rate)) => {
+                        self.depreciation_kind = DepreciationKind::Percentage;
+                        self.depreciation_input.set_content(rate.to_string());
+                    }
+                    None => self.depreciation_kind = DepreciationKind::None,
+                }
             }
             Message::AssetAccountCreated(id) => return Action::AssetAccountCreated(id),
             Message::NameInput(input) => self.name_input.edit_content(input),
             Message::NoteInput(input) => self.note_input.perform(input),
-            Message::IbanInput(input) => self.iban_input.edit_content(input),
+            Message::IbanInput(input) => {
+                self.iban_input.edit_content(input);
+                self.update_bic_validation();
+            }
             Message::BicInput(input) => self.bic_input.edit_content(input),
+            Message::AdditionalIdsInput(action) => self.additional_ids_input.perform(action),
+            Message::AliasesInput(action) => self.aliases_input.perform(action),
             Message::OffsetInput(action) => self.offset_input.perform(action),
+            Message::AssetTypeSelected(asset_type) => self.asset_type = asset_type,
+            Message::DepreciationKindSelected(kind) => self.depreciation_kind = kind,
+            Message::DepreciationInput(input) => self.depreciation_input.edit_content(input),
             Message::Submit => {
                 self.submitted = true;
                 let name = self.name_input.value().clone();
@@ -144,30 +224,33 @@ impl View {
                 } else {
                     Some(self.iban_input.value().parse().unwrap())
                 };
-                let bic = if self.bic_input.value().is_empty() {
-                    None
-                } else {
-                    Some(self.bic_input.value().clone())
+                // a BIC of only spaces counts as empty like in the validation
+                let bic = match self.bic_input.value().trim() {
+                    "" => None,
+                    bic => bic.parse().ok(),
                 };
                 let offset = self.offset_input.currency().unwrap();
+                let additional_ids = components::non_empty_lines(&self.additional_ids_input.text())
+                    .into_iter()
+                    .map(fm_core::AccountId::from)
+                    .collect::<Vec<_>>();
+                let aliases = components::non_empty_lines(&self.aliases_input.text());
+                let asset_type = self.asset_type;
+                let depreciation = self.depreciation();
                 let id = self.id;
                 return Action::Task(error::failing_task(async move {
-                    let account = if let Some(some_id) = id {
-                        finance_controller
-                            .update_asset_account(fm_core::account::AssetAccount::new(
-                                some_id,
-                                name,
-                                note,
-                                iban,
-                                bic.map(|x| x.into()),
-                                offset,
-                            ))
-                            .await?
+                    let mut account = if let Some(some_id) = id {
+                        fm_core::account::AssetAccount::new(some_id, name, note, iban, bic, offset)
                     } else {
                         finance_controller
-                            .create_asset_account(name, note, iban, bic.map(|x| x.into()), offset)
+                            .create_asset_account(name, note, iban, bic, offset)
                             .await?
                     };
+                    account.additional_ids = additional_ids;
+                    account.aliases = aliases;
+                    account.asset_type = asset_type;
+                    account.depreciation = depreciation;
+                    let account = finance_controller.update_asset_account(account).await?;
                     Ok(Message::AssetAccountCreated(account.id))
                 }));
             }
@@ -182,12 +265,33 @@ impl View {
 
         widget::scrollable(components::spaced_column![
             components::labeled_entry("Name", "", &self.name_input, Some(Message::NameInput)),
+            components::spal_row![
+                "Type",
+                widget::pick_list(
+                    fm_core::account::AssetType::ALL,
+                    Some(self.asset_type),
+                    Message::AssetTypeSelected
+                )
+            ],
+            self.depreciation_view(),
             components::spaced_row![
                 "Notes",
                 widget::text_editor(&self.note_input).on_action(Message::NoteInput)
             ],
             components::labeled_entry("IBAN", "", &self.iban_input, Some(Message::IbanInput)),
             components::labeled_entry("BIC", "", &self.bic_input, Some(Message::BicInput)),
+            components::spaced_row![
+                "Additional IDs",
+                widget::text_editor(&self.additional_ids_input)
+                    .placeholder("One IBAN or other ID per line")
+                    .on_action(Message::AdditionalIdsInput)
+            ],
+            components::spaced_row![
+                "Aliases",
+                widget::text_editor(&self.aliases_input)
+                    .placeholder("One name per line")
+                    .on_action(Message::AliasesInput)
+            ],
             components::spal_row!["Offset", self.offset_input.view().map(Message::OffsetInput),]
                 .width(iced::Fill),
             components::submit_cancel_row(
@@ -203,6 +307,57 @@ impl View {
     }
 
     fn can_submit(&self) -> bool {
-        self.name_input.is_valid() && self.offset_input.currency().is_some()
+        self.name_input.is_valid()
+            && self.bic_input.is_valid()
+            && self.offset_input.currency().is_some()
+            && (self.asset_type != fm_core::account::AssetType::Valuation
+                || self.depreciation_kind == DepreciationKind::None
+                || self.depreciation_input.is_valid())
+    }
+
+    fn depreciation_view(&self) -> iced::Element<'_, Message> {
+        if self.asset_type != fm_core::account::AssetType::Valuation {
+            return widget::Space::new(0, 0).into();
+        }
+        let mut row = components::spal_row![
+            "Depreciation",
+            widget::pick_list(
+                DepreciationKind::ALL,
+                Some(self.depreciation_kind),
+                Message::DepreciationKindSelected
+            )
+        ];
+        if self.depreciation_kind != DepreciationKind::None {
+            row = row.push(
+                self.depreciation_input
+                    .view("", Some(Message::DepreciationInput)),
+            );
+        }
+        row.into()
+    }
+
+    fn depreciation(&self) -> Option<fm_core::valuation::Depreciation> {
+        if self.asset_type != fm_core::account::AssetType::Valuation {
+            return None;
+        }
+        let value = self
+            .depreciation_input
+            .value()
+            .replace(',', ".")
+            .parse::<fm_core::bigdecimal::BigDecimal>();
+        match self.depreciation_kind {
+            DepreciationKind::None => None,
+            DepreciationKind::Linear => Some(fm_core::valuation::Depreciation::Linear(
+                fm_core::Currency::from_currency_id(1, value.unwrap()).unwrap(),
+            )),
+            DepreciationKind::Percentage => {
+                Some(fm_core::valuation::Depreciation::Percentage(value.unwrap()))
+            }
+        }
+    }
+
+    fn update_bic_validation(&mut self) {
+        self.bic_input = std::mem::take(&mut self.bic_input)
+            .validation(components::bic_validation(self.iban_input.value()));
     }
 }
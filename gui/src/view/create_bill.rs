@@ -656,7 +656,7 @@ mod add_transaction {
                                 return Action::Task(error::failing_task(async move {
                                     Ok(Message::FetchedTransactions(
                                         finance_controller
-                                            .get_filtered_transactions(submitted_filter.clone())
+                                            .get_filtered_transactions(*submitted_filter)
                                             .await?,
                                     ))
                                 }));
@@ -2,6 +2,7 @@ use fm_core;
 
 use anyhow::Context;
 use components::ValidationTextInput;
+use components::date_time::date_input;
 use iced::widget;
 
 pub enum Action {
@@ -18,11 +19,27 @@ pub enum Message {
     NoteInput(widget::text_editor::Action),
     IbanInput(String),
     BicInput(String),
+    KindSelected(fm_core::account::AccountKind),
+    AdditionalIdsInput(widget::text_editor::Action),
+    AliasesInput(widget::text_editor::Action),
+    LoanToggled(bool),
+    LoanPrincipalInput(components::currency_input::Action),
+    LoanInterestRateInput(String),
+    LoanTermInput(String),
+    LoanPaymentDayInput(String),
+    LoanStartInput(date_input::Action),
+    LoanInterestAccountSelected(fm_core::account::Account),
+    AccountsLoaded(Vec<fm_core::account::Account>),
     Submit,
-    Initialize(fm_core::account::BookCheckingAccount),
+    Initialize(
+        fm_core::account::BookCheckingAccount,
+        Vec<fm_core::account::Account>,
+    ),
     AccountCreated(fm_core::Id),
     Cancel,
-    Reload { exists: bool },
+    Reload {
+        exists: bool,
+    },
 }
 
 #[derive(Debug)]
@@ -32,6 +49,17 @@ pub struct View {
     note_input: widget::text_editor::Content,
     iban_input: ValidationTextInput,
     bic_input: ValidationTextInput,
+    kind: Option<fm_core::account::AccountKind>,
+    additional_ids_input: widget::text_editor::Content,
+    aliases_input: widget::text_editor::Content,
+    loan: bool,
+    loan_principal_input: components::CurrencyInput,
+    loan_interest_rate_input: ValidationTextInput,
+    loan_term_input: ValidationTextInput,
+    loan_payment_day_input: ValidationTextInput,
+    loan_start_input: date_input::State,
+    loan_interest_account: Option<fm_core::account::Account>,
+    accounts: Vec<fm_core::account::Account>,
     submitted: bool,
 }
 
@@ -42,13 +70,53 @@ impl Default for View {
             name_input: ValidationTextInput::default().required(true),
             note_input: widget::text_editor::Content::default(),
             iban_input: ValidationTextInput::default(),
-            bic_input: ValidationTextInput::default(),
+            bic_input: ValidationTextInput::default().validation(components::bic_validation("")),
+            kind: None,
+            additional_ids_input: widget::text_editor::Content::default(),
+            aliases_input: widget::text_editor::Content::default(),
+            loan: false,
+            loan_principal_input: components::CurrencyInput::default(),
+            loan_interest_rate_input: ValidationTextInput::default().required(true).validation(
+                |x| match x.parse::<fm_core::bigdecimal::BigDecimal>() {
+                    Ok(rate) if rate >= 0 => None,
+                    Ok(_) => Some("The interest rate cannot be negative".to_string()),
+                    Err(_) => Some("Not a valid interest rate".to_string()),
+                },
+            ),
+            loan_term_input: ValidationTextInput::default()
+                .required(true)
+                .validation(|x| match x.parse::<u32>() {
+                    Ok(months) if months > 0 => None,
+                    _ => Some("Enter the number of monthly payments".to_string()),
+                }),
+            loan_payment_day_input: ValidationTextInput::default()
+                .required(true)
+                .validation(|x| match x.parse::<u8>() {
+                    Ok(day) if (1..=31).contains(&day) => None,
+                    _ => Some("Enter a day of the month".to_string()),
+                }),
+            loan_start_input: date_input::State::default(),
+            loan_interest_account: None,
+            accounts: Vec::new(),
             submitted: false,
         }
     }
 }
 
 impl View {
+    pub fn new(
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+    ) -> (Self, iced::Task<Message>) {
+        (
+            View::default(),
+            error::failing_task(async move {
+                Ok(Message::AccountsLoaded(
+                    finance_controller.get_accounts().await?,
+                ))
+            }),
+        )
+    }
+
     pub fn reload(
         &self,
         finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
@@ -76,7 +144,10 @@ impl View {
                     .await?
                     .context(format!("Could not find account {account_id}"))?;
                 if let fm_core::account::Account::BookCheckingAccount(acc) = account {
-                    Ok(Message::Initialize(acc))
+                    Ok(Message::Initialize(
+                        acc,
+                        finance_controller.get_accounts().await?,
+                    ))
                 } else {
                     anyhow::bail!("Wrong account type");
                 }
@@ -88,6 +159,7 @@ impl View {
         &mut self,
         message: Message,
         finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+        utc_offset: time::UtcOffset,
     ) -> Action {
         match message {
             Message::Reload { exists } => {
@@ -96,20 +168,66 @@ impl View {
                 }
             }
             Message::AccountCreated(id) => return Action::AccountCreated(id),
-            Message::Initialize(account) => {
+            Message::AccountsLoaded(accounts) => self.accounts = accounts,
+            Message::Initialize(account, accounts) => {
                 self.id = Some(account.id);
                 self.name_input.set_content(account.name);
                 self.note_input =
                     widget::text_editor::Content::with_text(&account.note.unwrap_or_default());
                 self.iban_input
                     .set_content(account.iban.map_or(String::new(), |iban| iban.to_string()));
+                self.update_bic_validation();
                 self.bic_input
                     .set_content(account.bic.map_or(String::new(), |bic| bic.to_string()));
+                self.kind = account.kind;
+                self.additional_ids_input = widget::text_editor::Content::with_text(
+                    &account
+                        .additional_ids
+                        .iter()
+                        .map(|x| x.to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                );
+                self.aliases_input =
+                    widget::text_editor::Content::with_text(&account.aliases.join("\n"));
+                self.loan = account.loan.is_some();
+                if let Some(loan) = account.loan {
+                    self.loan_principal_input.set_value(loan.principal);
+                    self.loan_interest_rate_input
+                        .set_content(loan.interest_rate.to_string());
+                    self.loan_term_input
+                        .set_content(loan.term_months.to_string());
+                    self.loan_payment_day_input
+                        .set_content(loan.payment_day.to_string());
+                    self.loan_start_input =
+                        date_input::State::new(Some(loan.start.to_offset(utc_offset).date()));
+                    self.loan_interest_account = loan
+                        .interest_account
+                        .and_then(|id| accounts.iter().find(|acc| *acc.id() == id).cloned());
+                }
+                self.accounts = accounts;
             }
             Message::NameInput(input) => self.name_input.edit_content(input),
             Message::NoteInput(action) => self.note_input.perform(action),
-            Message::IbanInput(input) => self.iban_input.edit_content(input),
+            Message::IbanInput(input) => {
+                self.iban_input.edit_content(input);
+                self.update_bic_validation();
+            }
             Message::BicInput(input) => self.bic_input.edit_content(input),
+            Message::KindSelected(kind) => self.kind = Some(kind),
+            Message::LoanToggled(loan) => self.loan = loan,
+            Message::LoanPrincipalInput(action) => self.loan_principal_input.perform(action),
+            Message::LoanInterestRateInput(input) => {
+                self.loan_interest_rate_input.edit_content(input)
+            }
+            Message::LoanTermInput(input) => self.loan_term_input.edit_content(input),
+            Message::LoanPaymentDayInput(input) => self.loan_payment_day_input.edit_content(input),
+            Message::LoanStartInput(action) => self.loan_start_input.perform(action),
+            Message::LoanInterestAccountSelected(account) => {
+                self.loan_interest_account = Some(account)
+            }
+            Message::AdditionalIdsInput(action) => self.additional_ids_input.perform(action),
+            Message::AliasesInput(action) => self.aliases_input.perform(action),
             Message::Submit => {
                 self.submitted = true;
                 let name = self.name_input.value().clone();
@@ -123,30 +241,63 @@ impl View {
                 } else {
                     Some(self.iban_input.value().parse().unwrap())
                 };
-                let bic = if self.bic_input.value().is_empty() {
-                    None
+                // a BIC of only spaces counts as empty like in the validation
+                let bic = match self.bic_input.value().trim() {
+                    "" => None,
+                    bic => bic.parse().ok(),
+                };
+                let additional_ids = components::non_empty_lines(&self.additional_ids_input.text())
+                    .into_iter()
+                    .map(fm_core::AccountId::from)
+                    .collect::<Vec<_>>();
+                let aliases = components::non_empty_lines(&self.aliases_input.text());
+                let kind = self.kind;
+                let loan = if self.has_loan() {
+                    Some((
+                        self.loan_principal_input.currency().unwrap(),
+                        self.loan_interest_rate_input.value().parse().unwrap(),
+                        self.loan_term_input.value().parse().unwrap(),
+                        self.loan_payment_day_input.value().parse().unwrap(),
+                        components::date_time::primitive_to_offset(
+                            time::PrimitiveDateTime::new(
+                                self.loan_start_input.date().unwrap(),
+                                time::Time::MIDNIGHT,
+                            ),
+                            utc_offset,
+                        ),
+                        self.loan_interest_account.as_ref().map(|acc| *acc.id()),
+                    ))
                 } else {
-                    Some(self.bic_input.value().clone())
+                    None
                 };
                 let id = self.id;
                 return Action::Task(error::failing_task(async move {
-                    let account = if let Some(some_id) = id {
-                        finance_controller
-                            .update_book_checking_account(
-                                fm_core::account::BookCheckingAccount::new(
-                                    some_id,
-                                    name,
-                                    note,
-                                    iban,
-                                    bic.map(|x| x.into()),
-                                ),
-                            )
-                            .await?
+                    let mut account = if let Some(some_id) = id {
+                        fm_core::account::BookCheckingAccount::new(some_id, name, note, iban, bic)
                     } else {
                         finance_controller
-                            .create_book_checking_account(name, note, iban, bic.map(|x| x.into()))
+                            .create_book_checking_account(name, note, iban, bic)
                             .await?
                     };
+                    account.additional_ids = additional_ids;
+                    account.aliases = aliases;
+                    account.kind = kind;
+                    account.loan = match loan {
+                        Some((principal, rate, term, day, start, interest_account)) => {
+                            Some(Box::new(fm_core::loan::Loan::new(
+                                principal,
+                                rate,
+                                term,
+                                day,
+                                start,
+                                interest_account,
+                            )?))
+                        }
+                        None => None,
+                    };
+                    let account = finance_controller
+                        .update_book_checking_account(account)
+                        .await?;
                     Ok(Message::AccountCreated(account.id))
                 }));
             }
@@ -168,12 +319,34 @@ impl View {
 
         widget::scrollable(components::spaced_column![
             components::labeled_entry("Name", "", &self.name_input, Some(Message::NameInput)),
+            components::spal_row![
+                "Kind",
+                widget::pick_list(
+                    fm_core::account::AccountKind::BOOK_CHECKING,
+                    self.kind,
+                    Message::KindSelected
+                )
+                .placeholder("Not classified")
+            ],
+            self.loan_view(),
             components::spaced_row![
                 "Notes",
                 widget::text_editor(&self.note_input).on_action(Message::NoteInput)
             ],
             components::labeled_entry("IBAN", "", &self.iban_input, Some(Message::IbanInput)),
             components::labeled_entry("BIC", "", &self.bic_input, Some(Message::BicInput)),
+            components::spaced_row![
+                "Additional IDs",
+                widget::text_editor(&self.additional_ids_input)
+                    .placeholder("One IBAN or other ID per line")
+                    .on_action(Message::AdditionalIdsInput)
+            ],
+            components::spaced_row![
+                "Aliases",
+                widget::text_editor(&self.aliases_input)
+                    .placeholder("One name per line")
+                    .on_action(Message::AliasesInput)
+            ],
             components::submit_cancel_row(
                 if self.can_submit() {
                     Some(Message::Submit)
@@ -186,7 +359,81 @@ impl View {
         .into()
     }
 
+    fn loan_view(&self) -> iced::Element<'_, Message> {
+        if self.kind != Some(fm_core::account::AccountKind::Liability) {
+            return widget::Space::new(0, 0).into();
+        }
+        let mut column = components::spaced_column![
+            widget::checkbox("Loan", self.loan).on_toggle(Message::LoanToggled)
+        ];
+        if self.loan {
+            column = column
+                .push(
+                    components::spal_row![
+                        "Principal",
+                        self.loan_principal_input
+                            .view()
+                            .map(Message::LoanPrincipalInput),
+                    ]
+                    .width(iced::Fill),
+                )
+                .push(components::labeled_entry(
+                    "Interest rate (% p.a.)",
+                    "",
+                    &self.loan_interest_rate_input,
+                    Some(Message::LoanInterestRateInput),
+                ))
+                .push(components::labeled_entry(
+                    "Term (months)",
+                    "",
+                    &self.loan_term_input,
+                    Some(Message::LoanTermInput),
+                ))
+                .push(components::labeled_entry(
+                    "Payment day",
+                    "",
+                    &self.loan_payment_day_input,
+                    Some(Message::LoanPaymentDayInput),
+                ))
+                .push(
+                    components::spal_row![
+                        "Start",
+                        date_input::date_input(&self.loan_start_input, "", true)
+                            .view()
+                            .map(Message::LoanStartInput),
+                    ]
+                    .width(iced::Fill),
+                )
+                .push(components::spal_row![
+                    "Interest account",
+                    widget::pick_list(
+                        self.accounts.as_slice(),
+                        self.loan_interest_account.as_ref(),
+                        Message::LoanInterestAccountSelected
+                    )
+                    .placeholder("Do not split payments")
+                ]);
+        }
+        column.into()
+    }
+
+    fn has_loan(&self) -> bool {
+        self.loan && self.kind == Some(fm_core::account::AccountKind::Liability)
+    }
+
     fn can_submit(&self) -> bool {
         self.name_input.is_valid()
+            && self.bic_input.is_valid()
+            && (!self.has_loan()
+                || (self.loan_principal_input.currency().is_some()
+                    && self.loan_interest_rate_input.is_valid()
+                    && self.loan_term_input.is_valid()
+                    && self.loan_payment_day_input.is_valid()
+                    && self.loan_start_input.date().is_some()))
+    }
+
+    fn update_bic_validation(&mut self) {
+        self.bic_input = std::mem::take(&mut self.bic_input)
+            .validation(components::bic_validation(self.iban_input.value()));
     }
 }
@@ -5,7 +5,10 @@ use iced::widget;
 use iced_aw::widget::LabeledFrame;
 use itertools::Itertools;
 
+const CATEGORY_SUGGESTION_LIMIT: usize = 3;
+
 #[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
 enum SelectedAccount {
     Account(fm_core::account::Account),
     New(String),
@@ -64,9 +67,12 @@ enum Message {
     BudgetSelected(fm_core::Budget),
     BudgetSignChange(bool),
     ClearBudget,
+    StatusSelected(fm_core::TransactionStatus),
     Submit,
     SelectCategory(fm_core::Id),
     ChangeSelectedCategorySign(fm_core::Id, fm_core::Sign),
+    CategoryModel(Box<fm_core::category_suggestion::CategoryModel>),
+    ApplyCategorySuggestion(fm_core::Id, fm_core::Sign),
     Initialize(
         Box<(
             Vec<fm_core::Budget>,
@@ -100,9 +106,14 @@ pub struct View {
     budget_state: widget::combo_box::State<fm_core::Budget>,
     budget_input: Option<(fm_core::Budget, fm_core::Sign)>,
     date_input: date_time_input::State,
+    status_input: fm_core::TransactionStatus,
+    /// Kept from the existing transaction as it is not editable.
+    value_date: Option<fm_core::DateTime>,
     metadata_editor: components::key_value_editor::KeyValueEditor,
     available_categories: Vec<fm_core::Category>,
     selected_categories: Vec<(fm_core::Id, fm_core::Sign)>,
+    category_model: fm_core::category_suggestion::CategoryModel,
+    category_suggestions: Vec<fm_core::category_suggestion::CategorySuggestion>,
     submitted: bool,
     metadata_editor_open: bool,
 }
@@ -151,65 +162,108 @@ impl View {
                 budget_state: widget::combo_box::State::new(Vec::new()),
                 budget_input: None,
                 date_input: date_time_input::State::default(),
+                status_input: fm_core::TransactionStatus::default(),
+                value_date: None,
                 metadata_editor: components::key_value_editor::KeyValueEditor::default(),
                 metadata_editor_open: false,
                 selected_categories: Vec::new(),
                 available_categories: Vec::new(),
+                category_model: fm_core::category_suggestion::CategoryModel::default(),
+                category_suggestions: Vec::new(),
                 submitted: false,
             },
-            error::failing_task(async move {
-                let budgets = finance_controller.get_budgets().await?;
-                let accounts = finance_controller.get_accounts().await?;
-                let categories = finance_controller.get_categories().await?;
-                Ok(Message::Initialize(Box::new((
-                    budgets, accounts, categories,
-                ))))
-            })
-            .map(MessageContainer),
+            iced::Task::batch([
+                Self::category_model_task(finance_controller.clone()),
+                error::failing_task(async move {
+                    let budgets = finance_controller.get_budgets().await?;
+                    let accounts = finance_controller.get_accounts().await?;
+                    let categories = finance_controller.get_categories().await?;
+                    Ok(Message::Initialize(Box::new((
+                        budgets, accounts, categories,
+                    ))))
+                })
+                .map(MessageContainer),
+            ]),
         )
     }
 
+    fn category_model_task(
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+    ) -> iced::Task<MessageContainer> {
+        error::failing_task(async move {
+            Ok(MessageContainer(Message::CategoryModel(Box::new(
+                finance_controller.get_category_model().await?,
+            ))))
+        })
+    }
+
+    fn update_category_suggestions(&mut self) {
+        let account_id = |account: &Option<SelectedAccount>| match account {
+            Some(SelectedAccount::Account(acc)) => Some(*acc.id()),
+            _ => None,
+        };
+        self.category_suggestions = self
+            .category_model
+            .suggest(
+                self.title_input.value(),
+                account_id(&self.source_input),
+                account_id(&self.destination_input),
+                &self.amount_input.currency().unwrap_or_default(),
+            )
+            .into_iter()
+            .filter(|suggestion| {
+                self.available_categories
+                    .iter()
+                    .any(|category| category.id == suggestion.category)
+            })
+            .take(CATEGORY_SUGGESTION_LIMIT)
+            .collect();
+    }
+
     pub fn fetch(
         finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
         transaction_id: fm_core::Id,
     ) -> (Self, iced::Task<MessageContainer>) {
         (
             Self::new(finance_controller.clone()).0,
-            error::failing_task(async move {
-                let transaction = finance_controller
-                    .get_transaction(transaction_id)
-                    .await?
-                    .context(format!("Could not find transaction {transaction_id}"))?;
-                let source = finance_controller
-                    .get_account(transaction.source)
-                    .await?
-                    .context(format!("Could not find account {}", transaction.source))?;
-                let destination = finance_controller
-                    .get_account(transaction.destination)
-                    .await?
-                    .context(format!(
-                        "Could not find account {}",
-                        transaction.destination
-                    ))?;
-                let budget = match transaction.budget {
-                    Some(x) => finance_controller.get_budget(x.0).await?,
-                    None => None,
-                };
-                let budgets = finance_controller.get_budgets().await?;
-                let accounts = finance_controller.get_accounts().await?;
-                let available_categories = finance_controller.get_categories().await?;
-
-                Ok(Message::InitializeFromExisting(Box::new(InitExisting {
-                    transaction,
-                    source,
-                    destination,
-                    budget,
-                    budgets,
-                    accounts,
-                    available_categories,
-                })))
-            })
-            .map(MessageContainer),
+            iced::Task::batch([
+                Self::category_model_task(finance_controller.clone()),
+                error::failing_task(async move {
+                    let transaction = finance_controller
+                        .get_transaction(transaction_id)
+                        .await?
+                        .context(format!("Could not find transaction {transaction_id}"))?;
+                    let source = finance_controller
+                        .get_account(transaction.source)
+                        .await?
+                        .context(format!("Could not find account {}", transaction.source))?;
+                    let destination = finance_controller
+                        .get_account(transaction.destination)
+                        .await?
+                        .context(format!(
+                            "Could not find account {}",
+                            transaction.destination
+                        ))?;
+                    let budget = match transaction.budget {
+                        Some(x) => finance_controller.get_budget(x.0).await?,
+                        None => None,
+                    };
+                    let budgets = finance_controller.get_budgets().await?;
+                    let accounts = finance_controller.get_accounts().await?;
+                    let available_categories = finance_controller.get_categories().await?;
+
+                    Ok(Message::InitializeFromExisting(Box::new(InitExisting {
+                        transaction,
+                        source,
+                        destination,
+                        budget,
+                        budgets,
+                        accounts,
+                        available_categories,
+                    })))
+                })
+                .map(MessageContainer),
+            ]),
         )
     }
 
@@ -307,12 +361,17 @@ impl View {
             }
             Message::AmountInput(action) => {
                 self.amount_input.perform(action);
+                self.update_category_suggestions();
+            }
+            Message::TitleInput(content) => {
+                self.title_input.edit_content(content);
+                self.update_category_suggestions();
             }
-            Message::TitleInput(content) => self.title_input.edit_content(content),
             Message::DescriptionInput(action) => self.description_input.perform(action),
             Message::DateInput(action) => self.date_input.perform(action),
             Message::SourceInput(content) => {
-                self.source_input = Some(SelectedAccount::New(content))
+                self.source_input = Some(SelectedAccount::New(content));
+                self.update_category_suggestions();
             }
             Message::BudgetSelected(content) => {
                 self.budget_input = Some((
@@ -324,16 +383,22 @@ impl View {
             }
             Message::SourceSelected(content) => {
                 self.source_input = Some(content);
+                self.update_category_suggestions();
             }
             Message::DestinationInput(content) => {
                 self.destination_input = Some(SelectedAccount::New(content));
+                self.update_category_suggestions();
             }
             Message::DestinationSelected(content) => {
                 self.destination_input = Some(content);
+                self.update_category_suggestions();
             }
             Message::ClearBudget => {
                 self.budget_input = None;
             }
+            Message::StatusSelected(status) => {
+                self.status_input = status;
+            }
             Message::SelectCategory(id) => {
                 if self
                     .selected_categories
@@ -352,6 +417,17 @@ impl View {
                     x.1 = sign;
                 }
             }
+            Message::CategoryModel(model) => {
+                self.category_model = *model;
+                self.update_category_suggestions();
+            }
+            Message::ApplyCategorySuggestion(id, sign) => {
+                if let Some(x) = self.selected_categories.iter_mut().find(|x| x.0 == id) {
+                    x.1 = sign;
+                } else {
+                    self.selected_categories.push((id, sign));
+                }
+            }
             Message::BudgetSignChange(x) => {
                 if let Some(budget) = &self.budget_input {
                     self.budget_input = Some((
@@ -371,6 +447,7 @@ impl View {
                 categories.sort_by(|a, b| a.name.cmp(&b.name));
                 self.budget_state = widget::combo_box::State::new(budgets);
                 self.available_categories = categories;
+                self.update_category_suggestions();
                 self.source_state = widget::combo_box::State::new(
                     accounts
                         .iter()
@@ -425,6 +502,8 @@ impl View {
                 self.date_input = date_time_input::State::new(Some(
                     components::date_time::offset_to_primitive(init_existing.transaction.date),
                 ));
+                self.status_input = init_existing.transaction.status;
+                self.value_date = init_existing.transaction.value_date;
                 self.metadata_editor = components::key_value_editor::KeyValueEditor::from(
                     init_existing.transaction.metadata,
                 );
@@ -439,6 +518,7 @@ impl View {
                     .iter()
                     .map(|(k, v)| (*k, *v))
                     .collect::<Vec<_>>();
+                self.update_category_suggestions();
             }
             Message::Cancel => {
                 if let Some(id) = self.id {
@@ -495,6 +575,39 @@ impl View {
                 ]);
             }
 
+            let mut suggestions = components::spaced_row![];
+            for suggestion in &self.category_suggestions {
+                if self
+                    .selected_categories
+                    .contains(&(suggestion.category, suggestion.sign))
+                {
+                    continue;
+                }
+                if let Some(category) = self
+                    .available_categories
+                    .iter()
+                    .find(|c| c.id == suggestion.category)
+                {
+                    suggestions = suggestions.push(
+                        widget::button(widget::text!(
+                            "{}{} ({:.0}%)",
+                            category.name,
+                            if suggestion.sign == fm_core::Sign::Negative {
+                                " (Negative)"
+                            } else {
+                                ""
+                            },
+                            suggestion.confidence * 100.0
+                        ))
+                        .style(widget::button::secondary)
+                        .on_press(Message::ApplyCategorySuggestion(
+                            category.id,
+                            suggestion.sign,
+                        )),
+                    );
+                }
+            }
+
             let source_acc_style = if let Some(acc) = &self.source_input {
                 match acc {
                     SelectedAccount::Account(_) => style::text_input_success,
@@ -536,6 +649,14 @@ impl View {
                         .map(Message::DateInput)
                 ]
                 .width(iced::Fill),
+                components::spal_row![
+                    "Status: ",
+                    widget::pick_list(
+                        fm_core::TransactionStatus::ALL,
+                        Some(self.status_input),
+                        Message::StatusSelected
+                    )
+                ],
                 components::spal_row![
                     "Source",
                     widget::ComboBox::new(
@@ -580,7 +701,14 @@ impl View {
                     widget::button("X").on_press(Message::ClearBudget)
                 ]
                 .align_y(iced::Center),
-                LabeledFrame::new("Categories", categories).width(iced::Fill),
+                LabeledFrame::new(
+                    "Categories",
+                    components::spaced_column![
+                        components::spal_row!["Suggestions: ", suggestions].wrap(),
+                        categories
+                    ]
+                )
+                .width(iced::Fill),
                 widget::button("Metadata").on_press(Message::ToggleMetadataEditor)
             ])
         };
@@ -652,6 +780,8 @@ impl View {
             .as_ref()
             .map(|budget| (budget.0.id, budget.1));
         let date = self.date_input.datetime().unwrap();
+        let status = self.status_input;
+        let value_date = self.value_date;
         let metadata = std::collections::HashMap::from_iter(self.metadata_editor.pairs());
         let mut categories =
             std::collections::HashMap::with_capacity(self.selected_categories.len());
@@ -679,39 +809,27 @@ impl View {
                 }
             };
 
-            Ok(match option_id {
-                Some(id) => {
-                    finance_controller
-                        .update_transaction(fm_core::Transaction::new(
-                            id,
-                            amount,
-                            title,
-                            description,
-                            source_id,
-                            destination_id,
-                            budget,
-                            components::date_time::primitive_to_offset(date, utc_offset),
-                            metadata,
-                            categories,
-                        )?)
-                        .await?
-                }
-                _ => {
-                    finance_controller
-                        .create_transaction(
-                            amount,
-                            title,
-                            description,
-                            source_id,
-                            destination_id,
-                            budget,
-                            components::date_time::primitive_to_offset(date, utc_offset),
-                            metadata,
-                            categories,
-                        )
-                        .await?
-                }
-            })
+            let mut transaction = fm_core::Transaction::new(
+                option_id.unwrap_or_default(),
+                amount,
+                title,
+                description,
+                source_id,
+                destination_id,
+                budget,
+                components::date_time::primitive_to_offset(date, utc_offset),
+                metadata,
+                categories,
+            )?;
+            transaction.status = status;
+            transaction.value_date = value_date;
+            if option_id.is_some() {
+                finance_controller.update_transaction(transaction).await
+            } else {
+                finance_controller
+                    .create_transaction_from(transaction)
+                    .await
+            }
         })
     }
 }
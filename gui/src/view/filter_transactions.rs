@@ -1,17 +1,27 @@
 use anyhow::Context;
-use fm_core::transaction_filter::TransactionFilter;
+use fm_core::filter_expression::FilterExpression;
+use fm_core::transaction_filter::{SavedFilter, TransactionFilter, query};
 
 pub enum Action {
     None,
     Task(iced::Task<Message>),
     ViewAccount(fm_core::Id),
     ViewTransaction(fm_core::Id),
+    ViewCategoryPivot(TransactionFilter),
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     FilterComponent(Box<components::filter_component::InnerMessage>),
     ToggleEditFilter,
+    QueryInput(String),
+    SubmitQuery,
+    SavedFilterSelected(SavedFilter),
+    SavedFilterNameInput(String),
+    SaveFilter,
+    SavedFilterStored(SavedFilter),
+    DeleteSavedFilter,
+    SavedFilterDeleted(fm_core::Id),
     UpdateTransactions(
         Vec<(
             fm_core::Transaction,
@@ -24,6 +34,7 @@ pub enum Message {
         categories: Vec<fm_core::Category>,
         bills: Vec<fm_core::Bill>,
         budgets: Vec<fm_core::Budget>,
+        saved_filters: Vec<SavedFilter>,
     },
     Reload {
         transactions: Vec<(
@@ -35,10 +46,20 @@ pub enum Message {
         categories: Vec<fm_core::Category>,
         bills: Vec<fm_core::Bill>,
         budgets: Vec<fm_core::Budget>,
+        saved_filters: Vec<SavedFilter>,
         filter: TransactionFilter,
     },
     TransactionTable(components::transaction_table::Message),
     CategoryDistribution,
+    CategoryPivot,
+    BulkCategorySelected(fm_core::Category),
+    BulkCategoryNegative(bool),
+    BulkSetCategory,
+    BulkRemoveCategory,
+    MergeFromSelected(fm_core::Category),
+    MergeIntoSelected(fm_core::Category),
+    MergeCategories,
+    CategoriesChanged,
 }
 
 #[derive(Debug)]
@@ -51,6 +72,17 @@ pub struct View {
     transaction_table: components::TransactionTable,
     sums: Vec<(fm_core::DateTime, fm_core::Currency)>,
     filter: TransactionFilter,
+    query: String,
+    query_error: Option<query::QueryError>,
+    /// The parsed query that replaces `filter` until the filter is edited or the query is cleared.
+    expression: Option<FilterExpression>,
+    saved_filters: Vec<SavedFilter>,
+    selected_saved_filter: Option<SavedFilter>,
+    saved_filter_name: String,
+    bulk_category: Option<fm_core::Category>,
+    bulk_category_sign: fm_core::Sign,
+    merge_from: Option<fm_core::Category>,
+    merge_into: Option<fm_core::Category>,
 }
 
 impl View {
@@ -64,6 +96,7 @@ impl View {
             let categories = finance_controller.get_categories().await?;
             let bills = finance_controller.get_bills(None).await?;
             let budgets = finance_controller.get_budgets().await?;
+            let saved_filters = finance_controller.get_saved_filters().await?;
 
             filter.accounts.retain(|filter| {
                 if let Some(id) = filter.id {
@@ -118,6 +151,7 @@ impl View {
                 categories,
                 bills,
                 budgets,
+                saved_filters,
                 transactions: transaction_tuples,
                 filter,
             })
@@ -142,22 +176,48 @@ impl View {
                 ),
                 sums: Vec::new(),
                 filter: TransactionFilter::default(),
+                query: String::new(),
+                query_error: None,
+                expression: None,
+                saved_filters: Vec::new(),
+                selected_saved_filter: None,
+                saved_filter_name: String::new(),
+                bulk_category: None,
+                bulk_category_sign: fm_core::Sign::Positive,
+                merge_from: None,
+                merge_into: None,
             },
             error::failing_task(async move {
                 let accounts = finance_controller.get_accounts().await?;
                 let categories = finance_controller.get_categories().await?;
                 let bills = finance_controller.get_bills(None).await?;
                 let budgets = finance_controller.get_budgets().await?;
+                let saved_filters = finance_controller.get_saved_filters().await?;
                 Ok(Message::Initialize {
                     accounts,
                     categories,
                     bills,
                     budgets,
+                    saved_filters,
                 })
             }),
         )
     }
 
+    pub fn with_saved_filter(
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+        saved_filter: SavedFilter,
+        utc_offset: time::UtcOffset,
+    ) -> (Self, iced::Task<Message>) {
+        let (mut view, task) = Self::new(finance_controller.clone());
+        view.saved_filter_name = saved_filter.name.clone();
+        let mut filter = saved_filter.filter.clone();
+        filter.resolve_relative_timespans(time::OffsetDateTime::now_utc(), utc_offset);
+        let apply = view.apply_filter(finance_controller, filter);
+        view.selected_saved_filter = Some(saved_filter);
+        (view, task.chain(apply))
+    }
+
     pub fn update(
         &mut self,
         message: Message,
@@ -170,7 +230,9 @@ impl View {
                 categories,
                 bills,
                 budgets,
+                saved_filters,
             } => {
+                self.saved_filters = saved_filters;
                 self.accounts = accounts;
                 self.categories = categories;
                 self.bills = bills;
@@ -187,13 +249,33 @@ impl View {
                 categories,
                 bills,
                 budgets,
-                filter,
+                saved_filters,
+                mut filter,
                 transactions,
             } => {
+                // relative timespans move on while the view is open
+                filter.resolve_relative_timespans(time::OffsetDateTime::now_utc(), utc_offset);
+                if let Some(selected) = &self.selected_saved_filter {
+                    self.selected_saved_filter =
+                        saved_filters.iter().find(|x| x.id == selected.id).cloned();
+                }
+                self.saved_filters = saved_filters;
                 self.accounts = accounts;
                 self.categories = categories;
                 self.bills = bills;
                 self.budgets = budgets;
+                for selected in [
+                    &mut self.bulk_category,
+                    &mut self.merge_from,
+                    &mut self.merge_into,
+                ] {
+                    if selected
+                        .as_ref()
+                        .is_some_and(|category| !self.categories.contains(category))
+                    {
+                        *selected = None;
+                    }
+                }
                 self.transaction_table.reload(
                     transactions,
                     self.categories.clone(),
@@ -207,8 +289,95 @@ impl View {
                         self.budgets.clone(),
                     );
                 }
+                if let Some(expression) = self.expression.clone() {
+                    self.filter = filter;
+                    return Action::Task(self.apply_expression(finance_controller, expression));
+                }
+                return Action::Task(self.apply_filter(finance_controller, filter));
+            }
+            Message::QueryInput(query) => {
+                self.query = query;
+                self.query_error = None;
+            }
+            Message::SubmitQuery => {
+                if self.query.trim().is_empty() {
+                    self.query.clear();
+                    self.expression = None;
+                    return Action::Task(
+                        self.apply_filter(finance_controller, self.filter.clone()),
+                    );
+                }
+                let names =
+                    query::Names::new(&self.accounts, &self.categories, &self.bills, &self.budgets);
+                match query::parse(&self.query, &names, utc_offset) {
+                    Ok(expression) => {
+                        self.query = query::to_query(&expression, &names, utc_offset);
+                        return Action::Task(self.apply_expression(finance_controller, expression));
+                    }
+                    Err(error) => self.query_error = Some(error),
+                }
+            }
+            Message::SavedFilterSelected(saved_filter) => {
+                self.change_filter = None;
+                self.query.clear();
+                self.query_error = None;
+                self.expression = None;
+                self.saved_filter_name = saved_filter.name.clone();
+                let mut filter = saved_filter.filter.clone();
+                filter.resolve_relative_timespans(time::OffsetDateTime::now_utc(), utc_offset);
+                self.selected_saved_filter = Some(saved_filter);
                 return Action::Task(self.apply_filter(finance_controller, filter));
             }
+            Message::SavedFilterNameInput(name) => self.saved_filter_name = name,
+            Message::SaveFilter => {
+                let name = self.saved_filter_name.trim().to_string();
+                let filter = self.filter.clone();
+                // saving under the name of the selected filter overwrites it
+                let selected = self
+                    .selected_saved_filter
+                    .clone()
+                    .filter(|selected| selected.name == name);
+                return Action::Task(error::failing_task(async move {
+                    let saved_filter = match selected {
+                        Some(selected) => {
+                            finance_controller
+                                .update_saved_filter(SavedFilter::new(selected.id, name, filter))
+                                .await?
+                        }
+                        None => finance_controller.create_saved_filter(name, filter).await?,
+                    };
+                    Ok(Message::SavedFilterStored(saved_filter))
+                }));
+            }
+            Message::SavedFilterStored(saved_filter) => {
+                self.saved_filters.retain(|x| x.id != saved_filter.id);
+                self.saved_filters.push(saved_filter.clone());
+                self.selected_saved_filter = Some(saved_filter);
+            }
+            Message::DeleteSavedFilter => {
+                if let Some(selected) = self.selected_saved_filter.clone() {
+                    if !confirm(format!(
+                        "Do you really want to delete the saved filter {}?",
+                        selected.name
+                    )) {
+                        return Action::None;
+                    }
+                    return Action::Task(error::failing_task(async move {
+                        finance_controller.delete_saved_filter(selected.id).await?;
+                        Ok(Message::SavedFilterDeleted(selected.id))
+                    }));
+                }
+            }
+            Message::SavedFilterDeleted(id) => {
+                self.saved_filters.retain(|x| x.id != id);
+                if self
+                    .selected_saved_filter
+                    .as_ref()
+                    .is_some_and(|x| x.id == id)
+                {
+                    self.selected_saved_filter = None;
+                }
+            }
             Message::ToggleEditFilter => {
                 self.change_filter = if self.change_filter.is_some() {
                     None
@@ -233,7 +402,8 @@ impl View {
             }
             Message::TransactionTable(msg) => {
                 match self.transaction_table.update(msg, finance_controller) {
-                    components::transaction_table::Action::None => return Action::None,
+                    components::transaction_table::Action::None
+                    | components::transaction_table::Action::LoadPage(_) => return Action::None,
                     components::transaction_table::Action::ViewTransaction(id) => {
                         return Action::ViewTransaction(id);
                     }
@@ -250,12 +420,84 @@ impl View {
                     match component.update(*m, utc_offset) {
                         components::filter_component::Action::Submit(new_filter) => {
                             self.change_filter = None;
-                            return Action::Task(self.apply_filter(finance_controller, new_filter));
+                            self.query.clear();
+                            self.query_error = None;
+                            self.expression = None;
+                            return Action::Task(
+                                self.apply_filter(finance_controller, *new_filter),
+                            );
                         }
                         components::filter_component::Action::None => {}
                     }
                 }
             }
+            Message::BulkCategorySelected(category) => self.bulk_category = Some(category),
+            Message::BulkCategoryNegative(negative) => {
+                self.bulk_category_sign = if negative {
+                    fm_core::Sign::Negative
+                } else {
+                    fm_core::Sign::Positive
+                };
+            }
+            Message::BulkSetCategory => {
+                if let Some(category) = self.bulk_category.clone() {
+                    if !confirm(format!(
+                        "Do you really want to set the category {} on all {} filtered transactions?",
+                        category.name,
+                        self.transaction_table.transactions().len()
+                    )) {
+                        return Action::None;
+                    }
+                    let filter = self.filter.clone();
+                    let sign = self.bulk_category_sign;
+                    return Action::Task(error::failing_task(async move {
+                        finance_controller
+                            .set_category_of_filtered_transactions(filter, category.id, sign)
+                            .await?;
+                        Ok(Message::CategoriesChanged)
+                    }));
+                }
+            }
+            Message::BulkRemoveCategory => {
+                if let Some(category) = self.bulk_category.clone() {
+                    if !confirm(format!(
+                        "Do you really want to remove the category {} from all {} filtered transactions?",
+                        category.name,
+                        self.transaction_table.transactions().len()
+                    )) {
+                        return Action::None;
+                    }
+                    let filter = self.filter.clone();
+                    return Action::Task(error::failing_task(async move {
+                        finance_controller
+                            .remove_category_of_filtered_transactions(filter, category.id)
+                            .await?;
+                        Ok(Message::CategoriesChanged)
+                    }));
+                }
+            }
+            Message::MergeFromSelected(category) => self.merge_from = Some(category),
+            Message::MergeIntoSelected(category) => self.merge_into = Some(category),
+            Message::MergeCategories => {
+                if let (Some(from), Some(into)) = (self.merge_from.clone(), &self.merge_into) {
+                    if !confirm(format!(
+                        "Do you really want to merge the category {} into {}? The category {} is deleted afterwards.",
+                        from.name, into.name, from.name
+                    )) {
+                        return Action::None;
+                    }
+                    let into_id = into.id;
+                    self.merge_from = None;
+                    return Action::Task(error::failing_task(async move {
+                        finance_controller
+                            .merge_categories(into_id, from.id)
+                            .await?;
+                        Ok(Message::CategoriesChanged)
+                    }));
+                }
+            }
+            Message::CategoriesChanged => return Action::Task(self.reload(finance_controller)),
+            Message::CategoryPivot => return Action::ViewCategoryPivot(self.filter.clone()),
             Message::CategoryDistribution => {
                 return Action::Task(components::category_distribution_popup(
                     finance_controller,
@@ -282,26 +524,21 @@ impl View {
             let transactions = finance_controller
                 .get_filtered_transactions(filter.clone())
                 .await?;
-            let accounts = finance_controller.get_accounts().await?;
+            update_transactions(finance_controller, transactions).await
+        })
+    }
 
-            let mut tuples = Vec::new();
-            for transaction in transactions {
-                let source = accounts
-                    .iter()
-                    .find(|x| *x.id() == transaction.source)
-                    .context(format!("Could not find account {}", transaction.source))?
-                    .clone();
-                let destination = accounts
-                    .iter()
-                    .find(|x| *x.id() == transaction.destination)
-                    .context(format!(
-                        "Could not find account {}",
-                        transaction.destination
-                    ))?
-                    .clone();
-                tuples.push((transaction, source, destination));
-            }
-            Ok(Message::UpdateTransactions(tuples))
+    fn apply_expression(
+        &mut self,
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+        expression: FilterExpression,
+    ) -> iced::Task<Message> {
+        self.expression = Some(expression.clone());
+        error::failing_task(async move {
+            let transactions = finance_controller
+                .get_transactions_matching(expression)
+                .await?;
+            update_transactions(finance_controller, transactions).await
         })
     }
 
@@ -320,7 +557,93 @@ impl View {
             components::spal_row![
                 components::button::edit_with_text("Edit Filter", Some(Message::ToggleEditFilter)),
                 iced::widget::button("Category Distribution")
-                    .on_press(Message::CategoryDistribution)
+                    .on_press(Message::CategoryDistribution),
+                iced::widget::button("Category Pivot")
+                    .on_press_maybe(self.expression.is_none().then_some(Message::CategoryPivot)),
+            ],
+            components::spal_row![
+                "Saved filter: ",
+                iced::widget::pick_list(
+                    self.saved_filters.as_slice(),
+                    self.selected_saved_filter.as_ref(),
+                    Message::SavedFilterSelected
+                ),
+                iced::widget::text_input("Name", &self.saved_filter_name)
+                    .on_input(Message::SavedFilterNameInput),
+                iced::widget::button("Save").on_press_maybe(
+                    (!self.saved_filter_name.trim().is_empty() && self.expression.is_none())
+                        .then_some(Message::SaveFilter)
+                ),
+                components::button::delete(
+                    self.selected_saved_filter
+                        .as_ref()
+                        .map(|_| Message::DeleteSavedFilter)
+                ),
+            ],
+            components::spal_row![
+                "Query: ",
+                iced::widget::text_input(
+                    "category:Food -account:Savings amount>50 date:2024-01..2024-03 \"amazon\"",
+                    &self.query
+                )
+                .on_input(Message::QueryInput)
+                .on_submit(Message::SubmitQuery)
+                .style(if self.query_error.is_some() {
+                    style::text_input_danger
+                } else {
+                    iced::widget::text_input::default
+                }),
+                iced::widget::button("Apply").on_press(Message::SubmitQuery),
+            ],
+            if let Some(error) = &self.query_error {
+                iced::widget::text(error.to_string()).style(iced::widget::text::danger)
+            } else {
+                iced::widget::text("")
+            },
+            components::spal_row![
+                "Category: ",
+                iced::widget::pick_list(
+                    self.categories.as_slice(),
+                    self.bulk_category.as_ref(),
+                    Message::BulkCategorySelected
+                ),
+                iced::widget::checkbox(
+                    "Negative",
+                    self.bulk_category_sign == fm_core::Sign::Negative
+                )
+                .on_toggle(Message::BulkCategoryNegative),
+                iced::widget::button("Set on filtered").on_press_maybe(
+                    self.bulk_category
+                        .as_ref()
+                        .filter(|_| self.expression.is_none())
+                        .map(|_| Message::BulkSetCategory)
+                ),
+                iced::widget::button("Remove from filtered").on_press_maybe(
+                    self.bulk_category
+                        .as_ref()
+                        .filter(|_| self.expression.is_none())
+                        .map(|_| Message::BulkRemoveCategory)
+                ),
+            ],
+            components::spal_row![
+                "Merge category ",
+                iced::widget::pick_list(
+                    self.categories.as_slice(),
+                    self.merge_from.as_ref(),
+                    Message::MergeFromSelected
+                ),
+                " into ",
+                iced::widget::pick_list(
+                    self.categories.as_slice(),
+                    self.merge_into.as_ref(),
+                    Message::MergeIntoSelected
+                ),
+                iced::widget::button("Merge").on_press_maybe(
+                    match (&self.merge_from, &self.merge_into) {
+                        (Some(from), Some(into)) if from != into => Some(Message::MergeCategories),
+                        _ => None,
+                    }
+                ),
             ],
             if let Some(filter_component) = &self.change_filter {
                 filter_component
@@ -335,3 +658,39 @@ impl View {
         .into()
     }
 }
+
+async fn update_transactions(
+    finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+    transactions: Vec<fm_core::Transaction>,
+) -> anyhow::Result<Message> {
+    let accounts = finance_controller.get_accounts().await?;
+
+    let mut tuples = Vec::new();
+    for transaction in transactions {
+        let source = accounts
+            .iter()
+            .find(|x| *x.id() == transaction.source)
+            .context(format!("Could not find account {}", transaction.source))?
+            .clone();
+        let destination = accounts
+            .iter()
+            .find(|x| *x.id() == transaction.destination)
+            .context(format!(
+                "Could not find account {}",
+                transaction.destination
+            ))?
+            .clone();
+        tuples.push((transaction, source, destination));
+    }
+    Ok(Message::UpdateTransactions(tuples))
+}
+
+fn confirm(description: String) -> bool {
+    rfd::MessageDialog::new()
+        .set_title("Are you sure?")
+        .set_description(description)
+        .set_level(rfd::MessageLevel::Warning)
+        .set_buttons(rfd::MessageButtons::YesNo)
+        .show()
+        == rfd::MessageDialogResult::Yes
+}
@@ -14,6 +14,7 @@ pub enum Message {
     Next,
     Finished,
     SelectOption(Option<usize>),
+    Categories(Vec<fm_core::Category>),
 }
 
 #[derive(Debug)]
@@ -23,6 +24,7 @@ pub struct Import<
 > {
     importer: Arc<Mutex<fm_importer::Importer<FM, P>>>,
     action: Option<(fm_importer::action::Action, Option<usize>)>,
+    categories: Vec<fm_core::Category>,
 }
 
 impl<
@@ -31,12 +33,21 @@ impl<
 > Import<P, FM>
 {
     pub fn new(importer: Arc<Mutex<fm_importer::Importer<FM, P>>>) -> (Self, iced::Task<Message>) {
+        let categories_importer = importer.clone();
         (
             Self {
                 importer,
                 action: None,
+                categories: Vec::new(),
             },
-            iced::Task::done(Message::Next),
+            iced::Task::batch([
+                error::failing_task(async move {
+                    Ok(Message::Categories(
+                        categories_importer.lock().await.categories().to_vec(),
+                    ))
+                }),
+                iced::Task::done(Message::Next),
+            ]),
         )
     }
 
@@ -108,6 +119,10 @@ impl<
                 }
                 Action::None
             }
+            Message::Categories(categories) => {
+                self.categories = categories;
+                Action::None
+            }
             Message::Finished => Action::FinishedImport,
         }
     }
@@ -126,6 +141,7 @@ impl<
                             ) => obj_exists(
                                 account_exists,
                                 *selected_option,
+                                &self.categories,
                                 account_display,
                                 Message::SelectOption,
                             ),
@@ -133,6 +149,7 @@ impl<
                                 obj_exists(
                                     account_exists,
                                     *selected_option,
+                                    &self.categories,
                                     account_display,
                                     Message::SelectOption,
                                 )
@@ -141,6 +158,7 @@ impl<
                                 obj_exists(
                                     transaction_exists,
                                     *selected_option,
+                                    &self.categories,
                                     transaction_display,
                                     Message::SelectOption,
                                 )
@@ -195,7 +213,18 @@ fn account_entry_display<'a, Message: 'a>(
 
 fn transaction_entry_display<'a, Message: 'a>(
     entry: &'a fm_importer::TransactionEntry,
+    categories: &'a [fm_core::Category],
 ) -> iced::Element<'a, Message> {
+    let mut suggestions = components::spaced_column![];
+    for suggestion in &entry.category_suggestions {
+        if let Some(category) = categories.iter().find(|c| c.id == suggestion.category) {
+            suggestions = suggestions.push(widget::text!(
+                "{} ({:.0}%)",
+                category.name,
+                suggestion.confidence * 100.0
+            ));
+        }
+    }
     components::spaced_column![
         components::spaced_row!["Title: ", widget::text(&entry.title)],
         components::spaced_row!["Description: ", widget::text(&entry.description)],
@@ -213,7 +242,8 @@ fn transaction_entry_display<'a, Message: 'a>(
         components::spaced_row![
             "Destination Account: ",
             account_entry_display(&entry.destination_account, &entry.destination_entry)
-        ]
+        ],
+        components::spaced_row!["Suggested Categories: ", suggestions]
     ]
     .into()
 }
@@ -221,6 +251,7 @@ fn transaction_entry_display<'a, Message: 'a>(
 fn obj_exists<'a, T: Clone, Message: Clone + 'a>(
     obj_exists: &'a fm_importer::action::ObjectExists<T>,
     selected_option: Option<usize>,
+    categories: &'a [fm_core::Category],
     display_t: impl Fn(&'a T) -> iced::Element<'a, Message>,
     select_message: impl Fn(Option<usize>) -> Message + Clone,
 ) -> iced::Element<'a, Message> {
@@ -239,7 +270,10 @@ fn obj_exists<'a, T: Clone, Message: Clone + 'a>(
         components::LineSeparatedColumn::default()
             .spacing(style::SPACING)
             .align_x(iced::Alignment::Center)
-            .push(transaction_entry_display(obj_exists.transaction_entry()))
+            .push(transaction_entry_display(
+                obj_exists.transaction_entry(),
+                categories,
+            ))
             .push(option_column),
     )
     .into()
@@ -5,8 +5,10 @@ pub mod bill_overview;
 pub mod book_checking_account_overview;
 pub mod budget;
 pub mod budget_overview;
+pub mod cash_flow;
 pub mod category;
 pub mod category_overview;
+pub mod category_pivot;
 pub mod create_asset_account;
 pub mod create_bill;
 pub mod create_book_checking_account;
@@ -16,6 +18,7 @@ pub mod create_transaction;
 pub mod filter_transactions;
 #[cfg(feature = "native")]
 pub mod importer;
+pub mod net_worth;
 pub mod settings;
 pub mod transaction;
 
@@ -49,6 +52,9 @@ pub enum Message<FM: FinanceManager + 'static> {
     CreateBill(create_bill::Message),
     BillOverview(bill_overview::MessageContainer),
     Bill(bill::MessageContainer),
+    NetWorth(net_worth::Message),
+    CashFlow(cash_flow::Message),
+    CategoryPivot(category_pivot::Message),
     #[cfg(feature = "native")]
     Importer(importer::Message<FM>),
 }
@@ -76,6 +82,9 @@ pub enum View<FM: FinanceManager + 'static> {
     CreateBill(create_bill::View),
     BillOverview(bill_overview::View),
     Bill(bill::View),
+    NetWorth(net_worth::View),
+    CashFlow(cash_flow::View),
+    CategoryPivot(category_pivot::View),
     #[cfg(feature = "native")]
     Importer(importer::Importer<FM>),
 }
@@ -103,6 +112,9 @@ impl<FM: FinanceManager + 'static> std::fmt::Display for View<FM> {
             Self::CreateBill(_) => write!(f, "Create Bill"),
             Self::BillOverview(_) => write!(f, "Bill Overview"),
             Self::Bill(_) => write!(f, "Bill"),
+            Self::NetWorth(_) => write!(f, "Net Worth"),
+            Self::CashFlow(_) => write!(f, "Cash Flow"),
+            Self::CategoryPivot(_) => write!(f, "Category Pivot"),
             #[cfg(feature = "native")]
             Self::Importer(_) => write!(f, "Importer"),
         }
@@ -139,6 +151,9 @@ impl<'a, FM: FinanceManager + 'static> From<&'a View<FM>> for iced::Element<'a,
             View::CreateBill(view) => view.view().map(Message::CreateBill),
             View::BillOverview(view) => view.view().map(Message::BillOverview),
             View::Bill(view) => view.view().map(Message::Bill),
+            View::NetWorth(view) => view.view().map(Message::NetWorth),
+            View::CashFlow(view) => view.view().map(Message::CashFlow),
+            View::CategoryPivot(view) => view.view().map(Message::CategoryPivot),
             #[cfg(feature = "native")]
             View::Importer(view) => view.view().map(Message::Importer),
         }
@@ -197,6 +212,15 @@ impl<FM: FinanceManager + 'static> View<FM> {
                 view.reload(finance_controller).map(Message::CreateCategory)
             }
             Self::CreateBill(view) => view.reload(finance_controller).map(Message::CreateBill),
+            Self::NetWorth(view) => view
+                .reload(finance_controller, utc_offset)
+                .map(Message::NetWorth),
+            Self::CashFlow(view) => view
+                .reload(finance_controller, utc_offset)
+                .map(Message::CashFlow),
+            Self::CategoryPivot(view) => {
+                view.reload(finance_controller).map(Message::CategoryPivot)
+            }
             _ => iced::Task::none(),
         }
     }
@@ -331,6 +355,21 @@ impl<FM: FinanceManager + 'static> View<FM> {
         task.map(Message::FilterTransaction)
     }
 
+    pub fn saved_filter(
+        &mut self,
+        finance_controller: FMController<FM>,
+        saved_filter: fm_core::transaction_filter::SavedFilter,
+        utc_offset: time::UtcOffset,
+    ) -> iced::Task<Message<FM>> {
+        let (view, task) = filter_transactions::View::with_saved_filter(
+            finance_controller,
+            saved_filter,
+            utc_offset,
+        );
+        *self = Self::FilterTransaction(view);
+        task.map(Message::FilterTransaction)
+    }
+
     pub fn transaction(
         &mut self,
         finance_controller: FMController<FM>,
@@ -371,6 +410,28 @@ impl<FM: FinanceManager + 'static> View<FM> {
         task.map(Message::CreateBookCheckingAccount)
     }
 
+    pub fn net_worth(&mut self, finance_controller: FMController<FM>) -> iced::Task<Message<FM>> {
+        let (view, task) = net_worth::View::fetch(finance_controller);
+        *self = Self::NetWorth(view);
+        task.map(Message::NetWorth)
+    }
+
+    pub fn cash_flow(&mut self, finance_controller: FMController<FM>) -> iced::Task<Message<FM>> {
+        let (view, task) = cash_flow::View::fetch(finance_controller);
+        *self = Self::CashFlow(view);
+        task.map(Message::CashFlow)
+    }
+
+    pub fn category_pivot(
+        &mut self,
+        finance_controller: FMController<FM>,
+        filter: fm_core::transaction_filter::TransactionFilter,
+    ) -> iced::Task<Message<FM>> {
+        let (view, task) = category_pivot::View::fetch(finance_controller, filter);
+        *self = Self::CategoryPivot(view);
+        task.map(Message::CategoryPivot)
+    }
+
     pub fn new_bill_with_transaction(
         &mut self,
         finance_controller: FMController<FM>,
@@ -646,10 +707,10 @@ pub fn view_update<FM: FinanceManager + 'static>(
                 }
                 book_checking_account_overview::Action::None => Action::None,
                 book_checking_account_overview::Action::CreateNewAccount => {
-                    *view = View::CreateBookCheckingAccount(
-                        create_book_checking_account::View::default(),
-                    );
-                    Action::None
+                    let (new_view, task) =
+                        create_book_checking_account::View::new(finance_controller.clone());
+                    *view = View::CreateBookCheckingAccount(new_view);
+                    Action::Task(task.map(Message::CreateBookCheckingAccount))
                 }
                 book_checking_account_overview::Action::Task(task) => {
                     Action::Task(task.map(Message::BookCheckingAccountOverview))
@@ -661,7 +722,8 @@ pub fn view_update<FM: FinanceManager + 'static>(
                 view,
                 View::CreateBookCheckingAccount,
                 m,
-                finance_controller.clone()
+                finance_controller.clone(),
+                utc_offset
             ) {
                 create_book_checking_account::Action::Task(t) => {
                     Action::Task(t.map(Message::CreateBookCheckingAccount))
@@ -687,6 +749,38 @@ pub fn view_update<FM: FinanceManager + 'static>(
                 settings::Action::Task(task) => Action::Task(task.map(Message::Settings)),
             }
         }
+        Message::NetWorth(m) => {
+            match message_match_action!(
+                view,
+                View::NetWorth,
+                m,
+                finance_controller.clone(),
+                utc_offset
+            ) {
+                net_worth::Action::None => Action::None,
+                net_worth::Action::Task(task) => Action::Task(task.map(Message::NetWorth)),
+            }
+        }
+        Message::CashFlow(m) => {
+            match message_match_action!(
+                view,
+                View::CashFlow,
+                m,
+                finance_controller.clone(),
+                utc_offset
+            ) {
+                cash_flow::Action::None => Action::None,
+                cash_flow::Action::Task(task) => Action::Task(task.map(Message::CashFlow)),
+            }
+        }
+        Message::CategoryPivot(m) => {
+            match message_match_action!(view, View::CategoryPivot, m, finance_controller.clone()) {
+                category_pivot::Action::None => Action::None,
+                category_pivot::Action::Task(task) => {
+                    Action::Task(task.map(Message::CategoryPivot))
+                }
+            }
+        }
         Message::FilterTransaction(m) => {
             match message_match_action!(
                 view,
@@ -705,6 +799,9 @@ pub fn view_update<FM: FinanceManager + 'static>(
                 filter_transactions::Action::Task(t) => {
                     Action::Task(t.map(Message::FilterTransaction))
                 }
+                filter_transactions::Action::ViewCategoryPivot(filter) => {
+                    Action::Task(view.category_pivot(finance_controller.clone(), filter))
+                }
             }
         }
         Message::CreateBill(m) => {
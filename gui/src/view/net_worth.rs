@@ -0,0 +1,112 @@
+use components::date_time::date_span_input;
+use iced::widget;
+
+pub enum Action {
+    None,
+    Task(iced::Task<Message>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ChangedTimespan(date_span_input::Action),
+    IntervalSelected(fm_core::Interval),
+    Set(Vec<(fm_core::DateTime, fm_core::Currency)>),
+}
+
+#[derive(Debug)]
+pub struct View {
+    loaded: bool,
+    chart: components::LineChart,
+    timespan_input: date_span_input::State,
+    interval: fm_core::Interval,
+}
+
+impl View {
+    pub fn fetch(
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+    ) -> (Self, iced::Task<Message>) {
+        let interval = fm_core::Interval::Month;
+        (
+            Self {
+                loaded: false,
+                chart: components::LineChart::default(),
+                timespan_input: date_span_input::State::default(),
+                interval,
+            },
+            Self::load(finance_controller, (None, None), interval),
+        )
+    }
+
+    pub fn reload(
+        &mut self,
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+        utc_offset: time::UtcOffset,
+    ) -> iced::Task<Message> {
+        Self::load(
+            finance_controller,
+            components::date_time::date_span_to_time_span(
+                self.timespan_input.timespan(),
+                utc_offset,
+            ),
+            self.interval,
+        )
+    }
+
+    pub fn update(
+        &mut self,
+        message: Message,
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+        utc_offset: time::UtcOffset,
+    ) -> Action {
+        match message {
+            Message::ChangedTimespan(action) => {
+                self.timespan_input.perform(action);
+                Action::Task(self.reload(finance_controller, utc_offset))
+            }
+            Message::IntervalSelected(interval) => {
+                self.interval = interval;
+                Action::Task(self.reload(finance_controller, utc_offset))
+            }
+            Message::Set(values) => {
+                self.loaded = true;
+                self.chart.set_values(values);
+                Action::None
+            }
+        }
+    }
+
+    pub fn view(&self) -> iced::Element<'_, Message> {
+        if !self.loaded {
+            return widget::text("Loading...").into();
+        }
+        components::spaced_column![
+            components::heading("Net Worth", components::HeadingLevel::H1),
+            components::spal_row![
+                date_span_input::date_span_input(&self.timespan_input)
+                    .view()
+                    .map(Message::ChangedTimespan),
+                widget::pick_list(
+                    fm_core::Interval::ALL,
+                    Some(self.interval),
+                    Message::IntervalSelected
+                ),
+            ],
+            self.chart.view(),
+        ]
+        .height(iced::Fill)
+        .width(iced::Fill)
+        .into()
+    }
+
+    fn load(
+        finance_controller: fm_core::FMController<impl fm_core::FinanceManager>,
+        timespan: fm_core::Timespan,
+        interval: fm_core::Interval,
+    ) -> iced::Task<Message> {
+        error::failing_task(async move {
+            Ok(Message::Set(
+                finance_controller.net_worth(timespan, interval).await?,
+            ))
+        })
+    }
+}
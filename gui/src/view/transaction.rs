@@ -230,8 +230,18 @@ impl View {
                         components::date_time::offset_to_primitive(transaction.date)
                     )
                 ),
+                widget::text!("Status: {}", transaction.status),
             ];
 
+            if let Some(value_date) = transaction.value_date {
+                column = column.push(widget::text!(
+                    "Value date: {}",
+                    components::date_time::to_date_time_string(
+                        components::date_time::offset_to_primitive(value_date)
+                    )
+                ));
+            }
+
             if let Some(budget) = &budget {
                 column = column.push(components::spal_row![
                     components::link(widget::text!("Budget: {}", &budget.name))
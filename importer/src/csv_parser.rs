@@ -23,6 +23,8 @@ pub struct CSVParser<D: Read> {
     other_name: Box<dyn Fn(&csv::StringRecord) -> Option<String> + Send + Sync>,
     other_bic: Box<dyn Fn(&csv::StringRecord) -> Option<String> + Send + Sync>,
     date: Box<dyn Fn(&csv::StringRecord) -> fm_core::DateTime + Send + Sync>,
+    status: Box<dyn Fn(&csv::StringRecord) -> fm_core::TransactionStatus + Send + Sync>,
+    value_date: Box<dyn Fn(&csv::StringRecord) -> Option<fm_core::DateTime> + Send + Sync>,
     delimiter: u8,
 }
 
@@ -49,6 +51,8 @@ impl<D: Read> CSVParser<D> {
         other_name: impl Fn(&csv::StringRecord) -> Option<String> + Send + Sync + 'static,
         other_bic: impl Fn(&csv::StringRecord) -> Option<String> + Send + Sync + 'static,
         date: impl Fn(&csv::StringRecord) -> fm_core::DateTime + Send + Sync + 'static,
+        status: impl Fn(&csv::StringRecord) -> fm_core::TransactionStatus + Send + Sync + 'static,
+        value_date: impl Fn(&csv::StringRecord) -> Option<fm_core::DateTime> + Send + Sync + 'static,
     ) -> Result<Self> {
         // skip the first line because it is the header
         let mut data = data;
@@ -68,6 +72,8 @@ impl<D: Read> CSVParser<D> {
             other_name: Box::new(other_name),
             other_bic: Box::new(other_bic),
             date: Box::new(date),
+            status: Box::new(status),
+            value_date: Box::new(value_date),
             format_name,
         })
     }
@@ -130,30 +136,42 @@ impl<D: Read> CSVParser<D> {
                 (self.source_bic)(&record),
             )
         };
-        Ok(Some(IterOption::Entry(Box::new(
-            super::TransactionEntry::new(
-                raw,
-                (self.title)(&record),
-                (self.description)(&record),
-                if value.get_eur_num() < 0.0 {
-                    value.negative()
-                } else {
-                    value.clone()
-                },
-                super::AccountEntry::new(
-                    source_name,
-                    source_iban.parse()?,
-                    source_bic.map(|x| x.into()),
-                ),
-                super::AccountEntry::new(
-                    destination_name,
-                    destination_iban.parse()?,
-                    destination_bic.map(|x| x.into()),
-                ),
-                (self.date)(&record),
-            )?,
-        ))))
+        let mut entry = super::TransactionEntry::new(
+            raw,
+            (self.title)(&record),
+            (self.description)(&record),
+            if value.get_eur_num() < 0.0 {
+                value.negative()
+            } else {
+                value.clone()
+            },
+            super::AccountEntry::new(
+                source_name,
+                source_iban.parse()?,
+                source_bic.and_then(parse_bic),
+            ),
+            super::AccountEntry::new(
+                destination_name,
+                destination_iban.parse()?,
+                destination_bic.and_then(parse_bic),
+            ),
+            (self.date)(&record),
+        )?;
+        entry.status = (self.status)(&record);
+        entry.value_date =
+            (self.value_date)(&record).filter(|value_date| *value_date != entry.date);
+        Ok(Some(IterOption::Entry(Box::new(entry))))
+    }
+}
+
+/// Invalid or empty BICs in statements are ignored instead of failing the whole import.
+fn parse_bic(bic: String) -> Option<fm_core::Bic> {
+    if bic.trim().is_empty() {
+        return None;
     }
+    bic.parse()
+        .inspect_err(|error| tracing::warn!("ignored invalid BIC {bic}: {error}"))
+        .ok()
 }
 
 impl<D: Read + Send> super::Parser for CSVParser<D> {
@@ -183,7 +201,7 @@ pub fn csv_camt_v2_parser<D: Read>(utf8_data: D) -> Result<CSVParser<D>> {
         let mut parsed = time::parsing::Parsed::new();
         parsed.parse_items(
             date.as_bytes(),
-            &time::format_description::parse("[day].[month].[year repr:last_two]")
+            &time::format_description::parse_borrowed::<1>("[day].[month].[year repr:last_two]")
                 .context("Could not create format description")?,
         )?;
 
@@ -203,7 +221,12 @@ pub fn csv_camt_v2_parser<D: Read>(utf8_data: D) -> Result<CSVParser<D>> {
         BufReader::new(utf8_data),
         "CSV_CAMT_V2".to_string(),
         b';',
-        |record| record.get(16).unwrap() != "Umsatz gebucht",
+        |record| {
+            !matches!(
+                record.get(16).unwrap(),
+                "Umsatz gebucht" | "Umsatz vorgemerkt"
+            )
+        },
         |record| record.get(3).unwrap().to_string(),
         |record| {
             if record.get(15).unwrap() != "EUR" {
@@ -229,6 +252,24 @@ pub fn csv_camt_v2_parser<D: Read>(utf8_data: D) -> Result<CSVParser<D>> {
         |record| record.get(12).unwrap().to_string(),
         |record| Some(record.get(11).unwrap().to_string()),
         |record| Some(record.get(13).unwrap().to_string()),
-        |record| parse_to_datetime(record.get(1).unwrap()).unwrap(),
+        // pending entries might not have a booking date yet
+        |record| {
+            let date = match record.get(1).unwrap() {
+                "" => record.get(2).unwrap(),
+                date => date,
+            };
+            parse_to_datetime(date).unwrap()
+        },
+        |record| {
+            if record.get(16).unwrap() == "Umsatz vorgemerkt" {
+                fm_core::TransactionStatus::Pending
+            } else {
+                fm_core::TransactionStatus::Booked
+            }
+        },
+        |record| match record.get(2).unwrap() {
+            "" => None,
+            date => Some(parse_to_datetime(date).unwrap()),
+        },
     )
 }
@@ -5,12 +5,22 @@ use fm_core::FinanceManager;
 use std::collections::HashMap;
 
 pub mod csv_parser;
+pub mod price_csv;
 pub mod terminal_importer;
 
 const VERSION: u8 = 1;
 const METADATA_IMPORTER_VERSION: &str = "importer-version";
 const METADATA_RAW_CONTENT: &str = "importer-raw-content";
 const METADATA_IMPORT_FORMAT: &str = "importer-import-format";
+const CATEGORY_SUGGESTION_LIMIT: usize = 3;
+const DEFAULT_TRANSFER_DATE_DIFFERENCE: time::Duration = time::Duration::days(3);
+/// How long a pending transaction can take until it is booked.
+const PENDING_DATE_DIFFERENCE: time::Duration = time::Duration::days(14);
+/// Relative difference the booked amount can have to the pending amount, e.g. because of tips.
+const PENDING_AMOUNT_TOLERANCE: f64 = 0.2;
+/// Appended to the importer metadata keys to keep the fields of the pending entry
+/// when a pending transaction is booked.
+const METADATA_PENDING_SUFFIX: &str = "-pending";
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct AccountEntry {
@@ -49,6 +59,11 @@ pub struct TransactionEntry {
     pub date: fm_core::DateTime,
     pub source_account: Option<fm_core::account::Account>,
     pub destination_account: Option<fm_core::account::Account>,
+    /// Categories suggested from the already existing transactions, sorted by confidence.
+    pub category_suggestions: Vec<fm_core::category_suggestion::CategorySuggestion>,
+    pub status: fm_core::TransactionStatus,
+    /// Only set if it differs from the booking date.
+    pub value_date: Option<fm_core::DateTime>,
 }
 
 impl TransactionEntry {
@@ -74,6 +89,9 @@ impl TransactionEntry {
             date,
             source_account: None,
             destination_account: None,
+            category_suggestions: Vec::new(),
+            status: fm_core::TransactionStatus::Booked,
+            value_date: None,
         })
     }
 }
@@ -166,7 +184,11 @@ pub struct Importer<FM: fm_core::FinanceManager + 'static, P: Parser> {
     fm_controller: fm_core::FMController<FM>,
     cached_accounts: Vec<fm_core::account::Account>,
     cached_transactions: Vec<fm_core::Transaction>,
+    cached_categories: Vec<fm_core::Category>,
     saved_account_decisions: Vec<(AccountEntry, fm_core::account::Account)>,
+    category_model: fm_core::category_suggestion::CategoryModel,
+    category_threshold: Option<f64>,
+    transfer_date_difference: Option<time::Duration>,
 }
 
 impl<FM: fm_core::FinanceManager, P: Parser> Importer<FM, P> {
@@ -175,19 +197,61 @@ impl<FM: fm_core::FinanceManager, P: Parser> Importer<FM, P> {
         let cached_transactions = fm_controller
             .get_transactions_in_timespan((None, None))
             .await?;
+        let cached_categories = fm_controller.get_categories().await?;
+        let category_model = fm_core::category_suggestion::CategoryModel::new(&cached_transactions);
 
         Ok(Self {
             parser: importer,
             fm_controller,
             cached_accounts,
             cached_transactions,
+            cached_categories,
             saved_account_decisions: Vec::new(),
+            category_model,
+            category_threshold: None,
+            transfer_date_difference: Some(DEFAULT_TRANSFER_DATE_DIFFERENCE),
         })
     }
 
+    /// The categories that can be suggested for the transaction entries.
+    pub fn categories(&self) -> &[fm_core::Category] {
+        &self.cached_categories
+    }
+
+    /// If set, the best category suggestion is assigned to the created transactions
+    /// when its confidence is at least `threshold`.
+    pub fn set_category_threshold(&mut self, threshold: Option<f64>) {
+        self.category_threshold = threshold;
+    }
+
+    /// Sets how far apart the dates of the two sides of a transfer between own asset accounts can be.
+    /// If a created transaction and an existing transaction are such a transfer, they get merged.
+    /// `None` disables the transfer detection.
+    pub fn set_transfer_detection(&mut self, max_date_difference: Option<time::Duration>) {
+        self.transfer_date_difference = max_date_difference;
+    }
+
+    fn suggest_categories(
+        &self,
+        transaction_entry: &TransactionEntry,
+    ) -> Vec<fm_core::category_suggestion::CategorySuggestion> {
+        let mut suggestions = self.category_model.suggest(
+            &transaction_entry.title,
+            transaction_entry.source_account.as_ref().map(|a| *a.id()),
+            transaction_entry
+                .destination_account
+                .as_ref()
+                .map(|a| *a.id()),
+            &transaction_entry.value,
+        );
+        suggestions.truncate(CATEGORY_SUGGESTION_LIMIT);
+        suggestions
+    }
+
     pub async fn next(&mut self) -> Result<Option<action::Action>> {
         tracing::debug!("Next transaction entry");
         if let Some(mut transaction_entry) = self.parser.next_entry().await? {
+            transaction_entry.category_suggestions = self.suggest_categories(&transaction_entry);
             // check if the transactions exists
             if let Some(a) = self
                 .transaction_exists(
@@ -236,6 +300,9 @@ impl<FM: fm_core::FinanceManager, P: Parser> Importer<FM, P> {
             action::Action::SourceAccountExists(object_exists) => {
                 // create account if it does not exist
                 let selected_account = if let Some(acc) = object_exists.selected_object() {
+                    let acc = self
+                        .learn_account_entry(acc, &object_exists.transaction_entry.source_entry)
+                        .await?;
                     self.saved_account_decisions.push((
                         object_exists.transaction_entry.source_entry.clone(),
                         acc.clone(),
@@ -268,6 +335,12 @@ impl<FM: fm_core::FinanceManager, P: Parser> Importer<FM, P> {
             action::Action::DestinationAccountExists(object_exists) => {
                 // create account if it does not exist
                 let selected_account = if let Some(acc) = object_exists.selected_object() {
+                    let acc = self
+                        .learn_account_entry(
+                            acc,
+                            &object_exists.transaction_entry.destination_entry,
+                        )
+                        .await?;
                     self.saved_account_decisions.push((
                         object_exists.transaction_entry.destination_entry.clone(),
                         acc.clone(),
@@ -346,6 +419,7 @@ impl<FM: fm_core::FinanceManager, P: Parser> Importer<FM, P> {
                 (set_account)(transaction_entry, Some(account));
             }
             AccountExistsResult::PossibleAccounts(accounts) => {
+                transaction_entry.category_suggestions = self.suggest_categories(transaction_entry);
                 // create action to select account
                 return Ok(Some((exists_action)(action::ObjectExists::new(
                     accounts,
@@ -370,21 +444,30 @@ impl<FM: fm_core::FinanceManager, P: Parser> Importer<FM, P> {
         let mut possible_accounts = Vec::new();
 
         for account in &self.cached_accounts {
-            if let Some(account_iban) = account.iban() {
-                if account_entry.iban() == account_iban
-                    && if let Some(entry_name) = account_entry.name() {
-                        entry_name == account.name()
-                    } else {
-                        true
-                    }
+            if account.has_account_id(account_entry.iban()) {
+                if account_entry
+                    .name()
+                    .as_ref()
+                    .is_none_or(|entry_name| account.has_name(entry_name))
                 {
                     return AccountExistsResult::Found(account.clone());
-                } else if account_entry.iban() == account_iban {
-                    possible_accounts.push(account.clone());
                 }
+                possible_accounts.push(account.clone());
             }
         }
 
+        // the account might be known under a name but not with this id yet
+        if possible_accounts.is_empty()
+            && let Some(entry_name) = account_entry.name()
+        {
+            possible_accounts.extend(
+                self.cached_accounts
+                    .iter()
+                    .filter(|account| account.has_name(entry_name))
+                    .cloned(),
+            );
+        }
+
         if possible_accounts.is_empty() {
             AccountExistsResult::NotFond
         } else {
@@ -392,10 +475,53 @@ impl<FM: fm_core::FinanceManager, P: Parser> Importer<FM, P> {
         }
     }
 
+    /// Adds the id and the name of the entry to the selected account.
+    /// The account is found directly the next time it shows up with the same id and name.
+    async fn learn_account_entry(
+        &mut self,
+        mut account: fm_core::account::Account,
+        account_entry: &AccountEntry,
+    ) -> Result<fm_core::account::Account> {
+        let mut changed = account.add_account_id(account_entry.iban().to_owned());
+        if let Some(name) = account_entry.name() {
+            changed |= account.add_alias(name.to_owned());
+        }
+        if !changed {
+            return Ok(account);
+        }
+
+        let account: fm_core::account::Account = match account {
+            fm_core::account::Account::AssetAccount(acc) => {
+                self.fm_controller.update_asset_account(acc).await?.into()
+            }
+            fm_core::account::Account::BookCheckingAccount(acc) => self
+                .fm_controller
+                .update_book_checking_account(acc)
+                .await?
+                .into(),
+        };
+        if let Some(cached_account) = self
+            .cached_accounts
+            .iter_mut()
+            .find(|x| x.id() == account.id())
+        {
+            *cached_account = account.clone();
+        }
+        tracing::info!("Account updated with new id or alias: {:?}", account);
+        Ok(account)
+    }
+
     async fn create_book_checking_account(
         &mut self,
         account_entry: &AccountEntry,
     ) -> Result<fm_core::account::Account> {
+        let mut bic = account_entry.bic().map(|s| s.to_owned());
+        if let Err(error) =
+            fm_core::account::check_iban_bic_country(Some(account_entry.iban()), bic.as_ref())
+        {
+            tracing::warn!("ignored BIC of new account: {error}");
+            bic = None;
+        }
         let account = self
             .fm_controller
             .create_book_checking_account(
@@ -405,7 +531,7 @@ impl<FM: fm_core::FinanceManager, P: Parser> Importer<FM, P> {
                     .unwrap_or(account_entry.iban().to_string()),
                 None,
                 Some(account_entry.iban().to_owned()),
-                account_entry.bic().map(|s| s.to_owned()),
+                bic,
             )
             .await?;
 
@@ -429,12 +555,31 @@ impl<FM: fm_core::FinanceManager, P: Parser> Importer<FM, P> {
 
         for transaction in &self.cached_transactions {
             // check for importer specific fields
-            if let Some(parser_content) = transaction.metadata.get(METADATA_RAW_CONTENT)
-                && let Some(import_format) = transaction.metadata.get(METADATA_IMPORT_FORMAT)
-                && *parser_content == transaction_entry.raw_data
-                && *import_format == format_name
+            // merged transfers contain the fields of both imported transactions
+            // and booked transactions the fields of their pending entry
+            for suffix in [
+                "",
+                fm_core::transfer::MERGED_METADATA_SUFFIX,
+                METADATA_PENDING_SUFFIX,
+            ] {
+                if let Some(parser_content) = transaction
+                    .metadata
+                    .get(&format!("{METADATA_RAW_CONTENT}{suffix}"))
+                    && let Some(import_format) = transaction
+                        .metadata
+                        .get(&format!("{METADATA_IMPORT_FORMAT}{suffix}"))
+                    && *parser_content == transaction_entry.raw_data
+                    && *import_format == format_name
+                {
+                    return Ok(Some(action::Action::None));
+                }
+            }
+
+            // the booked entry of a pending transaction books it instead of being a duplicate
+            if transaction.status == fm_core::TransactionStatus::Pending
+                && transaction_entry.status == fm_core::TransactionStatus::Booked
             {
-                return Ok(Some(action::Action::None));
+                continue;
             }
 
             let source_acc = match accounts.iter().find(|a| *a.id() == transaction.id).cloned() {
@@ -450,18 +595,10 @@ impl<FM: fm_core::FinanceManager, P: Parser> Importer<FM, P> {
             // check for general fields
             if transaction.amount() == &transaction_entry.value
             && transaction.date.replace_offset(time::UtcOffset::UTC).date() == transaction_entry.date.replace_offset(time::UtcOffset::UTC).date()
-            // check if source iban is equal
-            && if let Some(source_iban) = source_acc.iban() {
-                source_iban == transaction_entry.source_entry.iban()
-            } else {
-                false
-            }
-            // check if destination iban is equal
-            && if let Some(destination_iban) = destination_acc.iban() {
-                destination_iban == transaction_entry.destination_entry.iban()
-            } else {
-                false
-            }
+            // check if source iban is one of the account ids
+            && source_acc.has_account_id(transaction_entry.source_entry.iban())
+            // check if destination iban is one of the account ids
+            && destination_acc.has_account_id(transaction_entry.destination_entry.iban())
             // check if source bic is equal
             && if let Some(source_bic) = source_acc.bic() {
                 if let Some(entry_source_bic) = transaction_entry.source_entry.bic() {
@@ -512,38 +649,169 @@ impl<FM: fm_core::FinanceManager, P: Parser> Importer<FM, P> {
             .as_ref()
             .map(|a| *a.id())
             .unwrap();
+        if transaction_entry.status == fm_core::TransactionStatus::Booked
+            && let Some(pending) = self.pending_transaction(transaction_entry, source, destination)
+        {
+            return self.book_pending(pending, transaction_entry).await;
+        }
+        // the accounts are known now which improves the suggestions
+        let categories = match (
+            self.category_threshold,
+            self.suggest_categories(transaction_entry).first(),
+        ) {
+            (Some(threshold), Some(suggestion)) if suggestion.confidence >= threshold => {
+                HashMap::from([(suggestion.category, suggestion.sign)])
+            }
+            _ => HashMap::new(),
+        };
+        let mut transaction = fm_core::Transaction::new(
+            0,
+            transaction_entry.value.clone(),
+            transaction_entry.title.clone(),
+            Some(transaction_entry.description.clone()),
+            source,
+            destination,
+            None,
+            transaction_entry.date,
+            importer_metadata(transaction_entry),
+            categories,
+        )?;
+        transaction.status = transaction_entry.status;
+        transaction.value_date = transaction_entry.value_date;
         let transaction = self
             .fm_controller
-            .create_transaction(
-                transaction_entry.value.clone(),
-                transaction_entry.title.clone(),
-                Some(transaction_entry.description.clone()),
-                source,
-                destination,
-                None,
-                transaction_entry.date,
-                HashMap::from([
-                    (
-                        METADATA_RAW_CONTENT.to_string(),
-                        transaction_entry.raw_data.clone(),
-                    ),
-                    (
-                        METADATA_IMPORT_FORMAT.to_string(),
-                        "CSV_CAMT_V2".to_string(),
-                    ),
-                    (METADATA_IMPORTER_VERSION.to_string(), VERSION.to_string()),
-                ]),
-                HashMap::new(),
-            )
+            .create_transaction_from(transaction)
             .await?;
-        self.cached_transactions.push(transaction.clone());
+        self.category_model.train(&transaction);
 
         tracing::info!("Transaction created: {:?}", transaction);
 
+        if let Some(max_date_difference) = self.transfer_date_difference
+            && let Some(transfer) = fm_core::transfer::find_transfer(
+                &transaction,
+                &self.cached_accounts,
+                &self.cached_transactions,
+                max_date_difference,
+            )
+        {
+            let remove_id = transfer.remove.id;
+            let merged = self
+                .fm_controller
+                .merge_transfer(transfer)
+                .await
+                .context("Error while merging a transfer")?;
+            self.cached_transactions
+                .retain(|t| t.id != remove_id && t.id != merged.id);
+            self.cached_transactions.push(merged.clone());
+
+            tracing::info!("Transfer merged: {:?}", merged);
+
+            return Ok(merged);
+        }
+
+        self.cached_transactions.push(transaction.clone());
+
+        Ok(transaction)
+    }
+
+    /// Finds the imported pending transaction that is booked by the entry.
+    /// The amount of a pending transaction can still change until it is booked,
+    /// so closer amounts and then closer dates are preferred.
+    fn pending_transaction(
+        &self,
+        transaction_entry: &TransactionEntry,
+        source: fm_core::Id,
+        destination: fm_core::Id,
+    ) -> Option<fm_core::Transaction> {
+        self.cached_transactions
+            .iter()
+            .filter(|transaction| {
+                transaction.status == fm_core::TransactionStatus::Pending
+                    && transaction.source == source
+                    && transaction.destination == destination
+                    && transaction.metadata.contains_key(METADATA_RAW_CONTENT)
+                    && (transaction.date - transaction_entry.date).abs() <= PENDING_DATE_DIFFERENCE
+            })
+            .filter_map(|transaction| {
+                pending_amount_difference(transaction.amount(), &transaction_entry.value)
+                    .map(|difference| (transaction, difference))
+            })
+            .min_by(|(a, a_difference), (b, b_difference)| {
+                a_difference.total_cmp(b_difference).then_with(|| {
+                    (a.date - transaction_entry.date)
+                        .abs()
+                        .cmp(&(b.date - transaction_entry.date).abs())
+                })
+            })
+            .map(|(transaction, _)| transaction.clone())
+    }
+
+    /// Updates the pending transaction with the booked entry.
+    /// Categories and the budget assigned in the meantime are kept.
+    async fn book_pending(
+        &mut self,
+        mut transaction: fm_core::Transaction,
+        transaction_entry: &TransactionEntry,
+    ) -> Result<fm_core::Transaction> {
+        for key in [METADATA_RAW_CONTENT, METADATA_IMPORT_FORMAT] {
+            if let Some(value) = transaction.metadata.remove(key) {
+                transaction
+                    .metadata
+                    .insert(format!("{key}{METADATA_PENDING_SUFFIX}"), value);
+            }
+        }
+        transaction
+            .metadata
+            .extend(importer_metadata(transaction_entry));
+        transaction.set_amount(transaction_entry.value.clone())?;
+        transaction.title = transaction_entry.title.clone();
+        transaction.description = Some(transaction_entry.description.clone());
+        transaction.date = transaction_entry.date;
+        transaction.value_date = transaction_entry.value_date;
+        transaction.status = fm_core::TransactionStatus::Booked;
+        let transaction = self.fm_controller.update_transaction(transaction).await?;
+
+        tracing::info!("Pending transaction booked: {:?}", transaction);
+
+        if let Some(cached) = self
+            .cached_transactions
+            .iter_mut()
+            .find(|t| t.id == transaction.id)
+        {
+            *cached = transaction.clone();
+        }
         Ok(transaction)
     }
 }
 
+/// The difference between the pending and the booked amount
+/// or `None` if it exceeds [`PENDING_AMOUNT_TOLERANCE`] or the currencies differ.
+fn pending_amount_difference(
+    pending: &fm_core::Currency,
+    booked: &fm_core::Currency,
+) -> Option<f64> {
+    if pending.get_currency_id() != booked.get_currency_id() {
+        return None;
+    }
+    let difference = (pending.get_eur_num() - booked.get_eur_num()).abs();
+    (difference <= pending.get_eur_num().abs() * PENDING_AMOUNT_TOLERANCE).then_some(difference)
+}
+
+fn importer_metadata(transaction_entry: &TransactionEntry) -> HashMap<String, String> {
+    HashMap::from([
+        (
+            METADATA_RAW_CONTENT.to_string(),
+            transaction_entry.raw_data.clone(),
+        ),
+        (
+            METADATA_IMPORT_FORMAT.to_string(),
+            "CSV_CAMT_V2".to_string(),
+        ),
+        (METADATA_IMPORTER_VERSION.to_string(), VERSION.to_string()),
+    ])
+}
+
+#[allow(clippy::large_enum_variant)]
 enum AccountExistsResult {
     NotFond,
     Found(fm_core::account::Account),
@@ -556,3 +824,111 @@ pub async fn csv_camt_v2_importer<D: std::io::Read + Send, FM: FinanceManager>(
 ) -> Result<Importer<FM, CSVParser<D>>> {
     Importer::new(csv_parser::csv_camt_v2_parser(data)?, fm_controller).await
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CAMT_V2_HEADER: &str = "\"Auftragskonto\";\"Buchungstag\";\"Valutadatum\";\"Buchungstext\";\"Verwendungszweck\";\"Glaeubiger ID\";\"Mandatsreferenz\";\"Kundenreferenz (End-to-End)\";\"Sammlerreferenz\";\"Lastschrift Ursprungsbetrag\";\"Auslagenersatz Ruecklastschrift\";\"Beguenstigter/Zahlungspflichtiger\";\"Kontonummer/IBAN\";\"BIC (SWIFT-Code)\";\"Betrag\";\"Waehrung\";\"Info\"";
+
+    /// A card payment at a supermarket in the CAMT V2 format.
+    fn card_payment(booking_date: &str, value_date: &str, amount: &str, info: &str) -> String {
+        format!(
+            "\"DE89370400440532013000\";\"{booking_date}\";\"{value_date}\";\"KARTENZAHLUNG\";\"Supermarkt\";\"\";\"\";\"\";\"\";\"\";\"\";\"Supermarkt GmbH\";\"DE02120300000000202051\";\"BYLADEM1001\";\"{amount}\";\"EUR\";\"{info}\""
+        )
+    }
+
+    /// Imports the rows as one CAMT V2 file without any decisions to make.
+    async fn import(
+        fm: &fm_core::FMController<fm_core::managers::RamFinanceManager>,
+        rows: &[&str],
+    ) {
+        let data = format!("{CAMT_V2_HEADER}\n{}\n", rows.join("\n"));
+        let mut importer = csv_camt_v2_importer(std::io::Cursor::new(data), fm.clone())
+            .await
+            .unwrap();
+        while let Some(action) = importer.next().await.unwrap() {
+            assert!(matches!(action, action::Action::None), "{action:?}");
+        }
+    }
+
+    fn new_fm() -> fm_core::FMController<fm_core::managers::RamFinanceManager> {
+        fm_core::FMController::with_finance_manager(
+            fm_core::managers::RamFinanceManager::new(()).unwrap(),
+        )
+    }
+
+    async fn transactions(
+        fm: &fm_core::FMController<fm_core::managers::RamFinanceManager>,
+    ) -> Vec<fm_core::Transaction> {
+        fm.get_transactions_in_timespan((None, None)).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn book_pending_transaction() {
+        let fm = new_fm();
+        let pending = card_payment("", "15.01.24", "-12,34", "Umsatz vorgemerkt");
+        import(&fm, &[&pending]).await;
+        let created = transactions(&fm).await;
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].status, fm_core::TransactionStatus::Pending);
+
+        // booked some days later within the pending date difference and with a tip
+        let booked = card_payment("18.01.24", "15.01.24", "-13,50", "Umsatz gebucht");
+        import(&fm, &[&booked]).await;
+        // a later export still containing the pending entry
+        import(&fm, &[&pending, &booked]).await;
+
+        let transactions = transactions(&fm).await;
+        assert_eq!(transactions.len(), 1);
+        let transaction = &transactions[0];
+        assert_eq!(transaction.id, created[0].id);
+        assert_eq!(transaction.status, fm_core::TransactionStatus::Booked);
+        assert_eq!(transaction.amount().get_eur_num(), 13.5);
+        assert_eq!(transaction.date.date(), time::macros::date!(2024 - 01 - 18));
+        assert_eq!(
+            transaction.value_date.map(|date| date.date()),
+            Some(time::macros::date!(2024 - 01 - 15))
+        );
+        assert_eq!(transaction.metadata[METADATA_RAW_CONTENT], booked);
+        assert_eq!(
+            transaction.metadata[&format!("{METADATA_RAW_CONTENT}{METADATA_PENDING_SUFFIX}")],
+            pending
+        );
+    }
+
+    #[tokio::test]
+    async fn booked_outside_of_pending_differences() {
+        let fm = new_fm();
+        import(
+            &fm,
+            &[&card_payment("", "15.01.24", "-12,34", "Umsatz vorgemerkt")],
+        )
+        .await;
+        import(
+            &fm,
+            &[
+                // outside of the pending date difference
+                &card_payment("14.02.24", "14.02.24", "-12,34", "Umsatz gebucht"),
+                // outside of the pending amount tolerance
+                &card_payment("16.01.24", "16.01.24", "-50,00", "Umsatz gebucht"),
+            ],
+        )
+        .await;
+
+        let mut statuses = transactions(&fm)
+            .await
+            .into_iter()
+            .map(|transaction| transaction.status)
+            .collect::<Vec<_>>();
+        statuses.sort_by_key(|status| *status == fm_core::TransactionStatus::Booked);
+        assert_eq!(
+            statuses,
+            vec![
+                fm_core::TransactionStatus::Pending,
+                fm_core::TransactionStatus::Booked,
+                fm_core::TransactionStatus::Booked
+            ]
+        );
+    }
+}
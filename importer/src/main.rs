@@ -8,7 +8,7 @@ struct Args {
     /// Import Source File
     #[clap(short, long)]
     source: String,
-    /// Import Format
+    /// Import Format: CSV_CAMT_V2 for transactions or PRICES_CSV for prices of securities
     #[clap(short, long, default_value = "CSV_CAMT_V2")]
     format: String,
     /// The url to bind to
@@ -57,6 +57,13 @@ async fn main() {
             .await
             .unwrap();
         }
+        "PRICES_CSV" => {
+            let data = std::fs::File::open(args.source).unwrap();
+            let count = fm_importer::price_csv::import_prices(data, finance_controller)
+                .await
+                .unwrap();
+            println!("Imported {count} prices");
+        }
         _ => eprintln!("Unknown format: {}", args.format),
     }
 }
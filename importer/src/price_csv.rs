@@ -0,0 +1,145 @@
+//! Import of the price history of securities from CSV files.
+//!
+//! The first line is a header that names the columns `ISIN`, `Date` and `Price` in any order.
+//! Other columns are ignored. The columns are separated by `;` or `,`.
+//! Dates are written as `YYYY-MM-DD` or `DD.MM.YYYY` and prices can use a decimal comma.
+
+use anyhow::{Context, Result};
+use std::io::Read;
+
+pub fn parse_prices<D: Read>(mut data: D) -> Result<Vec<fm_core::securities::Price>> {
+    let mut content = String::new();
+    data.read_to_string(&mut content)
+        .context("Could not read price data")?;
+    let header = content.lines().next().context("The price data is empty")?;
+    let delimiter = if header.contains(';') { b';' } else { b',' };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .from_reader(content.as_bytes());
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|x| x.trim().eq_ignore_ascii_case(name))
+            .context(format!("The price data has no column {name}"))
+    };
+    let isin_column = column("isin")?;
+    let date_column = column("date")?;
+    let price_column = column("price")?;
+
+    let utc_offset = fm_core::get_local_timezone()?;
+    let mut prices = Vec::new();
+    for (line, record) in reader.records().enumerate() {
+        let record = record?;
+        let field = |column: usize| record.get(column).unwrap_or_default().trim();
+        let price = (|| {
+            let isin: fm_core::securities::Isin = field(isin_column).parse()?;
+            let date = parse_date(field(date_column))?;
+            let value: fm_core::bigdecimal::BigDecimal =
+                field(price_column).replace(',', ".").parse()?;
+            Ok::<_, anyhow::Error>(fm_core::securities::Price {
+                isin,
+                date: time::OffsetDateTime::new_in_offset(
+                    date,
+                    time::Time::from_hms(12, 0, 0).unwrap(),
+                    utc_offset,
+                ),
+                price: fm_core::Currency::from_currency_id(1, value)?,
+            })
+        })();
+        prices.push(price.context(format!("Invalid price in line {}", line + 2))?);
+    }
+    Ok(prices)
+}
+
+fn parse_date(date: &str) -> Result<time::Date> {
+    let format = if date.contains('.') {
+        "[day].[month].[year]"
+    } else {
+        "[year]-[month]-[day]"
+    };
+    time::Date::parse(
+        date,
+        &time::format_description::parse_borrowed::<1>(format)
+            .context("Could not create format description")?,
+    )
+    .context(format!("{date} is not a valid date"))
+}
+
+/// Parses the prices and adds them to the price history.
+/// Returns the number of imported prices.
+pub async fn import_prices<D: Read, FM: fm_core::FinanceManager>(
+    data: D,
+    fm_controller: fm_core::FMController<FM>,
+) -> Result<usize> {
+    let prices = parse_prices(data)?;
+    let count = prices.len();
+    fm_controller.add_prices(prices).await?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use fm_core::eur;
+    use time::macros::date;
+
+    fn error_chain(result: Result<Vec<fm_core::securities::Price>>) -> String {
+        format!("{:#}", result.unwrap_err())
+    }
+
+    #[test]
+    fn valid() {
+        let prices = parse_prices(
+            "Name;Price;Date;ISIN\n\
+            Apple;170,25;15.01.2024;US0378331005\n\
+            Apple;180;2024-02-01;US0378331005\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(prices.len(), 2);
+        assert!(
+            prices
+                .iter()
+                .all(|price| price.isin.to_string() == "US0378331005")
+        );
+        assert_eq!(prices[0].date.date(), date!(2024 - 01 - 15));
+        assert_eq!(prices[0].price, eur("170.25"));
+        assert_eq!(prices[1].date.date(), date!(2024 - 02 - 01));
+        assert_eq!(prices[1].price, eur("180"));
+
+        // comma separated without prices
+        assert!(
+            parse_prices("isin,date,price\n".as_bytes())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn invalid_isin() {
+        let error = error_chain(parse_prices(
+            "ISIN;Date;Price\nUS0378331005;2024-01-15;170\nUS0378331006;2024-01-16;171\n"
+                .as_bytes(),
+        ));
+        assert!(error.contains("line 3"), "{error}");
+    }
+
+    #[test]
+    fn invalid_date() {
+        let error = error_chain(parse_prices(
+            "ISIN;Date;Price\nUS0378331005;2024-13-15;170\n".as_bytes(),
+        ));
+        assert!(error.contains("line 2"), "{error}");
+        assert!(error.contains("2024-13-15"), "{error}");
+    }
+
+    #[test]
+    fn invalid_price() {
+        let error = error_chain(parse_prices(
+            "ISIN;Date;Price\nUS0378331005;2024-01-15;1.70,5\n".as_bytes(),
+        ));
+        assert!(error.contains("line 2"), "{error}");
+    }
+}
@@ -48,7 +48,7 @@ async fn do_action(
                             transaction.title,
                             transaction.description.as_ref().unwrap_or(&String::new()),
                             transaction.amount(),
-                            transaction.date.to_offset(fm_core::get_local_timezone().unwrap()).format(&time::format_description::parse("[day].[month].[year]")?)?,
+                            transaction.date.to_offset(fm_core::get_local_timezone().unwrap()).format(&time::format_description::parse_borrowed::<1>("[day].[month].[year]")?)?,
                             format_account(&source),
                             format_account(&destination)
                         ))
@@ -119,6 +119,21 @@ where
         "You are making the decision for the following transaction:\n{}",
         format_transaction_entry(object_exists.transaction_entry())?
     );
+    let categories = finance_controller.get_categories().await?;
+    let suggestions = object_exists
+        .transaction_entry()
+        .category_suggestions
+        .iter()
+        .filter_map(|suggestion| {
+            categories
+                .iter()
+                .find(|c| c.id == suggestion.category)
+                .map(|c| format!("{} ({:.0}%)", c.name, suggestion.confidence * 100.0))
+        })
+        .collect::<Vec<_>>();
+    if !suggestions.is_empty() {
+        println!("Suggested categories: {}\n", suggestions.join(", "));
+    }
     println!("You have do decide between the following options (enter the number or None):");
     for (i, item) in object_exists.possible_objects().iter().enumerate() {
         println!(
@@ -173,7 +188,9 @@ fn format_transaction_entry(entry: &super::TransactionEntry) -> Result<String> {
         entry
             .date
             .to_offset(fm_core::get_local_timezone().unwrap())
-            .format(&time::format_description::parse("[day].[month].[year]")?)?
+            .format(&time::format_description::parse_borrowed::<1>(
+                "[day].[month].[year]"
+            )?)?
     ))
 }
 
@@ -7,12 +7,6 @@ pub struct Client {
     token: String,
 }
 
-impl Client {
-    pub fn url(&self) -> &str {
-        &self.url
-    }
-}
-
 #[macro_export]
 macro_rules! client_post_macro {
     ( $url:expr, $token:expr, $path:expr, $x:expr ) => {{
@@ -35,6 +29,56 @@ macro_rules! client_post_macro {
     }};
 }
 
+impl Client {
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The net worth calculated by the server (see [`fm_core::FMController::net_worth`]).
+    pub async fn net_worth(
+        &self,
+        timespan: fm_core::Timespan,
+        interval: fm_core::Interval,
+    ) -> Result<Vec<(fm_core::DateTime, fm_core::Currency)>> {
+        client_post_macro!(
+            self.url,
+            self.token.clone(),
+            "net_worth",
+            (timespan, interval)
+        )
+    }
+
+    /// The cash flow calculated by the server (see [`fm_core::FMController::cash_flow`]).
+    pub async fn cash_flow(
+        &self,
+        timespan: fm_core::Timespan,
+        interval: fm_core::Interval,
+        breakdown: fm_core::reporting::Breakdown,
+    ) -> Result<Vec<fm_core::reporting::CashFlowPeriod>> {
+        client_post_macro!(
+            self.url,
+            self.token.clone(),
+            "cash_flow",
+            (timespan, interval, breakdown)
+        )
+    }
+
+    /// The category pivot calculated by the server
+    /// (see [`fm_core::FMController::category_pivot`]).
+    pub async fn category_pivot(
+        &self,
+        filter: fm_core::transaction_filter::TransactionFilter,
+        interval: fm_core::Interval,
+    ) -> Result<fm_core::reporting::CategoryPivot> {
+        client_post_macro!(
+            self.url,
+            self.token.clone(),
+            "category_pivot",
+            (filter, interval)
+        )
+    }
+}
+
 impl fm_core::FinanceManager for Client {
     type Flags = (String, String);
 
@@ -81,6 +125,19 @@ impl fm_core::FinanceManager for Client {
         client_post_macro!(self.url, self.token.clone(), "delete_account", id)
     }
 
+    async fn merge_accounts(
+        &mut self,
+        keep: fm_core::account::Account,
+        remove: fm_core::Id,
+    ) -> Result<fm_core::account::Account> {
+        client_post_macro!(
+            self.url,
+            self.token.clone(),
+            "merge_accounts",
+            (keep, remove)
+        )
+    }
+
     async fn create_book_checking_account(
         &mut self,
         name: String,
@@ -112,12 +169,13 @@ impl fm_core::FinanceManager for Client {
         &self,
         account: &fm_core::account::Account,
         date: fm_core::DateTime,
+        date_kind: fm_core::DateKind,
     ) -> Result<fm_core::Currency> {
         client_post_macro!(
             self.url,
             self.token.clone(),
             "get_account_sum",
-            (account, date)
+            (account, date, date_kind)
         )
     }
 
@@ -154,6 +212,67 @@ impl fm_core::FinanceManager for Client {
         client_post_macro!(self.url, self.token.clone(), "delete_bill", id)
     }
 
+    async fn create_trade(
+        &mut self,
+        account: fm_core::Id,
+        isin: fm_core::securities::Isin,
+        kind: fm_core::securities::TradeKind,
+        quantity: fm_core::bigdecimal::BigDecimal,
+        amount: fm_core::Currency,
+        date: fm_core::DateTime,
+    ) -> Result<fm_core::securities::Trade> {
+        client_post_macro!(
+            self.url,
+            self.token.clone(),
+            "create_trade",
+            (account, isin, kind, quantity, amount, date)
+        )
+    }
+
+    async fn delete_trade(&mut self, id: fm_core::Id) -> Result<()> {
+        client_post_macro!(self.url, self.token.clone(), "delete_trade", id)
+    }
+
+    async fn get_trades(&self, account: fm_core::Id) -> Result<Vec<fm_core::securities::Trade>> {
+        client_post_macro!(self.url, self.token.clone(), "get_trades", account)
+    }
+
+    async fn add_prices(&mut self, prices: Vec<fm_core::securities::Price>) -> Result<()> {
+        client_post_macro!(self.url, self.token.clone(), "add_prices", prices)
+    }
+
+    async fn get_prices(
+        &self,
+        isin: fm_core::securities::Isin,
+    ) -> Result<Vec<fm_core::securities::Price>> {
+        client_post_macro!(self.url, self.token.clone(), "get_prices", isin)
+    }
+
+    async fn create_valuation(
+        &mut self,
+        account: fm_core::Id,
+        value: fm_core::Currency,
+        date: fm_core::DateTime,
+    ) -> Result<fm_core::valuation::Valuation> {
+        client_post_macro!(
+            self.url,
+            self.token.clone(),
+            "create_valuation",
+            (account, value, date)
+        )
+    }
+
+    async fn delete_valuation(&mut self, id: fm_core::Id) -> Result<()> {
+        client_post_macro!(self.url, self.token.clone(), "delete_valuation", id)
+    }
+
+    async fn get_valuations(
+        &self,
+        account: fm_core::Id,
+    ) -> Result<Vec<fm_core::valuation::Valuation>> {
+        client_post_macro!(self.url, self.token.clone(), "get_valuations", account)
+    }
+
     async fn get_filtered_transactions(
         &self,
         filter: fm_core::transaction_filter::TransactionFilter,
@@ -166,6 +285,74 @@ impl fm_core::FinanceManager for Client {
         )
     }
 
+    async fn get_transactions_matching(
+        &self,
+        expression: fm_core::filter_expression::FilterExpression,
+    ) -> Result<Vec<fm_core::Transaction>> {
+        client_post_macro!(
+            self.url,
+            self.token.clone(),
+            "get_transactions_matching",
+            expression
+        )
+    }
+
+    fn stream_transactions(
+        &self,
+        expression: fm_core::filter_expression::FilterExpression,
+    ) -> fm_core::TransactionStream {
+        use futures::TryStreamExt;
+
+        let request = reqwest::Client::new()
+            .post(format!("{}/stream_transactions", self.url))
+            .body(
+                serde_json::json!(crate::Tokenized {
+                    token: self.token.clone(),
+                    content: expression
+                })
+                .to_string(),
+            )
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(reqwest::header::ACCESS_CONTROL_ALLOW_ORIGIN, "*");
+        Box::pin(
+            futures::stream::once(async move {
+                anyhow::Ok(ndjson_transactions(
+                    request.send().await?.error_for_status()?,
+                ))
+            })
+            .try_flatten()
+            .map_ok(|transactions| futures::stream::iter(transactions.into_iter().map(Ok)))
+            .try_flatten(),
+        )
+    }
+
+    async fn get_transactions_page(
+        &self,
+        expression: fm_core::filter_expression::FilterExpression,
+        page: fm_core::pagination::PageRequest,
+    ) -> Result<fm_core::pagination::Page<fm_core::Transaction>> {
+        client_post_macro!(
+            self.url,
+            self.token.clone(),
+            "get_transactions_page",
+            (expression, page)
+        )
+    }
+
+    async fn aggregate_transactions(
+        &self,
+        filter: fm_core::transaction_filter::TransactionFilter,
+        group_by: Option<fm_core::aggregation::GroupBy>,
+        period: Option<fm_core::aggregation::Period>,
+    ) -> Result<Vec<fm_core::aggregation::Aggregate>> {
+        client_post_macro!(
+            self.url,
+            self.token.clone(),
+            "aggregate_transactions",
+            (filter, group_by, period)
+        )
+    }
+
     async fn get_accounts(&self) -> Result<Vec<fm_core::account::Account>> {
         client_post_macro!(self.url, self.token.clone(), "get_accounts", ())
     }
@@ -174,6 +361,22 @@ impl fm_core::FinanceManager for Client {
         client_post_macro!(self.url, self.token.clone(), "get_account", id)
     }
 
+    async fn get_accounts_by_account_id(
+        &self,
+        account_id: fm_core::AccountId,
+    ) -> Result<Vec<fm_core::account::Account>> {
+        client_post_macro!(
+            self.url,
+            self.token.clone(),
+            "get_accounts_by_account_id",
+            account_id
+        )
+    }
+
+    async fn get_accounts_by_name(&self, name: String) -> Result<Vec<fm_core::account::Account>> {
+        client_post_macro!(self.url, self.token.clone(), "get_accounts_by_name", name)
+    }
+
     async fn get_transaction(&self, id: fm_core::Id) -> Result<Option<fm_core::Transaction>> {
         client_post_macro!(self.url, self.token.clone(), "get_transaction", id)
     }
@@ -221,6 +424,18 @@ impl fm_core::FinanceManager for Client {
         )
     }
 
+    async fn create_transaction_from(
+        &mut self,
+        transaction: fm_core::Transaction,
+    ) -> Result<fm_core::Transaction> {
+        client_post_macro!(
+            self.url,
+            self.token.clone(),
+            "create_transaction_from",
+            transaction
+        )
+    }
+
     async fn create_budget(
         &mut self,
         name: String,
@@ -277,6 +492,19 @@ impl fm_core::FinanceManager for Client {
         client_post_macro!(self.url, self.token.clone(), "delete_transaction", id)
     }
 
+    async fn merge_transactions(
+        &mut self,
+        keep: fm_core::Transaction,
+        remove: fm_core::Id,
+    ) -> Result<fm_core::Transaction> {
+        client_post_macro!(
+            self.url,
+            self.token.clone(),
+            "merge_transactions",
+            (keep, remove)
+        )
+    }
+
     async fn update_budget(&mut self, budget: fm_core::Budget) -> Result<fm_core::Budget> {
         client_post_macro!(self.url, self.token.clone(), "update_budget", budget)
     }
@@ -313,6 +541,46 @@ impl fm_core::FinanceManager for Client {
         client_post_macro!(self.url, self.token.clone(), "delete_category", id)
     }
 
+    async fn get_saved_filters(&self) -> Result<Vec<fm_core::transaction_filter::SavedFilter>> {
+        client_post_macro!(self.url, self.token.clone(), "get_saved_filters", ())
+    }
+
+    async fn get_saved_filter(
+        &self,
+        id: fm_core::Id,
+    ) -> Result<Option<fm_core::transaction_filter::SavedFilter>> {
+        client_post_macro!(self.url, self.token.clone(), "get_saved_filter", id)
+    }
+
+    async fn create_saved_filter(
+        &mut self,
+        name: String,
+        filter: fm_core::transaction_filter::TransactionFilter,
+    ) -> Result<fm_core::transaction_filter::SavedFilter> {
+        client_post_macro!(
+            self.url,
+            self.token.clone(),
+            "create_saved_filter",
+            (name, filter)
+        )
+    }
+
+    async fn update_saved_filter(
+        &mut self,
+        saved_filter: fm_core::transaction_filter::SavedFilter,
+    ) -> Result<fm_core::transaction_filter::SavedFilter> {
+        client_post_macro!(
+            self.url,
+            self.token.clone(),
+            "update_saved_filter",
+            saved_filter
+        )
+    }
+
+    async fn delete_saved_filter(&mut self, id: fm_core::Id) -> Result<()> {
+        client_post_macro!(self.url, self.token.clone(), "delete_saved_filter", id)
+    }
+
     async fn get_transactions_of_category(
         &self,
         category: fm_core::Id,
@@ -330,3 +598,34 @@ impl fm_core::FinanceManager for Client {
         client_post_macro!(self.url, self.token.clone(), "get_transactions", ids)
     }
 }
+
+/// Parses the transactions of a newline delimited JSON response chunk by chunk.
+fn ndjson_transactions(
+    response: reqwest::Response,
+) -> impl futures::Stream<Item = Result<Vec<fm_core::Transaction>>> {
+    futures::stream::try_unfold(
+        (response, Vec::new()),
+        |(mut response, mut buffer)| async move {
+            loop {
+                let Some(chunk) = response.chunk().await? else {
+                    anyhow::ensure!(
+                        buffer.iter().all(u8::is_ascii_whitespace),
+                        "Incomplete transaction at the end of the stream"
+                    );
+                    return Ok(None);
+                };
+                buffer.extend_from_slice(&chunk);
+                // only parse complete lines and keep the rest for the next chunk
+                if let Some(end) = buffer.iter().rposition(|byte| *byte == b'\n') {
+                    let rest = buffer.split_off(end + 1);
+                    let transactions = buffer
+                        .split(|byte| *byte == b'\n')
+                        .filter(|line| !line.is_empty())
+                        .map(serde_json::from_slice)
+                        .collect::<serde_json::Result<Vec<_>>>()?;
+                    return Ok(Some((transactions, (response, rest))));
+                }
+            }
+        },
+    )
+}
@@ -43,16 +43,15 @@ fn is_timeouted(map: &HashMap<std::net::IpAddr, Vec<u64>>, addr: &std::net::IpAd
     const TIMEOUT: u64 = 60;
 
     if let Some(entry) = map.get(addr) {
-        if let Some(last) = entry.last() {
-            if std::time::SystemTime::now()
+        if let Some(last) = entry.last()
+            && std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs()
                 - last
                 > TIMEOUT
-            {
-                return false;
-            }
+        {
+            return false;
         }
         if entry.len() == 5 {
             let mut diff = 0;
@@ -161,6 +160,7 @@ pub async fn run_with_listener(
         .route("/create_budget", post(create_budget))
         .route("/delete_budget", post(delete_budget))
         .route("/create_transaction", post(create_transaction))
+        .route("/create_transaction_from", post(create_transaction_from))
         .route(
             "/create_book_checking_account",
             post(create_book_checking_account),
@@ -181,6 +181,11 @@ pub async fn run_with_listener(
         .route("/create_category", post(create_category))
         .route("/update_category", post(update_category))
         .route("/delete_category", post(delete_category))
+        .route("/get_saved_filters", post(get_saved_filters))
+        .route("/get_saved_filter", post(get_saved_filter))
+        .route("/create_saved_filter", post(create_saved_filter))
+        .route("/update_saved_filter", post(update_saved_filter))
+        .route("/delete_saved_filter", post(delete_saved_filter))
         .route(
             "/get_transactions_of_category",
             post(get_transactions_of_category),
@@ -193,12 +198,37 @@ pub async fn run_with_listener(
             "/get_filtered_transactions",
             post(get_filtered_transactions),
         )
+        .route(
+            "/get_transactions_matching",
+            post(get_transactions_matching),
+        )
+        .route("/get_transactions_page", post(get_transactions_page))
+        .route("/stream_transactions", post(stream_transactions))
+        .route("/aggregate_transactions", post(aggregate_transactions))
         .route("/create_bill", post(create_bill))
         .route("/delete_bill", post(delete_bill))
         .route("/update_bill", post(update_bill))
         .route("/get_bills", post(get_bills))
         .route("/get_bill", post(get_bill))
+        .route("/create_trade", post(create_trade))
+        .route("/delete_trade", post(delete_trade))
+        .route("/get_trades", post(get_trades))
+        .route("/add_prices", post(add_prices))
+        .route("/get_prices", post(get_prices))
+        .route("/create_valuation", post(create_valuation))
+        .route("/delete_valuation", post(delete_valuation))
+        .route("/get_valuations", post(get_valuations))
+        .route("/net_worth", post(net_worth))
+        .route("/cash_flow", post(cash_flow))
+        .route("/category_pivot", post(category_pivot))
         .route("/delete_account", post(delete_account))
+        .route("/merge_accounts", post(merge_accounts))
+        .route("/merge_transactions", post(merge_transactions))
+        .route(
+            "/get_accounts_by_account_id",
+            post(get_accounts_by_account_id),
+        )
+        .route("/get_accounts_by_name", post(get_accounts_by_name))
         .route("/last_modified", post(last_modified))
         .layer(axum::middleware::from_fn_with_state(state.clone(), auth))
         .route("/status", get(status))
@@ -297,6 +327,7 @@ async fn get_account_sum(
     axum::extract::Json(account_data): axum::extract::Json<(
         fm_core::account::Account,
         fm_core::DateTime,
+        fm_core::DateKind,
     )>,
 ) -> Json<Value> {
     let sum = state
@@ -306,7 +337,7 @@ async fn get_account_sum(
         .raw_fm()
         .lock()
         .await
-        .get_account_sum(&account_data.0, account_data.1)
+        .get_account_sum(&account_data.0, account_data.1, account_data.2)
         .await
         .unwrap();
     json!(sum).into()
@@ -400,6 +431,20 @@ async fn create_transaction(
     json!(transaction).into()
 }
 
+async fn create_transaction_from(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<fm_core::Transaction>,
+) -> Json<Value> {
+    let transaction = state
+        .finance_controller
+        .lock()
+        .await
+        .create_transaction_from(data)
+        .await
+        .unwrap();
+    json!(transaction).into()
+}
+
 #[allow(clippy::type_complexity)]
 async fn create_book_checking_account(
     axum::extract::State(state): axum::extract::State<State>,
@@ -601,6 +646,78 @@ async fn delete_category(
     json!(()).into()
 }
 
+async fn get_saved_filters(
+    axum::extract::State(state): axum::extract::State<State>,
+) -> Json<Value> {
+    let saved_filters = state
+        .finance_controller
+        .lock()
+        .await
+        .get_saved_filters()
+        .await
+        .unwrap();
+    json!(saved_filters).into()
+}
+
+async fn get_saved_filter(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<fm_core::Id>,
+) -> Json<Value> {
+    let saved_filter = state
+        .finance_controller
+        .lock()
+        .await
+        .get_saved_filter(data)
+        .await
+        .unwrap();
+    json!(saved_filter).into()
+}
+
+async fn create_saved_filter(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<(
+        String,
+        fm_core::transaction_filter::TransactionFilter,
+    )>,
+) -> Json<Value> {
+    let saved_filter = state
+        .finance_controller
+        .lock()
+        .await
+        .create_saved_filter(data.0, data.1)
+        .await
+        .unwrap();
+    json!(saved_filter).into()
+}
+
+async fn update_saved_filter(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<fm_core::transaction_filter::SavedFilter>,
+) -> Json<Value> {
+    let saved_filter = state
+        .finance_controller
+        .lock()
+        .await
+        .update_saved_filter(data)
+        .await
+        .unwrap();
+    json!(saved_filter).into()
+}
+
+async fn delete_saved_filter(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<fm_core::Id>,
+) -> Json<Value> {
+    state
+        .finance_controller
+        .lock()
+        .await
+        .delete_saved_filter(data)
+        .await
+        .unwrap();
+    json!(()).into()
+}
+
 async fn get_transactions_of_category(
     axum::extract::State(state): axum::extract::State<State>,
     axum::extract::Json(data): axum::extract::Json<(fm_core::Id, fm_core::Timespan)>,
@@ -644,6 +761,76 @@ async fn get_filtered_transactions(
     json!(transactions).into()
 }
 
+async fn get_transactions_matching(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<fm_core::filter_expression::FilterExpression>,
+) -> Json<Value> {
+    let transactions = state
+        .finance_controller
+        .lock()
+        .await
+        .get_transactions_matching(data)
+        .await
+        .unwrap();
+    json!(transactions).into()
+}
+
+/// Sends the transactions as newline delimited JSON while they are read.
+async fn stream_transactions(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<fm_core::filter_expression::FilterExpression>,
+) -> impl axum::response::IntoResponse {
+    use futures::TryStreamExt;
+
+    let transactions = state
+        .finance_controller
+        .lock()
+        .await
+        .stream_transactions(data)
+        .await;
+    (
+        [(axum::http::header::CONTENT_TYPE, "application/x-ndjson")],
+        axum::body::Body::from_stream(
+            transactions.map_ok(|transaction| json!(transaction).to_string() + "\n"),
+        ),
+    )
+}
+
+async fn get_transactions_page(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<(
+        fm_core::filter_expression::FilterExpression,
+        fm_core::pagination::PageRequest,
+    )>,
+) -> Json<Value> {
+    let page = state
+        .finance_controller
+        .lock()
+        .await
+        .get_transactions_page(data.0, data.1)
+        .await
+        .unwrap();
+    json!(page).into()
+}
+
+async fn aggregate_transactions(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<(
+        fm_core::transaction_filter::TransactionFilter,
+        Option<fm_core::aggregation::GroupBy>,
+        Option<fm_core::aggregation::Period>,
+    )>,
+) -> Json<Value> {
+    let aggregates = state
+        .finance_controller
+        .lock()
+        .await
+        .aggregate_transactions(data.0, data.1, data.2)
+        .await
+        .unwrap();
+    json!(aggregates).into()
+}
+
 #[allow(clippy::type_complexity)]
 async fn create_bill(
     axum::extract::State(state): axum::extract::State<State>,
@@ -680,6 +867,179 @@ async fn delete_bill(
     json!(()).into()
 }
 
+#[allow(clippy::type_complexity)]
+async fn create_trade(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<(
+        fm_core::Id,
+        fm_core::securities::Isin,
+        fm_core::securities::TradeKind,
+        fm_core::bigdecimal::BigDecimal,
+        fm_core::Currency,
+        fm_core::DateTime,
+    )>,
+) -> Json<Value> {
+    let trade = state
+        .finance_controller
+        .lock()
+        .await
+        .create_trade(data.0, data.1, data.2, data.3, data.4, data.5)
+        .await
+        .unwrap();
+    json!(trade).into()
+}
+
+async fn delete_trade(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(id): axum::extract::Json<fm_core::Id>,
+) -> Json<Value> {
+    state
+        .finance_controller
+        .lock()
+        .await
+        .delete_trade(id)
+        .await
+        .unwrap();
+    json!(()).into()
+}
+
+async fn get_trades(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(account): axum::extract::Json<fm_core::Id>,
+) -> Json<Value> {
+    let trades = state
+        .finance_controller
+        .lock()
+        .await
+        .get_trades(account)
+        .await
+        .unwrap();
+    json!(trades).into()
+}
+
+async fn add_prices(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(prices): axum::extract::Json<Vec<fm_core::securities::Price>>,
+) -> Json<Value> {
+    state
+        .finance_controller
+        .lock()
+        .await
+        .add_prices(prices)
+        .await
+        .unwrap();
+    json!(()).into()
+}
+
+async fn get_prices(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(isin): axum::extract::Json<fm_core::securities::Isin>,
+) -> Json<Value> {
+    let prices = state
+        .finance_controller
+        .lock()
+        .await
+        .get_prices(isin)
+        .await
+        .unwrap();
+    json!(prices).into()
+}
+
+async fn create_valuation(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<(
+        fm_core::Id,
+        fm_core::Currency,
+        fm_core::DateTime,
+    )>,
+) -> Json<Value> {
+    let valuation = state
+        .finance_controller
+        .lock()
+        .await
+        .create_valuation(data.0, data.1, data.2)
+        .await
+        .unwrap();
+    json!(valuation).into()
+}
+
+async fn net_worth(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<(fm_core::Timespan, fm_core::Interval)>,
+) -> Json<Value> {
+    let net_worth = state
+        .finance_controller
+        .lock()
+        .await
+        .net_worth(data.0, data.1)
+        .await
+        .unwrap();
+    json!(net_worth).into()
+}
+
+async fn cash_flow(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<(
+        fm_core::Timespan,
+        fm_core::Interval,
+        fm_core::reporting::Breakdown,
+    )>,
+) -> Json<Value> {
+    let cash_flow = state
+        .finance_controller
+        .lock()
+        .await
+        .cash_flow(data.0, data.1, data.2)
+        .await
+        .unwrap();
+    json!(cash_flow).into()
+}
+
+async fn category_pivot(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<(
+        fm_core::transaction_filter::TransactionFilter,
+        fm_core::Interval,
+    )>,
+) -> Json<Value> {
+    let pivot = state
+        .finance_controller
+        .lock()
+        .await
+        .category_pivot(data.0, data.1)
+        .await
+        .unwrap();
+    json!(pivot).into()
+}
+
+async fn delete_valuation(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(id): axum::extract::Json<fm_core::Id>,
+) -> Json<Value> {
+    state
+        .finance_controller
+        .lock()
+        .await
+        .delete_valuation(id)
+        .await
+        .unwrap();
+    json!(()).into()
+}
+
+async fn get_valuations(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(account): axum::extract::Json<fm_core::Id>,
+) -> Json<Value> {
+    let valuations = state
+        .finance_controller
+        .lock()
+        .await
+        .get_valuations(account)
+        .await
+        .unwrap();
+    json!(valuations).into()
+}
+
 #[allow(clippy::type_complexity)]
 async fn update_bill(
     axum::extract::State(state): axum::extract::State<State>,
@@ -736,3 +1096,65 @@ async fn delete_account(
         .unwrap();
     json!(()).into()
 }
+
+async fn merge_accounts(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<(fm_core::account::Account, fm_core::Id)>,
+) -> Json<Value> {
+    let account = state
+        .finance_controller
+        .lock()
+        .await
+        .raw_fm()
+        .lock()
+        .await
+        .merge_accounts(data.0, data.1)
+        .await
+        .unwrap();
+    json!(account).into()
+}
+
+async fn merge_transactions(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(data): axum::extract::Json<(fm_core::Transaction, fm_core::Id)>,
+) -> Json<Value> {
+    let transaction = state
+        .finance_controller
+        .lock()
+        .await
+        .raw_fm()
+        .lock()
+        .await
+        .merge_transactions(data.0, data.1)
+        .await
+        .unwrap();
+    json!(transaction).into()
+}
+
+async fn get_accounts_by_account_id(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(account_id): axum::extract::Json<fm_core::AccountId>,
+) -> Json<Value> {
+    let accounts = state
+        .finance_controller
+        .lock()
+        .await
+        .get_accounts_by_account_id(account_id)
+        .await
+        .unwrap();
+    json!(accounts).into()
+}
+
+async fn get_accounts_by_name(
+    axum::extract::State(state): axum::extract::State<State>,
+    axum::extract::Json(name): axum::extract::Json<String>,
+) -> Json<Value> {
+    let accounts = state
+        .finance_controller
+        .lock()
+        .await
+        .get_accounts_by_name(name)
+        .await
+        .unwrap();
+    json!(accounts).into()
+}
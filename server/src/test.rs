@@ -1,8 +1,10 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod test {
     use crate::client::Client;
     use crate::server::run_with_listener;
     use fm_core::FinanceManager;
+    use fm_core::eur;
     use tokio::net::TcpListener;
 
     fn run_in_tokio_context(f: impl std::future::Future<Output = ()>) {
@@ -12,7 +14,7 @@ mod test {
             }),
             Err(_) => {
                 let rt = tokio::runtime::Runtime::new().unwrap();
-                rt.handle().block_on(async { f.await });
+                rt.handle().block_on(f);
             }
         };
     }
@@ -34,4 +36,165 @@ mod test {
     }
 
     fm_core::finance_manager_test::unit_tests!(test_runner);
+
+    #[async_std::test]
+    async fn net_worth() {
+        test_runner(async |mut client: Client| {
+            let bank = client
+                .create_asset_account("bank".to_string(), None, None, None, eur("100"))
+                .await
+                .unwrap();
+            let shop = client
+                .create_book_checking_account("shop".to_string(), None, None, None)
+                .await
+                .unwrap();
+            client
+                .create_transaction(
+                    eur("30"),
+                    "shopping".to_string(),
+                    None,
+                    bank.id,
+                    shop.id,
+                    None,
+                    // 2024-01-10
+                    fm_core::DateTime::from_unix_timestamp(1704888000).unwrap(),
+                    std::collections::HashMap::new(),
+                    std::collections::HashMap::new(),
+                )
+                .await
+                .unwrap();
+            // 2024-01-01 to 2024-01-20
+            let timespan = (
+                Some(fm_core::DateTime::from_unix_timestamp(1704067200).unwrap()),
+                Some(fm_core::DateTime::from_unix_timestamp(1705708800).unwrap()),
+            );
+            let net_worth = client
+                .net_worth(timespan, fm_core::Interval::Week)
+                .await
+                .unwrap();
+            assert_eq!(
+                net_worth.iter().map(|x| x.1.clone()).collect::<Vec<_>>(),
+                vec![eur("100"), eur("100"), eur("70"), eur("70")]
+            );
+            assert_eq!(
+                net_worth,
+                fm_core::FMController::with_finance_manager(client)
+                    .net_worth(timespan, fm_core::Interval::Week)
+                    .await
+                    .unwrap()
+            );
+        })
+        .await;
+    }
+
+    #[async_std::test]
+    async fn cash_flow() {
+        test_runner(async |mut client: Client| {
+            let bank = client
+                .create_asset_account("bank".to_string(), None, None, None, eur("0"))
+                .await
+                .unwrap();
+            let shop = client
+                .create_book_checking_account("shop".to_string(), None, None, None)
+                .await
+                .unwrap();
+            client
+                .create_transaction(
+                    eur("30"),
+                    "shopping".to_string(),
+                    None,
+                    bank.id,
+                    shop.id,
+                    None,
+                    // 2024-01-10
+                    fm_core::DateTime::from_unix_timestamp(1704888000).unwrap(),
+                    std::collections::HashMap::new(),
+                    std::collections::HashMap::new(),
+                )
+                .await
+                .unwrap();
+            // 2024-01-01 to 2024-01-20
+            let timespan = (
+                Some(fm_core::DateTime::from_unix_timestamp(1704067200).unwrap()),
+                Some(fm_core::DateTime::from_unix_timestamp(1705708800).unwrap()),
+            );
+            let cash_flow = client
+                .cash_flow(
+                    timespan,
+                    fm_core::Interval::Month,
+                    fm_core::reporting::Breakdown::Counterparty,
+                )
+                .await
+                .unwrap();
+            assert_eq!(cash_flow.len(), 1);
+            assert_eq!(cash_flow[0].expenses, eur("30"));
+            assert_eq!(cash_flow[0].groups[0].id, Some(shop.id));
+            assert_eq!(
+                cash_flow,
+                fm_core::FMController::with_finance_manager(client)
+                    .cash_flow(
+                        timespan,
+                        fm_core::Interval::Month,
+                        fm_core::reporting::Breakdown::Counterparty
+                    )
+                    .await
+                    .unwrap()
+            );
+        })
+        .await;
+    }
+
+    #[async_std::test]
+    async fn category_pivot() {
+        test_runner(async |mut client: Client| {
+            let bank = client
+                .create_asset_account("bank".to_string(), None, None, None, eur("0"))
+                .await
+                .unwrap();
+            let shop = client
+                .create_book_checking_account("shop".to_string(), None, None, None)
+                .await
+                .unwrap();
+            let food = client.create_category("food".to_string()).await.unwrap();
+            client
+                .create_transaction(
+                    eur("30"),
+                    "shopping".to_string(),
+                    None,
+                    bank.id,
+                    shop.id,
+                    None,
+                    // 2024-01-10
+                    fm_core::DateTime::from_unix_timestamp(1704888000).unwrap(),
+                    std::collections::HashMap::new(),
+                    std::collections::HashMap::from([(food.id, fm_core::Sign::Positive)]),
+                )
+                .await
+                .unwrap();
+            let filter = fm_core::transaction_filter::TransactionFilter::default().push_account(
+                fm_core::transaction_filter::Filter {
+                    negated: false,
+                    id: Some(bank.id),
+                    include: true,
+                    timespan: None,
+                    relative_timespan: None,
+                },
+            );
+            let pivot = client
+                .category_pivot(filter.clone(), fm_core::Interval::Month)
+                .await
+                .unwrap();
+            assert_eq!(pivot.periods.len(), 1);
+            assert_eq!(pivot.rows[0].category, Some(food.id));
+            assert_eq!(pivot.total(), eur("30"));
+            assert_eq!(
+                pivot,
+                fm_core::FMController::with_finance_manager(client)
+                    .category_pivot(filter, fm_core::Interval::Month)
+                    .await
+                    .unwrap()
+            );
+        })
+        .await;
+    }
 }